@@ -0,0 +1,239 @@
+//! Live A/B comparison of two strategy variants
+//!
+//! Signals alternate between the variants in proportion to the configured
+//! split, each variant keeps its own scorecard, and a winner is only declared
+//! once both have enough trades for the difference to be significant.
+
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::core::config::AbTestConfig;
+use crate::core::types::OrderId;
+
+/// One arm of an A/B test
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Variant {
+    A,
+    B,
+}
+
+/// Running trade statistics for one variant
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Scorecard {
+    pub trades: u64,
+    pub wins: u64,
+    pub total_pnl: Decimal,
+    mean: f64,
+    m2: f64, // Sum of squared deviations from the mean (Welford)
+}
+
+impl Scorecard {
+    fn record(&mut self, pnl: Decimal) {
+        self.trades += 1;
+        if pnl > Decimal::ZERO {
+            self.wins += 1;
+        }
+        self.total_pnl += pnl;
+
+        let value = pnl.to_f64().unwrap_or(0.0);
+        let delta = value - self.mean;
+        self.mean += delta / self.trades as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    /// Mean P&L per trade
+    pub fn mean_pnl(&self) -> f64 {
+        self.mean
+    }
+
+    /// Fraction of trades that made money
+    pub fn win_rate(&self) -> f64 {
+        if self.trades == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.trades as f64
+        }
+    }
+
+    fn variance(&self) -> f64 {
+        if self.trades < 2 {
+            0.0
+        } else {
+            self.m2 / (self.trades - 1) as f64
+        }
+    }
+}
+
+/// Comparison of the two variants
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AbReport {
+    pub variant_a: String,
+    pub variant_b: String,
+    pub scorecard_a: Scorecard,
+    pub scorecard_b: Scorecard,
+    /// Welch's t statistic for mean P&L (A minus B), once both variants have two trades
+    pub t_statistic: Option<f64>,
+    /// Set once both variants have `min_trades` and the difference clears `min_z_score`
+    pub winner: Option<Variant>,
+}
+
+/// Allocates signals between two strategy variants and scores their trades
+#[derive(Debug, Clone)]
+pub struct AbTest {
+    config: AbTestConfig,
+    allocated: HashMap<Variant, u64>,
+    assignments: HashMap<OrderId, Variant>,
+    scorecards: HashMap<Variant, Scorecard>,
+}
+
+impl AbTest {
+    /// Create a test from configuration
+    pub fn from_config(config: &AbTestConfig) -> Self {
+        Self {
+            config: config.clone(),
+            allocated: HashMap::new(),
+            assignments: HashMap::new(),
+            scorecards: HashMap::new(),
+        }
+    }
+
+    /// Strategy name behind a variant
+    pub fn strategy(&self, variant: Variant) -> &str {
+        match variant {
+            Variant::A => &self.config.variant_a,
+            Variant::B => &self.config.variant_b,
+        }
+    }
+
+    /// Share of capital (or signals) given to a variant
+    pub fn share(&self, variant: Variant) -> f64 {
+        let split = self.config.split.clamp(0.0, 1.0);
+        match variant {
+            Variant::A => split,
+            Variant::B => 1.0 - split,
+        }
+    }
+
+    /// Capital allotted to a variant out of `total`
+    pub fn capital_for(&self, variant: Variant, total: Decimal) -> Decimal {
+        total * Decimal::from_f64(self.share(variant)).unwrap_or(Decimal::ZERO)
+    }
+
+    /// Pick the variant for the next signal, keeping allocations as close to
+    /// the split as possible
+    pub fn allocate(&mut self) -> Variant {
+        let a = self.allocated.get(&Variant::A).copied().unwrap_or(0);
+        let total = a + self.allocated.get(&Variant::B).copied().unwrap_or(0);
+
+        let variant = if (a as f64) < self.share(Variant::A) * (total + 1) as f64 {
+            Variant::A
+        } else {
+            Variant::B
+        };
+        *self.allocated.entry(variant).or_insert(0) += 1;
+        variant
+    }
+
+    /// Remember which variant an order was placed for
+    pub fn assign(&mut self, order_id: OrderId, variant: Variant) {
+        self.assignments.insert(order_id, variant);
+    }
+
+    /// Score the closed trade of an assigned order, returning its variant
+    pub fn record_outcome(&mut self, order_id: &OrderId, pnl: Decimal) -> Option<Variant> {
+        let variant = self.assignments.remove(order_id)?;
+        self.record(variant, pnl);
+        Some(variant)
+    }
+
+    /// Score a closed trade for a variant
+    pub fn record(&mut self, variant: Variant, pnl: Decimal) {
+        self.scorecards.entry(variant).or_default().record(pnl);
+    }
+
+    /// Current scorecard of a variant
+    pub fn scorecard(&self, variant: Variant) -> Scorecard {
+        self.scorecards.get(&variant).cloned().unwrap_or_default()
+    }
+
+    /// Compare the variants
+    pub fn report(&self) -> AbReport {
+        let a = self.scorecard(Variant::A);
+        let b = self.scorecard(Variant::B);
+
+        let t_statistic = (a.trades >= 2 && b.trades >= 2).then(|| {
+            let standard_error = (a.variance() / a.trades as f64 + b.variance() / b.trades as f64).sqrt();
+            if standard_error > 0.0 {
+                (a.mean - b.mean) / standard_error
+            } else {
+                // Identical, noiseless outcomes: any difference is decisive
+                (a.mean - b.mean).signum() * f64::INFINITY
+            }
+        });
+
+        let enough_trades = a.trades >= self.config.min_trades && b.trades >= self.config.min_trades;
+        let winner = t_statistic
+            .filter(|t| enough_trades && t.abs() >= self.config.min_z_score)
+            .map(|t| if t > 0.0 { Variant::A } else { Variant::B });
+
+        AbReport {
+            variant_a: self.config.variant_a.clone(),
+            variant_b: self.config.variant_b.clone(),
+            scorecard_a: a,
+            scorecard_b: b,
+            t_statistic,
+            winner,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signals_follow_split_and_scorecards_accumulate() {
+        let mut test = AbTest::from_config(&AbTestConfig {
+            enabled: true,
+            variant_a: "momentum_scalping".to_string(),
+            variant_b: "mean_reversion".to_string(),
+            split: 0.75,
+            min_trades: 10,
+            min_z_score: 1.96,
+        });
+
+        let allocations: Vec<Variant> = (0..8).map(|_| test.allocate()).collect();
+        assert_eq!(allocations.iter().filter(|v| **v == Variant::A).count(), 6);
+        assert_eq!(allocations.iter().filter(|v| **v == Variant::B).count(), 2);
+        assert_eq!(test.capital_for(Variant::B, Decimal::from(100)), Decimal::from(25));
+
+        let order = uuid::Uuid::new_v4();
+        test.assign(order, Variant::B);
+        assert_eq!(test.record_outcome(&order, Decimal::from(-1)), Some(Variant::B));
+        assert_eq!(test.record_outcome(&order, Decimal::from(-1)), None);
+
+        for i in 0..12 {
+            test.record(Variant::A, Decimal::from(2 + i % 2));
+        }
+        for i in 0..11 {
+            test.record(Variant::B, Decimal::from(-(i % 2)));
+        }
+
+        let a = test.scorecard(Variant::A);
+        assert_eq!(a.trades, 12);
+        assert_eq!(a.wins, 12);
+        assert_eq!(a.total_pnl, Decimal::from(30));
+        assert!((a.mean_pnl() - 2.5).abs() < 1e-9);
+
+        let b = test.scorecard(Variant::B);
+        assert_eq!(b.trades, 12);
+        assert_eq!(b.wins, 0);
+        assert!(b.total_pnl < Decimal::ZERO);
+
+        let report = test.report();
+        assert_eq!(report.winner, Some(Variant::A));
+        assert!(report.t_statistic.unwrap() > 1.96);
+    }
+}
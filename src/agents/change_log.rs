@@ -0,0 +1,138 @@
+//! Audit trail of configuration changes made by self-evolution
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::agents::parameters::{ParameterChange, ParameterValue};
+use crate::core::types::AgentId;
+
+/// One parameter change applied to a running agent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigChange {
+    pub agent: AgentId,
+    pub key: String,
+    pub old: ParameterValue,
+    pub new: ParameterValue,
+    pub timestamp: DateTime<Utc>,
+    pub reason: String,
+}
+
+/// Shared, bounded history of applied configuration changes, oldest first
+#[derive(Debug, Clone)]
+pub struct ConfigChangeLog {
+    entries: Arc<RwLock<VecDeque<ConfigChange>>>,
+    capacity: usize,
+}
+
+impl ConfigChangeLog {
+    /// Create a log keeping at most `capacity` changes
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Arc::new(RwLock::new(VecDeque::new())),
+            capacity,
+        }
+    }
+
+    /// Record the changes an agent applied, evicting the oldest entries beyond
+    /// capacity. Updates that left a value as it was are not recorded.
+    pub async fn record(&self, agent: AgentId, changes: &[ParameterChange], reason: &str) {
+        if self.capacity == 0 || changes.iter().all(|change| change.old == change.new) {
+            return;
+        }
+
+        let timestamp = Utc::now();
+        let mut entries = self.entries.write().await;
+        for change in changes.iter().filter(|change| change.old != change.new) {
+            entries.push_back(ConfigChange {
+                agent,
+                key: change.key.to_string(),
+                old: change.old,
+                new: change.new,
+                timestamp,
+                reason: reason.to_string(),
+            });
+        }
+        while entries.len() > self.capacity {
+            entries.pop_front();
+        }
+    }
+
+    /// Recorded changes, optionally restricted to one agent
+    pub async fn entries(&self, agent: Option<AgentId>) -> Vec<ConfigChange> {
+        self.entries
+            .read()
+            .await
+            .iter()
+            .filter(|change| agent.is_none_or(|id| change.agent == id))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::intelligence::MarketIntelligenceAgent;
+    use crate::agents::risk::RiskManagementAgent;
+    use crate::agents::traits::{AgentWiring, AutonomousAgent, SystemFeedback};
+    use crate::core::config::SystemConfig;
+    use crate::core::types::SystemContext;
+    use tokio::sync::{broadcast, mpsc};
+
+    #[tokio::test]
+    async fn test_evolution_updates_are_recorded_with_before_and_after() {
+        let config = SystemConfig::default();
+        let context = SystemContext::initial(&config);
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let log = ConfigChangeLog::new(config.monitoring.max_config_changes);
+
+        let mut intelligence = MarketIntelligenceAgent::new(
+            config.agents.market_intelligence.clone(),
+            config.api.clone(),
+            sender.clone(),
+            event_sender.clone(),
+        ).await.unwrap()
+            .with_change_log(log.clone());
+        let mut risk = RiskManagementAgent::new(
+            config.agents.risk_management.clone(),
+            config.risk.clone(),
+            sender,
+            event_sender,
+            Arc::new(RwLock::new(context.clone())),
+        ).await.unwrap()
+            .with_change_log(log.clone());
+
+        let feedback = SystemFeedback {
+            performance_score: 0.3,
+            market_conditions: "Volatile".to_string(),
+            recent_trades: Vec::new(),
+            risk_metrics: context.risk_metrics.clone(),
+            suggestions: Vec::new(),
+        };
+        intelligence.handle_feedback(&feedback).await.unwrap();
+        risk.handle_feedback(&feedback).await.unwrap();
+
+        let entries = log.entries(None).await;
+        assert_eq!(entries.len(), 2);
+
+        assert_eq!(entries[0].agent, intelligence.agent_id());
+        assert_eq!(entries[0].key, "update_interval_ms");
+        assert_eq!(entries[0].old, ParameterValue::Integer(100));
+        assert_eq!(entries[0].new, ParameterValue::Integer(50));
+
+        assert_eq!(entries[1].agent, risk.agent_id());
+        assert_eq!(entries[1].key, "max_portfolio_heat");
+        assert_eq!(entries[1].old, ParameterValue::Float(0.8));
+        assert_eq!(entries[1].new, ParameterValue::Float(0.6));
+        assert!(entries[1].reason.contains("Volatile"));
+
+        // Re-applying the same value is not a change
+        intelligence.handle_feedback(&feedback).await.unwrap();
+        assert_eq!(log.entries(None).await.len(), 2);
+        assert_eq!(log.entries(Some(risk.agent_id())).await, vec![entries[1].clone()]);
+    }
+}
@@ -0,0 +1,253 @@
+//! Reliability-weighted consensus across signal sources
+//!
+//! Each source's confidence is scaled by how often its past calls turned out
+//! right, so a historically unreliable agent's votes count for less when
+//! signals on the same symbol are combined.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::core::config::ReliabilityConfig;
+use crate::core::types::{SignalType, Symbol, TradingSignal};
+
+/// Tracks a reliability weight per signal source from realized outcomes
+#[derive(Debug, Clone)]
+pub struct SourceReliability {
+    config: ReliabilityConfig,
+    weights: HashMap<String, f64>,
+}
+
+impl SourceReliability {
+    /// Create a tracker from configuration; unseen sources start fully trusted
+    pub fn from_config(config: &ReliabilityConfig) -> Self {
+        Self {
+            config: config.clone(),
+            weights: HashMap::new(),
+        }
+    }
+
+    /// Current weight of a source in (min_weight, 1.0], or 1.0 when weighting is disabled
+    pub fn weight(&self, source: &str) -> f64 {
+        if !self.config.enabled {
+            return 1.0;
+        }
+        self.weights.get(source).copied().unwrap_or(1.0)
+    }
+
+    /// Override a source's weight, clamped to the configured range
+    pub fn set_weight(&mut self, source: &str, weight: f64) {
+        let weight = weight.clamp(self.config.min_weight, 1.0);
+        self.weights.insert(source.to_string(), weight);
+    }
+
+    /// Move a source's weight towards its realized accuracy
+    pub fn record_outcome(&mut self, source: &str, correct: bool) {
+        let accuracy = if correct { 1.0 } else { 0.0 };
+        let weight = self.weights.get(source).copied().unwrap_or(1.0);
+        self.set_weight(source, weight + self.config.learning_rate * (accuracy - weight));
+    }
+}
+
+/// Direction a consensus settled on for one symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsensusDecision {
+    pub symbol: Symbol,
+    pub signal_type: SignalType,
+    pub confidence: f64, // Share of reliability-weighted confidence behind the decision
+    pub sources: Vec<String>,
+}
+
+impl ConsensusDecision {
+    /// Whether a signal voted for the side this decision settled on
+    pub fn agrees_with(&self, signal: &TradingSignal) -> bool {
+        signal.symbol == self.symbol && Side::from(&signal.signal_type) == Side::from(&self.signal_type)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Side {
+    Buy,
+    Sell,
+    Hold,
+}
+
+impl From<&SignalType> for Side {
+    fn from(signal_type: &SignalType) -> Self {
+        match signal_type {
+            SignalType::Buy | SignalType::StrongBuy => Side::Buy,
+            SignalType::Sell | SignalType::StrongSell => Side::Sell,
+            SignalType::Hold => Side::Hold,
+        }
+    }
+}
+
+/// Combine signals on one symbol into a decision, weighting each signal's
+/// confidence by its source's reliability. Returns `None` if there are no
+/// signals or the winning side's share of weighted confidence is below `threshold`.
+pub fn aggregate(
+    signals: &[TradingSignal],
+    reliability: &SourceReliability,
+    threshold: f64,
+) -> Option<ConsensusDecision> {
+    let symbol = signals.first()?.symbol.clone();
+
+    let mut scores: HashMap<Side, f64> = HashMap::new();
+    for signal in signals.iter().filter(|signal| signal.symbol == symbol) {
+        let vote = signal.confidence.clamp(0.0, 1.0) * reliability.weight(&signal.source);
+        *scores.entry(Side::from(&signal.signal_type)).or_insert(0.0) += vote;
+    }
+
+    let total: f64 = scores.values().sum();
+    if total <= 0.0 {
+        return None;
+    }
+
+    let (side, score) = [Side::Buy, Side::Sell, Side::Hold]
+        .into_iter()
+        .map(|side| (side, scores.get(&side).copied().unwrap_or(0.0)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+    let confidence = score / total;
+    if confidence < threshold {
+        return None;
+    }
+
+    let sources = signals
+        .iter()
+        .filter(|signal| signal.symbol == symbol && Side::from(&signal.signal_type) == side)
+        .map(|signal| signal.source.clone())
+        .collect();
+    let signal_type = match side {
+        Side::Buy => SignalType::Buy,
+        Side::Sell => SignalType::Sell,
+        Side::Hold => SignalType::Hold,
+    };
+
+    Some(ConsensusDecision {
+        symbol,
+        signal_type,
+        confidence,
+        sources,
+    })
+}
+
+/// Coordinator's request for agents to vote on a proposed signal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoteRequest {
+    pub id: Uuid,
+    pub signal: TradingSignal,
+}
+
+/// One agent's answer to a vote request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Vote {
+    pub request_id: Uuid,
+    pub voter: String,
+    pub approve: bool,
+    pub confidence: f64,
+    pub veto: bool, // Rejects the proposal outright, whatever the other votes
+    pub reason: String,
+}
+
+/// Outcome of tallying the votes on one proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoteTally {
+    pub approval: f64, // Share of confidence- and reliability-weighted votes in favour
+    pub vetoed_by: Option<String>,
+    pub approved: bool,
+}
+
+/// Tally votes on a proposal, each weighted by its confidence and its voter's
+/// reliability. The proposer counts as an approval weighted the same way by
+/// the signal's source; any veto rejects it, and otherwise the approval share
+/// must exceed `threshold`.
+pub fn tally(proposal: &TradingSignal, votes: &[Vote], reliability: &SourceReliability, threshold: f64) -> VoteTally {
+    let mut approving = proposal.confidence.clamp(0.0, 1.0) * reliability.weight(&proposal.source);
+    let mut total = approving;
+    for vote in votes {
+        let weight = vote.confidence.clamp(0.0, 1.0) * reliability.weight(&vote.voter);
+        total += weight;
+        if vote.approve {
+            approving += weight;
+        }
+    }
+
+    let approval = if total > 0.0 { approving / total } else { 0.0 };
+    let vetoed_by = votes.iter().find(|vote| vote.veto).map(|vote| vote.voter.clone());
+    VoteTally {
+        approval,
+        approved: vetoed_by.is_none() && approval > threshold,
+        vetoed_by,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signal(source: &str, signal_type: SignalType, confidence: f64) -> TradingSignal {
+        TradingSignal {
+            symbol: "AAPL".to_string(),
+            signal_type,
+            strength: 0.5,
+            confidence,
+            timestamp: chrono::Utc::now(),
+            reasoning: String::new(),
+            source: source.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_lower_reliability_reduces_influence() {
+        let mut reliability = SourceReliability::from_config(&ReliabilityConfig::default());
+        let signals = vec![
+            signal("momentum", SignalType::StrongBuy, 0.8),
+            signal("sentiment", SignalType::Sell, 0.7),
+        ];
+
+        let decision = aggregate(&signals, &reliability, 0.5).unwrap();
+        assert!(matches!(decision.signal_type, SignalType::Buy));
+        assert!((decision.confidence - 0.8 / 1.5).abs() < 1e-9);
+        assert_eq!(decision.sources, vec!["momentum".to_string()]);
+
+        // Halving the buyer's weight hands the decision to the seller
+        reliability.set_weight("momentum", 0.5);
+        let decision = aggregate(&signals, &reliability, 0.5).unwrap();
+        assert!(matches!(decision.signal_type, SignalType::Sell));
+        assert!((decision.confidence - 0.7 / 1.1).abs() < 1e-9);
+
+        // Wrong calls erode a weight towards the floor, right calls restore it
+        for _ in 0..100 {
+            reliability.record_outcome("sentiment", false);
+        }
+        assert_eq!(reliability.weight("sentiment"), 0.1);
+        reliability.record_outcome("sentiment", true);
+        assert!(reliability.weight("sentiment") > 0.1);
+    }
+
+    #[test]
+    fn test_votes_count_by_voter_reliability() {
+        let mut reliability = SourceReliability::from_config(&ReliabilityConfig::default());
+        let proposal = signal("market_intelligence", SignalType::Buy, 0.6);
+        let vote = |voter: &str, approve: bool| Vote {
+            request_id: Uuid::new_v4(),
+            voter: voter.to_string(),
+            approve,
+            confidence: 0.8,
+            veto: false,
+            reason: String::new(),
+        };
+        let votes = vec![vote("risk_management", true), vote("learning_engine", false)];
+
+        // Equally trusted, the dissent holds approval to (0.6 + 0.8) / 2.2
+        let result = tally(&proposal, &votes, &reliability, 0.65);
+        assert!((result.approval - 1.4 / 2.2).abs() < 1e-9);
+        assert!(!result.approved);
+
+        // A discredited dissenter counts for less and the proposal carries
+        reliability.set_weight("learning_engine", 0.25);
+        let result = tally(&proposal, &votes, &reliability, 0.65);
+        assert!((result.approval - 1.4 / 1.6).abs() < 1e-9);
+        assert!(result.approved);
+    }
+}
@@ -0,0 +1,98 @@
+//! Minimum interval between applied evolution updates
+//!
+//! Feedback arriving while an agent's cooldown runs is skipped rather than
+//! evolved, so parameters don't thrash between successive feedback rounds.
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tokio::time::{Duration, Instant};
+
+/// When an agent last applied evolved parameters, shared across its clones
+#[derive(Debug, Clone)]
+pub struct EvolutionCooldown {
+    period: Duration,
+    last_applied: Arc<RwLock<Option<Instant>>>,
+}
+
+impl EvolutionCooldown {
+    /// Require `period` between applied updates (zero disables the cooldown)
+    pub fn new(period: Duration) -> Self {
+        Self {
+            period,
+            last_applied: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Time left before another update may be applied, or `None` if one may now
+    pub async fn remaining(&self) -> Option<Duration> {
+        let last_applied = (*self.last_applied.read().await)?;
+        let remaining = self.period.saturating_sub(last_applied.elapsed());
+        (!remaining.is_zero()).then_some(remaining)
+    }
+
+    /// Start the cooldown from an update applied now
+    pub async fn record_applied(&self) {
+        *self.last_applied.write().await = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::change_log::ConfigChangeLog;
+    use crate::agents::intelligence::MarketIntelligenceAgent;
+    use crate::agents::traits::{AgentWiring, AutonomousAgent, SystemFeedback};
+    use crate::core::config::SystemConfig;
+    use crate::core::types::SystemContext;
+    use std::collections::HashMap;
+    use tokio::sync::{broadcast, mpsc};
+
+    #[tokio::test(start_paused = true)]
+    async fn test_evolution_within_cooldown_is_skipped() {
+        let config = SystemConfig::default();
+        let context = SystemContext::initial(&config);
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let log = ConfigChangeLog::new(config.monitoring.max_config_changes);
+
+        let mut intelligence = MarketIntelligenceAgent::new(
+            config.agents.market_intelligence.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+        ).await.unwrap()
+            .with_change_log(log.clone())
+            .with_evolution_cooldown(EvolutionCooldown::new(Duration::from_secs(60)));
+
+        let feedback = SystemFeedback {
+            performance_score: 0.3,
+            market_conditions: "Volatile".to_string(),
+            recent_trades: Vec::new(),
+            risk_metrics: context.risk_metrics.clone(),
+            suggestions: Vec::new(),
+        };
+        let reset = HashMap::from([("update_interval_ms".to_string(), serde_json::json!(100))]);
+
+        assert!(intelligence.handle_feedback(&feedback).await.unwrap().strategy_updated);
+        assert_eq!(log.entries(None).await.len(), 1);
+
+        // Feedback inside the cooldown is skipped, leaving the parameter alone
+        intelligence.apply_parameters(&reset).unwrap();
+        tokio::time::advance(Duration::from_secs(59)).await;
+        assert!(!intelligence.handle_feedback(&feedback).await.unwrap().strategy_updated);
+        assert_eq!(log.entries(None).await.len(), 1);
+
+        // Once it has passed the update is applied again
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert!(intelligence.handle_feedback(&feedback).await.unwrap().strategy_updated);
+        assert_eq!(log.entries(None).await.len(), 2);
+    }
+
+    #[test]
+    fn test_cooldown_for_unknown_agent_is_refused() {
+        let mut config = SystemConfig::default();
+        config.agents.evolution_cooldowns.per_agent.insert("risk_managment".to_string(), 60);
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("Unknown agent 'risk_managment'"), "{}", error);
+    }
+}
@@ -1,27 +1,43 @@
 //! Master Coordinator Agent - Strategic planning and agent orchestration
 
 use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{broadcast, mpsc, RwLock};
 use tokio::time::{interval, Duration};
 use tracing::{info, warn, error};
+use uuid::Uuid;
 
 use crate::core::config::CoordinatorConfig;
 use crate::core::errors::TradingResult;
+use crate::core::performance::TradeRecord;
 use crate::core::types::{
     AgentCapability, AgentId, AgentMessage, SystemContext, 
     PerformanceMetrics, TradingSignal, MessageType
 };
+use crate::agents::change_log::ConfigChangeLog;
+use crate::agents::cooldown::EvolutionCooldown;
+use crate::agents::consensus::{aggregate, tally, ConsensusDecision, SourceReliability, Vote, VoteRequest, VoteTally};
+use crate::agents::decision_trace::{DecisionTraces, PipelineStage};
+use crate::agents::parameters::{validate_parameters, ParameterChange, ParameterSpec, ParameterValue};
 use crate::agents::traits::{
-    AutonomousAgent, BaseAgent, AgentResult, SystemFeedback, 
+    next_message, AgentWiring, AutonomousAgent, BaseAgent, AgentResult, SystemFeedback, 
     EvolutionResult, Requirements, CodeGeneration
 };
 
+/// Parameters this agent accepts from strategy evolution
+const EVOLVABLE_PARAMETERS: &[ParameterSpec] = &[
+    ParameterSpec::integer("decision_timeout_ms", 10, 10_000),
+];
+
 /// Master Coordinator Agent for strategic planning and system orchestration
 #[derive(Clone)]
 pub struct MasterCoordinatorAgent {
     base: BaseAgent,
     config: CoordinatorConfig,
+    reliability: Arc<RwLock<SourceReliability>>,
+    decision_traces: Option<DecisionTraces>,
 }
 
 impl MasterCoordinatorAgent {
@@ -29,17 +45,134 @@ impl MasterCoordinatorAgent {
     pub async fn new(
         config: CoordinatorConfig,
         message_sender: mpsc::UnboundedSender<AgentMessage>,
+        event_sender: broadcast::Sender<AgentMessage>,
         system_context: Arc<RwLock<SystemContext>>,
     ) -> TradingResult<Self> {
         let capabilities = config.capabilities.clone();
-        let base = BaseAgent::new(capabilities, message_sender, system_context);
+        let base = BaseAgent::new(capabilities, message_sender, event_sender, system_context);
+        
+        let reliability = Arc::new(RwLock::new(SourceReliability::from_config(&config.reliability)));
         
         Ok(Self {
             base,
             config,
+            reliability,
+            decision_traces: None,
         })
     }
     
+    /// Record consensus verdicts in the given decision traces
+    pub fn with_decision_traces(mut self, traces: DecisionTraces) -> Self {
+        self.decision_traces = Some(traces);
+        self
+    }
+    
+    /// Combine signals on one symbol, weighting each source by its track record
+    pub async fn consensus(&self, signals: &[TradingSignal]) -> Option<ConsensusDecision> {
+        let decision = {
+            let reliability = self.reliability.read().await;
+            aggregate(signals, &reliability, self.config.consensus_threshold)
+        };
+        
+        if let (Some(traces), Some(first)) = (&self.decision_traces, signals.first()) {
+            for signal in signals.iter().filter(|signal| signal.symbol == first.symbol) {
+                match &decision {
+                    Some(decision) if decision.agrees_with(signal) => traces.pass(signal, PipelineStage::Consensus).await,
+                    Some(decision) => {
+                        let reason = format!("Outvoted by {:?} at {:.2} confidence", decision.signal_type, decision.confidence);
+                        traces.fail(signal, PipelineStage::Consensus, reason).await;
+                    }
+                    None => {
+                        let reason = format!("No side reached the {:.2} consensus threshold", self.config.consensus_threshold);
+                        traces.fail(signal, PipelineStage::Consensus, reason).await;
+                    }
+                }
+            }
+        }
+        decision
+    }
+    
+    /// Put a proposed signal to the configured voters and forward it to
+    /// execution if the reliability-weighted approval clears the consensus
+    /// threshold. Only voters registered in the directory, i.e. enabled and
+    /// started, are asked; votes not back within `decision_timeout_ms` are
+    /// not counted.
+    pub async fn put_to_vote(&self, signal: TradingSignal) -> TradingResult<VoteTally> {
+        let request = VoteRequest { id: Uuid::new_v4(), signal };
+        let payload = serde_json::to_value(&request)?;
+        let timeout = Duration::from_millis(self.config.decision_timeout_ms);
+        let voters: Vec<(&String, AgentId)> = self
+            .config
+            .voters
+            .iter()
+            .filter_map(|voter| Some((voter, self.base.agent_id_of(voter)?)))
+            .collect();
+        
+        // Ask every voter at once, each reply correlated with its own request
+        let replies = futures::future::join_all(voters.iter().map(|(_, to)| {
+            let message = AgentMessage::new(self.base.id, *to, MessageType::VoteRequest, payload.clone());
+            self.base.request(message, timeout)
+        })).await;
+        
+        let mut votes: Vec<Vote> = Vec::new();
+        for ((voter, _), reply) in voters.iter().zip(replies) {
+            let vote = reply.and_then(|reply| Ok(serde_json::from_value::<Vote>(reply.payload)?));
+            match vote {
+                Ok(vote) if &vote.voter == *voter => votes.push(vote),
+                Ok(vote) => warn!("🗳️  Ignoring a vote cast as {} by {}", vote.voter, voter),
+                Err(e) => warn!("🗳️  No vote from {} on {}: {}", voter, request.signal.symbol, e),
+            }
+        }
+        
+        let signal = request.signal;
+        let result = {
+            let reliability = self.reliability.read().await;
+            tally(&signal, &votes, &reliability, self.config.consensus_threshold)
+        };
+        let reason = match &result.vetoed_by {
+            Some(voter) => {
+                let vote = votes.iter().find(|vote| &vote.voter == voter);
+                format!("Vetoed by {}: {}", voter, vote.map(|vote| vote.reason.as_str()).unwrap_or_default())
+            }
+            None => format!(
+                "Approval {:.2} against the {:.2} consensus threshold",
+                result.approval, self.config.consensus_threshold
+            ),
+        };
+        
+        if let Some(traces) = &self.decision_traces {
+            if result.approved {
+                traces.pass(&signal, PipelineStage::Consensus).await;
+            } else {
+                traces.fail(&signal, PipelineStage::Consensus, reason.clone()).await;
+            }
+        }
+        
+        if result.approved {
+            info!("🗳️  {} {:?} approved by vote: {}", signal.symbol, signal.signal_type, reason);
+            let message = AgentMessage::broadcast(self.base.id, MessageType::TradingSignal, serde_json::to_value(&signal)?);
+            self.base.send_message(message).await?;
+        } else {
+            info!("🗳️  {} {:?} rejected by vote: {}", signal.symbol, signal.signal_type, reason);
+        }
+        Ok(result)
+    }
+    
+    /// Update a source's reliability once its signal's outcome is known
+    pub async fn record_signal_outcome(&self, source: &str, correct: bool) {
+        let mut reliability = self.reliability.write().await;
+        reliability.record_outcome(source, correct);
+        info!("⚖️  Reliability of {} now {:.2}", source, reliability.weight(source));
+    }
+    
+    /// Score a closed trade against the source whose signal opened it: a
+    /// profit counts as a correct call, anything else as a wrong one
+    async fn record_trade_outcome(&self, trade: &TradeRecord) {
+        if let Some(source) = &trade.source {
+            self.record_signal_outcome(source, trade.pnl > Decimal::ZERO).await;
+        }
+    }
+    
     /// Perform strategic planning
     async fn strategic_planning(&self) -> TradingResult<()> {
         info!("🎯 Executing strategic planning...");
@@ -54,13 +187,7 @@ impl MasterCoordinatorAgent {
         
         // Send recommendations to other agents
         for recommendation in recommendations {
-            let message = AgentMessage {
-                from: self.base.id,
-                to: uuid::Uuid::nil(), // Broadcast
-                message_type: MessageType::SystemCommand,
-                payload: serde_json::to_value(&recommendation)?,
-                timestamp: chrono::Utc::now(),
-            };
+            let message = AgentMessage::broadcast(self.base.id, MessageType::SystemCommand, serde_json::to_value(&recommendation)?);
             
             self.base.send_message(message).await?;
         }
@@ -69,6 +196,29 @@ impl MasterCoordinatorAgent {
         Ok(())
     }
     
+    /// Build system feedback from the current context
+    async fn build_system_feedback(&self, context: &SystemContext) -> TradingResult<SystemFeedback> {
+        Ok(SystemFeedback {
+            performance_score: self.calculate_performance_score(context).await?,
+            market_conditions: format!("{:?}", context.market_regime),
+            recent_trades: Vec::new(),
+            risk_metrics: context.risk_metrics.clone(),
+            suggestions: self.generate_strategic_recommendations(context).await?,
+        })
+    }
+    
+    /// Aggregate system feedback and distribute it to all agents for evolution
+    async fn distribute_feedback(&self) -> TradingResult<()> {
+        let context = self.base.get_system_context().await;
+        let feedback = self.build_system_feedback(&context).await?;
+        
+        info!("📣 Distributing system feedback (score: {:.2})", feedback.performance_score);
+        
+        let message = AgentMessage::broadcast(self.base.id, MessageType::SystemFeedback, serde_json::to_value(&feedback)?);
+        
+        self.base.send_message(message).await
+    }
+    
     /// Calculate overall system performance score
     async fn calculate_performance_score(&self, context: &SystemContext) -> TradingResult<f64> {
         let metrics = &context.performance_metrics;
@@ -114,6 +264,14 @@ impl MasterCoordinatorAgent {
     }
 }
 
+impl AgentWiring for MasterCoordinatorAgent {
+    const NAME: &'static str = "master_coordinator";
+    
+    fn base_mut(&mut self) -> &mut BaseAgent {
+        &mut self.base
+    }
+}
+
 #[async_trait]
 impl AutonomousAgent for MasterCoordinatorAgent {
     async fn execute_mission(&self, context: &SystemContext) -> TradingResult<AgentResult> {
@@ -178,6 +336,26 @@ impl AutonomousAgent for MasterCoordinatorAgent {
         })
     }
     
+    fn apply_parameters(&mut self, parameters: &HashMap<String, serde_json::Value>) -> TradingResult<Vec<ParameterChange>> {
+        let updates = validate_parameters(EVOLVABLE_PARAMETERS, parameters)?;
+        let mut applied = Vec::new();
+        
+        for (key, value) in updates {
+            let old = match key {
+                "decision_timeout_ms" => {
+                    info!("🔧 decision_timeout_ms: {} -> {}", self.config.decision_timeout_ms, value);
+                    let old = ParameterValue::Integer(self.config.decision_timeout_ms);
+                    self.config.decision_timeout_ms = value.as_u64();
+                    old
+                }
+                _ => continue,
+            };
+            applied.push(ParameterChange { key, old, new: value });
+        }
+        
+        Ok(applied)
+    }
+    
     fn capabilities(&self) -> Vec<AgentCapability> {
         self.base.capabilities.clone()
     }
@@ -186,12 +364,29 @@ impl AutonomousAgent for MasterCoordinatorAgent {
         self.base.id
     }
     
+    fn change_log(&self) -> Option<&ConfigChangeLog> {
+        self.base.change_log.as_ref()
+    }
+    
+    fn evolution_cooldown(&self) -> Option<&EvolutionCooldown> {
+        self.base.evolution_cooldown.as_ref()
+    }
+    
     async fn run(&mut self) -> TradingResult<()> {
+        if !self.base.await_prerequisites().await? {
+            return Ok(());
+        }
         info!("🎯 Master Coordinator starting execution loop...");
         
         let mut planning_interval = interval(Duration::from_secs(
             self.config.strategic_planning_interval_hours * 3600
         ));
+        let mut feedback_interval = interval(Duration::from_secs(self.config.feedback_interval_secs));
+        let mut events = self.base.subscribe_events();
+        let mut inbox = self.base.inbox().await;
+        let mut heartbeat = self.base.heartbeat_timer();
+        
+        self.base.mark_ready();
         
         loop {
             tokio::select! {
@@ -200,11 +395,38 @@ impl AutonomousAgent for MasterCoordinatorAgent {
                         error!("Strategic planning error: {}", e);
                     }
                 }
-                _ = tokio::time::sleep(Duration::from_millis(100)) => {
-                    if self.base.should_shutdown().await {
-                        break;
+                _ = feedback_interval.tick() => {
+                    if let Err(e) = self.distribute_feedback().await {
+                        error!("Feedback distribution error: {}", e);
+                    }
+                }
+                _ = heartbeat.tick() => self.base.send_heartbeat(),
+                Some(message) = next_message(&mut events, &mut inbox) => {
+                    match message.message_type {
+                        MessageType::SignalProposal => match serde_json::from_value::<TradingSignal>(message.payload) {
+                            Ok(signal) => {
+                                // Waiting on votes here would hold up every other message
+                                let coordinator = self.clone();
+                                tokio::spawn(async move {
+                                    if let Err(e) = coordinator.put_to_vote(signal).await {
+                                        error!("Signal vote error: {}", e);
+                                    }
+                                });
+                            }
+                            Err(e) => warn!("Ignoring unreadable signal proposal: {}", e),
+                        },
+                        MessageType::TradeClosed => match serde_json::from_value::<TradeRecord>(message.payload) {
+                            Ok(trade) => self.record_trade_outcome(&trade).await,
+                            Err(e) => warn!("Ignoring unreadable closed trade: {}", e),
+                        },
+                        _ => {
+                            if let Err(e) = self.handle_event(message).await {
+                                error!("Coordinator event error: {}", e);
+                            }
+                        }
                     }
                 }
+                _ = self.base.shutdown_requested() => break,
             }
         }
         
@@ -214,7 +436,214 @@ impl AutonomousAgent for MasterCoordinatorAgent {
     
     async fn shutdown(&mut self) -> TradingResult<()> {
         info!("🛑 Master Coordinator shutting down...");
-        self.base.request_shutdown().await;
+        self.base.request_shutdown();
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::learning::LearningEngineAgent;
+    use crate::agents::risk::RiskManagementAgent;
+    use crate::core::ai_thoughts::AIThoughtBroadcaster;
+    use crate::core::config::SystemConfig;
+    use crate::core::directory::AgentDirectory;
+    use crate::core::replies::ReplyRouter;
+    use crate::core::types::SignalType;
+    use crate::risk::heat::HeatMethod;
+
+    /// Answer the coordinator's vote requests on behalf of risk and, if it
+    /// runs, learning, handing each reply to the router as the system would
+    async fn answer_votes(
+        replies: &ReplyRouter,
+        bus: &mut mpsc::UnboundedReceiver<AgentMessage>,
+        risk: &RiskManagementAgent,
+        learning: Option<&LearningEngineAgent>,
+    ) {
+        for _ in 0..1 + usize::from(learning.is_some()) {
+            let message = bus.recv().await.unwrap();
+            assert!(matches!(message.message_type, MessageType::VoteRequest));
+            let request: VoteRequest = serde_json::from_value(message.payload.clone()).unwrap();
+            let (voter, vote) = match learning {
+                Some(learning) if message.to == learning.agent_id() => (learning.agent_id(), learning.vote_on(&request).await.unwrap()),
+                _ => {
+                    assert_eq!(message.to, risk.agent_id());
+                    (risk.agent_id(), risk.vote_on(&request).await.unwrap())
+                }
+            };
+            let reply = message.reply(voter, MessageType::VoteResponse, serde_json::to_value(&vote).unwrap());
+            assert!(replies.deliver(reply).await);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_risk_veto_blocks_signal_despite_intelligence_approval() {
+        let mut config = SystemConfig::default();
+        config.risk.audit.enabled = false;
+        config.risk.portfolio_heat.method = HeatMethod::DailyLoss;
+        config.risk.validation_cache.enabled = false;
+        config.agents.master_coordinator.decision_timeout_ms = 5_000;
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, mut bus) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let directory = AgentDirectory::new();
+        let replies = ReplyRouter::new();
+
+        let coordinator = MasterCoordinatorAgent::new(
+            config.agents.master_coordinator.clone(),
+            sender.clone(),
+            event_sender.clone(),
+            system_context.clone(),
+        ).await.unwrap()
+            .with_directory(directory.clone())
+            .with_reply_router(replies.clone());
+        let risk = RiskManagementAgent::new(
+            config.agents.risk_management.clone(),
+            config.risk.clone(),
+            sender.clone(),
+            event_sender.clone(),
+            system_context.clone(),
+        ).await.unwrap().with_directory(directory.clone());
+        let learning = LearningEngineAgent::new(
+            config.agents.learning_engine.clone(),
+            sender,
+            event_sender,
+            system_context.clone(),
+            AIThoughtBroadcaster::new(10),
+        ).await.unwrap().with_directory(directory);
+
+        // Intelligence proposes with high confidence
+        let signal = TradingSignal {
+            symbol: "AAPL".to_string(),
+            signal_type: SignalType::Buy,
+            strength: 0.8,
+            confidence: 0.9,
+            timestamp: chrono::Utc::now(),
+            reasoning: "test".to_string(),
+            source: "market_intelligence".to_string(),
+        };
+
+        // Within limits every voter approves and the signal goes on to execution
+        let vote = tokio::spawn({
+            let coordinator = coordinator.clone();
+            let signal = signal.clone();
+            async move { coordinator.put_to_vote(signal).await.unwrap() }
+        });
+        answer_votes(&replies, &mut bus, &risk, Some(&learning)).await;
+        let result = vote.await.unwrap();
+        assert!(result.approved);
+        assert!((result.approval - 1.0).abs() < 1e-9);
+        let forwarded = bus.recv().await.unwrap();
+        assert!(matches!(forwarded.message_type, MessageType::TradingSignal));
+        assert_eq!(serde_json::from_value::<TradingSignal>(forwarded.payload).unwrap().symbol, "AAPL");
+
+        // A 90% daily loss pushes portfolio heat past the limit and risk vetoes
+        system_context.write().await.portfolio.daily_pnl = Decimal::from(-90);
+        let vote = tokio::spawn({
+            let coordinator = coordinator.clone();
+            async move { coordinator.put_to_vote(signal).await.unwrap() }
+        });
+        answer_votes(&replies, &mut bus, &risk, Some(&learning)).await;
+        let result = vote.await.unwrap();
+        assert!(!result.approved);
+        assert_eq!(result.vetoed_by.as_deref(), Some("risk_management"));
+        assert!(bus.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_vote_waits_only_for_running_voters() {
+        let mut config = SystemConfig::default();
+        config.risk.audit.enabled = false;
+        config.risk.validation_cache.enabled = false;
+        config.agents.master_coordinator.decision_timeout_ms = 60_000;
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, mut bus) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let directory = AgentDirectory::new();
+        let replies = ReplyRouter::new();
+
+        let coordinator = MasterCoordinatorAgent::new(
+            config.agents.master_coordinator.clone(),
+            sender.clone(),
+            event_sender.clone(),
+            system_context.clone(),
+        ).await.unwrap()
+            .with_directory(directory.clone())
+            .with_reply_router(replies.clone());
+        // The learning engine is a configured voter but never started
+        let risk = RiskManagementAgent::new(
+            config.agents.risk_management.clone(),
+            config.risk.clone(),
+            sender,
+            event_sender,
+            system_context,
+        ).await.unwrap().with_directory(directory);
+
+        let signal = TradingSignal {
+            symbol: "AAPL".to_string(),
+            signal_type: SignalType::Buy,
+            strength: 0.8,
+            confidence: 0.9,
+            timestamp: chrono::Utc::now(),
+            reasoning: "test".to_string(),
+            source: "market_intelligence".to_string(),
+        };
+        let vote = tokio::spawn({
+            let coordinator = coordinator.clone();
+            async move { coordinator.put_to_vote(signal).await.unwrap() }
+        });
+        answer_votes(&replies, &mut bus, &risk, None).await;
+
+        // Decided on the one running voter instead of waiting out the timeout
+        let result = tokio::time::timeout(Duration::from_secs(5), vote).await.unwrap().unwrap();
+        assert!(result.approved);
+    }
+
+    #[tokio::test]
+    async fn test_losing_closed_trade_lowers_its_source_reliability() {
+        let config = SystemConfig::default();
+        let (sender, _bus) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let coordinator = MasterCoordinatorAgent::new(
+            config.agents.master_coordinator.clone(),
+            sender,
+            event_sender.clone(),
+            Arc::new(RwLock::new(SystemContext::initial(&config))),
+        ).await.unwrap();
+        let reliability = coordinator.reliability.clone();
+        let running = tokio::spawn({
+            let mut coordinator = coordinator.clone();
+            async move { coordinator.run().await }
+        });
+        while event_sender.receiver_count() == 0 {
+            tokio::task::yield_now().await;
+        }
+
+        let closed = TradeRecord {
+            timestamp: chrono::Utc::now(),
+            pnl: Decimal::from(-25),
+            execution_time_ms: 1.0,
+            strategy: None,
+            order_id: Some(Uuid::new_v4()),
+            notional: Decimal::from(1_000),
+            costs: Decimal::ONE,
+            holding_secs: 60,
+            source: Some("market_intelligence".to_string()),
+        };
+        event_sender.send(AgentMessage::broadcast(
+            Uuid::new_v4(),
+            MessageType::TradeClosed,
+            serde_json::to_value(&closed).unwrap(),
+        )).unwrap();
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while reliability.read().await.weight("market_intelligence") >= 1.0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }).await.expect("closed trade never reached the source's reliability");
+        // Only the source behind the trade is marked down
+        assert_eq!(reliability.read().await.weight("technical_analysis"), 1.0);
+        running.abort();
+    }
+}
@@ -0,0 +1,192 @@
+//! Per-signal record of each decision pipeline stage's verdict
+//!
+//! Agents record whether a signal passed their stage and, if not, why. The
+//! resulting trace answers why a signal did or didn't become a trade.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::core::config::DecisionTraceConfig;
+use crate::core::types::{Symbol, TradingSignal};
+
+/// Stages a signal passes through on its way to becoming a trade
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PipelineStage {
+    SignalFilter,
+    Consensus,
+    RiskCheck,
+    Sizing,
+    Compliance,
+    Execution,
+}
+
+/// One stage's verdict on a signal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageVerdict {
+    pub stage: PipelineStage,
+    pub passed: bool,
+    pub reason: Option<String>, // Why the stage refused the signal
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Verdicts recorded for one signal, in the order stages reached them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionTrace {
+    pub signal_id: String,
+    pub symbol: Symbol,
+    pub verdicts: Vec<StageVerdict>,
+}
+
+impl DecisionTrace {
+    /// Latest verdict for a stage, if the signal reached it
+    pub fn verdict(&self, stage: PipelineStage) -> Option<&StageVerdict> {
+        self.verdicts.iter().rev().find(|verdict| verdict.stage == stage)
+    }
+
+    /// First stage that refused the signal
+    pub fn rejected_at(&self) -> Option<&StageVerdict> {
+        self.verdicts.iter().find(|verdict| !verdict.passed)
+    }
+}
+
+/// Shared, bounded store of decision traces, oldest first
+#[derive(Debug, Clone)]
+pub struct DecisionTraces {
+    traces: Arc<RwLock<VecDeque<DecisionTrace>>>,
+    capacity: usize,
+}
+
+impl DecisionTraces {
+    /// Keep traces for at most `max_traces` signals
+    pub fn from_config(config: &DecisionTraceConfig) -> Self {
+        Self {
+            traces: Arc::new(RwLock::new(VecDeque::new())),
+            capacity: config.max_traces,
+        }
+    }
+
+    /// Identifier a signal's trace is stored under
+    pub fn signal_id(signal: &TradingSignal) -> String {
+        format!("{}:{}:{}", signal.source, signal.symbol, signal.timestamp.to_rfc3339())
+    }
+
+    /// Record that a signal passed a stage
+    pub async fn pass(&self, signal: &TradingSignal, stage: PipelineStage) {
+        self.record(signal, stage, None).await;
+    }
+
+    /// Record that a stage refused a signal, and why
+    pub async fn fail(&self, signal: &TradingSignal, stage: PipelineStage, reason: impl Into<String>) {
+        self.record(signal, stage, Some(reason.into())).await;
+    }
+
+    async fn record(&self, signal: &TradingSignal, stage: PipelineStage, reason: Option<String>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let verdict = StageVerdict {
+            stage,
+            passed: reason.is_none(),
+            reason,
+            timestamp: Utc::now(),
+        };
+        let signal_id = Self::signal_id(signal);
+        let mut traces = self.traces.write().await;
+        if let Some(trace) = traces.iter_mut().rev().find(|trace| trace.signal_id == signal_id) {
+            trace.verdicts.push(verdict);
+            return;
+        }
+
+        traces.push_back(DecisionTrace {
+            signal_id,
+            symbol: signal.symbol.clone(),
+            verdicts: vec![verdict],
+        });
+        while traces.len() > self.capacity {
+            traces.pop_front();
+        }
+    }
+
+    /// Trace for one signal
+    pub async fn get(&self, signal_id: &str) -> Option<DecisionTrace> {
+        self.traces.read().await.iter().rev().find(|trace| trace.signal_id == signal_id).cloned()
+    }
+
+    /// Traces for signals on one symbol, oldest first
+    pub async fn for_symbol(&self, symbol: &str) -> Vec<DecisionTrace> {
+        self.traces.read().await.iter().filter(|trace| trace.symbol == symbol).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::coordinator::MasterCoordinatorAgent;
+    use crate::agents::risk::RiskManagementAgent;
+    use crate::agents::traits::RiskManager;
+    use crate::core::config::SystemConfig;
+    use crate::core::types::{SignalType, SystemContext};
+    use crate::risk::heat::HeatMethod;
+    use rust_decimal::Decimal;
+    use tokio::sync::{broadcast, mpsc};
+
+    #[tokio::test]
+    async fn test_risk_rejection_is_traced_after_earlier_stages_pass() {
+        let mut config = SystemConfig::default();
+        config.risk.audit.enabled = false;
+        config.risk.portfolio_heat.method = HeatMethod::DailyLoss;
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let traces = DecisionTraces::from_config(&config.monitoring.decision_trace);
+
+        let coordinator = MasterCoordinatorAgent::new(
+            config.agents.master_coordinator.clone(),
+            sender.clone(),
+            event_sender.clone(),
+            system_context.clone(),
+        ).await.unwrap()
+            .with_decision_traces(traces.clone());
+        let risk = RiskManagementAgent::new(
+            config.agents.risk_management.clone(),
+            config.risk.clone(),
+            sender,
+            event_sender,
+            system_context.clone(),
+        ).await.unwrap()
+            .with_decision_traces(traces.clone());
+
+        let signal = TradingSignal {
+            symbol: "AAPL".to_string(),
+            signal_type: SignalType::Buy,
+            strength: 0.8,
+            confidence: 0.9,
+            timestamp: Utc::now(),
+            reasoning: "test".to_string(),
+            source: "momentum".to_string(),
+        };
+        traces.pass(&signal, PipelineStage::SignalFilter).await;
+        assert!(coordinator.consensus(std::slice::from_ref(&signal)).await.is_some());
+
+        // A 90% daily loss pushes portfolio heat past the limit
+        system_context.write().await.portfolio.daily_pnl = Decimal::from(-90);
+        assert!(!risk.validate_trade(&signal).await.unwrap().approved);
+
+        let trace = traces.get(&DecisionTraces::signal_id(&signal)).await.unwrap();
+        let stages: Vec<(PipelineStage, bool)> = trace.verdicts.iter().map(|v| (v.stage, v.passed)).collect();
+        assert_eq!(stages, vec![
+            (PipelineStage::SignalFilter, true),
+            (PipelineStage::Consensus, true),
+            (PipelineStage::RiskCheck, false),
+        ]);
+        let rejected = trace.rejected_at().unwrap();
+        assert_eq!(rejected.stage, PipelineStage::RiskCheck);
+        assert!(rejected.reason.as_deref().unwrap().contains("portfolio heat"));
+        assert!(trace.verdict(PipelineStage::Execution).is_none());
+        assert_eq!(traces.for_symbol("AAPL").await.len(), 1);
+    }
+}
@@ -1,29 +1,98 @@
 //! Execution Engine Agent - High-speed trade execution
 
 use async_trait::async_trait;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tokio::sync::{mpsc, RwLock};
-use tokio::time::{interval, Duration};
+use tokio::sync::{broadcast, mpsc, RwLock};
+use tokio::time::{interval, timeout, Duration};
 use tracing::{info, warn, error};
 
-use crate::core::config::{ExecutionConfig, ApiConfig};
-use crate::core::errors::TradingResult;
+use crate::core::config::{ExecutionConfig, ApiConfig, TakeProfitConfig};
+use crate::core::errors::{TradingError, TradingResult};
 use crate::core::types::{
     AgentCapability, AgentId, AgentMessage, SystemContext, 
-    PerformanceMetrics, TradingSignal, Order, OrderType, OrderSide, OrderStatus, ExecutionResult
+    PerformanceMetrics, TradingSignal, Order, OrderType, OrderSide, OrderStatus, ExecutionResult,
+    MarketData, MarketRegime, Position, Fill, Liquidity, OrderId, CloseReason, ClosingOrder, OrderRejection, OrderCompletion,
+    AlertSeverity, RiskAlertNotice
 };
+use crate::agents::ab_test::AbTest;
+use crate::core::ai_thoughts::{AIAgent, AIThought, AIThoughtBroadcaster, ThoughtType};
+use crate::core::calendar::MarketCalendar;
+use crate::core::metrics::MetricsCollector;
+use crate::core::performance::TradeRecord;
+use crate::execution::algorithm::{AlgorithmSelector, ExecutionConditions};
+use crate::execution::api::{ApiClient, TradingEnvironment};
+use crate::execution::canary::CanaryRouter;
+use crate::execution::commission::CommissionModel;
+use crate::execution::journal::{ExecutionError, TradeJournal};
+use crate::execution::orders::{ConflictResolution, OrderBook, OrderManager, OrderProgress, OrderStatusSource};
+use crate::execution::protection::ProtectionStore;
+use crate::execution::retry::RetryBudget;
+use crate::execution::multi_leg::{MultiLegFill, MultiLegOrder, MultiLegSimulator, QuoteSource};
+use crate::execution::sizing::{MinimumCheck, OrderMinimum, PositionSizer};
+use crate::execution::slippage::{SlippageAttributor, SlippageMonitor};
+use crate::execution::take_profit::{ProtectiveLevels, ScaleOut};
+use crate::execution::tick_size::TickSizeTable;
+use crate::execution::twap::{execute_twap, SliceAbort, TWAP_ALGORITHM};
+use crate::execution::venues::VenueStatistics;
+use crate::infrastructure::faults::FaultInjector;
+use crate::risk::cash::{CashBuffer, CashCheck};
+use crate::risk::compliance::CompliancePipeline;
+use crate::intelligence::quotes::QuoteBook;
+use crate::agents::change_log::ConfigChangeLog;
+use crate::agents::cooldown::EvolutionCooldown;
+use crate::agents::decision_trace::{DecisionTraces, PipelineStage};
+use crate::agents::parameters::{validate_parameters, ParameterChange, ParameterSpec, ParameterValue};
 use crate::agents::traits::{
-    AutonomousAgent, BaseAgent, AgentResult, SystemFeedback, 
+    next_message, AgentWiring, AutonomousAgent, BaseAgent, AgentResult, SystemFeedback, 
     EvolutionResult, Requirements, CodeGeneration, TradeExecutor,
     ExecutionPlan, OrderStatus as TraitOrderStatus
 };
 
+/// Parameters this agent accepts from strategy evolution
+const EVOLVABLE_PARAMETERS: &[ParameterSpec] = &[
+    ParameterSpec::integer("max_latency_ms", 1, 10_000),
+];
+
 /// Execution Engine Agent for high-speed trade execution
 #[derive(Clone)]
 pub struct ExecutionEngineAgent {
     base: BaseAgent,
     config: ExecutionConfig,
     api_config: ApiConfig,
+    tick_sizes: TickSizeTable,
+    commission: CommissionModel,
+    algorithm_selector: AlgorithmSelector,
+    position_sizer: PositionSizer,
+    order_minimum: Arc<RwLock<OrderMinimum>>,
+    orders: Arc<RwLock<OrderManager>>,
+    order_book: Arc<RwLock<OrderBook>>,
+    order_status_source: Option<Arc<dyn OrderStatusSource>>,
+    api_client: Option<ApiClient>,
+    option_quotes: Option<Arc<dyn QuoteSource>>,
+    gateway_connected: Arc<AtomicBool>,
+    scale_outs: ProtectiveLevels,
+    take_profit: Option<TakeProfitConfig>, // Plan new positions are put under
+    quote_guard: Option<(QuoteBook, u64)>,
+    market_calendar: Option<MarketCalendar>,
+    extended_hours: bool, // Some enabled strategy opted into pre-market/after-hours
+    canary: Option<CanaryRouter>,
+    cash_buffer: Option<CashBuffer>,
+    compliance: Option<CompliancePipeline>,
+    slippage_monitor: Option<Arc<RwLock<SlippageMonitor>>>,
+    slippage_attributor: Option<SlippageAttributor>,
+    venue_stats: Arc<RwLock<VenueStatistics>>,
+    journal: Option<TradeJournal>,
+    decision_traces: Option<DecisionTraces>,
+    thought_broadcaster: Option<AIThoughtBroadcaster>,
+    aggressiveness: f64,
+    ab_test: Option<Arc<RwLock<AbTest>>>,
+    signal_sources: Arc<RwLock<HashMap<OrderId, String>>>, // Signal source behind each order still holding a position
+    protection_store: Option<ProtectionStore>,
+    faults: FaultInjector,
+    twap_abort: SliceAbort,
 }
 
 impl ExecutionEngineAgent {
@@ -32,296 +101,2980 @@ impl ExecutionEngineAgent {
         config: ExecutionConfig,
         api_config: ApiConfig,
         message_sender: mpsc::UnboundedSender<AgentMessage>,
+        event_sender: broadcast::Sender<AgentMessage>,
+        system_context: Arc<RwLock<SystemContext>>,
     ) -> TradingResult<Self> {
         let capabilities = vec![
             AgentCapability::ExecutionOptimization,
         ];
         
-        // Create a dummy system context for the base agent
-        let system_context = Arc::new(RwLock::new(SystemContext {
-            market_regime: crate::core::types::MarketRegime::Sideways,
-            portfolio: crate::core::types::Portfolio {
-                total_value: rust_decimal::Decimal::from(100),
-                cash_balance: rust_decimal::Decimal::from(100),
-                positions: Default::default(),
-                daily_pnl: Default::default(),
-                total_pnl: Default::default(),
-                max_drawdown: Default::default(),
-                sharpe_ratio: None,
-                last_updated: chrono::Utc::now(),
-            },
-            risk_metrics: crate::core::types::RiskMetrics {
-                var_95: Default::default(),
-                var_99: Default::default(),
-                expected_shortfall: Default::default(),
-                max_position_size: rust_decimal::Decimal::from(20),
-                daily_loss_limit: rust_decimal::Decimal::from(2),
-                portfolio_heat: 0.0,
-            },
-            performance_metrics: crate::core::types::PerformanceMetrics {
-                total_trades: 0,
-                winning_trades: 0,
-                losing_trades: 0,
-                win_rate: 0.0,
-                average_win: Default::default(),
-                average_loss: Default::default(),
-                profit_factor: 0.0,
-                max_consecutive_wins: 0,
-                max_consecutive_losses: 0,
-                average_execution_time_ms: 0.0,
-            },
-            active_positions: 0,
-            available_capital: rust_decimal::Decimal::from(100),
-            system_health: crate::core::types::SystemHealth::Healthy,
-        }));
-        
-        let base = BaseAgent::new(capabilities, message_sender, system_context);
+        let base = BaseAgent::new(capabilities, message_sender, event_sender, system_context);
+        let tick_sizes = TickSizeTable::from_config(&config.tick_sizes);
+        let algorithm_selector = AlgorithmSelector::from_config(&config.algorithm_selection);
+        let position_sizer = PositionSizer::from_config(&config.position_sizing);
+        let order_minimum = Arc::new(RwLock::new(OrderMinimum::from_config(&config.order_minimum)));
+        let slippage_attributor = config
+            .slippage_attribution
+            .enabled
+            .then(|| SlippageAttributor::from_config(&config.slippage_attribution));
         
         Ok(Self {
             base,
             config,
             api_config,
+            tick_sizes,
+            commission: CommissionModel::default(),
+            algorithm_selector,
+            position_sizer,
+            order_minimum,
+            orders: Arc::new(RwLock::new(OrderManager::new())),
+            order_book: Arc::new(RwLock::new(OrderBook::new())),
+            order_status_source: None,
+            api_client: None,
+            option_quotes: None,
+            gateway_connected: Arc::new(AtomicBool::new(true)),
+            scale_outs: Arc::new(RwLock::new(HashMap::new())),
+            take_profit: None,
+            quote_guard: None,
+            market_calendar: None,
+            extended_hours: false,
+            canary: None,
+            cash_buffer: None,
+            compliance: None,
+            slippage_monitor: None,
+            slippage_attributor,
+            venue_stats: Arc::new(RwLock::new(VenueStatistics::new())),
+            journal: None,
+            decision_traces: None,
+            thought_broadcaster: None,
+            aggressiveness: 1.0,
+            ab_test: None,
+            signal_sources: Arc::new(RwLock::new(HashMap::new())),
+            protection_store: None,
+            faults: FaultInjector::new(),
+            twap_abort: SliceAbort::new(),
         })
     }
     
+    /// Charge commissions with the given rate, rounding and minimum
+    pub fn with_commission_model(mut self, commission: CommissionModel) -> Self {
+        self.commission = commission;
+        self
+    }
+    
+    /// Journal executed orders, and rejected ones with their structured reason
+    pub fn with_trade_journal(mut self, journal: TradeJournal) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+    
+    /// Record sizing, compliance and execution verdicts in the given decision traces
+    pub fn with_decision_traces(mut self, traces: DecisionTraces) -> Self {
+        self.decision_traces = Some(traces);
+        self
+    }
+    
+    /// Publish execution thoughts to the given broadcaster
+    pub fn with_thought_broadcaster(mut self, thought_broadcaster: AIThoughtBroadcaster) -> Self {
+        self.thought_broadcaster = Some(thought_broadcaster);
+        self
+    }
+    
+    /// Place orders through the OpenD gateway instead of the simulator
+    pub fn with_api_client(mut self, api_client: ApiClient) -> Self {
+        self.api_client = Some(api_client);
+        self
+    }
+    
+    /// Scale out of each newly held position according to `plan`
+    pub fn with_take_profit_plan(mut self, plan: TakeProfitConfig) -> Self {
+        self.take_profit = Some(plan);
+        self
+    }
+    
+    /// Price multi-leg orders from quotes fetched on demand
+    pub fn with_option_quotes(mut self, source: Arc<dyn QuoteSource>) -> Self {
+        self.option_quotes = Some(source);
+        self
+    }
+    
+    /// Refuse to trade symbols without a quote newer than `max_quote_age_secs`
+    pub fn with_quote_guard(mut self, quote_book: QuoteBook, max_quote_age_secs: u64) -> Self {
+        self.quote_guard = Some((quote_book, max_quote_age_secs));
+        self
+    }
+    
+    /// Only trade while the calendar reports the market open, judged by its session clock
+    pub fn with_market_calendar(mut self, calendar: MarketCalendar) -> Self {
+        self.market_calendar = Some(calendar);
+        self
+    }
+    
+    /// Also trade the calendar's extended sessions, under their spread and size limits
+    pub fn with_extended_hours(mut self, allowed: bool) -> Self {
+        self.extended_hours = allowed;
+        self
+    }
+    
+    /// Scale down or reject buys that would dip into the free-cash buffer
+    pub fn with_cash_buffer(mut self, cash_buffer: CashBuffer) -> Self {
+        self.cash_buffer = Some(cash_buffer);
+        self
+    }
+    
+    /// Run new orders through pre-trade compliance checks that may veto them
+    pub fn with_compliance(mut self, compliance: CompliancePipeline) -> Self {
+        self.compliance = Some(compliance);
+        self
+    }
+    
+    /// Resync open orders from the broker whenever the gateway reconnects
+    pub fn with_order_status_source(mut self, source: Arc<dyn OrderStatusSource>) -> Self {
+        self.order_status_source = Some(source);
+        self
+    }
+    
+    /// Keep stops and take-profit plans in `levels`, shared with other agents
+    pub fn with_protective_levels(mut self, levels: ProtectiveLevels) -> Self {
+        self.scale_outs = levels;
+        self
+    }
+    
+    /// Persist stops and take-profit plans so they survive a restart
+    pub fn with_protection_store(mut self, store: ProtectionStore) -> Self {
+        self.protection_store = Some(store);
+        self
+    }
+    
+    /// Allocate signals between the variants of a running A/B test
+    pub fn with_ab_test(mut self, ab_test: Arc<RwLock<AbTest>>) -> Self {
+        self.ab_test = Some(ab_test);
+        self
+    }
+    
+    /// Scale position sizes by the configured aggressiveness multiplier
+    pub fn with_aggressiveness(mut self, aggressiveness: f64) -> Self {
+        self.aggressiveness = aggressiveness;
+        self
+    }
+    
+    /// Compare fills against the cost model's slippage and alert on persistent discrepancies
+    pub fn with_slippage_monitor(mut self, monitor: SlippageMonitor) -> Self {
+        self.slippage_monitor = Some(Arc::new(RwLock::new(monitor)));
+        self
+    }
+    
+    /// Mirror every paper fill to a live canary sub-account (only while paper trading)
+    pub fn with_canary(mut self, canary: CanaryRouter) -> Self {
+        self.canary = Some(canary);
+        self
+    }
+    
+    /// The canary router, if canary validation is active
+    pub fn canary(&self) -> Option<&CanaryRouter> {
+        self.canary.as_ref()
+    }
+    
+    /// Attach a fault injector for resilience testing
+    pub fn with_fault_injector(mut self, faults: FaultInjector) -> Self {
+        self.faults = faults;
+        self
+    }
+    
+    /// Put a batch of concurrently produced signals into processing order
+    fn order_signals(&self, mut signals: Vec<TradingSignal>) -> Vec<TradingSignal> {
+        if self.config.deterministic_signal_order {
+            TradingSignal::sort_deterministic(&mut signals);
+        }
+        signals
+    }
+    
+    /// Execute a batch of signals sequentially in processing order
+    pub async fn execute_signals(&self, signals: Vec<TradingSignal>) -> Vec<TradingResult<ExecutionResult>> {
+        let mut results = Vec::with_capacity(signals.len());
+        for signal in self.order_signals(signals) {
+            results.push(self.execute_signal(&signal).await);
+        }
+        results
+    }
+    
     /// Execute a trading signal with optimal routing
     async fn execute_signal(&self, signal: &TradingSignal) -> TradingResult<ExecutionResult> {
         info!("⚡ Executing trade for {} - {:?}", signal.symbol, signal.signal_type);
         
         let start_time = std::time::Instant::now();
         
-        // Create order from signal
-        let order = self.create_order_from_signal(signal).await?;
+        // Never trade on a context the monitor has stopped refreshing
+        self.base.get_fresh_system_context(self.config.max_context_age_secs).await?;
         
-        // Optimize execution
-        let execution_plan = self.create_execution_plan(&order).await?;
+        // Session checks use the signal's data timestamp when the calendar is driven by it
+        let session = self.market_calendar.as_ref().map(|calendar| (calendar, calendar.status_for(signal.timestamp)));
+        if let Some((_, status)) = &session {
+            if !status.permits(self.extended_hours) {
+                let reason = ExecutionError::MarketClosed { session: status.session };
+                return Err(self.reject(signal, None, reason).await);
+            }
+        }
         
-        // Simulate order execution
-        let result = self.simulate_order_execution(&order, &execution_plan).await?;
+        // Never size an order off a stale price
+        if let Some((quote_book, max_age_secs)) = &self.quote_guard {
+            if !quote_book.is_fresh(&signal.symbol, chrono::Utc::now(), *max_age_secs).await {
+                let reason = ExecutionError::StaleQuote { max_age_secs: *max_age_secs };
+                return Err(self.reject(signal, None, reason).await);
+            }
+        }
         
-        let execution_time = start_time.elapsed().as_millis() as u64;
+        // Slippage is attributed against the quote the decision was made on
+        let decision_quote = match &self.quote_guard {
+            Some((quote_book, _)) => quote_book.latest(&signal.symbol).await,
+            None => None,
+        };
         
-        info!("✅ Trade executed in {}ms", execution_time);
+        // Extended sessions have thinner books: skip wide quotes and trade smaller
+        if let (Some((calendar, status)), Some(MarketData { bid: Some(bid), ask: Some(ask), .. })) = (&session, &decision_quote) {
+            if !calendar.spread_acceptable(status, *bid, *ask) {
+                let reason = ExecutionError::WideSpread { session: status.session };
+                return Err(self.reject(signal, None, reason).await);
+            }
+        }
         
-        Ok(ExecutionResult {
-            order_id: result.order_id,
-            executed_quantity: result.executed_quantity,
-            executed_price: result.executed_price,
-            execution_time_ms: execution_time,
-            slippage: result.slippage,
-            commission: result.commission,
-            success: result.success,
-            error_message: result.error_message,
-        })
+        // Create order from signal, refusing a bad size before anything acts on it
+        let mut order = self.create_order_from_signal(signal).await?;
+        if let Some((calendar, status)) = &session {
+            let multiplier = calendar.session_size_multiplier(status);
+            if multiplier < 1.0 {
+                order.quantity *= rust_decimal::Decimal::from_f64(multiplier).unwrap_or(rust_decimal::Decimal::ONE);
+            }
+        }
+        if let Err(reason) = self.check_quantity(&order) {
+            return Err(self.reject(signal, Some(&order), reason).await);
+        }
+        let sized = self.order_value(&order).await?;
+        if let Some(accumulated) = self.apply_order_minimum(signal, &mut order).await? {
+            self.trace_pass(signal, PipelineStage::Sizing).await;
+            return Ok(ExecutionResult {
+                order_id: order.id,
+                executed_quantity: rust_decimal::Decimal::ZERO,
+                executed_price: rust_decimal::Decimal::ZERO,
+                execution_time_ms: start_time.elapsed().as_millis() as u64,
+                slippage: rust_decimal::Decimal::ZERO,
+                commission: rust_decimal::Decimal::ZERO,
+                success: true,
+                error_message: Some(format!("Deferred until the minimum order size is reached ({} held)", accumulated)),
+                fills: Vec::new(),
+            });
+        }
+        
+        // Intent carried over from deferred signals is held again if this order never trades
+        let carried = self.order_value(&order).await? - sized;
+        let (symbol, side) = (order.symbol.clone(), order.side.clone());
+        let result = self.execute_sized_order(signal, order, decision_quote, start_time).await;
+        if carried > rust_decimal::Decimal::ZERO && !matches!(&result, Ok(fill) if fill.success) {
+            info!("🪙 Holding ${} of {} intent again after the order failed", carried, symbol);
+            self.order_minimum.write().await.restore(&symbol, &side, carried);
+        }
+        result
     }
     
-    /// Create order from trading signal
-    async fn create_order_from_signal(&self, signal: &TradingSignal) -> TradingResult<Order> {
-        let order_side = match signal.signal_type {
-            crate::core::types::SignalType::Buy | crate::core::types::SignalType::StrongBuy => OrderSide::Buy,
-            crate::core::types::SignalType::Sell | crate::core::types::SignalType::StrongSell => OrderSide::Sell,
-            crate::core::types::SignalType::Hold => return Err(crate::core::errors::TradingError::execution("Cannot execute hold signal")),
-        };
+    /// Finish sizing against the cash buffer, then run the order through
+    /// compliance and netting and execute it. Stages are traced as they pass.
+    async fn execute_sized_order(
+        &self,
+        signal: &TradingSignal,
+        mut order: Order,
+        decision_quote: Option<MarketData>,
+        start_time: std::time::Instant,
+    ) -> TradingResult<ExecutionResult> {
+        self.apply_cash_buffer(signal, &mut order).await?;
+        // Cash scaling can shrink the order below the minimum
+        if let Err(reason) = self.check_quantity(&order) {
+            return Err(self.reject(signal, Some(&order), reason).await);
+        }
+        self.trace_pass(signal, PipelineStage::Sizing).await;
         
-        // Calculate position size based on signal strength
-        let base_size = rust_decimal::Decimal::from(10); // $10 base position
-        let quantity = base_size * rust_decimal::Decimal::from_f64_retain(signal.strength).unwrap();
+        self.check_compliance(signal, &order).await?;
+        self.trace_pass(signal, PipelineStage::Compliance).await;
         
-        Ok(Order {
-            id: uuid::Uuid::new_v4(),
-            symbol: signal.symbol.clone(),
-            order_type: OrderType::Market, // Use market orders for speed
-            side: order_side,
-            quantity,
-            price: None, // Market order
-            timestamp: chrono::Utc::now(),
-            status: OrderStatus::Pending,
-        })
+        if !self.resolve_order_conflicts(signal, &mut order).await? {
+            self.trace_pass(signal, PipelineStage::Execution).await;
+            return Ok(ExecutionResult {
+                order_id: order.id,
+                executed_quantity: rust_decimal::Decimal::ZERO,
+                executed_price: rust_decimal::Decimal::ZERO,
+                execution_time_ms: start_time.elapsed().as_millis() as u64,
+                slippage: rust_decimal::Decimal::ZERO,
+                commission: rust_decimal::Decimal::ZERO,
+                success: true,
+                error_message: Some("Fully netted against open orders".to_string()),
+                fills: Vec::new(),
+            });
+        }
+        // Netting can leave less than is worth sending
+        let minimum = self.config.min_order_quantity;
+        if order.quantity < minimum {
+            let reason = ExecutionError::NettedBelowMinimum { remaining: order.quantity, minimum };
+            return Err(self.reject(signal, Some(&order), reason).await);
+        }
+        
+        // Attribute the trade to an A/B variant so its outcome lands on the right scorecard
+        if let Some(ab_test) = &self.ab_test {
+            let mut ab_test = ab_test.write().await;
+            let variant = ab_test.allocate();
+            info!("🧪 {} signal allocated to variant {:?} ({})", signal.symbol, variant, ab_test.strategy(variant));
+            ab_test.assign(order.id, variant);
+        }
+        // Remember the source so a position this order opens credits its outcome back
+        if !signal.source.is_empty() {
+            self.signal_sources.write().await.insert(order.id, signal.source.clone());
+        }
+        
+        let result = self.execute_order(order, None, decision_quote, start_time).await;
+        if let Some(traces) = &self.decision_traces {
+            match &result {
+                Ok(fill) if fill.success => traces.pass(signal, PipelineStage::Execution).await,
+                Ok(fill) => {
+                    let reason = fill.error_message.clone().unwrap_or_else(|| "Order not filled".to_string());
+                    traces.fail(signal, PipelineStage::Execution, reason).await;
+                }
+                Err(e) => traces.fail(signal, PipelineStage::Execution, e.to_string()).await,
+            }
+        }
+        result
     }
     
-    /// Create optimal execution plan
-    async fn create_execution_plan(&self, order: &Order) -> TradingResult<ExecutionPlan> {
-        // Simple execution plan - in reality this would be much more sophisticated
-        let algorithm = if order.quantity > rust_decimal::Decimal::from(100) {
-            "TWAP".to_string() // Time-weighted average price for large orders
-        } else {
-            "MARKET".to_string() // Direct market execution for small orders
-        };
-        
-        Ok(ExecutionPlan {
-            algorithm,
-            time_horizon: Duration::from_millis(self.config.max_latency_ms),
-            slice_size: 0.1, // 10% slices
-            price_improvement_target: 0.001, // 0.1% improvement target
-            contingency_plans: vec!["CANCEL_ON_TIMEOUT".to_string()],
-        })
+    /// Record that a signal passed one of this agent's stages
+    async fn trace_pass(&self, signal: &TradingSignal, stage: PipelineStage) {
+        if let Some(traces) = &self.decision_traces {
+            traces.pass(signal, stage).await;
+        }
     }
     
-    /// Simulate order execution (in real system, this would call Moomoo API)
-    async fn simulate_order_execution(&self, order: &Order, _plan: &ExecutionPlan) -> TradingResult<ExecutionResult> {
-        // Simulate execution with random slippage and latency
-        let slippage = rust_decimal::Decimal::from_f64_retain(rand::random::<f64>() * 0.001).unwrap(); // 0-0.1% slippage
-        let commission = order.quantity * rust_decimal::Decimal::from_f64_retain(0.001).unwrap(); // 0.1% commission
-        
-        // Simulate market price
-        let market_price = rust_decimal::Decimal::from_f64_retain(150.0 + rand::random::<f64>() * 10.0).unwrap();
-        let executed_price = match order.side {
-            OrderSide::Buy => market_price + slippage,
-            OrderSide::Sell => market_price - slippage,
+    /// Record realized slippage and raise a risk alert if fills run worse than modeled
+    async fn track_slippage(&self, fill: &ExecutionResult) -> TradingResult<()> {
+        let Some(monitor) = &self.slippage_monitor else {
+            return Ok(());
+        };
+        let Some(alert) = monitor.write().await.record(fill) else {
+            return Ok(());
         };
         
-        Ok(ExecutionResult {
-            order_id: order.id,
-            executed_quantity: order.quantity,
-            executed_price,
-            execution_time_ms: (rand::random::<f64>() * self.config.max_latency_ms as f64) as u64,
-            slippage,
-            commission,
-            success: true,
-            error_message: None,
-        })
+        warn!(
+            "📉 Fills averaging {:.1}bps worse than the slippage model over {} trades - consider recalibrating",
+            alert.average_discrepancy_bps, alert.sample_size
+        );
+        self.base.send_message(AgentMessage::broadcast(
+            self.base.id,
+            crate::core::types::MessageType::RiskAlert,
+            serde_json::json!({
+                "reason": "Slippage model discrepancy",
+                "details": alert,
+            }),
+        )).await
     }
-}
-
-#[async_trait]
-impl AutonomousAgent for ExecutionEngineAgent {
-    async fn execute_mission(&self, _context: &SystemContext) -> TradingResult<AgentResult> {
-        info!("⚡ Execution Engine executing mission...");
+    
+    /// Veto orders a compliance check rejects. Requested orders such as forced
+    /// exits skip this, so a position can always be closed.
+    async fn check_compliance(&self, signal: &TradingSignal, order: &Order) -> TradingResult<()> {
+        let Some(compliance) = &self.compliance else {
+            return Ok(());
+        };
         
-        // Execution engine waits for signals from other agents
-        Ok(AgentResult {
-            success: true,
-            signals: Vec::new(), // Execution engine doesn't generate signals
-            metrics: PerformanceMetrics {
-                total_trades: 0,
-                winning_trades: 0,
-                losing_trades: 0,
-                win_rate: 0.0,
-                average_win: Default::default(),
-                average_loss: Default::default(),
-                profit_factor: 0.0,
-                max_consecutive_wins: 0,
-                max_consecutive_losses: 0,
-                average_execution_time_ms: self.config.max_latency_ms as f64,
-            },
-            recommendations: vec!["Ready for trade execution".to_string()],
-            errors: Vec::new(),
-        })
+        let context = self.base.get_system_context().await;
+        match compliance.review(order, &context) {
+            Some((check, reason)) => {
+                warn!("🚫 Compliance check {} vetoed {} order: {}", check, order.symbol, reason);
+                Err(self.reject(signal, Some(order), ExecutionError::ComplianceVeto { check, reason }).await)
+            }
+            None => Ok(()),
+        }
     }
     
-    async fn self_evaluate(&self) -> TradingResult<PerformanceMetrics> {
-        Ok(PerformanceMetrics {
-            total_trades: 0,
-            winning_trades: 0,
-            losing_trades: 0,
-            win_rate: 0.0,
-            average_win: Default::default(),
-            average_loss: Default::default(),
-            profit_factor: 0.0,
-            max_consecutive_wins: 0,
-            max_consecutive_losses: 0,
-            average_execution_time_ms: self.config.max_latency_ms as f64,
-        })
+    /// Apply the configured policy to a new order opposing open orders on the
+    /// same symbol. Returns whether any of the order is left to send.
+    async fn resolve_order_conflicts(&self, signal: &TradingSignal, order: &mut Order) -> TradingResult<bool> {
+        let policy = self.config.order_conflict_policy;
+        let resolution = self.orders.write().await.resolve_conflict(order, policy);
+        match resolution {
+            ConflictResolution::NoConflict => Ok(true),
+            ConflictResolution::Rejected(open) => {
+                warn!("⚔️ Rejecting {:?} order for {}: opposes {} open order(s)", order.side, order.symbol, open.len());
+                Err(self.reject(signal, Some(order), ExecutionError::OrderConflict { open_orders: open }).await)
+            }
+            ConflictResolution::Netted { cancelled, reduced, remaining } => {
+                info!(
+                    "⚖️ Netted {} order against open orders, cancelled {:?}, reduced {:?}, {} left to send",
+                    order.symbol, cancelled, reduced, remaining
+                );
+                self.cancel_at_gateway(&cancelled).await;
+                self.amend_at_gateway(&reduced).await;
+                Ok(!remaining.is_zero())
+            }
+            ConflictResolution::Replaced(cancelled) => {
+                info!("🔁 Cancelled open orders {:?} in favour of new {} order", cancelled, order.symbol);
+                self.cancel_at_gateway(&cancelled).await;
+                Ok(true)
+            }
+        }
     }
     
-    async fn evolve_strategy(&mut self, feedback: &SystemFeedback) -> TradingResult<EvolutionResult> {
-        info!("🧬 Execution engine evolving strategy...");
-        
-        let mut new_parameters = std::collections::HashMap::new();
-        
-        if feedback.performance_score > 0.8 {
-            // Increase aggressiveness for better performance
-            new_parameters.insert(
-                "max_latency_ms".to_string(),
-                serde_json::Value::Number(serde_json::Number::from_f64(0.5).unwrap()),
-            );
+    /// Cancel orders dropped locally at the gateway too, when one is attached
+    async fn cancel_at_gateway(&self, order_ids: &[OrderId]) {
+        {
+            let mut order_book = self.order_book.write().await;
+            for order_id in order_ids {
+                order_book.finish(*order_id, OrderStatus::Cancelled);
+            }
+        }
+        let Some(api_client) = &self.api_client else {
+            return;
+        };
+        for order_id in order_ids {
+            if let Err(e) = api_client.cancel_order(*order_id).await {
+                warn!("📨 Failed to cancel order {} at the gateway: {}", order_id, e);
+            }
         }
-        
-        Ok(EvolutionResult {
-            strategy_updated: !new_parameters.is_empty(),
-            new_parameters,
-            performance_improvement: feedback.performance_score * 0.1,
-            confidence: 0.85,
-        })
     }
     
-    async fn generate_code(&self, requirements: &Requirements) -> TradingResult<CodeGeneration> {
-        info!("🔧 Execution engine generating code for: {}", requirements.functionality);
-        
-        let code = format!(
-            "// Execution optimization code for: {}\nuse tokio::time::Instant;\n\npub async fn optimize_{}() -> Result<f64, Box<dyn std::error::Error>> {{\n    let start = Instant::now();\n    // Execution optimization implementation\n    Ok(start.elapsed().as_secs_f64())\n}}",
-            requirements.functionality,
-            requirements.functionality.to_lowercase().replace(' ', "_")
-        );
-        
-        Ok(CodeGeneration {
-            code,
-            language: "rust".to_string(),
-            tests: vec!["#[tokio::test] async fn test_optimization() { assert!(optimize_test().await.is_ok()); }".to_string()],
-            documentation: format!("Execution optimization function for {}", requirements.functionality),
-            performance_estimate: requirements.performance_targets.clone(),
-        })
+    /// Shrink orders netting left partly working, at the gateway too when one
+    /// is attached. The order book follows only once the broker accepts.
+    async fn amend_at_gateway(&self, amendments: &[(OrderId, rust_decimal::Decimal)]) {
+        for (order_id, quantity) in amendments {
+            if let Some(api_client) = &self.api_client {
+                if let Err(e) = api_client.modify_order(*order_id, *quantity).await {
+                    warn!("📨 Failed to amend order {} to {} at the gateway: {}", order_id, quantity, e);
+                    continue;
+                }
+            }
+            self.order_book.write().await.amend(*order_id, *quantity);
+        }
     }
     
-    fn capabilities(&self) -> Vec<AgentCapability> {
-        self.base.capabilities.clone()
+    /// Cancel an order at the gateway, returning whether the broker confirmed
+    /// it. Without a gateway nothing works the order, so it is always final.
+    async fn confirm_cancel(&self, order_id: OrderId) -> bool {
+        let Some(api_client) = &self.api_client else {
+            return true;
+        };
+        match api_client.cancel_order(order_id).await {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("📨 Failed to cancel order {} at the gateway: {}", order_id, e);
+                false
+            }
+        }
     }
     
-    fn agent_id(&self) -> AgentId {
-        self.base.id
+    /// Keep buys from consuming the cash reserved for fees and slippage
+    async fn apply_cash_buffer(&self, signal: &TradingSignal, order: &mut Order) -> TradingResult<()> {
+        let Some(cash_buffer) = &self.cash_buffer else {
+            return Ok(());
+        };
+        if !matches!(order.side, OrderSide::Buy) {
+            return Ok(());
+        }
+        
+        let order_value = self.order_value(order).await?;
+        let context = self.base.get_system_context().await;
+        match cash_buffer.check(&context.portfolio, order_value) {
+            CashCheck::Approved => Ok(()),
+            CashCheck::Scaled(factor) => {
+                warn!("💵 Scaling {} order by {:.2} to preserve the cash buffer", order.symbol, factor);
+                order.quantity *= factor;
+                Ok(())
+            }
+            CashCheck::Rejected => {
+                let reason = ExecutionError::InsufficientCash { reserve: cash_buffer.reserve(&context.portfolio) };
+                Err(self.reject(signal, Some(order), reason).await)
+            }
+        }
     }
     
-    async fn run(&mut self) -> TradingResult<()> {
-        info!("⚡ Execution Engine starting execution loop...");
-        
-        let mut health_check = interval(Duration::from_secs(1));
-        
-        loop {
-            tokio::select! {
-                _ = health_check.tick() => {
-                    // Perform health checks and maintain connections
-                    info!("⚡ Execution engine healthy - ready for trades");
+    /// Hold the order to the minimum tradable notional. Returns the intent
+    /// held back so far if the order is deferred rather than sent now.
+    async fn apply_order_minimum(&self, signal: &TradingSignal, order: &mut Order) -> TradingResult<Option<rust_decimal::Decimal>> {
+        let price = self.valuation_price(order).await?;
+        let notional = order_value(order, price)?;
+        let (check, minimum) = {
+            let mut order_minimum = self.order_minimum.write().await;
+            (order_minimum.check(&order.symbol, &order.side, notional), order_minimum.min_notional())
+        };
+        match check {
+            MinimumCheck::Tradable(total) => {
+                if total > notional {
+                    info!("🪙 Adding ${} of deferred {} intent to the order", total - notional, order.symbol);
                 }
-                _ = tokio::time::sleep(Duration::from_millis(10)) => {
-                    if self.base.should_shutdown().await {
-                        break;
-                    }
+                if !price.is_zero() {
+                    order.quantity = total / price;
                 }
+                Ok(None)
+            }
+            MinimumCheck::Skipped => {
+                self.think_about_minimum(order, format!(
+                    "Skipping {} {:?}: the sized order is worth ${}, below the ${} minimum tradable size for this account.",
+                    order.symbol, order.side, notional.round_dp(2), minimum
+                )).await;
+                let reason = ExecutionError::BelowMinimumNotional { notional, minimum };
+                Err(self.reject(signal, Some(order), reason).await)
+            }
+            MinimumCheck::Deferred { accumulated } => {
+                self.think_about_minimum(order, format!(
+                    "Holding back {} {:?}: ${} accumulated so far, waiting for the ${} minimum tradable size.",
+                    order.symbol, order.side, accumulated.round_dp(2), minimum
+                )).await;
+                Ok(Some(accumulated))
             }
         }
-        
-        info!("⚡ Execution Engine execution loop ended");
-        Ok(())
     }
     
-    async fn shutdown(&mut self) -> TradingResult<()> {
-        info!("🛑 Execution Engine shutting down...");
-        self.base.request_shutdown().await;
-        Ok(())
+    /// Price an order is valued at: its limit price, or the latest quote for a market order
+    async fn valuation_price(&self, order: &Order) -> TradingResult<rust_decimal::Decimal> {
+        if let Some(price) = order.price {
+            return Ok(price);
+        }
+        let quote = match &self.quote_guard {
+            Some((quote_book, _)) => quote_book.latest(&order.symbol).await,
+            None => None,
+        };
+        quote
+            .map(|quote| quote.price)
+            .ok_or_else(|| TradingError::market_data(format!("No quote to value the {} market order", order.symbol)))
     }
-}
-
-#[async_trait]
-impl TradeExecutor for ExecutionEngineAgent {
-    async fn execute_trade(&self, signal: &TradingSignal) -> TradingResult<ExecutionResult> {
-        self.execute_signal(signal).await
+    
+    /// Notional value of an order at its valuation price
+    async fn order_value(&self, order: &Order) -> TradingResult<rust_decimal::Decimal> {
+        order_value(order, self.valuation_price(order).await?)
     }
     
-    async fn optimize_execution(&self, order: &Order) -> TradingResult<ExecutionPlan> {
-        self.create_execution_plan(order).await
+    /// Explain an order held to the minimum tradable size
+    async fn think_about_minimum(&self, order: &Order, content: String) {
+        info!("🪙 {}", content);
+        if let Some(broadcaster) = &self.thought_broadcaster {
+            broadcaster.broadcast_thought(
+                AIThought::new(AIAgent::ExecutionEngine, ThoughtType::Execution, content, 0.9)
+                    .with_symbols(vec![order.symbol.clone()])
+                    .with_tags(vec!["order_minimum".to_string(), "sizing".to_string()])
+            ).await;
+        }
+    }
+    
+    /// Execute every leg of a multi-leg order as one position. The simulator
+    /// fills the legs against quotes from the option quote source, and the
+    /// fill is booked as one position at its net debit.
+    pub async fn execute_multi_leg(&self, order: &MultiLegOrder) -> TradingResult<MultiLegFill> {
+        let started = std::time::Instant::now();
+        order.validate()?;
+        if self.api_client.as_ref().is_some_and(|client| client.environment() == TradingEnvironment::Real) {
+            return Err(TradingError::execution("Multi-leg orders cannot be routed to a live account yet"));
+        }
+        let Some(source) = &self.option_quotes else {
+            return Err(TradingError::execution("Multi-leg orders need an option quote source"));
+        };
+        let mut quotes = HashMap::new();
+        for leg in &order.legs {
+            if let Some(quote) = source.quote(&leg.symbol).await? {
+                quotes.insert(leg.symbol.clone(), quote);
+            }
+        }
+        
+        let fill = MultiLegSimulator::from_config(&self.config.multi_leg).fill(order, &quotes)?;
+        let commission = self.commission.commission(fill.gross_premium());
+        let result = fill.execution_result(commission, started.elapsed().as_millis() as u64)?;
+        self.book_multi_leg(&fill, commission).await?;
+        info!(
+            "🦵 {} {} filled {} of {} legs for a net debit of ${}",
+            order.strategy, order.id, fill.legs.len(), order.legs.len(), fill.net_debit()
+        );
+        
+        if let Some(journal) = &self.journal {
+            let combined = Order {
+                id: order.id,
+                symbol: fill.position_symbol(),
+                order_type: OrderType::Market,
+                side: OrderSide::Buy,
+                quantity: result.executed_quantity,
+                price: Some(result.executed_price),
+                timestamp: order.timestamp,
+                status: OrderStatus::Filled,
+            };
+            if let Err(e) = journal.record_execution(&combined, &result, None, None).await {
+                warn!("📓 Failed to journal multi-leg order {}: {}", order.id, e);
+            }
+        }
+        self.publish(crate::core::types::MessageType::OrderExecution, serde_json::to_value(&result)).await;
+        Ok(fill)
+    }
+    
+    /// Book a multi-leg fill as one position, paying its net debit from cash
+    /// and its commission from P&L
+    async fn book_multi_leg(&self, fill: &MultiLegFill, commission: rust_decimal::Decimal) -> TradingResult<()> {
+        let position = fill.position();
+        let mut context = self.base.system_context.write().await;
+        let cash_balance = context
+            .portfolio
+            .cash_balance
+            .checked_sub(fill.net_debit())
+            .ok_or_else(|| TradingError::overflow(format!("cash after {}", position.symbol)))?;
+        context.portfolio.cash_balance = cash_balance;
+        context.portfolio.apply_realized_pnl(-commission)?;
+        context.portfolio.positions.insert(position.symbol.clone(), position);
+        context.active_positions = context.portfolio.positions.len() as u32;
+        Ok(())
+    }
+    
+    /// Execute an order requested directly by another agent, such as a forced risk exit
+    async fn execute_requested_order(
+        &self,
+        request: ClosingOrder,
+        decision_quote: Option<MarketData>,
+    ) -> TradingResult<ExecutionResult> {
+        let ClosingOrder { order, close_reason } = request;
+        if let Err(reason) = self.check_quantity(&order) {
+            if let Some(journal) = &self.journal {
+                if let Err(e) = journal.record_rejection(&order.symbol, Some(&order), &reason).await {
+                    warn!("📓 Failed to journal rejected {} order: {}", order.symbol, e);
+                }
+            }
+            return Err(reason.into());
+        }
+        info!("⚡ Executing requested {:?} order for {} ({:?})", order.side, order.symbol, close_reason);
+        self.execute_order(order, close_reason, decision_quote, std::time::Instant::now()).await
+    }
+    
+    /// Route an order to the venue and wait for its fill
+    async fn execute_order(
+        &self,
+        mut order: Order,
+        close_reason: Option<CloseReason>,
+        decision_quote: Option<MarketData>,
+        start_time: std::time::Instant,
+    ) -> TradingResult<ExecutionResult> {
+        // Snap limit/stop prices onto the venue tick grid
+        if let Some(price) = order.price {
+            order.price = Some(self.tick_sizes.round_price(&order.symbol, &order.side, &order.order_type, price)?);
+        }
+        
+        // Optimize execution
+        let execution_plan = self.create_execution_plan(&order, close_reason).await?;
+        
+        // Wait for the fill; a late fill parks the order for reconciliation
+        let budget = RetryBudget::new(self.config.max_retries_per_order);
+        self.order_book.write().await.submit(&order, budget.clone());
+        let fill = timeout(
+            self.fill_deadline(&execution_plan),
+            self.await_fill(&order, &execution_plan, &budget),
+        ).await;
+        let result = match fill {
+            Ok(Ok(result)) => self.fill_remainder(&order, &execution_plan, &budget, result, start_time).await?,
+            Ok(Err(e)) => {
+                self.order_book.write().await.finish(order.id, OrderStatus::Rejected);
+                return Err(e);
+            }
+            Err(_) => {
+                warn!("⏳ Fill for order {} timed out, holding for reconciliation", order.id);
+                let (order_id, symbol) = (order.id, order.symbol.clone());
+                self.orders.write().await.track(order);
+                
+                let result = ExecutionResult {
+                    order_id,
+                    executed_quantity: rust_decimal::Decimal::ZERO,
+                    executed_price: rust_decimal::Decimal::ZERO,
+                    execution_time_ms: start_time.elapsed().as_millis() as u64,
+                    slippage: rust_decimal::Decimal::ZERO,
+                    commission: rust_decimal::Decimal::ZERO,
+                    success: false,
+                    error_message: Some("Fill timed out, pending reconciliation".to_string()),
+                    fills: Vec::new(),
+                };
+                MetricsCollector::record_failed_trade(&symbol);
+                self.venue_stats.write().await.record(&self.config.venue_routing.venue, &result);
+                return Ok(result);
+            }
+        };
+        
+        // Validate live connectivity and slippage assumptions without risking the paper book
+        if let Some(canary) = &self.canary {
+            if result.success {
+                if let Err(e) = canary.mirror(&order, &result).await {
+                    warn!("🐤 Canary order for {} failed: {}", order.symbol, e);
+                }
+            }
+        }
+        
+        self.track_slippage(&result).await?;
+        
+        let execution_time = start_time.elapsed().as_millis() as u64;
+        
+        info!("✅ Trade executed in {}ms", execution_time);
+        MetricsCollector::record_execution_latency(&order.symbol, start_time.elapsed());
+        if result.success {
+            MetricsCollector::record_successful_trade(&order.symbol);
+        } else {
+            MetricsCollector::record_failed_trade(&order.symbol);
+        }
+        
+        let result = ExecutionResult {
+            execution_time_ms: execution_time,
+            ..result
+        };
+        self.venue_stats.write().await.record(&self.config.venue_routing.venue, &result);
+        let attribution = match (&self.slippage_attributor, &decision_quote) {
+            (Some(attributor), Some(quote)) if result.success => {
+                let attribution = attributor.attribute(&order.side, quote, result.executed_price, result.executed_quantity);
+                info!(
+                    "📐 {} slippage {} = spread {} + impact {} + timing {}",
+                    order.symbol, attribution.total, attribution.spread, attribution.impact, attribution.timing
+                );
+                Some(attribution)
+            }
+            _ => None,
+        };
+        if let Some(journal) = &self.journal {
+            if let Err(e) = journal.record_execution(&order, &result, close_reason, attribution).await {
+                warn!("📓 Failed to journal order {}: {}", order.id, e);
+            }
+        }
+        self.book_fill(&order, &result).await;
+        self.publish(crate::core::types::MessageType::OrderExecution, serde_json::to_value(&result)).await;
+        Ok(result)
+    }
+    
+    /// Book an order's first fill and re-submit any unfilled remainder, up to
+    /// `partial_fill_resubmits` times. Whatever is still unfilled after that is
+    /// cancelled, keeping the partial fill.
+    async fn fill_remainder(
+        &self,
+        order: &Order,
+        plan: &ExecutionPlan,
+        budget: &RetryBudget,
+        first: ExecutionResult,
+        start_time: std::time::Instant,
+    ) -> TradingResult<ExecutionResult> {
+        // TWAP slices are already accumulated; an aborted TWAP must not be topped up
+        let max_resubmits = if plan.algorithm == TWAP_ALGORITHM { 0 } else { self.config.partial_fill_resubmits };
+        let mut completed = self.order_book.write().await.record_fills(order.id, &first.fills);
+        let mut slippage_notional = first.slippage * first.executed_quantity;
+        let mut commission = first.commission;
+        let mut fills = first.fills;
+        let mut working = order.id;
+        
+        let mut attempts = 0;
+        while completed.is_none() && !fills.is_empty() && attempts < max_resubmits {
+            let Some(remaining) = self.order_book.read().await.remaining(order.id) else {
+                break;
+            };
+            if !budget.try_consume() {
+                warn!("🔁 Retry budget for order {} exhausted, not re-submitting", order.id);
+                break;
+            }
+            attempts += 1;
+            
+            // The broker may still be working the remainder; re-sending it
+            // before the cancel is confirmed could fill it twice
+            if !self.confirm_cancel(working).await {
+                break;
+            }
+            let child = Order {
+                id: uuid::Uuid::new_v4(),
+                quantity: remaining,
+                timestamp: chrono::Utc::now(),
+                status: OrderStatus::Pending,
+                ..order.clone()
+            };
+            working = child.id;
+            info!("🔁 Re-submitting {} unfilled of order {} ({}/{})", remaining, order.id, attempts, max_resubmits);
+            
+            let result = match timeout(self.fill_deadline(plan), self.await_fill(&child, plan, budget)).await {
+                Ok(Ok(result)) => result,
+                Ok(Err(e)) => {
+                    warn!("🔁 Re-submission for order {} failed: {}", order.id, e);
+                    break;
+                }
+                Err(_) => {
+                    warn!("⏳ Re-submission for order {} timed out", order.id);
+                    break;
+                }
+            };
+            slippage_notional += result.slippage * result.executed_quantity;
+            commission += result.commission;
+            let child_fills: Vec<Fill> = result.fills.into_iter().map(|fill| Fill { order_id: order.id, ..fill }).collect();
+            completed = self.order_book.write().await.record_fills(order.id, &child_fills);
+            fills.extend(child_fills);
+        }
+        
+        // Whatever is unfilled may still be working at the broker, even with
+        // nothing filled yet; it only ends once the broker confirms the cancel
+        let partial = completed.is_none();
+        if partial {
+            if self.confirm_cancel(working).await {
+                let status = if fills.is_empty() { OrderStatus::Rejected } else { OrderStatus::Cancelled };
+                completed = self.order_book.write().await.finish(order.id, status);
+            } else {
+                let remaining = self.order_book.read().await.remaining(order.id).unwrap_or(order.quantity);
+                warn!("⏳ Cancel of order {} unconfirmed, holding {} for reconciliation", working, remaining);
+                self.orders.write().await.track(Order { id: working, quantity: remaining, ..order.clone() });
+            }
+        }
+        
+        let executed: rust_decimal::Decimal = fills.iter().map(|fill| fill.quantity).sum();
+        let slippage = if executed.is_zero() { rust_decimal::Decimal::ZERO } else { slippage_notional / executed };
+        let mut result = ExecutionResult::from_fills(
+            order.id,
+            fills,
+            slippage,
+            commission,
+            start_time.elapsed().as_millis() as u64,
+        )?;
+        result.error_message = first.error_message;
+        if partial && !executed.is_zero() {
+            warn!("✂️ Order {} filled {} of {}, remainder cancelled", order.id, executed, order.quantity);
+            result.error_message = Some(format!("Partially filled {} of {}, remainder cancelled", executed, order.quantity));
+        }
+        
+        if let Some(progress) = completed.filter(|progress| !progress.filled_quantity.is_zero()) {
+            self.publish_order_completion(&progress, result.execution_time_ms).await;
+        }
+        Ok(result)
+    }
+    
+    /// Book the fills of an order that is no longer being worked, cancelling
+    /// any unfilled remainder, and publish its completion
+    async fn settle(&self, order_id: OrderId, fills: &[Fill], execution_time_ms: u64) {
+        let settled = {
+            let mut order_book = self.order_book.write().await;
+            match order_book.record_fills(order_id, fills) {
+                Some(progress) => Some(progress),
+                None => order_book.finish(order_id, OrderStatus::Cancelled),
+            }
+        };
+        if let Some(progress) = settled.filter(|progress| !progress.filled_quantity.is_zero()) {
+            self.publish_order_completion(&progress, execution_time_ms).await;
+        }
+    }
+    
+    /// Book a single-leg fill into the portfolio, publishing the closed trade
+    /// when it reduced a held position
+    async fn book_fill(&self, order: &Order, result: &ExecutionResult) {
+        if !result.success || result.executed_quantity.is_zero() {
+            self.signal_sources.write().await.remove(&order.id);
+            return;
+        }
+        let quantity = match order.side {
+            OrderSide::Buy => result.executed_quantity,
+            OrderSide::Sell => -result.executed_quantity,
+        };
+        let now = chrono::Utc::now();
+        let (closed, holding) = {
+            let mut context = self.base.system_context.write().await;
+            let closed = context.portfolio.apply_fill(&order.symbol, order.id, quantity, result.executed_price, result.commission, now);
+            context.active_positions = context.portfolio.positions.len() as u32;
+            (closed, context.portfolio.positions.get(&order.symbol).map(|position| position.id))
+        };
+        let source = {
+            let mut sources = self.signal_sources.write().await;
+            if holding != Some(order.id) {
+                sources.remove(&order.id);
+            }
+            match &closed {
+                // A partial close leaves the opening order holding the rest
+                Ok(Some(closed)) if holding == Some(closed.opened_by) => sources.get(&closed.opened_by).cloned(),
+                Ok(Some(closed)) => sources.remove(&closed.opened_by),
+                _ => None,
+            }
+        };
+        
+        match closed {
+            Ok(Some(closed)) => {
+                info!("💼 Closed {} {} at {} for {}", closed.quantity, order.symbol, result.executed_price, closed.pnl);
+                let trade = TradeRecord {
+                    timestamp: now,
+                    pnl: closed.pnl,
+                    execution_time_ms: result.execution_time_ms as f64,
+                    strategy: None,
+                    order_id: Some(closed.opened_by),
+                    notional: closed.entry_price * closed.quantity,
+                    costs: result.commission,
+                    holding_secs: (now - closed.opened_at).num_seconds().max(0) as u64,
+                    source,
+                };
+                self.publish(crate::core::types::MessageType::TradeClosed, serde_json::to_value(&trade)).await;
+            }
+            Ok(None) => {}
+            Err(e) => error!("💼 Failed to book order {} into the portfolio: {}", order.id, e),
+        }
+    }
+    
+    /// Broadcast an execution outcome; failing to publish never fails the order
+    async fn publish(&self, message_type: crate::core::types::MessageType, payload: serde_json::Result<serde_json::Value>) {
+        let sent = match payload {
+            Ok(payload) => self.base.send_message(AgentMessage::broadcast(self.base.id, message_type.clone(), payload)).await,
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = sent {
+            warn!("📦 Failed to publish {:?}: {}", message_type, e);
+        }
+    }
+    
+    /// Announce an order that reached a terminal state. Performance metrics
+    /// are left to the closed-trade window the system keeps.
+    async fn publish_order_completion(&self, progress: &OrderProgress, execution_time_ms: u64) {
+        info!(
+            "📦 Order {} {:?}: {} of {} {} at {}",
+            progress.order.id, progress.order.status, progress.filled_quantity,
+            progress.order.quantity, progress.order.symbol, progress.average_price()
+        );
+        let completion = OrderCompletion {
+            order_id: progress.order.id,
+            symbol: progress.order.symbol.clone(),
+            status: progress.order.status.clone(),
+            filled_quantity: progress.filled_quantity,
+            remaining_quantity: (progress.order.quantity - progress.filled_quantity).max(rust_decimal::Decimal::ZERO),
+            average_price: progress.average_price(),
+            execution_time_ms,
+        };
+        self.publish(crate::core::types::MessageType::OrderCompleted, serde_json::to_value(&completion)).await;
+    }
+    
+    /// Final guard before submission: never send a zero, negative or undersized quantity
+    fn check_quantity(&self, order: &Order) -> Result<(), ExecutionError> {
+        let minimum = self.config.min_order_quantity;
+        if order.quantity <= rust_decimal::Decimal::ZERO || order.quantity < minimum {
+            return Err(ExecutionError::InvalidQuantity { quantity: order.quantity, minimum });
+        }
+        Ok(())
+    }
+    
+    /// Journal and trace a refused order, turning its reason into the error returned to the caller
+    async fn reject(&self, signal: &TradingSignal, order: Option<&Order>, reason: ExecutionError) -> TradingError {
+        if let Some(journal) = &self.journal {
+            if let Err(e) = journal.record_rejection(&signal.symbol, order, &reason).await {
+                warn!("📓 Failed to journal rejected {} order: {}", signal.symbol, e);
+            }
+        }
+        if let Some(traces) = &self.decision_traces {
+            traces.fail(signal, reason.stage(), reason.to_string()).await;
+        }
+        TradingError::from(reason)
+    }
+    
+    /// Fill quality recorded so far for each venue orders were sent to
+    pub async fn venue_statistics(&self) -> VenueStatistics {
+        self.venue_stats.read().await.clone()
+    }
+    
+    /// Create order from trading signal
+    async fn create_order_from_signal(&self, signal: &TradingSignal) -> TradingResult<Order> {
+        let order_side = match signal.signal_type {
+            crate::core::types::SignalType::Buy | crate::core::types::SignalType::StrongBuy => OrderSide::Buy,
+            crate::core::types::SignalType::Sell | crate::core::types::SignalType::StrongSell => OrderSide::Sell,
+            crate::core::types::SignalType::Hold => return Err(self.reject(signal, None, ExecutionError::HoldSignal).await),
+        };
+        
+        // Size by signal strength and confidence, then aggressiveness
+        let quantity = self.position_sizer.quantity(signal.strength, signal.confidence)
+            * rust_decimal::Decimal::from_f64(self.aggressiveness).unwrap_or(rust_decimal::Decimal::ONE);
+        
+        Ok(Order {
+            id: uuid::Uuid::new_v4(),
+            symbol: signal.symbol.clone(),
+            order_type: OrderType::Market, // Use market orders for speed
+            side: order_side,
+            quantity,
+            price: None, // Market order
+            timestamp: chrono::Utc::now(),
+            status: OrderStatus::Pending,
+        })
+    }
+    
+    /// Create an execution plan, choosing the algorithm by the configured
+    /// selection rules
+    async fn create_execution_plan(&self, order: &Order, close_reason: Option<CloseReason>) -> TradingResult<ExecutionPlan> {
+        let conditions = ExecutionConditions {
+            quantity: order.quantity,
+            urgent: matches!(
+                close_reason,
+                Some(CloseReason::StopLoss | CloseReason::TrailingStop | CloseReason::EodFlatten | CloseReason::Emergency)
+            ),
+            high_volatility: matches!(self.base.get_system_context().await.market_regime, MarketRegime::HighVolatility),
+            spread_bps: self.spread_bps(&order.symbol).await,
+        };
+        let choice = self.algorithm_selector.select(&conditions);
+        info!("🧭 {:?} {} {} via {}: {}", order.side, order.quantity, order.symbol, choice.algorithm, choice.rationale);
+        
+        if let Some(broadcaster) = &self.thought_broadcaster {
+            broadcaster.broadcast_thought(
+                AIThought::new(
+                    AIAgent::ExecutionEngine,
+                    ThoughtType::Execution,
+                    format!("Executing {} {} via {} because of {}.", order.quantity, order.symbol, choice.algorithm, choice.rationale),
+                    0.9,
+                )
+                .with_symbols(vec![order.symbol.clone()])
+                .with_tags(vec!["algorithm_selection".to_string(), choice.algorithm.to_lowercase()])
+            ).await;
+        }
+        
+        Ok(ExecutionPlan {
+            algorithm: choice.algorithm,
+            time_horizon: Duration::from_millis(self.config.algorithm_selection.twap_horizon_ms),
+            slice_size: 0.1, // 10% slices
+            price_improvement_target: 0.001, // 0.1% improvement target
+            contingency_plans: vec!["CANCEL_ON_TIMEOUT".to_string()],
+            rationale: choice.rationale,
+        })
+    }
+    
+    /// Quoted bid/ask spread for `symbol` in basis points of the mid, if known
+    async fn spread_bps(&self, symbol: &str) -> Option<f64> {
+        let (quote_book, _) = self.quote_guard.as_ref()?;
+        let quote = quote_book.latest(symbol).await?;
+        let (bid, ask) = (quote.bid?.to_f64()?, quote.ask?.to_f64()?);
+        let mid = (bid + ask) / 2.0;
+        (mid > 0.0).then(|| (ask - bid) / mid * 10_000.0)
+    }
+    
+    /// Wait for an order's fill from the venue, slicing it over time for TWAP plans
+    async fn await_fill(&self, order: &Order, plan: &ExecutionPlan, budget: &RetryBudget) -> TradingResult<ExecutionResult> {
+        if let Some(delay) = self.faults.fill_delay() {
+            tokio::time::sleep(delay).await;
+        }
+        if plan.algorithm == TWAP_ALGORITHM {
+            return execute_twap(order, plan, &self.twap_abort, |child| async move {
+                self.submit_to_venue(&child, plan, budget).await
+            }).await;
+        }
+        self.submit_to_venue(order, plan, budget).await
+    }
+    
+    /// How long to wait for an order's fill before parking it for reconciliation
+    fn fill_deadline(&self, plan: &ExecutionPlan) -> Duration {
+        let deadline = Duration::from_millis(self.config.fill_timeout_ms);
+        if plan.algorithm == TWAP_ALGORITHM {
+            deadline + plan.time_horizon
+        } else {
+            deadline
+        }
+    }
+    
+    /// Stop sending the remaining slices of any TWAP orders in flight
+    pub fn abort_twaps(&self) {
+        self.twap_abort.abort_all();
+    }
+    
+    /// Handle ticks, messages and signal batches until shutdown
+    async fn trading_loop(&mut self) {
+        let mut health_check = interval(Duration::from_secs(1));
+        let mut events = self.base.subscribe_events();
+        let mut inbox = self.base.inbox().await;
+        let mut heartbeat = self.base.heartbeat_timer();
+        
+        self.base.mark_ready();
+        
+        // Approved signals queued together, executed as one ordered batch
+        let mut signal_batch: Vec<TradingSignal> = Vec::new();
+        
+        loop {
+            tokio::select! {
+                // Messages already waiting are taken before the batch is flushed
+                biased;
+                _ = health_check.tick() => {
+                    // Perform health checks and maintain connections
+                    info!("⚡ Execution engine healthy - ready for trades");
+                    self.manage_positions().await;
+                    if let Err(e) = self.check_gateway_connection().await {
+                        error!("Order resync error: {}", e);
+                    }
+                    // Re-checking fills while disconnected would re-submit orders the broker may already hold
+                    if self.gateway_connected() {
+                        if let Err(e) = self.reconcile_pending_fills().await {
+                            error!("Fill reconciliation error: {}", e);
+                        }
+                    }
+                }
+                _ = heartbeat.tick() => self.base.send_heartbeat(),
+                Some(message) = next_message(&mut events, &mut inbox) => {
+                    // Nothing may stay working at the broker once trading is halted
+                    if let crate::core::types::MessageType::EmergencyShutdown = message.message_type {
+                        error!("🚨 Emergency stop received - cancelling open orders: {:?}", message.payload);
+                        self.abort_twaps();
+                        let cancelled = self.cancel_open_orders().await;
+                        info!("🛑 Cancelled {} open orders", cancelled);
+                        self.base.request_shutdown();
+                        break;
+                    }
+                    let handled = match message.message_type {
+                        crate::core::types::MessageType::OrderRequest => {
+                            match serde_json::from_value::<ClosingOrder>(message.payload) {
+                                Ok(request) => {
+                                    let (order_id, symbol) = (request.order.id, request.order.symbol.clone());
+                                    let executed = self.execute_requested_order(request, None).await;
+                                    if let Err(e) = &executed {
+                                        let rejection = OrderRejection { order_id, symbol, reason: e.to_string() };
+                                        self.publish(crate::core::types::MessageType::OrderRejected, serde_json::to_value(&rejection)).await;
+                                    }
+                                    executed.map(|_| ())
+                                }
+                                Err(e) => Err(e.into()),
+                            }
+                        }
+                        crate::core::types::MessageType::MultiLegOrderRequest => {
+                            match serde_json::from_value::<MultiLegOrder>(message.payload) {
+                                Ok(order) => {
+                                    let executed = self.execute_multi_leg(&order).await;
+                                    if let Err(e) = &executed {
+                                        let symbol = order.legs.first().map(|leg| leg.symbol.clone()).unwrap_or_default();
+                                        let rejection = OrderRejection { order_id: order.id, symbol, reason: e.to_string() };
+                                        self.publish(crate::core::types::MessageType::OrderRejected, serde_json::to_value(&rejection)).await;
+                                    }
+                                    executed.map(|_| ())
+                                }
+                                Err(e) => Err(e.into()),
+                            }
+                        }
+                        // Signals the coordinator's vote approved
+                        crate::core::types::MessageType::TradingSignal => {
+                            match serde_json::from_value::<TradingSignal>(message.payload) {
+                                Ok(signal) => {
+                                    signal_batch.push(signal);
+                                    Ok(())
+                                }
+                                Err(e) => Err(e.into()),
+                            }
+                        }
+                        crate::core::types::MessageType::MarketUpdate => {
+                            match serde_json::from_value::<MarketData>(message.payload) {
+                                Ok(quote) => self.on_price_update(&quote).await.map(|_| ()),
+                                Err(e) => Err(e.into()),
+                            }
+                        }
+                        _ => self.handle_event(message).await,
+                    };
+                    if let Err(e) = handled {
+                        error!("Execution Engine event error: {}", e);
+                    }
+                }
+                // Votes finish in any order, so the batch is sorted before it trades
+                _ = std::future::ready(()), if !signal_batch.is_empty() => {
+                    for result in self.execute_signals(std::mem::take(&mut signal_batch)).await {
+                        if let Err(e) = result {
+                            error!("Execution Engine signal error: {}", e);
+                        }
+                    }
+                }
+                _ = self.base.shutdown_requested() => break,
+            }
+        }
+    }
+    
+    /// Stop TWAP slices as risk alerts and emergency stops arrive. Polled
+    /// beside the run loop, which may be busy awaiting the very TWAP to stop.
+    fn guard_twaps(&self) -> impl std::future::Future<Output = ()> + Send + 'static {
+        let (abort, own_id) = (self.twap_abort.clone(), self.base.id);
+        let mut events = self.base.subscribe_events();
+        async move {
+            loop {
+                let message = match events.recv().await {
+                    Ok(message) => message,
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return std::future::pending().await,
+                };
+                match message.message_type {
+                    crate::core::types::MessageType::EmergencyShutdown => abort.abort_all(),
+                    crate::core::types::MessageType::RiskAlert if message.from != own_id => {
+                        if let Ok(alert) = serde_json::from_value::<RiskAlertNotice>(message.payload) {
+                            abort_twaps_for(&abort, &alert);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    
+    /// Send one order to the gateway, or simulate its fill without one
+    async fn submit_to_venue(&self, order: &Order, plan: &ExecutionPlan, budget: &RetryBudget) -> TradingResult<ExecutionResult> {
+        match &self.api_client {
+            Some(api_client) => {
+                let result = api_client.place_order(order, budget).await?;
+                let commission = if result.success {
+                    self.commission_on(&result)?
+                } else {
+                    rust_decimal::Decimal::ZERO
+                };
+                Ok(ExecutionResult { commission, ..result })
+            }
+            None => self.simulate_order_execution(order, plan).await,
+        }
+    }
+    
+    /// Poll the broker for orders whose fills timed out, returning fills that
+    /// have since arrived. Parked orders are never re-sent: the broker may
+    /// already be working them.
+    pub async fn reconcile_pending_fills(&self) -> TradingResult<Vec<ExecutionResult>> {
+        match &self.order_status_source {
+            Some(source) => self.resync_orders(source.as_ref()).await,
+            None => Ok(Vec::new()),
+        }
+    }
+    
+    /// Track gateway connectivity, resyncing open orders when it comes back.
+    /// Returns fills that completed during the outage.
+    pub async fn check_gateway_connection(&self) -> TradingResult<Vec<ExecutionResult>> {
+        let Some(source) = &self.order_status_source else {
+            return Ok(Vec::new());
+        };
+        
+        let connected = source.is_connected();
+        let was_connected = self.gateway_connected.swap(connected, Ordering::SeqCst);
+        if was_connected && !connected {
+            warn!("🔌 Gateway disconnected with {} open orders", self.pending_fill_count().await);
+        } else if !was_connected && connected && self.config.resync_on_reconnect {
+            info!("🔌 Gateway reconnected, resyncing open orders");
+            return self.resync_orders(source.as_ref()).await;
+        }
+        Ok(Vec::new())
+    }
+    
+    /// Whether the gateway was connected at the last check
+    pub fn gateway_connected(&self) -> bool {
+        self.gateway_connected.load(Ordering::SeqCst)
+    }
+    
+    /// Bring locally open orders in line with the broker's view of them
+    async fn resync_orders(&self, source: &dyn OrderStatusSource) -> TradingResult<Vec<ExecutionResult>> {
+        let open = self.orders.read().await.open_orders();
+        let mut filled = Vec::new();
+        
+        for order in open {
+            let Some(report) = source.order_status(order.id).await? else {
+                warn!("❓ Broker has no record of order {}, leaving it for reconciliation", order.id);
+                continue;
+            };
+            let Some(closed) = self.orders.write().await.apply_status(&report) else {
+                continue;
+            };
+            
+            match closed.status {
+                OrderStatus::Filled => {
+                    info!("✅ Order {} filled during the outage", closed.id);
+                    let fill = Fill {
+                        order_id: closed.id,
+                        quantity: report.filled_quantity,
+                        price: report.average_price,
+                        timestamp: report.estimated_completion.unwrap_or_else(chrono::Utc::now),
+                        liquidity: Liquidity::Taker,
+                    };
+                    self.settle(closed.id, std::slice::from_ref(&fill), 0).await;
+                    self.confirm_scale_out(&closed.symbol, closed.id, report.filled_quantity).await;
+                    let result = ExecutionResult {
+                        order_id: closed.id,
+                        executed_quantity: report.filled_quantity,
+                        executed_price: report.average_price,
+                        execution_time_ms: 0,
+                        slippage: closed.price
+                            .map(|price| (report.average_price - price).abs())
+                            .unwrap_or(rust_decimal::Decimal::ZERO),
+                        commission: rust_decimal::Decimal::ZERO,
+                        success: true,
+                        error_message: None,
+                        fills: vec![fill],
+                    };
+                    self.book_fill(&closed, &result).await;
+                    self.publish(crate::core::types::MessageType::OrderExecution, serde_json::to_value(&result)).await;
+                    filled.push(result);
+                }
+                status => {
+                    info!("🗑️ Order {} was {:?} during the outage", closed.id, status);
+                    let rejection = OrderRejection {
+                        order_id: closed.id,
+                        symbol: closed.symbol.clone(),
+                        reason: format!("{:?} at the broker", status),
+                    };
+                    self.order_book.write().await.finish(closed.id, status);
+                    self.confirm_scale_out(&closed.symbol, closed.id, rust_decimal::Decimal::ZERO).await;
+                    self.publish(crate::core::types::MessageType::OrderRejected, serde_json::to_value(&rejection)).await;
+                }
+            }
+        }
+        
+        Ok(filled)
+    }
+    
+    /// Scale out of `position` according to its strategy's take-profit plan
+    pub async fn manage_take_profits(&self, position: &Position, plan: &TakeProfitConfig) {
+        info!("🎯 Managing {} take-profit levels for {}", plan.levels.len(), position.symbol);
+        self.scale_outs.write().await.insert(position.symbol.clone(), ScaleOut::new(position, plan));
+        self.persist_protective_levels().await;
+    }
+    
+    /// Put positions that aren't managed yet under the take-profit plan, and
+    /// drop plans for positions no longer held
+    pub async fn manage_positions(&self) {
+        let Some(plan) = &self.take_profit else {
+            return;
+        };
+        let positions = self.base.get_system_context().await.portfolio.positions;
+        let unmanaged: Vec<&Position> = {
+            let mut scale_outs = self.scale_outs.write().await;
+            scale_outs.retain(|symbol, _| positions.contains_key(symbol));
+            positions
+                .values()
+                .filter(|position| !position.quantity.is_zero() && !scale_outs.contains_key(&position.symbol))
+                .collect()
+        };
+        for position in unmanaged {
+            self.manage_take_profits(position, plan).await;
+        }
+    }
+    
+    /// Re-arm the stops and take-profit plans persisted before a restart
+    pub async fn restore_protective_levels(&self) -> TradingResult<usize> {
+        let Some(store) = &self.protection_store else {
+            return Ok(0);
+        };
+        
+        let restored = store.load().await?;
+        let count = restored.len();
+        for (symbol, scale_out) in &restored {
+            info!("🛡️ Re-armed protective levels for {} ({} remaining)", symbol, scale_out.remaining());
+        }
+        self.scale_outs.write().await.extend(restored);
+        Ok(count)
+    }
+    
+    /// Snapshot the protective levels; a failed write is logged rather than interrupting trading
+    async fn persist_protective_levels(&self) {
+        let Some(store) = &self.protection_store else {
+            return;
+        };
+        let snapshot = self.scale_outs.read().await.clone();
+        if let Err(e) = store.save(&snapshot).await {
+            warn!("Failed to persist protective levels: {}", e);
+        }
+    }
+    
+    /// Execute any take-profit tranches triggered by a new price
+    pub async fn on_price_update(&self, quote: &MarketData) -> TradingResult<Vec<ExecutionResult>> {
+        let orders = match self.scale_outs.write().await.get_mut(&quote.symbol) {
+            Some(scale_out) => scale_out.on_price(quote.price),
+            None => return Ok(Vec::new()),
+        };
+        
+        let mut results = Vec::with_capacity(orders.len());
+        for (order, close_reason) in orders {
+            info!("🎯 Protective level hit on {}: closing {}", order.symbol, order.quantity);
+            let (order_id, symbol) = (order.id, order.symbol.clone());
+            let request = ClosingOrder { order, close_reason: Some(close_reason) };
+            let executed = self.execute_requested_order(request, Some(quote.clone())).await;
+            // A fill held for reconciliation is confirmed once the broker reports it
+            if !self.orders.read().await.is_open(&order_id) {
+                let filled = executed.as_ref().map(|result| result.executed_quantity).unwrap_or_default();
+                self.confirm_scale_out(&symbol, order_id, filled).await;
+            }
+            results.push(executed?);
+        }
+        Ok(results)
+    }
+    
+    /// Reduce a scaled-out position by a closing order's confirmed fill,
+    /// dropping the plan once nothing is left
+    async fn confirm_scale_out(&self, symbol: &str, order_id: OrderId, filled: rust_decimal::Decimal) {
+        {
+            let mut scale_outs = self.scale_outs.write().await;
+            let Some(scale_out) = scale_outs.get_mut(symbol).filter(|scale_out| scale_out.awaits(&order_id)) else {
+                return;
+            };
+            scale_out.confirm(&order_id, filled);
+            if scale_out.is_closed() {
+                scale_outs.remove(symbol);
+            }
+        }
+        self.persist_protective_levels().await;
+    }
+    
+    /// Quantity still held in a position being scaled out of
+    pub async fn take_profit_remaining(&self, symbol: &str) -> Option<rust_decimal::Decimal> {
+        self.scale_outs.read().await.get(symbol).map(ScaleOut::remaining)
+    }
+    
+    /// Cancel every open order at the gateway and stop tracking it, returning
+    /// how many were cancelled. Covers orders parked for reconciliation and
+    /// those still working, such as in-flight TWAP slices.
+    pub async fn cancel_open_orders(&self) -> usize {
+        let mut order_ids: Vec<OrderId> = self.orders.write().await.drain().iter().map(|order| order.id).collect();
+        for order_id in self.order_book.read().await.open_ids() {
+            if !order_ids.contains(&order_id) {
+                order_ids.push(order_id);
+            }
+        }
+        self.cancel_at_gateway(&order_ids).await;
+        order_ids.len()
+    }
+    
+    /// Number of orders awaiting fill reconciliation
+    pub async fn pending_fill_count(&self) -> usize {
+        self.orders.read().await.open_count()
+    }
+    
+    /// Simulate order execution when no OpenD gateway is attached
+    async fn simulate_order_execution(&self, order: &Order, _plan: &ExecutionPlan) -> TradingResult<ExecutionResult> {
+        // Simulate execution with random slippage and latency
+        let slippage = rust_decimal::Decimal::from_f64_retain(rand::random::<f64>() * 0.001).unwrap(); // 0-0.1% slippage
+        
+        // Split the order into partial fills at independently simulated market prices
+        let slices = self.config.simulated_fill_slices.max(1);
+        let slice = (order.quantity / rust_decimal::Decimal::from(slices)).round_dp(order.quantity.scale());
+        let liquidity = if matches!(order.order_type, OrderType::Market) {
+            Liquidity::Taker
+        } else {
+            Liquidity::Maker
+        };
+        
+        let mut remaining = order.quantity;
+        let mut fills = Vec::with_capacity(slices as usize);
+        for i in 0..slices {
+            let quantity = if i + 1 == slices { remaining } else { slice.min(remaining) };
+            if quantity <= rust_decimal::Decimal::ZERO {
+                continue;
+            }
+            remaining -= quantity;
+            
+            let market_price = rust_decimal::Decimal::from_f64_retain(150.0 + rand::random::<f64>() * 10.0).unwrap();
+            fills.push(Fill {
+                order_id: order.id,
+                quantity,
+                price: match order.side {
+                    OrderSide::Buy => market_price + slippage,
+                    OrderSide::Sell => market_price - slippage,
+                },
+                timestamp: chrono::Utc::now(),
+                liquidity,
+            });
+        }
+        
+        let mut result = ExecutionResult::from_fills(
+            order.id,
+            fills,
+            slippage,
+            rust_decimal::Decimal::ZERO,
+            (rand::random::<f64>() * self.config.max_latency_ms as f64) as u64,
+        )?;
+        result.commission = self.commission_on(&result)?;
+        Ok(result)
+    }
+    
+    /// Commission on the value traded by an executed order
+    fn commission_on(&self, result: &ExecutionResult) -> TradingResult<rust_decimal::Decimal> {
+        let notional = result
+            .executed_price
+            .checked_mul(result.executed_quantity)
+            .ok_or_else(|| TradingError::overflow(format!("traded value of order {}", result.order_id)))?;
+        Ok(self.commission.commission(notional))
+    }
+}
+
+impl AgentWiring for ExecutionEngineAgent {
+    const NAME: &'static str = "execution_engine";
+    
+    fn base_mut(&mut self) -> &mut BaseAgent {
+        &mut self.base
+    }
+}
+
+#[async_trait]
+impl AutonomousAgent for ExecutionEngineAgent {
+    async fn execute_mission(&self, _context: &SystemContext) -> TradingResult<AgentResult> {
+        info!("⚡ Execution Engine executing mission...");
+        
+        // Execution engine waits for signals from other agents
+        Ok(AgentResult {
+            success: true,
+            signals: Vec::new(), // Execution engine doesn't generate signals
+            metrics: PerformanceMetrics {
+                total_trades: 0,
+                winning_trades: 0,
+                losing_trades: 0,
+                win_rate: 0.0,
+                average_win: Default::default(),
+                average_loss: Default::default(),
+                profit_factor: 0.0,
+                max_consecutive_wins: 0,
+                max_consecutive_losses: 0,
+                average_execution_time_ms: self.config.max_latency_ms as f64,
+            },
+            recommendations: vec!["Ready for trade execution".to_string()],
+            errors: Vec::new(),
+        })
+    }
+    
+    async fn self_evaluate(&self) -> TradingResult<PerformanceMetrics> {
+        Ok(PerformanceMetrics {
+            total_trades: 0,
+            winning_trades: 0,
+            losing_trades: 0,
+            win_rate: 0.0,
+            average_win: Default::default(),
+            average_loss: Default::default(),
+            profit_factor: 0.0,
+            max_consecutive_wins: 0,
+            max_consecutive_losses: 0,
+            average_execution_time_ms: self.config.max_latency_ms as f64,
+        })
+    }
+    
+    async fn evolve_strategy(&mut self, feedback: &SystemFeedback) -> TradingResult<EvolutionResult> {
+        info!("🧬 Execution engine evolving strategy...");
+        
+        let mut new_parameters = std::collections::HashMap::new();
+        
+        if feedback.performance_score > 0.8 {
+            // Increase aggressiveness for better performance
+            new_parameters.insert(
+                "max_latency_ms".to_string(),
+                serde_json::Value::Number(serde_json::Number::from(1)),
+            );
+        }
+        
+        Ok(EvolutionResult {
+            strategy_updated: !new_parameters.is_empty(),
+            new_parameters,
+            performance_improvement: feedback.performance_score * 0.1,
+            confidence: 0.85,
+        })
+    }
+    
+    async fn generate_code(&self, requirements: &Requirements) -> TradingResult<CodeGeneration> {
+        info!("🔧 Execution engine generating code for: {}", requirements.functionality);
+        
+        let code = format!(
+            "// Execution optimization code for: {}\nuse tokio::time::Instant;\n\npub async fn optimize_{}() -> Result<f64, Box<dyn std::error::Error>> {{\n    let start = Instant::now();\n    // Execution optimization implementation\n    Ok(start.elapsed().as_secs_f64())\n}}",
+            requirements.functionality,
+            requirements.functionality.to_lowercase().replace(' ', "_")
+        );
+        
+        Ok(CodeGeneration {
+            code,
+            language: "rust".to_string(),
+            tests: vec!["#[tokio::test] async fn test_optimization() { assert!(optimize_test().await.is_ok()); }".to_string()],
+            documentation: format!("Execution optimization function for {}", requirements.functionality),
+            performance_estimate: requirements.performance_targets.clone(),
+        })
+    }
+    
+    fn apply_parameters(&mut self, parameters: &HashMap<String, serde_json::Value>) -> TradingResult<Vec<ParameterChange>> {
+        let updates = validate_parameters(EVOLVABLE_PARAMETERS, parameters)?;
+        let mut applied = Vec::new();
+        
+        for (key, value) in updates {
+            let old = match key {
+                "max_latency_ms" => {
+                    info!("🔧 max_latency_ms: {} -> {}", self.config.max_latency_ms, value);
+                    let old = ParameterValue::Integer(self.config.max_latency_ms);
+                    self.config.max_latency_ms = value.as_u64();
+                    old
+                }
+                _ => continue,
+            };
+            applied.push(ParameterChange { key, old, new: value });
+        }
+        
+        Ok(applied)
+    }
+    
+    fn capabilities(&self) -> Vec<AgentCapability> {
+        self.base.capabilities.clone()
+    }
+    
+    fn agent_id(&self) -> AgentId {
+        self.base.id
+    }
+    
+    fn change_log(&self) -> Option<&ConfigChangeLog> {
+        self.base.change_log.as_ref()
+    }
+    
+    fn evolution_cooldown(&self) -> Option<&EvolutionCooldown> {
+        self.base.evolution_cooldown.as_ref()
+    }
+    
+    async fn run(&mut self) -> TradingResult<()> {
+        if !self.base.await_prerequisites().await? {
+            return Ok(());
+        }
+        info!("⚡ Execution Engine starting execution loop...");
+        
+        // Alerts must reach TWAPs the loop is blocked executing, so they're watched beside it
+        let guard = self.guard_twaps();
+        tokio::select! {
+            _ = self.trading_loop() => {}
+            _ = guard => {}
+        }
+        
+        info!("⚡ Execution Engine execution loop ended");
+        Ok(())
+    }
+    
+    async fn shutdown(&mut self) -> TradingResult<()> {
+        info!("🛑 Execution Engine shutting down...");
+        self.abort_twaps();
+        self.base.request_shutdown();
+        Ok(())
+    }
+}
+
+/// Stop the TWAP slices a risk alert reaches: every one on a halt, else
+/// those on the alert's symbol. Book-wide warnings leave them running.
+fn abort_twaps_for(abort: &SliceAbort, alert: &RiskAlertNotice) {
+    match (alert.severity, &alert.symbol) {
+        (AlertSeverity::Halt, _) => {
+            warn!("🛑 Aborting in-flight TWAP slices: {}", alert.reason);
+            abort.abort_all();
+        }
+        (AlertSeverity::Warning, Some(symbol)) => {
+            warn!("🛑 Aborting in-flight {} TWAP slices: {}", symbol, alert.reason);
+            abort.abort_symbol(symbol);
+        }
+        (AlertSeverity::Warning, None) => {}
+    }
+}
+
+/// Dollar value of an order at `price` per share
+fn order_value(order: &Order, price: rust_decimal::Decimal) -> TradingResult<rust_decimal::Decimal> {
+    price.checked_mul(order.quantity).ok_or_else(|| TradingError::overflow("order value"))
+}
+
+#[async_trait]
+impl TradeExecutor for ExecutionEngineAgent {
+    async fn execute_trade(&self, signal: &TradingSignal) -> TradingResult<ExecutionResult> {
+        self.execute_signal(signal).await
+    }
+    
+    async fn optimize_execution(&self, order: &Order) -> TradingResult<ExecutionPlan> {
+        self.create_execution_plan(order, None).await
     }
     
     async fn monitor_orders(&self) -> TradingResult<Vec<TraitOrderStatus>> {
-        // Simulate order monitoring
-        Ok(vec![
-            TraitOrderStatus {
-                order_id: uuid::Uuid::new_v4(),
-                status: OrderStatus::Filled,
-                filled_quantity: rust_decimal::Decimal::from(10),
+        Ok(self.order_book.read().await.reports())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::{CanaryConfig, SystemConfig};
+    use crate::core::types::Symbol;
+    use crate::execution::canary::LiveVenue;
+    use crate::core::types::OrderId;
+
+    /// Quote book holding a fresh quote at `price` for each symbol
+    async fn quotes(symbols: &[&str], price: i64) -> QuoteBook {
+        let quote_book = QuoteBook::new();
+        for symbol in symbols {
+            quote_book.record(&MarketData {
+                symbol: symbol.to_string(),
+                timestamp: chrono::Utc::now(),
+                price: rust_decimal::Decimal::from(price),
+                volume: 1_000,
+                bid: None,
+                ask: None,
+                bid_size: None,
+                ask_size: None,
+                source: Default::default(),
+            }).await;
+        }
+        quote_book
+    }
+
+    fn buy_signal() -> TradingSignal {
+        TradingSignal {
+            symbol: "AAPL".to_string(),
+            signal_type: crate::core::types::SignalType::Buy,
+            strength: 0.8,
+            confidence: 0.8,
+            timestamp: chrono::Utc::now(),
+            reasoning: "test".to_string(),
+            source: "test".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bad_quantity_is_refused_before_open_orders_are_touched() {
+        let mut config = SystemConfig::default();
+        config.agents.execution_engine.order_conflict_policy = crate::execution::orders::OrderConflictPolicy::CancelAndReplace;
+        config.agents.execution_engine.min_order_quantity = rust_decimal::Decimal::from(1_000_000);
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+            system_context,
+        ).await.unwrap();
+        agent.orders.write().await.track(Order {
+            id: uuid::Uuid::new_v4(),
+            symbol: "AAPL".to_string(),
+            order_type: OrderType::Limit,
+            side: OrderSide::Sell,
+            quantity: rust_decimal::Decimal::from(5),
+            price: Some(rust_decimal::Decimal::from(100)),
+            timestamp: chrono::Utc::now(),
+            status: OrderStatus::Pending,
+        });
+
+        let error = agent.execute_signal(&buy_signal()).await.unwrap_err();
+        assert!(error.to_string().contains("quantity"), "{}", error);
+        // The opposing sell would have been cancelled in favour of an order that was never valid
+        assert_eq!(agent.pending_fill_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_stale_context_blocks_trading() {
+        let config = SystemConfig::default();
+        let mut context = SystemContext::initial(&config);
+        context.last_updated = chrono::Utc::now() - chrono::Duration::seconds(120);
+        let system_context = Arc::new(RwLock::new(context));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+            system_context.clone(),
+        ).await.unwrap()
+            .with_quote_guard(quotes(&["AAPL"], 100).await, 60);
+
+        assert!(agent.execute_trade(&buy_signal()).await.is_err());
+
+        system_context.write().await.last_updated = chrono::Utc::now();
+        assert!(agent.execute_trade(&buy_signal()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_non_positive_quantity_never_reaches_venue() {
+        let config = SystemConfig::default();
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+            system_context,
+        ).await.unwrap()
+            .with_quote_guard(quotes(&["AAPL"], 100).await, 60);
+
+        for strength in [0.0, -0.5] {
+            let error = agent.execute_trade(&TradingSignal { strength, ..buy_signal() }).await.unwrap_err();
+            assert!(error.to_string().contains("not above the minimum"), "{}", error);
+        }
+        assert!(agent.venue_statistics().await.venues().is_empty());
+
+        let result = agent.execute_trade(&buy_signal()).await.unwrap();
+        assert!(result.success);
+        assert!(result.executed_quantity > rust_decimal::Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_data_timestamp_clock_ignores_wall_clock() {
+        use crate::core::calendar::SessionClock;
+        use chrono::TimeZone;
+
+        let mut config = SystemConfig::default();
+        config.trading.trading_hours.clock = SessionClock::DataTimestamp;
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let calendar = MarketCalendar::from_config(&config.trading.trading_hours).unwrap();
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+            system_context,
+        ).await.unwrap()
+            .with_quote_guard(quotes(&["AAPL"], 100).await, 60)
+            .with_market_calendar(calendar.clone());
+
+        // Tuesday 10:00 and Saturday 10:00 in New York, whatever the time is now
+        let open = chrono::Utc.with_ymd_and_hms(2024, 3, 12, 15, 0, 0).unwrap();
+        let weekend = chrono::Utc.with_ymd_and_hms(2024, 3, 16, 15, 0, 0).unwrap();
+        let signal = |timestamp| TradingSignal { timestamp, ..buy_signal() };
+
+        assert!(agent.execute_trade(&signal(open)).await.is_ok());
+        assert!(agent.execute_trade(&signal(weekend)).await.is_err());
+
+        let agent = agent.with_market_calendar(calendar.with_clock(SessionClock::AlwaysOpen));
+        assert!(agent.execute_trade(&signal(weekend)).await.is_ok());
+    }
+    
+    #[tokio::test]
+    async fn test_after_hours_needs_opt_in_and_trades_smaller_on_tight_spreads() {
+        use crate::core::calendar::SessionClock;
+        use chrono::TimeZone;
+
+        let mut config = SystemConfig::default();
+        config.trading.trading_hours.clock = SessionClock::DataTimestamp;
+        config.trading.trading_hours.extended.after_hours.enabled = true;
+        let quote = |bid: i64, ask: i64| MarketData {
+            symbol: "AAPL".to_string(),
+            timestamp: chrono::Utc::now(),
+            price: rust_decimal::Decimal::from(100),
+            volume: 1_000,
+            bid: Some(rust_decimal::Decimal::new(bid, 2)),
+            ask: Some(rust_decimal::Decimal::new(ask, 2)),
+            bid_size: None,
+            ask_size: None,
+            source: Default::default(),
+        };
+        let agent = |extended_hours: bool, quote: MarketData| {
+            let config = config.clone();
+            async move {
+                let (sender, _receiver) = mpsc::unbounded_channel();
+                let (event_sender, _) = broadcast::channel(16);
+                let quote_book = QuoteBook::new();
+                quote_book.record(&quote).await;
+                ExecutionEngineAgent::new(
+                    config.agents.execution_engine.clone(),
+                    config.api.clone(),
+                    sender,
+                    event_sender,
+                    Arc::new(RwLock::new(SystemContext::initial(&config))),
+                ).await.unwrap()
+                    .with_quote_guard(quote_book, 60)
+                    .with_market_calendar(MarketCalendar::from_config(&config.trading.trading_hours).unwrap())
+                    .with_extended_hours(extended_hours)
+            }
+        };
+
+        // Tuesday 11:00 and 17:00 in New York
+        let regular = TradingSignal { timestamp: chrono::Utc.with_ymd_and_hms(2024, 3, 12, 15, 0, 0).unwrap(), ..buy_signal() };
+        let after_hours = TradingSignal { timestamp: chrono::Utc.with_ymd_and_hms(2024, 3, 12, 21, 0, 0).unwrap(), ..buy_signal() };
+
+        let error = agent(false, quote(9_999, 10_001)).await.execute_trade(&after_hours).await.unwrap_err();
+        assert!(error.to_string().contains("AfterHours"), "{}", error);
+
+        let full = agent(true, quote(9_999, 10_001)).await.execute_trade(&regular).await.unwrap();
+        let reduced = agent(true, quote(9_999, 10_001)).await.execute_trade(&after_hours).await.unwrap();
+        assert_eq!(reduced.executed_quantity * rust_decimal::Decimal::from(2), full.executed_quantity);
+
+        // 2% wide is beyond the 50bps extended-session limit
+        let error = agent(true, quote(9_900, 10_100)).await.execute_trade(&after_hours).await.unwrap_err();
+        assert!(error.to_string().contains("Spread too wide"), "{}", error);
+    }
+    
+    #[tokio::test]
+    async fn test_rejected_order_is_journaled_with_reason() {
+        use crate::core::calendar::{SessionClock, SessionType};
+        use crate::core::config::TradeJournalConfig;
+        use crate::execution::journal::{JournalEntry, JournalOutcome};
+        use chrono::TimeZone;
+
+        let path = std::env::temp_dir().join(format!("trade-journal-{}.jsonl", uuid::Uuid::new_v4()));
+        let mut config = SystemConfig::default();
+        config.trading.trading_hours.clock = SessionClock::DataTimestamp;
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let journal = TradeJournalConfig { path: path.to_string_lossy().into_owned(), ..TradeJournalConfig::default() };
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+            system_context,
+        ).await.unwrap()
+            .with_quote_guard(quotes(&["AAPL"], 100).await, 60)
+            .with_market_calendar(MarketCalendar::from_config(&config.trading.trading_hours).unwrap())
+            .with_trade_journal(TradeJournal::from_config(&journal));
+
+        let open = chrono::Utc.with_ymd_and_hms(2024, 3, 12, 15, 0, 0).unwrap();
+        let weekend = chrono::Utc.with_ymd_and_hms(2024, 3, 16, 15, 0, 0).unwrap();
+        agent.execute_trade(&TradingSignal { timestamp: open, ..buy_signal() }).await.unwrap();
+        let error = agent.execute_trade(&TradingSignal { timestamp: weekend, ..buy_signal() }).await.unwrap_err();
+        assert!(matches!(error, TradingError::Execution { .. }));
+
+        let contents = tokio::fs::read_to_string(&path).await.unwrap();
+        let entries: Vec<JournalEntry> = contents.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(entries.len(), 2);
+        assert!(matches!(entries[0].outcome, JournalOutcome::Executed { .. }));
+        assert_eq!(entries[1].symbol, "AAPL");
+        assert!(entries[1].order_id.is_none());
+        match &entries[1].outcome {
+            JournalOutcome::Rejected { reason } => {
+                assert_eq!(*reason, ExecutionError::MarketClosed { session: SessionType::Closed });
+            }
+            other => panic!("expected a rejection, got {:?}", other),
+        }
+        let _ = tokio::fs::remove_file(&path).await;
+    }
+    
+    #[tokio::test]
+    async fn test_stages_are_traced_in_pipeline_order() {
+        use crate::agents::decision_trace::DecisionTraces;
+        use crate::core::config::DecisionTraceConfig;
+
+        let config = SystemConfig::default();
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let traces = DecisionTraces::from_config(&DecisionTraceConfig::default());
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+            system_context,
+        ).await.unwrap()
+            .with_quote_guard(quotes(&["AAPL"], 100).await, 60)
+            .with_decision_traces(traces.clone());
+
+        let signal = buy_signal();
+        assert!(agent.execute_trade(&signal).await.unwrap().success);
+
+        let trace = traces.get(&DecisionTraces::signal_id(&signal)).await.unwrap();
+        let stages: Vec<PipelineStage> = trace.verdicts.iter().map(|verdict| verdict.stage).collect();
+        assert_eq!(stages, [PipelineStage::Sizing, PipelineStage::Compliance, PipelineStage::Execution]);
+        assert!(trace.rejected_at().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_signals_processed_in_deterministic_order() {
+        let config = SystemConfig::default();
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+            system_context,
+        ).await.unwrap();
+
+        let timestamp = chrono::Utc::now();
+        let inputs = [
+            ("TSLA", "risk", 0),
+            ("AAPL", "intelligence", 1),
+            ("TSLA", "intelligence", 0),
+            ("AAPL", "risk", 0),
+            ("AAPL", "intelligence", 0),
+        ];
+        let handles: Vec<_> = inputs
+            .iter()
+            .map(|&(symbol, source, offset)| {
+                tokio::spawn(async move {
+                    let mut signal = buy_signal();
+                    signal.symbol = symbol.to_string();
+                    signal.source = source.to_string();
+                    signal.timestamp = timestamp + chrono::Duration::milliseconds(offset);
+                    signal
+                })
+            })
+            .collect();
+
+        let mut batch = Vec::new();
+        for handle in handles.into_iter().rev() {
+            batch.push(handle.await.unwrap());
+        }
+
+        let ordered: Vec<(String, String)> = agent
+            .order_signals(batch)
+            .into_iter()
+            .map(|s| (s.symbol, s.source))
+            .collect();
+        let expected = [
+            ("AAPL", "intelligence"),
+            ("AAPL", "risk"),
+            ("TSLA", "intelligence"),
+            ("TSLA", "risk"),
+            ("AAPL", "intelligence"),
+        ];
+        assert_eq!(
+            ordered,
+            expected.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect::<Vec<_>>()
+        );
+    }
+
+    /// Live venue that records submitted orders and fills them at a fixed price
+    #[derive(Default)]
+    struct RecordingVenue {
+        orders: std::sync::Mutex<Vec<Order>>,
+    }
+
+    #[async_trait]
+    impl LiveVenue for RecordingVenue {
+        async fn submit(&self, order: &Order) -> TradingResult<ExecutionResult> {
+            self.orders.lock().unwrap().push(order.clone());
+            Ok(ExecutionResult {
+                order_id: order.id,
+                executed_quantity: order.quantity,
+                executed_price: rust_decimal::Decimal::from(155),
+                execution_time_ms: 1,
+                slippage: rust_decimal::Decimal::ZERO,
+                commission: rust_decimal::Decimal::ZERO,
+                success: true,
+                error_message: None,
+                fills: Vec::new(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_signals_queued_together_execute_in_deterministic_order() {
+        use crate::core::config::TradeJournalConfig;
+        use crate::execution::journal::JournalEntry;
+
+        let path = std::env::temp_dir().join(format!("trade-journal-{}.jsonl", uuid::Uuid::new_v4()));
+        let config = SystemConfig::default();
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let journal = TradeJournalConfig { path: path.to_string_lossy().into_owned(), ..TradeJournalConfig::default() };
+        let mut agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender.clone(),
+            system_context,
+        ).await.unwrap()
+            .with_quote_guard(quotes(&["AAPL", "MSFT", "TSLA"], 100).await, 60)
+            .with_trade_journal(TradeJournal::from_config(&journal));
+        let running = tokio::spawn(async move { agent.run().await });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // Approved in reverse order, all waiting before the agent runs again
+        let timestamp = chrono::Utc::now();
+        for symbol in ["TSLA", "MSFT", "AAPL"] {
+            let signal = TradingSignal { symbol: symbol.to_string(), timestamp, ..buy_signal() };
+            let message = AgentMessage::broadcast(uuid::Uuid::new_v4(), crate::core::types::MessageType::TradingSignal, serde_json::to_value(&signal).unwrap());
+            event_sender.send(message).unwrap();
+        }
+
+        let mut symbols = Vec::new();
+        for _ in 0..100 {
+            let contents = tokio::fs::read_to_string(&path).await.unwrap_or_default();
+            symbols = contents
+                .lines()
+                .map(|line| serde_json::from_str::<JournalEntry>(line).unwrap().symbol)
+                .collect();
+            if symbols.len() == 3 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(symbols, ["AAPL", "MSFT", "TSLA"]);
+
+        running.abort();
+        let _ = tokio::fs::remove_file(&path).await;
+    }
+    
+    #[tokio::test]
+    async fn test_risk_alert_aborts_twap_the_run_loop_is_executing() {
+        use crate::core::config::TradeJournalConfig;
+        use crate::core::types::{AlertSeverity, MessageType};
+        use crate::execution::journal::{JournalEntry, JournalOutcome};
+
+        let path = std::env::temp_dir().join(format!("trade-journal-{}.jsonl", uuid::Uuid::new_v4()));
+        let mut config = SystemConfig::default();
+        config.agents.execution_engine.algorithm_selection.default_algorithm = TWAP_ALGORITHM.to_string();
+        config.agents.execution_engine.algorithm_selection.rules.clear();
+        config.agents.execution_engine.algorithm_selection.twap_horizon_ms = 4000;
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let journal = TradeJournalConfig { path: path.to_string_lossy().into_owned(), ..TradeJournalConfig::default() };
+        let mut agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender.clone(),
+            system_context,
+        ).await.unwrap()
+            .with_quote_guard(quotes(&["AAPL"], 100).await, 60)
+            .with_trade_journal(TradeJournal::from_config(&journal));
+        let running = tokio::spawn(async move { agent.run().await });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let signal = AgentMessage::broadcast(uuid::Uuid::new_v4(), MessageType::TradingSignal, serde_json::to_value(buy_signal()).unwrap());
+        event_sender.send(signal).unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let alert = RiskAlertNotice { reason: "Loss cap".to_string(), symbol: Some("AAPL".to_string()), severity: AlertSeverity::Warning };
+        event_sender.send(AgentMessage::broadcast(uuid::Uuid::new_v4(), MessageType::RiskAlert, serde_json::to_value(&alert).unwrap())).unwrap();
+
+        // The TWAP stops well before its horizon instead of sending every slice
+        let mut entries = Vec::new();
+        for _ in 0..50 {
+            let contents = tokio::fs::read_to_string(&path).await.unwrap_or_default();
+            entries = contents.lines().map(|line| serde_json::from_str::<JournalEntry>(line).unwrap()).collect::<Vec<_>>();
+            if !entries.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        match &entries[0].outcome {
+            JournalOutcome::Executed { result } => {
+                assert!(result.executed_quantity < entries[0].quantity.unwrap(), "{:?}", result);
+                assert!(result.execution_time_ms < 1000, "{:?}", result);
+            }
+            other => panic!("expected an execution, got {:?}", other),
+        }
+
+        running.abort();
+        let _ = tokio::fs::remove_file(&path).await;
+    }
+    
+    #[tokio::test]
+    async fn test_canary_routes_reduced_live_order_alongside_paper_fill() {
+        let mut config = SystemConfig::default();
+        config.agents.execution_engine.canary = CanaryConfig {
+            enabled: true,
+            size_fraction: 0.1,
+            max_slippage_bps: 1_000.0,
+            min_clean_fills: 1,
+        };
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let venue = Arc::new(RecordingVenue::default());
+
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+            system_context,
+        ).await.unwrap()
+            .with_quote_guard(quotes(&["AAPL"], 100).await, 60)
+            .with_canary(CanaryRouter::new(config.agents.execution_engine.canary.clone(), venue.clone()));
+
+        let mut signal = buy_signal();
+        signal.strength = 0.5;
+        signal.confidence = config.agents.execution_engine.position_sizing.reference_confidence;
+        let paper = agent.execute_trade(&signal).await.unwrap();
+        assert!(paper.success);
+        assert_eq!(paper.executed_quantity, rust_decimal::Decimal::new(5, 0));
+        // The default 0.1% is charged on the value traded, not the share count
+        assert_eq!(paper.commission, paper.executed_price * paper.executed_quantity * rust_decimal::Decimal::new(1, 3));
+
+        let live_orders = venue.orders.lock().unwrap().clone();
+        assert_eq!(live_orders.len(), 1);
+        assert_eq!(live_orders[0].quantity, rust_decimal::Decimal::new(5, 1));
+        assert_ne!(live_orders[0].id, paper.order_id);
+
+        let canary = agent.canary().unwrap();
+        assert_eq!(canary.stats().await.fills, 1);
+        assert!(canary.promotion_ready().await);
+    }
+
+    #[tokio::test]
+    async fn test_sizing_follows_aggressiveness_not_target_return() {
+        let mut config = SystemConfig::default();
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+
+        let mut quantities = Vec::new();
+        for (target, aggressiveness) in [(0.01, 1.0), (0.9, 1.0), (0.01, 0.5)] {
+            config.trading.target_daily_return = target;
+            config.trading.aggressiveness = aggressiveness;
+            let (sender, _receiver) = mpsc::unbounded_channel();
+            let (event_sender, _) = broadcast::channel(16);
+            let agent = ExecutionEngineAgent::new(
+                config.agents.execution_engine.clone(),
+                config.api.clone(),
+                sender,
+                event_sender,
+                system_context.clone(),
+            ).await.unwrap().with_aggressiveness(config.trading.sizing_multiplier());
+            quantities.push(agent.create_order_from_signal(&buy_signal()).await.unwrap().quantity);
+        }
+
+        assert_eq!(quantities[0], quantities[1]);
+        assert_eq!(quantities[2] * rust_decimal::Decimal::from(2), quantities[0]);
+
+        config.trading.target_daily_return = 0.9;
+        assert!(config.trading.target_return_warning().is_some());
+        config.trading.target_daily_return = 0.01;
+        assert!(config.trading.target_return_warning().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sub_minimum_order_is_skipped_with_thought() {
+        use crate::execution::sizing::SubMinimumPolicy;
+
+        let mut config = SystemConfig::default();
+        config.trading.initial_capital = rust_decimal::Decimal::from(100);
+        config.agents.execution_engine.order_minimum.min_notional = rust_decimal::Decimal::from(10);
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let broadcaster = AIThoughtBroadcaster::new(10);
+        let mut thoughts = broadcaster.subscribe();
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender.clone(),
+            event_sender.clone(),
+            system_context.clone(),
+        ).await.unwrap()
+            .with_quote_guard(quotes(&["AAPL"], 2).await, 60)
+            .with_thought_broadcaster(broadcaster);
+
+        // A weak signal sizes to 3 shares at the reference confidence, $6 at the $2 quote
+        let weak = TradingSignal { strength: 0.3, confidence: 0.7, ..buy_signal() };
+        let error = agent.execute_trade(&weak).await.unwrap_err();
+        assert!(error.to_string().contains("below the minimum tradable"), "{}", error);
+        assert!(agent.venue_statistics().await.venues().is_empty());
+        let thought = thoughts.recv().await.unwrap();
+        assert!(thought.tags.contains(&"order_minimum".to_string()));
+        assert!(thought.message.contains("Skipping AAPL"));
+
+        // Accumulating holds the first weak signal and sends both once they reach the minimum
+        config.agents.execution_engine.order_minimum.policy = SubMinimumPolicy::Accumulate;
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+            system_context,
+        ).await.unwrap()
+            .with_quote_guard(quotes(&["AAPL"], 2).await, 60);
+        let deferred = agent.execute_trade(&weak).await.unwrap();
+        assert!(deferred.executed_quantity.is_zero());
+        let sent = agent.execute_trade(&weak).await.unwrap();
+        assert!(sent.success);
+        assert_eq!(sent.executed_quantity, rust_decimal::Decimal::from(6));
+    }
+
+    struct OptionQuotes(HashMap<Symbol, MarketData>);
+
+    #[async_trait]
+    impl QuoteSource for OptionQuotes {
+        async fn quote(&self, symbol: &str) -> TradingResult<Option<MarketData>> {
+            Ok(self.0.get(symbol).cloned())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_multi_leg_fill_is_booked_as_one_position() {
+        use crate::execution::multi_leg::OptionRight;
+
+        let config = SystemConfig::default();
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+            system_context.clone(),
+        ).await.unwrap();
+        let expiry = chrono::NaiveDate::from_ymd_opt(2025, 1, 17).unwrap();
+        let spread = MultiLegOrder::vertical_spread(
+            "AAPL", expiry, OptionRight::Call, rust_decimal::Decimal::from(150), rust_decimal::Decimal::from(155),
+            rust_decimal::Decimal::ONE, rust_decimal::Decimal::ONE,
+        ).unwrap();
+
+        // No quote source: refused rather than filled at made-up prices
+        assert!(agent.execute_multi_leg(&spread).await.is_err());
+
+        let quote = |symbol: &Symbol, price: i64| {
+            let price = rust_decimal::Decimal::new(price, 1);
+            (symbol.clone(), MarketData {
+                symbol: symbol.clone(),
+                timestamp: chrono::Utc::now(),
+                price,
+                volume: 10,
+                bid: Some(price),
+                ask: Some(price),
+                bid_size: None,
+                ask_size: None,
+                source: Default::default(),
+            })
+        };
+        let quotes = OptionQuotes([quote(&spread.legs[0].symbol, 50), quote(&spread.legs[1].symbol, 20)].into());
+        let agent = agent.with_option_quotes(Arc::new(quotes));
+        let fill = agent.execute_multi_leg(&spread).await.unwrap();
+        assert_eq!(fill.net_debit(), rust_decimal::Decimal::from(3));
+
+        // $3 debit out of cash, 0.1% commission on $7 of premium out of P&L
+        let context = system_context.read().await;
+        let position = &context.portfolio.positions[&fill.position_symbol()];
+        assert_eq!(position.market_value().unwrap(), rust_decimal::Decimal::from(3));
+        assert_eq!(context.portfolio.cash_balance, config.trading.initial_capital - rust_decimal::Decimal::new(3007, 3));
+        assert_eq!(context.portfolio.daily_pnl, rust_decimal::Decimal::new(-7, 3));
+        let published = receiver.try_recv().unwrap();
+        assert!(matches!(published.message_type, crate::core::types::MessageType::OrderExecution));
+    }
+
+    struct MockGateway {
+        connected: AtomicBool,
+        filled: std::sync::Mutex<Option<OrderId>>,
+    }
+
+    #[async_trait]
+    impl OrderStatusSource for MockGateway {
+        fn is_connected(&self) -> bool {
+            self.connected.load(Ordering::SeqCst)
+        }
+
+        async fn order_status(&self, order_id: OrderId) -> TradingResult<Option<TraitOrderStatus>> {
+            let filled = *self.filled.lock().unwrap() == Some(order_id);
+            Ok(Some(TraitOrderStatus {
+                order_id,
+                status: if filled { OrderStatus::Filled } else { OrderStatus::Pending },
+                filled_quantity: rust_decimal::Decimal::from(8),
                 average_price: rust_decimal::Decimal::from(150),
                 remaining_quantity: rust_decimal::Decimal::ZERO,
-                estimated_completion: Some(chrono::Utc::now()),
+                estimated_completion: None,
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delayed_fill_is_reconciled_without_loss() {
+        let mut config = SystemConfig::default();
+        config.agents.execution_engine.fill_timeout_ms = 50;
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let faults = FaultInjector::new();
+        let gateway = Arc::new(MockGateway {
+            connected: AtomicBool::new(true),
+            filled: std::sync::Mutex::new(None),
+        });
+
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+            system_context,
+        ).await.unwrap()
+            .with_quote_guard(quotes(&["AAPL"], 100).await, 60)
+            .with_fault_injector(faults.clone())
+            .with_order_status_source(gateway.clone());
+
+        faults.set_fill_delay(Duration::from_millis(200));
+        let result = agent.execute_trade(&buy_signal()).await.unwrap();
+        assert!(!result.success);
+        assert_eq!(agent.pending_fill_count().await, 1);
+
+        // Still working at the broker: the order stays parked and isn't re-sent,
+        // even once the venue would fill a resubmission immediately
+        faults.clear();
+        assert!(agent.reconcile_pending_fills().await.unwrap().is_empty());
+        assert_eq!(agent.pending_fill_count().await, 1);
+
+        *gateway.filled.lock().unwrap() = Some(result.order_id);
+        let fills = agent.reconcile_pending_fills().await.unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].order_id, result.order_id);
+        assert!(fills[0].success);
+        assert_eq!(agent.pending_fill_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_resyncs_pending_order_to_filled() {
+        let mut config = SystemConfig::default();
+        config.agents.execution_engine.fill_timeout_ms = 50;
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let faults = FaultInjector::new();
+        let gateway = Arc::new(MockGateway {
+            connected: AtomicBool::new(true),
+            filled: std::sync::Mutex::new(None),
+        });
+
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+            system_context,
+        ).await.unwrap()
+            .with_quote_guard(quotes(&["AAPL"], 100).await, 60)
+            .with_fault_injector(faults.clone())
+            .with_order_status_source(gateway.clone());
+
+        faults.set_fill_delay(Duration::from_millis(200));
+        let pending = agent.execute_trade(&buy_signal()).await.unwrap();
+        assert_eq!(agent.pending_fill_count().await, 1);
+
+        // The order fills at the broker while the gateway is down
+        gateway.connected.store(false, Ordering::SeqCst);
+        assert!(agent.check_gateway_connection().await.unwrap().is_empty());
+        assert!(!agent.gateway_connected());
+        *gateway.filled.lock().unwrap() = Some(pending.order_id);
+
+        gateway.connected.store(true, Ordering::SeqCst);
+        let fills = agent.check_gateway_connection().await.unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].order_id, pending.order_id);
+        assert_eq!(fills[0].executed_quantity, rust_decimal::Decimal::from(8));
+        assert!(fills[0].success);
+
+        // No longer tracked, so reconciliation won't re-submit it
+        assert_eq!(agent.pending_fill_count().await, 0);
+        assert!(agent.reconcile_pending_fills().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_protective_levels_survive_restart() {
+        let config = SystemConfig::default();
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let path = std::env::temp_dir().join(format!("protective-{}.json", uuid::Uuid::new_v4()));
+        let agent = |store: ProtectionStore| {
+            let (sender, _receiver) = mpsc::unbounded_channel();
+            let (event_sender, _) = broadcast::channel(16);
+            let config = config.clone();
+            let system_context = system_context.clone();
+            async move {
+                ExecutionEngineAgent::new(
+                    config.agents.execution_engine.clone(),
+                    config.api.clone(),
+                    sender,
+                    event_sender,
+                    system_context,
+                ).await.unwrap().with_protection_store(store)
+            }
+        };
+
+        let position = Position {
+            id: uuid::Uuid::new_v4(),
+            symbol: "AAPL".to_string(),
+            quantity: rust_decimal::Decimal::from(2),
+            entry_price: rust_decimal::Decimal::from(100),
+            current_price: rust_decimal::Decimal::from(100),
+            unrealized_pnl: rust_decimal::Decimal::ZERO,
+            realized_pnl: rust_decimal::Decimal::ZERO,
+            timestamp: chrono::Utc::now(),
+        };
+        let plan = TakeProfitConfig {
+            levels: vec![crate::core::config::TakeProfitLevel { gain_pct: 0.05, fraction: 0.5 }],
+            trail_pct: None,
+            stop_loss_pct: Some(0.05),
+        };
+        agent(ProtectionStore::new(&path)).await.manage_take_profits(&position, &plan).await;
+
+        // A fresh agent after a restart re-arms the bracket from the snapshot
+        let restarted = agent(ProtectionStore::new(&path)).await;
+        assert_eq!(restarted.restore_protective_levels().await.unwrap(), 1);
+        assert_eq!(restarted.take_profit_remaining("AAPL").await, Some(rust_decimal::Decimal::from(2)));
+
+        let quote = |price: i64| MarketData {
+            symbol: "AAPL".to_string(),
+            timestamp: chrono::Utc::now(),
+            price: rust_decimal::Decimal::from(price),
+            volume: 100,
+            bid: None,
+            ask: None,
+            bid_size: None,
+            ask_size: None,
+            source: Default::default(),
+        };
+
+        // Take-profit still active
+        assert_eq!(restarted.on_price_update(&quote(105)).await.unwrap().len(), 1);
+        assert_eq!(restarted.take_profit_remaining("AAPL").await, Some(rust_decimal::Decimal::from(1)));
+
+        // Stop still active, and the persisted snapshot tracks the partial close
+        assert_eq!(restarted.on_price_update(&quote(95)).await.unwrap().len(), 1);
+        assert_eq!(restarted.take_profit_remaining("AAPL").await, None);
+        assert!(ProtectionStore::new(&path).load().await.unwrap().is_empty());
+
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_held_positions_are_put_under_the_take_profit_plan() {
+        let config = SystemConfig::default();
+        let mut context = SystemContext::initial(&config);
+        let position = Position {
+            id: uuid::Uuid::new_v4(),
+            symbol: "AAPL".to_string(),
+            quantity: rust_decimal::Decimal::from(2),
+            entry_price: rust_decimal::Decimal::from(100),
+            current_price: rust_decimal::Decimal::from(100),
+            unrealized_pnl: rust_decimal::Decimal::ZERO,
+            realized_pnl: rust_decimal::Decimal::ZERO,
+            timestamp: chrono::Utc::now(),
+        };
+        context.portfolio.positions.insert(position.symbol.clone(), position);
+        let system_context = Arc::new(RwLock::new(context));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+            system_context.clone(),
+        ).await.unwrap()
+            .with_take_profit_plan(TakeProfitConfig {
+                levels: vec![crate::core::config::TakeProfitLevel { gain_pct: 0.05, fraction: 0.5 }],
+                trail_pct: None,
+                stop_loss_pct: None,
+            });
+
+        agent.manage_positions().await;
+        assert_eq!(agent.take_profit_remaining("AAPL").await, Some(rust_decimal::Decimal::from(2)));
+
+        // A position closed elsewhere leaves no plan behind
+        system_context.write().await.portfolio.positions.clear();
+        agent.manage_positions().await;
+        assert_eq!(agent.take_profit_remaining("AAPL").await, None);
+    }
+    
+    #[tokio::test]
+    async fn test_stop_loss_and_take_profit_exits_journal_close_reasons() {
+        use crate::core::config::TradeJournalConfig;
+        use crate::execution::journal::JournalEntry;
+
+        let path = std::env::temp_dir().join(format!("close-reasons-{}.jsonl", uuid::Uuid::new_v4()));
+        let config = SystemConfig::default();
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let journal = TradeJournalConfig { path: path.to_string_lossy().into_owned(), ..TradeJournalConfig::default() };
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+            system_context,
+        ).await.unwrap()
+            .with_trade_journal(TradeJournal::from_config(&journal));
+
+        let plan = TakeProfitConfig {
+            levels: vec![crate::core::config::TakeProfitLevel { gain_pct: 0.05, fraction: 1.0 }],
+            trail_pct: None,
+            stop_loss_pct: Some(0.05),
+        };
+        let quote = |symbol: &str, price: i64| MarketData {
+            symbol: symbol.to_string(),
+            timestamp: chrono::Utc::now(),
+            price: rust_decimal::Decimal::from(price),
+            volume: 100,
+            bid: None,
+            ask: None,
+            bid_size: None,
+            ask_size: None,
+            source: Default::default(),
+        };
+        for symbol in ["AAPL", "MSFT"] {
+            let position = Position {
+                id: uuid::Uuid::new_v4(),
+                symbol: symbol.to_string(),
+                quantity: rust_decimal::Decimal::from(2),
+                entry_price: rust_decimal::Decimal::from(100),
+                current_price: rust_decimal::Decimal::from(100),
+                unrealized_pnl: rust_decimal::Decimal::ZERO,
+                realized_pnl: rust_decimal::Decimal::ZERO,
+                timestamp: chrono::Utc::now(),
+            };
+            agent.manage_take_profits(&position, &plan).await;
+        }
+
+        assert_eq!(agent.on_price_update(&quote("AAPL", 94)).await.unwrap().len(), 1);
+        assert_eq!(agent.on_price_update(&quote("MSFT", 106)).await.unwrap().len(), 1);
+
+        let contents = tokio::fs::read_to_string(&path).await.unwrap();
+        let entries: Vec<JournalEntry> = contents.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        let reasons: Vec<(&str, Option<CloseReason>)> = entries
+            .iter()
+            .map(|entry| (entry.symbol.as_str(), entry.close_reason))
+            .collect();
+        assert_eq!(reasons, vec![
+            ("AAPL", Some(CloseReason::StopLoss)),
+            ("MSFT", Some(CloseReason::TakeProfit)),
+        ]);
+        assert!(contents.contains("\"close_reason\":\"stop_loss\""));
+        let _ = tokio::fs::remove_file(&path).await;
+    }
+    
+    #[tokio::test]
+    async fn test_emergency_stop_cancels_every_open_order() {
+        use crate::core::config::MoomooConfig;
+        use crate::core::types::MessageType;
+        use hyper::service::{make_service_fn, service_fn};
+        use hyper::{Body, Method, Request, Server};
+        use std::sync::Mutex;
+
+        // Fake gateway recording the order ids it is asked to cancel
+        let cancelled: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let recorded = cancelled.clone();
+        let make_service = make_service_fn(move |_| {
+            let recorded = recorded.clone();
+            async move {
+                Ok::<_, std::convert::Infallible>(service_fn(move |request: Request<Body>| {
+                    let recorded = recorded.clone();
+                    async move {
+                        let status = if request.method() == Method::DELETE {
+                            recorded.lock().unwrap().push(request.uri().path().trim_start_matches("/orders/").to_string());
+                            200
+                        } else {
+                            404
+                        };
+                        Ok::<_, std::convert::Infallible>(hyper::Response::builder().status(status).body(Body::empty()).unwrap())
+                    }
+                }))
+            }
+        });
+        let server = Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(make_service);
+        let gateway = MoomooConfig {
+            base_url: format!("http://{}", server.local_addr()),
+            api_key: String::new(),
+            secret_key: String::new(),
+            paper_trading: true,
+            timeout_ms: 1000,
+            retry_attempts: 0,
+            use_gateway: true,
+        };
+        tokio::spawn(server);
+
+        let config = SystemConfig::default();
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let mut agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender.clone(),
+            system_context,
+        ).await.unwrap()
+            .with_api_client(ApiClient::new(&gateway, reqwest::Client::new()));
+
+        let mut open = Vec::new();
+        for symbol in ["AAPL", "MSFT"] {
+            let order = Order {
+                id: uuid::Uuid::new_v4(),
+                symbol: symbol.to_string(),
+                order_type: OrderType::Limit,
+                side: OrderSide::Buy,
+                quantity: rust_decimal::Decimal::from(5),
+                price: Some(rust_decimal::Decimal::from(100)),
+                timestamp: chrono::Utc::now(),
+                status: OrderStatus::Pending,
+            };
+            open.push(order.id.to_string());
+            agent.orders.write().await.track(order);
+        }
+        // A working order that hasn't timed out into reconciliation is cancelled too
+        let working = Order {
+            id: uuid::Uuid::new_v4(),
+            symbol: "TSLA".to_string(),
+            order_type: OrderType::Limit,
+            side: OrderSide::Sell,
+            quantity: rust_decimal::Decimal::from(3),
+            price: Some(rust_decimal::Decimal::from(200)),
+            timestamp: chrono::Utc::now(),
+            status: OrderStatus::Pending,
+        };
+        open.push(working.id.to_string());
+        agent.order_book.write().await.submit(&working, RetryBudget::new(1));
+
+        let handle = agent.clone();
+        let running = tokio::spawn(async move { agent.run().await });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        event_sender.send(AgentMessage::broadcast(uuid::Uuid::new_v4(), MessageType::EmergencyShutdown, serde_json::json!({ "reason": "Daily loss limit exceeded" }))).unwrap();
+        timeout(Duration::from_secs(5), running).await.unwrap().unwrap().unwrap();
+
+        let mut cancelled = cancelled.lock().unwrap().clone();
+        cancelled.sort();
+        open.sort();
+        assert_eq!(cancelled, open);
+        assert_eq!(handle.pending_fill_count().await, 0);
+    }
+    
+    /// Gateway requests recorded by [`fake_gateway`]
+    type GatewayLog = Arc<std::sync::Mutex<Vec<(hyper::Method, serde_json::Value)>>>;
+    
+    /// Fake OpenD gateway recording every request. `respond` gets the method,
+    /// the number of placements before this request and the request body.
+    async fn fake_gateway(
+        respond: fn(&hyper::Method, usize, &serde_json::Value) -> (u16, serde_json::Value),
+    ) -> (crate::core::config::MoomooConfig, GatewayLog) {
+        use hyper::service::{make_service_fn, service_fn};
+        use hyper::{Body, Method, Request, Server};
+        
+        let requests: GatewayLog = Arc::default();
+        let recorded = requests.clone();
+        let make_service = make_service_fn(move |_| {
+            let recorded = recorded.clone();
+            async move {
+                Ok::<_, std::convert::Infallible>(service_fn(move |request: Request<Body>| {
+                    let recorded = recorded.clone();
+                    async move {
+                        let method = request.method().clone();
+                        let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                        let body: serde_json::Value = serde_json::from_slice(&body).unwrap_or_default();
+                        let mut recorded = recorded.lock().unwrap();
+                        let placements = recorded.iter().filter(|(method, _)| method == Method::POST).count();
+                        let (status, report) = respond(&method, placements, &body);
+                        recorded.push((method, body));
+                        let response = hyper::Response::builder().status(status).body(Body::from(report.to_string()));
+                        Ok::<_, std::convert::Infallible>(response.unwrap())
+                    }
+                }))
+            }
+        });
+        let server = Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(make_service);
+        let gateway = crate::core::config::MoomooConfig {
+            base_url: format!("http://{}", server.local_addr()),
+            api_key: String::new(),
+            secret_key: String::new(),
+            paper_trading: true,
+            timeout_ms: 1000,
+            retry_attempts: 3,
+            use_gateway: true,
+        };
+        tokio::spawn(server);
+        (gateway, requests)
+    }
+    
+    fn limit_buy(quantity: i64) -> Order {
+        Order {
+            id: uuid::Uuid::new_v4(),
+            symbol: "AAPL".to_string(),
+            order_type: OrderType::Limit,
+            side: OrderSide::Buy,
+            quantity: rust_decimal::Decimal::from(quantity),
+            price: Some(rust_decimal::Decimal::from(100)),
+            timestamp: chrono::Utc::now(),
+            status: OrderStatus::Pending,
+        }
+    }
+    
+    /// First placement fills 6; any later placement fills whatever it asks for
+    fn partial_then_full(method: &hyper::Method, placements: usize, body: &serde_json::Value) -> (u16, serde_json::Value) {
+        match (method.as_str(), placements) {
+            ("POST", 0) => (200, serde_json::json!({
+                "status": "PartiallyFilled", "filled_quantity": "6", "average_price": "100", "remaining_quantity": "4",
+            })),
+            ("POST", _) => (200, serde_json::json!({
+                "status": "Filled", "filled_quantity": body["quantity"], "average_price": "103", "remaining_quantity": "0",
+            })),
+            _ => (200, serde_json::json!({})),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_netting_amends_a_partly_absorbed_order_at_the_gateway() {
+        use crate::execution::orders::OrderConflictPolicy;
+        use hyper::Method;
+
+        let (gateway, requests) = fake_gateway(|_, _, _| (200, serde_json::json!({}))).await;
+        let mut config = SystemConfig::default();
+        config.agents.execution_engine.order_conflict_policy = OrderConflictPolicy::Net;
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+            system_context,
+        ).await.unwrap()
+            .with_api_client(ApiClient::new(&gateway, reqwest::Client::new()));
+
+        let sell = Order { side: OrderSide::Sell, ..limit_buy(4) };
+        agent.orders.write().await.track(sell.clone());
+        agent.order_book.write().await.submit(&sell, RetryBudget::new(1));
+
+        // Buying 3 against the working sell of 4 leaves it selling 1
+        let mut buy = limit_buy(3);
+        assert!(!agent.resolve_order_conflicts(&buy_signal(), &mut buy).await.unwrap());
+
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].0, Method::PATCH);
+        assert_eq!(requests[0].1["quantity"], "1");
+        assert_eq!(agent.order_book.read().await.remaining(sell.id), Some(rust_decimal::Decimal::ONE));
+    }
+    
+    #[tokio::test]
+    async fn test_partial_fill_remainder_is_resubmitted_and_completion_published() {
+        use crate::core::types::MessageType;
+        use hyper::Method;
+
+        let (gateway, requests) = fake_gateway(partial_then_full).await;
+        let config = SystemConfig::default();
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+            system_context,
+        ).await.unwrap()
+            .with_api_client(ApiClient::new(&gateway, reqwest::Client::new()));
+
+        let order = limit_buy(10);
+        let request = ClosingOrder { order: order.clone(), close_reason: None };
+        let result = agent.execute_requested_order(request, None).await.unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.executed_quantity, rust_decimal::Decimal::from(10));
+        assert_eq!(result.executed_price, rust_decimal::Decimal::new(1012, 1));
+        assert!(result.fills.iter().all(|fill| fill.order_id == order.id));
+
+        // The working remainder was cancelled before 4 more were sent
+        let methods: Vec<(Method, serde_json::Value)> = requests.lock().unwrap().clone();
+        assert_eq!(methods.iter().map(|(method, _)| method.clone()).collect::<Vec<_>>(), vec![Method::POST, Method::DELETE, Method::POST]);
+        assert_eq!(methods[2].1["quantity"], "4");
+        assert!(agent.monitor_orders().await.unwrap().is_empty());
+
+        // Completion is announced on its own, leaving performance metrics untouched
+        let mut completions = Vec::new();
+        while let Ok(message) = receiver.try_recv() {
+            assert!(!matches!(message.message_type, MessageType::PerformanceUpdate));
+            if let MessageType::OrderCompleted = message.message_type {
+                completions.push(serde_json::from_value::<OrderCompletion>(message.payload).unwrap());
+            }
+        }
+        assert_eq!(completions.len(), 1);
+        assert_eq!(completions[0].order_id, order.id);
+        assert_eq!(completions[0].filled_quantity, rust_decimal::Decimal::from(10));
+        assert_eq!(completions[0].remaining_quantity, rust_decimal::Decimal::ZERO);
+    }
+    
+    #[tokio::test]
+    async fn test_fills_are_booked_and_closing_one_publishes_the_trade() {
+        use crate::core::types::MessageType;
+
+        let config = SystemConfig::default();
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+            system_context.clone(),
+        ).await.unwrap();
+
+        let buy = limit_buy(10);
+        // As recorded when a signal's order is sent
+        agent.signal_sources.write().await.insert(buy.id, "market_intelligence".to_string());
+        let bought = agent.execute_requested_order(ClosingOrder { order: buy.clone(), close_reason: None }, None).await.unwrap();
+        assert_eq!(system_context.read().await.portfolio.positions["AAPL"].quantity, bought.executed_quantity);
+
+        let sell = Order { id: uuid::Uuid::new_v4(), side: OrderSide::Sell, ..limit_buy(10) };
+        let sold = agent
+            .execute_requested_order(ClosingOrder { order: sell, close_reason: Some(CloseReason::TakeProfit) }, None)
+            .await
+            .unwrap();
+        let context = system_context.read().await;
+        assert!(context.portfolio.positions.is_empty());
+        assert_eq!(context.active_positions, 0);
+
+        let trades: Vec<TradeRecord> = std::iter::from_fn(|| receiver.try_recv().ok())
+            .filter(|message| matches!(message.message_type, MessageType::TradeClosed))
+            .map(|message| serde_json::from_value(message.payload).unwrap())
+            .collect();
+        assert_eq!(trades.len(), 1);
+        let gross = (sold.executed_price - bought.executed_price) * sold.executed_quantity;
+        assert_eq!(trades[0].pnl, gross - sold.commission);
+        assert_eq!(trades[0].costs, sold.commission);
+        assert_eq!(trades[0].notional, bought.executed_price * bought.executed_quantity);
+        assert_eq!(trades[0].order_id, Some(buy.id));
+        assert_eq!(trades[0].source.as_deref(), Some("market_intelligence"));
+        assert!(agent.signal_sources.read().await.is_empty());
+        assert_eq!(context.portfolio.total_pnl, trades[0].pnl - bought.commission);
+        assert_eq!(context.portfolio.total_value, context.portfolio.cash_balance);
+    }
+    
+    #[tokio::test]
+    async fn test_retry_budget_spent_by_gateway_blocks_resubmission() {
+        use hyper::Method;
+
+        // A 503 is retried by the gateway client; the retry then only partially fills
+        fn unavailable_then_partial(method: &hyper::Method, placements: usize, body: &serde_json::Value) -> (u16, serde_json::Value) {
+            match (method.as_str(), placements) {
+                ("POST", 0) => (503, serde_json::json!({})),
+                _ => partial_then_full(method, placements - usize::from(method == Method::POST), body),
+            }
+        }
+        let (gateway, requests) = fake_gateway(unavailable_then_partial).await;
+        let mut config = SystemConfig::default();
+        config.agents.execution_engine.max_retries_per_order = 1;
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let agent = ExecutionEngineAgent::new(
+            config.agents.execution_engine.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+            system_context,
+        ).await.unwrap()
+            .with_api_client(ApiClient::new(&gateway, reqwest::Client::new()));
+
+        let request = ClosingOrder { order: limit_buy(10), close_reason: None };
+        let result = agent.execute_requested_order(request, None).await.unwrap();
+
+        // Re-submission would normally top up the remaining 4, but the budget is gone
+        let methods: Vec<Method> = requests.lock().unwrap().iter().map(|(method, _)| method.clone()).collect();
+        assert_eq!(methods, vec![Method::POST, Method::POST, Method::DELETE]);
+        assert_eq!(result.executed_quantity, rust_decimal::Decimal::from(6));
+        assert!(result.error_message.unwrap().contains("remainder cancelled"));
+    }
+    
+    #[tokio::test]
+    async fn test_unfilled_order_is_cancelled_at_gateway_before_it_ends() {
+        use hyper::Method;
+
+        // The gateway accepts the order without filling any of it
+        fn working(method: &hyper::Method, _placements: usize, _body: &serde_json::Value) -> (u16, serde_json::Value) {
+            match method.as_str() {
+                "POST" => (200, serde_json::json!({
+                    "status": "Pending", "filled_quantity": "0", "average_price": "0", "remaining_quantity": "10",
+                })),
+                _ => (200, serde_json::json!({})),
+            }
+        }
+        fn cancel_fails(method: &hyper::Method, placements: usize, body: &serde_json::Value) -> (u16, serde_json::Value) {
+            match method.as_str() {
+                "DELETE" => (404, serde_json::json!({})),
+                _ => working(method, placements, body),
             }
-        ])
+        }
+        
+        for (respond, confirmed) in [(working as fn(&_, _, &_) -> _, true), (cancel_fails, false)] {
+            let (gateway, requests) = fake_gateway(respond).await;
+            let config = SystemConfig::default();
+            let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+            let (sender, _receiver) = mpsc::unbounded_channel();
+            let (event_sender, _) = broadcast::channel(16);
+            let agent = ExecutionEngineAgent::new(
+                config.agents.execution_engine.clone(),
+                config.api.clone(),
+                sender,
+                event_sender,
+                system_context,
+            ).await.unwrap()
+                .with_api_client(ApiClient::new(&gateway, reqwest::Client::new()));
+
+            let order = limit_buy(10);
+            let request = ClosingOrder { order: order.clone(), close_reason: None };
+            let result = agent.execute_requested_order(request, None).await.unwrap();
+            assert_eq!(result.executed_quantity, rust_decimal::Decimal::ZERO);
+
+            let methods: Vec<Method> = requests.lock().unwrap().iter().map(|(method, _)| method.clone()).collect();
+            assert!(methods.contains(&Method::DELETE));
+            // Unconfirmed, the order stays open for reconciliation instead of ending locally
+            assert_eq!(agent.order_book.read().await.open_ids().contains(&order.id), !confirmed);
+            assert_eq!(agent.pending_fill_count().await, usize::from(!confirmed));
+        }
     }
 }
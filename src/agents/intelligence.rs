@@ -1,28 +1,68 @@
 //! Market Intelligence Agent - Real-time market analysis and signal generation
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{broadcast, mpsc, RwLock};
 use tokio::time::{interval, Duration};
-use tracing::{info, warn, error};
+use tracing::{debug, info, warn, error};
 
 use crate::core::config::{IntelligenceConfig, ApiConfig};
-use crate::core::errors::TradingResult;
+use crate::core::errors::{TradingError, TradingResult};
 use crate::core::types::{
     AgentCapability, AgentId, AgentMessage, SystemContext, 
-    PerformanceMetrics, TradingSignal, SignalType, MarketData
+    PerformanceMetrics, TradingSignal, SignalType, MarketData, DataSource, Symbol
 };
+use crate::infrastructure::faults::FaultInjector;
+use crate::core::ai_thoughts::{AIAgent, AIThought, AIThoughtBroadcaster, ThoughtType};
+use crate::core::metrics::MetricsCollector;
+use crate::intelligence::adaptive::AdaptiveInterval;
+use crate::intelligence::budget::ComputeBudget;
+use crate::intelligence::coalescer::QuoteCoalescer;
+use crate::intelligence::feed::{MarketDataFeed, FEED_CHANNEL_CAPACITY};
+use crate::intelligence::indicators::{IndicatorReadings, IndicatorSet, PriceHistory};
+use crate::intelligence::quotes::QuoteBook;
+use crate::agents::change_log::ConfigChangeLog;
+use crate::agents::cooldown::EvolutionCooldown;
+use crate::agents::decision_trace::{DecisionTraces, PipelineStage};
+use crate::agents::parameters::{refresh_interval, validate_parameters, ParameterChange, ParameterSpec, ParameterValue};
 use crate::agents::traits::{
-    AutonomousAgent, BaseAgent, AgentResult, SystemFeedback, 
+    next_message, AgentWiring, AutonomousAgent, BaseAgent, AgentResult, SystemFeedback, 
     EvolutionResult, Requirements, CodeGeneration, MarketAnalyzer, MarketAnalysis
 };
 
+/// Annualized volatility above which the market counts as highly volatile
+const HIGH_VOLATILITY: f64 = 0.4;
+
+/// Parameters this agent accepts from strategy evolution
+const EVOLVABLE_PARAMETERS: &[ParameterSpec] = &[
+    ParameterSpec::integer("update_interval_ms", 10, 60_000),
+];
+
+/// When each symbol was last signalled, and how
+type LastSignals = HashMap<Symbol, (DateTime<Utc>, SignalType)>;
+
 /// Market Intelligence Agent for real-time market analysis
 #[derive(Clone)]
 pub struct MarketIntelligenceAgent {
     base: BaseAgent,
     config: IntelligenceConfig,
     api_config: ApiConfig,
+    quotes: Arc<RwLock<QuoteCoalescer>>,
+    adaptive_interval: AdaptiveInterval,
+    compute_budget: Arc<RwLock<ComputeBudget>>,
+    last_volatility: Arc<RwLock<Option<f64>>>,
+    indicators: IndicatorSet,
+    price_history: Arc<RwLock<PriceHistory>>,
+    quote_book: QuoteBook,
+    max_quote_age_secs: u64,
+    active_source: Arc<RwLock<Option<DataSource>>>,
+    last_signals: Arc<RwLock<LastSignals>>,
+    thought_broadcaster: Option<AIThoughtBroadcaster>,
+    decision_traces: Option<DecisionTraces>,
+    faults: FaultInjector,
 }
 
 impl MarketIntelligenceAgent {
@@ -31,6 +71,7 @@ impl MarketIntelligenceAgent {
         config: IntelligenceConfig,
         api_config: ApiConfig,
         message_sender: mpsc::UnboundedSender<AgentMessage>,
+        event_sender: broadcast::Sender<AgentMessage>,
     ) -> TradingResult<Self> {
         let capabilities = vec![
             AgentCapability::MarketAnalysis,
@@ -49,6 +90,7 @@ impl MarketIntelligenceAgent {
                 max_drawdown: Default::default(),
                 sharpe_ratio: None,
                 last_updated: chrono::Utc::now(),
+                trading_day: None,
             },
             risk_metrics: crate::core::types::RiskMetrics {
                 var_95: Default::default(),
@@ -73,39 +115,249 @@ impl MarketIntelligenceAgent {
             active_positions: 0,
             available_capital: rust_decimal::Decimal::from(100),
             system_health: crate::core::types::SystemHealth::Healthy,
+            equity_curve: Default::default(),
+            last_updated: chrono::Utc::now(),
         }));
         
-        let base = BaseAgent::new(capabilities, message_sender, system_context);
+        let base = BaseAgent::new(capabilities, message_sender, event_sender, system_context);
+        let quotes = Arc::new(RwLock::new(QuoteCoalescer::new(config.coalesce_quotes)));
+        let adaptive_interval = AdaptiveInterval::from_config(&config.adaptive_interval);
+        let compute_budget = Arc::new(RwLock::new(ComputeBudget::from_config(&config.compute_budget)));
+        let indicators = IndicatorSet::from_config(&config.technical_indicators);
         
         Ok(Self {
             base,
             config,
             api_config,
+            quotes,
+            adaptive_interval,
+            compute_budget,
+            last_volatility: Arc::new(RwLock::new(None)),
+            indicators,
+            price_history: Arc::new(RwLock::new(PriceHistory::new())),
+            quote_book: QuoteBook::new(),
+            max_quote_age_secs: 0,
+            active_source: Arc::new(RwLock::new(None)),
+            last_signals: Arc::new(RwLock::new(HashMap::new())),
+            thought_broadcaster: None,
+            decision_traces: None,
+            faults: FaultInjector::new(),
         })
     }
     
+    /// Share a quote book and skip signals for symbols without a quote
+    /// newer than `max_quote_age_secs` (0 disables the guard)
+    pub fn with_quote_guard(mut self, quote_book: QuoteBook, max_quote_age_secs: u64) -> Self {
+        self.quote_book = quote_book;
+        self.max_quote_age_secs = max_quote_age_secs;
+        self
+    }
+    
+    /// Publish analysis thoughts to the given broadcaster
+    pub fn with_thought_broadcaster(mut self, thought_broadcaster: AIThoughtBroadcaster) -> Self {
+        self.thought_broadcaster = Some(thought_broadcaster);
+        self
+    }
+    
+    /// Record signal filter verdicts in the given decision traces
+    pub fn with_decision_traces(mut self, traces: DecisionTraces) -> Self {
+        self.decision_traces = Some(traces);
+        self
+    }
+    
+    /// Attach a fault injector for resilience testing
+    pub fn with_fault_injector(mut self, faults: FaultInjector) -> Self {
+        self.faults = faults;
+        self
+    }
+    
+    /// Period of the market analysis loop, taken from the live config
+    fn update_period(&self) -> Duration {
+        Duration::from_millis(self.config.update_interval_ms)
+    }
+    
+    /// Loop period adjusted for the most recent volatility reading
+    async fn effective_period(&self) -> Duration {
+        let volatility = *self.last_volatility.read().await;
+        self.adaptive_interval.period(self.update_period(), volatility)
+    }
+    
+    /// Buffer an inbound quote until the next analysis cycle
+    pub async fn ingest_quote(&self, quote: MarketData) {
+        self.quotes.write().await.push(quote);
+    }
+    
+    /// Start streaming quotes from the live feed if one is configured
+    fn start_live_feed(&self) -> TradingResult<Option<mpsc::Receiver<MarketData>>> {
+        if !self.config.live_feed.enabled {
+            return Ok(None);
+        }
+        
+        let source = self.config.data_sources.first().map(|config| config.source()).unwrap_or_default();
+        let feed = MarketDataFeed::from_config(&self.config.live_feed, &self.api_config, self.config.watchlist.clone(), source)?;
+        let (sender, ticks) = mpsc::channel(FEED_CHANNEL_CAPACITY);
+        tokio::spawn(feed.run(sender));
+        Ok(Some(ticks))
+    }
+    
+    /// Buffer a tick from the live feed along with any others already
+    /// waiting, then analyze them together
+    async fn analyze_ticks(&self, tick: MarketData, ticks: &mut mpsc::Receiver<MarketData>) -> TradingResult<Vec<TradingSignal>> {
+        self.ingest_quote(tick).await;
+        while let Ok(tick) = ticks.try_recv() {
+            self.ingest_quote(tick).await;
+        }
+        self.analyze_and_signal().await
+    }
+    
     /// Analyze market data and generate signals
     async fn analyze_and_signal(&self) -> TradingResult<Vec<TradingSignal>> {
-        info!("📊 Analyzing market data...");
+        debug!("📊 Analyzing market data...");
         
         // Simulate market data analysis
         let market_data = self.fetch_market_data().await?;
+        let market_data = self.apply_compute_budget(market_data).await;
+        
+        let started = std::time::Instant::now();
         let analysis = self.analyze_market_data(&market_data).await?;
+        self.compute_budget.write().await.observe(market_data.len(), started.elapsed());
+        *self.last_volatility.write().await = (!analysis.symbol_volatility.is_empty()).then_some(analysis.volatility);
         let signals = self.generate_trading_signals(&analysis).await?;
+        let now = Utc::now();
+        let signals = self.suppress_repeats(signals, now).await;
+        let signals = self.guard_quotes(signals).await;
+        self.start_cooldowns(&signals, now).await;
         
-        info!("📊 Generated {} trading signals", signals.len());
+        debug!("📊 Generated {} trading signals", signals.len());
         Ok(signals)
     }
     
+    /// Propose signals to the coordinator, which puts them to a vote before execution
+    async fn propose_signals(&self, signals: &[TradingSignal]) -> TradingResult<()> {
+        for signal in signals {
+            let message = AgentMessage::broadcast(self.base.id, crate::core::types::MessageType::SignalProposal, serde_json::to_value(signal)?);
+            self.base.send_message(message).await?;
+        }
+        Ok(())
+    }
+    
+    /// Keep this cycle's analysis within budget, requeueing deferred quotes for the next cycle
+    async fn apply_compute_budget(&self, market_data: Vec<MarketData>) -> Vec<MarketData> {
+        let (analyzed, deferred) = self.compute_budget.write().await.select(market_data);
+        if !deferred.is_empty() {
+            info!("⏱️ Compute budget reached: deferring {} symbols to the next cycle", deferred.len());
+            MetricsCollector::record_deferred_analysis(deferred.len());
+            
+            // A newer quote arriving meanwhile supersedes the requeued one
+            let mut quotes = self.quotes.write().await;
+            for quote in deferred {
+                quotes.push(quote);
+            }
+        }
+        analyzed
+    }
+    
+    /// Drop signals for symbols signalled within `signal_cooldown_secs`, so
+    /// the execution engine isn't sent the same order every cycle. A signal
+    /// reversing the last one's direction always goes through.
+    async fn suppress_repeats(&self, signals: Vec<TradingSignal>, now: DateTime<Utc>) -> Vec<TradingSignal> {
+        let cooldown = chrono::Duration::seconds(self.config.signal_cooldown_secs as i64);
+        let last_signals = self.last_signals.read().await;
+        let mut emitted = Vec::with_capacity(signals.len());
+        
+        for signal in signals {
+            let repeat = last_signals.get(&signal.symbol).filter(|(at, signal_type)| {
+                now - *at < cooldown && direction(signal_type) == direction(&signal.signal_type)
+            });
+            let Some((at, _)) = repeat else {
+                emitted.push(signal);
+                continue;
+            };
+            
+            let remaining = (cooldown - (now - *at)).num_seconds();
+            info!("📊 Suppressing repeat {:?} signal for {}: cooldown has {}s left", signal.signal_type, signal.symbol, remaining);
+            if let Some(traces) = &self.decision_traces {
+                let reason = format!("Repeat signal within the {}s cooldown", self.config.signal_cooldown_secs);
+                traces.fail(&signal, PipelineStage::SignalFilter, reason).await;
+            }
+            if let Some(broadcaster) = &self.thought_broadcaster {
+                broadcaster.broadcast_thought(
+                    AIThought::new(
+                        AIAgent::MarketIntelligence,
+                        ThoughtType::Analysis,
+                        format!(
+                            "Holding back another {:?} signal for {}: one was sent {}s ago, and I wait {}s before repeating it unless the direction changes.",
+                            signal.signal_type, signal.symbol, (now - *at).num_seconds(), self.config.signal_cooldown_secs
+                        ),
+                        0.9,
+                    )
+                    .with_symbols(vec![signal.symbol.clone()])
+                    .with_tags(vec!["signal_cooldown".to_string(), "skipped".to_string()])
+                ).await;
+            }
+        }
+        
+        emitted
+    }
+    
+    /// Start the repeat cooldown of each signal that passed every filter;
+    /// one dropped for another reason may go out on the next cycle
+    async fn start_cooldowns(&self, signals: &[TradingSignal], now: DateTime<Utc>) {
+        let mut last_signals = self.last_signals.write().await;
+        for signal in signals {
+            last_signals.insert(signal.symbol.clone(), (now, signal.signal_type.clone()));
+        }
+    }
+    
+    /// Drop signals for symbols without a fresh quote, since sizing and risk
+    /// checks would otherwise read a stale price
+    async fn guard_quotes(&self, signals: Vec<TradingSignal>) -> Vec<TradingSignal> {
+        let now = chrono::Utc::now();
+        let mut fresh = Vec::with_capacity(signals.len());
+        
+        for signal in signals {
+            if self.quote_book.is_fresh(&signal.symbol, now, self.max_quote_age_secs).await {
+                if let Some(traces) = &self.decision_traces {
+                    traces.pass(&signal, PipelineStage::SignalFilter).await;
+                }
+                fresh.push(signal);
+                continue;
+            }
+            
+            warn!("📊 Skipping {} signal: no quote in the last {}s", signal.symbol, self.max_quote_age_secs);
+            if let Some(traces) = &self.decision_traces {
+                let reason = format!("No quote in the last {}s", self.max_quote_age_secs);
+                traces.fail(&signal, PipelineStage::SignalFilter, reason).await;
+            }
+            if let Some(broadcaster) = &self.thought_broadcaster {
+                broadcaster.broadcast_thought(
+                    AIThought::new(
+                        AIAgent::MarketIntelligence,
+                        ThoughtType::Analysis,
+                        format!("Skipping {}: no fresh quote, so its price can't be trusted for sizing or risk checks.", signal.symbol),
+                        0.9,
+                    )
+                    .with_symbols(vec![signal.symbol.clone()])
+                    .with_tags(vec!["data_quality".to_string(), "skipped".to_string()])
+                ).await;
+            }
+        }
+        
+        fresh
+    }
+    
     /// Fetch market data from configured sources
     async fn fetch_market_data(&self) -> TradingResult<Vec<MarketData>> {
-        // Simulate fetching market data
-        // In a real implementation, this would connect to Moomoo API or other data sources
+        if self.faults.is_feed_disconnected() {
+            return Err(TradingError::market_data("Market data feed disconnected"));
+        }
         
-        let symbols = vec!["AAPL", "TSLA", "MSFT", "GOOGL"];
-        let mut market_data = Vec::new();
+        let source = self.config.data_sources.first().map(|config| config.source()).unwrap_or_default();
+        let mut quotes = self.quotes.write().await;
         
-        for symbol in symbols {
+        // Without a live feed, simulate quotes for the watchlist
+        let simulated = if self.config.live_feed.enabled { &[][..] } else { &self.config.watchlist[..] };
+        for symbol in simulated {
             let data = MarketData {
                 symbol: symbol.to_string(),
                 timestamp: chrono::Utc::now(),
@@ -115,25 +367,74 @@ impl MarketIntelligenceAgent {
                 ask: Some(rust_decimal::Decimal::from_f64_retain(150.05).unwrap()),
                 bid_size: Some(1000),
                 ask_size: Some(1000),
+                source: source.clone(),
             };
-            market_data.push(data);
+            quotes.push(data);
+        }
+        
+        // Analyze at most one quote per symbol per interval under load
+        let market_data = quotes.drain();
+        for quote in &market_data {
+            self.quote_book.record(quote).await;
         }
+        self.track_active_source().await;
         
         Ok(market_data)
     }
     
+    /// Log when quotes fail over to a different data source
+    async fn track_active_source(&self) {
+        let active = self.quote_book.active_source(chrono::Utc::now()).await;
+        let mut current = self.active_source.write().await;
+        if *current == active {
+            return;
+        }
+        
+        match (&*current, &active) {
+            (Some(from), Some(to)) => warn!("📡 Market data failing over from {}/{} to {}/{}", from.provider, from.feed, to.provider, to.feed),
+            (None, Some(to)) => info!("📡 Market data now sourced from {}/{}", to.provider, to.feed),
+            (Some(from), None) => warn!("📡 Market data source {}/{} is stale and no fallback is fresh", from.provider, from.feed),
+            (None, None) => {}
+        }
+        *current = active;
+    }
+    
     /// Analyze market data using technical indicators
     async fn analyze_market_data(&self, data: &[MarketData]) -> TradingResult<MarketAnalysis> {
-        // Simulate technical analysis
-        let volatility = 0.2 + rand::random::<f64>() * 0.3; // 20-50% volatility
-        let trend_strength = rand::random::<f64>(); // 0-1 trend strength
-        let sentiment_score = rand::random::<f64>() * 2.0 - 1.0; // -1 to 1
+        // Indicator readings for each symbol with enough history, averaged for the market
+        let readings: Vec<(String, IndicatorReadings)> = {
+            let mut history = self.price_history.write().await;
+            for quote in data {
+                if let Some(price) = quote.price.to_f64() {
+                    history.record(&quote.symbol, price);
+                }
+            }
+            data.iter()
+                .filter_map(|quote| Some((quote.symbol.clone(), self.indicators.compute(&history.series(&quote.symbol))?)))
+                .collect()
+        };
+        let symbol_trends: HashMap<String, f64> = readings
+            .iter()
+            .filter_map(|(symbol, readings)| Some((symbol.clone(), readings.trend()?)))
+            .collect();
+        let symbol_volatility: HashMap<String, f64> = readings
+            .iter()
+            .filter_map(|(symbol, readings)| Some((symbol.clone(), readings.volatility()?)))
+            .collect();
+        let mean = |values: &HashMap<String, f64>| {
+            if values.is_empty() { 0.0 } else { values.values().sum::<f64>() / values.len() as f64 }
+        };
+        let trend = mean(&symbol_trends);
+        let trend_strength = trend.abs();
+        let volatility = mean(&symbol_volatility);
         
         Ok(MarketAnalysis {
-            regime: if volatility > 0.4 {
+            regime: if volatility > HIGH_VOLATILITY {
                 crate::core::types::MarketRegime::HighVolatility
-            } else if trend_strength > 0.7 {
+            } else if trend_strength > 0.7 && trend > 0.0 {
                 crate::core::types::MarketRegime::Bull
+            } else if trend_strength > 0.7 {
+                crate::core::types::MarketRegime::Bear
             } else {
                 crate::core::types::MarketRegime::Sideways
             },
@@ -141,40 +442,35 @@ impl MarketIntelligenceAgent {
             trend_strength,
             support_levels: vec![145.0, 140.0, 135.0],
             resistance_levels: vec![155.0, 160.0, 165.0],
-            sentiment_score,
             volume_profile: crate::agents::traits::VolumeProfile {
                 total_volume: data.iter().map(|d| d.volume).sum(),
-                average_volume: data.iter().map(|d| d.volume).sum::<u64>() / data.len() as u64,
-                volume_trend: if rand::random::<f64>() > 0.5 { 1.0 } else { -1.0 },
+                average_volume: data.iter().map(|d| d.volume).sum::<u64>().checked_div(data.len() as u64).unwrap_or(0),
                 high_volume_nodes: vec![150.0, 152.0, 148.0],
             },
+            symbol_trends,
+            symbol_volatility,
         })
     }
     
-    /// Generate trading signals based on analysis
+    /// Generate at most one signal per watchlist symbol from that symbol's own
+    /// indicator trend
     async fn generate_trading_signals(&self, analysis: &MarketAnalysis) -> TradingResult<Vec<TradingSignal>> {
         let mut signals = Vec::new();
         
-        // Generate signals based on market analysis
-        if analysis.trend_strength > 0.7 && analysis.sentiment_score > 0.3 {
+        for symbol in &self.config.watchlist {
+            let trend = analysis.symbol_trends.get(symbol).copied().unwrap_or(0.0);
+            if trend <= 0.7 {
+                continue;
+            }
+            
             signals.push(TradingSignal {
-                symbol: "AAPL".to_string(),
+                symbol: symbol.clone(),
                 signal_type: SignalType::Buy,
-                strength: analysis.trend_strength,
+                strength: trend,
                 confidence: 0.8,
                 timestamp: chrono::Utc::now(),
-                reasoning: "Strong upward trend with positive sentiment".to_string(),
-            });
-        }
-        
-        if analysis.volatility > 0.4 {
-            signals.push(TradingSignal {
-                symbol: "TSLA".to_string(),
-                signal_type: SignalType::StrongBuy,
-                strength: analysis.volatility,
-                confidence: 0.7,
-                timestamp: chrono::Utc::now(),
-                reasoning: "High volatility presents trading opportunities".to_string(),
+                reasoning: "Strong upward trend across indicators".to_string(),
+                source: "market_intelligence".to_string(),
             });
         }
         
@@ -182,6 +478,14 @@ impl MarketIntelligenceAgent {
     }
 }
 
+impl AgentWiring for MarketIntelligenceAgent {
+    const NAME: &'static str = "market_intelligence";
+    
+    fn base_mut(&mut self) -> &mut BaseAgent {
+        &mut self.base
+    }
+}
+
 #[async_trait]
 impl AutonomousAgent for MarketIntelligenceAgent {
     async fn execute_mission(&self, _context: &SystemContext) -> TradingResult<AgentResult> {
@@ -263,6 +567,26 @@ impl AutonomousAgent for MarketIntelligenceAgent {
         })
     }
     
+    fn apply_parameters(&mut self, parameters: &HashMap<String, serde_json::Value>) -> TradingResult<Vec<ParameterChange>> {
+        let updates = validate_parameters(EVOLVABLE_PARAMETERS, parameters)?;
+        let mut applied = Vec::new();
+        
+        for (key, value) in updates {
+            let old = match key {
+                "update_interval_ms" => {
+                    info!("🔧 update_interval_ms: {} -> {}", self.config.update_interval_ms, value);
+                    let old = ParameterValue::Integer(self.config.update_interval_ms);
+                    self.config.update_interval_ms = value.as_u64();
+                    old
+                }
+                _ => continue,
+            };
+            applied.push(ParameterChange { key, old, new: value });
+        }
+        
+        Ok(applied)
+    }
+    
     fn capabilities(&self) -> Vec<AgentCapability> {
         self.base.capabilities.clone()
     }
@@ -271,23 +595,65 @@ impl AutonomousAgent for MarketIntelligenceAgent {
         self.base.id
     }
     
+    fn change_log(&self) -> Option<&ConfigChangeLog> {
+        self.base.change_log.as_ref()
+    }
+    
+    fn evolution_cooldown(&self) -> Option<&EvolutionCooldown> {
+        self.base.evolution_cooldown.as_ref()
+    }
+    
     async fn run(&mut self) -> TradingResult<()> {
+        if !self.base.await_prerequisites().await? {
+            return Ok(());
+        }
         info!("📊 Market Intelligence starting execution loop...");
         
-        let mut update_interval = interval(Duration::from_millis(self.config.update_interval_ms));
+        let mut update_interval = interval(self.update_period());
+        let mut events = self.base.subscribe_events();
+        let mut inbox = self.base.inbox().await;
+        let mut heartbeat = self.base.heartbeat_timer();
+        let mut ticks = self.start_live_feed()?;
         
+        // Ready once the first quote is in, so dependents never trade on an empty quote book
         loop {
             tokio::select! {
-                _ = update_interval.tick() => {
-                    if let Err(e) = self.analyze_and_signal().await {
-                        error!("Market analysis error: {}", e);
+                // A live feed drives analysis tick by tick instead of the interval
+                Some(tick) = next_tick(&mut ticks) => {
+                    self.base.mark_ready();
+                    let Some(feed) = ticks.as_mut() else { continue };
+                    match self.analyze_ticks(tick, feed).await {
+                        Ok(signals) => if let Err(e) = self.propose_signals(&signals).await {
+                            error!("Signal proposal error: {}", e);
+                        },
+                        Err(e) => error!("Market analysis error: {}", e),
+                    }
+                }
+                // Without one, quotes are simulated: their signals are never proposed for execution
+                _ = update_interval.tick(), if ticks.is_none() => {
+                    let analyzed = self.analyze_and_signal().await;
+                    if analyzed.is_ok() {
+                        self.base.mark_ready();
                     }
+                    match analyzed {
+                        Ok(signals) if !signals.is_empty() => {
+                            debug!("📊 Not proposing {} signals from simulated quotes", signals.len());
+                        }
+                        Ok(_) => {}
+                        Err(e) => error!("Market analysis error: {}", e),
+                    }
+                    refresh_interval(&mut update_interval, self.effective_period().await);
                 }
-                _ = tokio::time::sleep(Duration::from_millis(10)) => {
-                    if self.base.should_shutdown().await {
-                        break;
+                _ = heartbeat.tick() => self.base.send_heartbeat(),
+                Some(message) = next_message(&mut events, &mut inbox) => {
+                    if let Err(e) = self.handle_event(message).await {
+                        error!("Market Intelligence event error: {}", e);
+                    }
+                    if refresh_interval(&mut update_interval, self.effective_period().await) {
+                        info!("📊 Analysis interval now {:?}", update_interval.period());
                     }
                 }
+                _ = self.base.shutdown_requested() => break,
             }
         }
         
@@ -297,11 +663,28 @@ impl AutonomousAgent for MarketIntelligenceAgent {
     
     async fn shutdown(&mut self) -> TradingResult<()> {
         info!("🛑 Market Intelligence shutting down...");
-        self.base.request_shutdown().await;
+        self.base.request_shutdown();
         Ok(())
     }
 }
 
+/// Whether a signal leans long (1), short (-1) or neither (0)
+fn direction(signal_type: &SignalType) -> i8 {
+    match signal_type {
+        SignalType::Buy | SignalType::StrongBuy => 1,
+        SignalType::Sell | SignalType::StrongSell => -1,
+        SignalType::Hold => 0,
+    }
+}
+
+/// Next quote from the live feed, or never without one
+async fn next_tick(ticks: &mut Option<mpsc::Receiver<MarketData>>) -> Option<MarketData> {
+    match ticks {
+        Some(ticks) => ticks.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
 #[async_trait]
 impl MarketAnalyzer for MarketIntelligenceAgent {
     async fn analyze_market(&self) -> TradingResult<MarketAnalysis> {
@@ -313,3 +696,234 @@ impl MarketAnalyzer for MarketIntelligenceAgent {
         self.generate_trading_signals(analysis).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::SystemConfig;
+    use crate::core::types::RiskMetrics;
+
+    #[tokio::test]
+    async fn test_evolved_update_interval_changes_loop_period() {
+        let config = SystemConfig::default();
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+
+        let mut agent = MarketIntelligenceAgent::new(
+            config.agents.market_intelligence.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+        ).await.unwrap();
+        let mut update_interval = interval(agent.update_period());
+        assert_eq!(update_interval.period(), Duration::from_millis(100));
+
+        let feedback = SystemFeedback {
+            performance_score: 0.3,
+            market_conditions: "Sideways".to_string(),
+            recent_trades: Vec::new(),
+            risk_metrics: RiskMetrics {
+                var_95: Default::default(),
+                var_99: Default::default(),
+                expected_shortfall: Default::default(),
+                max_position_size: Default::default(),
+                daily_loss_limit: Default::default(),
+                portfolio_heat: 0.0,
+            },
+            suggestions: Vec::new(),
+        };
+        agent.handle_feedback(&feedback).await.unwrap();
+
+        assert!(refresh_interval(&mut update_interval, agent.update_period()));
+        assert_eq!(update_interval.period(), Duration::from_millis(50));
+        assert!(!refresh_interval(&mut update_interval, agent.update_period()));
+    }
+
+    #[tokio::test]
+    async fn test_volatility_and_regime_come_from_price_history() {
+        let config = SystemConfig::default();
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+
+        let agent = MarketIntelligenceAgent::new(
+            config.agents.market_intelligence.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+        ).await.unwrap();
+        let quote = |symbol: &str, price: i64| MarketData {
+            symbol: symbol.to_string(),
+            timestamp: chrono::Utc::now(),
+            price: rust_decimal::Decimal::from(price),
+            volume: 100,
+            bid: None,
+            ask: None,
+            bid_size: None,
+            ask_size: None,
+            source: Default::default(),
+        };
+
+        // Too little history to read anything
+        let analysis = agent.analyze_market_data(&[quote("AAPL", 100)]).await.unwrap();
+        assert!(analysis.symbol_volatility.is_empty());
+        assert_eq!(analysis.volatility, 0.0);
+
+        // A price whipsawing 10% every bar is highly volatile; a flat one isn't
+        let mut analysis = analysis;
+        for bar in 0..30 {
+            analysis = agent
+                .analyze_market_data(&[quote("AAPL", 100 + (bar % 2) * 10), quote("MSFT", 300)])
+                .await
+                .unwrap();
+        }
+        assert!(analysis.symbol_volatility["AAPL"] > HIGH_VOLATILITY);
+        assert_eq!(analysis.symbol_volatility["MSFT"], 0.0);
+        assert!(matches!(analysis.regime, crate::core::types::MarketRegime::HighVolatility));
+    }
+
+    #[tokio::test]
+    async fn test_signals_without_fresh_quote_are_skipped() {
+        let config = SystemConfig::default();
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let quote_book = QuoteBook::new();
+        let thoughts = AIThoughtBroadcaster::new(10);
+
+        let agent = MarketIntelligenceAgent::new(
+            config.agents.market_intelligence.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+        ).await.unwrap()
+            .with_quote_guard(quote_book.clone(), 5)
+            .with_thought_broadcaster(thoughts.clone());
+
+        let now = chrono::Utc::now();
+        for (symbol, age_secs) in [("AAPL", 1), ("TSLA", 60)] {
+            quote_book.record(&MarketData {
+                symbol: symbol.to_string(),
+                timestamp: now - chrono::Duration::seconds(age_secs),
+                price: rust_decimal::Decimal::from(100),
+                volume: 100,
+                bid: None,
+                ask: None,
+                bid_size: None,
+                ask_size: None,
+                source: Default::default(),
+            }).await;
+        }
+
+        let signals = ["AAPL", "TSLA", "MSFT"]
+            .iter()
+            .map(|symbol| TradingSignal {
+                symbol: symbol.to_string(),
+                signal_type: SignalType::Buy,
+                strength: 0.8,
+                confidence: 0.8,
+                timestamp: now,
+                reasoning: "test".to_string(),
+                source: "test".to_string(),
+            })
+            .collect();
+
+        let processed = agent.guard_quotes(signals).await;
+        assert_eq!(processed.len(), 1);
+        assert_eq!(processed[0].symbol, "AAPL");
+        assert_eq!(thoughts.get_recent_thoughts(10).await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_signals_follow_the_configured_watchlist() {
+        let mut config = SystemConfig::default();
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let watchlist = ["NVDA", "AMD", "INTC"].map(String::from).to_vec();
+        config.agents.market_intelligence.watchlist = watchlist.clone();
+
+        let agent = MarketIntelligenceAgent::new(
+            config.agents.market_intelligence.clone(),
+            config.api.clone(),
+            sender.clone(),
+            event_sender.clone(),
+        ).await.unwrap();
+
+        // Only the symbol whose own indicators trend strongly up signals, even
+        // when the market as a whole is volatile
+        let mut analysis = agent.analyze_market_data(&[]).await.unwrap();
+        analysis.volatility = 0.5;
+        analysis.symbol_trends = HashMap::from([("AMD".to_string(), 0.9), ("INTC".to_string(), 0.3)]);
+        let signals = agent.generate_trading_signals(&analysis).await.unwrap();
+        let symbols: Vec<&str> = signals.iter().map(|signal| signal.symbol.as_str()).collect();
+        assert_eq!(symbols, ["AMD"]);
+        assert!(matches!(signals[0].signal_type, SignalType::Buy));
+
+        // Without a trend nothing signals
+        analysis.symbol_trends.clear();
+        assert!(agent.generate_trading_signals(&analysis).await.unwrap().is_empty());
+
+        // An empty watchlist never signals, and is refused by validation
+        config.api.moomoo.base_url = "http://127.0.0.1:11111".to_string();
+        assert!(config.validate().is_ok());
+        config.agents.market_intelligence.watchlist.clear();
+        assert!(config.validate().is_err());
+        let agent = MarketIntelligenceAgent::new(
+            config.agents.market_intelligence.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+        ).await.unwrap();
+        analysis.symbol_trends = HashMap::from([("AMD".to_string(), 0.9)]);
+        assert!(agent.generate_trading_signals(&analysis).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rapid_repeat_signals_are_suppressed_until_direction_flips() {
+        let config = SystemConfig::default();
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let thoughts = AIThoughtBroadcaster::new(10);
+
+        let agent = MarketIntelligenceAgent::new(
+            config.agents.market_intelligence.clone(),
+            config.api.clone(),
+            sender,
+            event_sender,
+        ).await.unwrap()
+            .with_thought_broadcaster(thoughts.clone());
+
+        let now = Utc::now();
+        let signal = |signal_type: SignalType| TradingSignal {
+            symbol: "AAPL".to_string(),
+            signal_type,
+            strength: 0.8,
+            confidence: 0.8,
+            timestamp: now,
+            reasoning: "test".to_string(),
+            source: "test".to_string(),
+        };
+
+        let emit = |signal_type: SignalType, secs: i64| {
+            let (agent, at) = (&agent, now + chrono::Duration::seconds(secs));
+            async move {
+                let emitted = agent.suppress_repeats(vec![signal(signal_type)], at).await;
+                agent.start_cooldowns(&emitted, at).await;
+                emitted.into_iter().map(|signal| signal.signal_type).collect::<Vec<_>>()
+            }
+        };
+
+        // A signal dropped by a later filter never started a cooldown
+        assert_eq!(agent.suppress_repeats(vec![signal(SignalType::StrongBuy)], now).await.len(), 1);
+
+        // Two same-direction signals a second apart: only the first goes out
+        assert!(matches!(emit(SignalType::Buy, 0).await[..], [SignalType::Buy]));
+        assert!(emit(SignalType::StrongBuy, 1).await.is_empty());
+        let recent = thoughts.get_recent_thoughts(10).await;
+        assert_eq!(recent.len(), 1);
+        assert!(recent[0].message.contains("StrongBuy signal for AAPL"), "{}", recent[0].message);
+
+        // A reversal goes straight through, and the cooldown lapses after 60s
+        assert!(matches!(emit(SignalType::Sell, 2).await[..], [SignalType::Sell]));
+        assert!(emit(SignalType::Sell, 3).await.is_empty());
+        assert!(matches!(emit(SignalType::Sell, 62).await[..], [SignalType::Sell]));
+    }
+}
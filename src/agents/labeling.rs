@@ -0,0 +1,83 @@
+//! Success labels for trade outcomes
+//!
+//! Outcomes arrive with whatever `success` flag their producer chose. The
+//! learning engine relabels them with one configured rule before training, so
+//! a trade that merely covered its costs is not counted as a win.
+
+use crate::agents::traits::TradeOutcome;
+use crate::core::config::OutcomeLabelingConfig;
+
+/// Labels outcomes by net return and holding period
+#[derive(Debug, Clone)]
+pub struct OutcomeLabeler {
+    min_net_return: f64,
+    max_holding_secs: Option<u64>,
+}
+
+impl OutcomeLabeler {
+    pub fn from_config(config: &OutcomeLabelingConfig) -> Self {
+        Self {
+            min_net_return: config.min_net_return,
+            max_holding_secs: config.max_holding_secs,
+        }
+    }
+
+    /// Return after slippage and costs
+    pub fn net_return(outcome: &TradeOutcome) -> f64 {
+        outcome.actual_return - outcome.slippage - outcome.costs
+    }
+
+    /// A success beat the net return threshold within the holding window
+    pub fn is_success(&self, outcome: &TradeOutcome) -> bool {
+        let within_window = self.max_holding_secs.is_none_or(|max| outcome.holding_secs <= max);
+        within_window && Self::net_return(outcome) > self.min_net_return
+    }
+
+    /// Copies of `outcomes` with `success` set by this labeler
+    pub fn label(&self, outcomes: &[TradeOutcome]) -> Vec<TradeOutcome> {
+        outcomes
+            .iter()
+            .map(|outcome| TradeOutcome {
+                success: self.is_success(outcome),
+                ..outcome.clone()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(actual_return: f64, holding_secs: u64) -> TradeOutcome {
+        TradeOutcome {
+            signal_strength: 0.8,
+            actual_return,
+            execution_time_ms: 50,
+            slippage: 0.001,
+            costs: 0.001,
+            holding_secs,
+            success: true,
+        }
+    }
+
+    #[test]
+    fn test_outcomes_labeled_by_net_return_threshold() {
+        let outcomes = [outcome(0.010, 60), outcome(0.004, 60), outcome(0.0015, 60), outcome(0.010, 7200)];
+        let labels = |config: &OutcomeLabelingConfig| -> Vec<bool> {
+            OutcomeLabeler::from_config(config).label(&outcomes).iter().map(|o| o.success).collect()
+        };
+
+        // Costs turn a small gross gain into a failure
+        let mut config = OutcomeLabelingConfig { min_net_return: 0.0, max_holding_secs: Some(3600) };
+        assert_eq!(labels(&config), [true, true, false, false]);
+
+        // Raising the threshold reclassifies the borderline 0.2% net trade
+        config.min_net_return = 0.005;
+        assert_eq!(labels(&config), [true, false, false, false]);
+
+        // Without a holding window the long-held winner counts
+        config.max_holding_secs = None;
+        assert_eq!(labels(&config), [true, false, false, true]);
+    }
+}
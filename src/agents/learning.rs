@@ -1,24 +1,41 @@
 //! Learning Engine Agent - AI model training and strategy evolution
 
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{broadcast, mpsc, RwLock};
 use tokio::time::{interval, Duration};
 use tracing::{info, warn, error};
 
 use crate::core::ai_thoughts::{AIThoughtBroadcaster, ThoughtTemplates, AIAgent, ThoughtType, AIThought};
+use crate::core::calendar::MarketCalendar;
 use crate::core::config::LearningConfig;
 use crate::core::errors::TradingResult;
+use crate::core::performance::TradeRecord;
 use crate::core::types::{
     AgentCapability, AgentId, AgentMessage, SystemContext, 
-    PerformanceMetrics, TradingSignal
+    PerformanceMetrics, StrategyState, TradingSignal
 };
+use crate::agents::change_log::ConfigChangeLog;
+use crate::agents::consensus::{Vote, VoteRequest};
+use crate::agents::cooldown::EvolutionCooldown;
+use crate::agents::labeling::OutcomeLabeler;
+use crate::agents::parameters::{clamp_parameters, refresh_interval, validate_parameters, ParameterChange, ParameterSpec, ParameterValue};
+use crate::agents::strategies::{GenerationQuota, StrategyManager};
 use crate::agents::traits::{
-    AutonomousAgent, BaseAgent, AgentResult, SystemFeedback, 
+    next_message, AgentWiring, AutonomousAgent, BaseAgent, AgentResult, SystemFeedback, 
     EvolutionResult, Requirements, CodeGeneration, LearningAgent,
     TradeOutcome, LearningResult, ModelUpdateData, ModelUpdateResult, GeneratedStrategy
 };
 
+/// Parameters this agent accepts from strategy evolution
+const EVOLVABLE_PARAMETERS: &[ParameterSpec] = &[
+    ParameterSpec::integer("model_update_interval_hours", 1, 168),
+];
+
 /// Learning Engine Agent for AI model training and strategy evolution
 #[derive(Clone)]
 pub struct LearningEngineAgent {
@@ -26,6 +43,9 @@ pub struct LearningEngineAgent {
     config: LearningConfig,
     model_version: String,
     training_data: Vec<TradeOutcome>,
+    strategies: StrategyManager,
+    generation_quota: GenerationQuota,
+    market_calendar: Option<MarketCalendar>, // Session boundary for the generation quota
     thought_broadcaster: AIThoughtBroadcaster,
 }
 
@@ -34,6 +54,7 @@ impl LearningEngineAgent {
     pub async fn new(
         config: LearningConfig,
         message_sender: mpsc::UnboundedSender<AgentMessage>,
+        event_sender: broadcast::Sender<AgentMessage>,
         system_context: Arc<RwLock<SystemContext>>,
         thought_broadcaster: AIThoughtBroadcaster,
     ) -> TradingResult<Self> {
@@ -42,7 +63,7 @@ impl LearningEngineAgent {
             AgentCapability::SelfModification,
         ];
         
-        let base = BaseAgent::new(capabilities, message_sender, system_context);
+        let base = BaseAgent::new(capabilities, message_sender, event_sender, system_context);
         
         // Share initial thought
         thought_broadcaster.broadcast_thought(
@@ -61,15 +82,42 @@ impl LearningEngineAgent {
             .educational()
         ).await;
         
+        let strategies = StrategyManager::new(config.max_active_strategies);
+        let generation_quota = GenerationQuota::new(config.max_new_strategies_per_day);
+        
         Ok(Self {
             base,
             config,
             model_version: "v1.0.0".to_string(),
             training_data: Vec::new(),
+            strategies,
+            generation_quota,
+            market_calendar: None,
             thought_broadcaster,
         })
     }
     
+    /// Count the generation quota per trading day of this calendar
+    pub fn with_market_calendar(mut self, calendar: MarketCalendar) -> Self {
+        self.market_calendar = Some(calendar);
+        self
+    }
+    
+    /// Trading session the generation quota counts towards at `now`: the day
+    /// the system last rolled the portfolio to, or the calendar's trading day
+    /// before the first roll. Only without a calendar is the UTC date used.
+    fn session(&self, context: &SystemContext, now: DateTime<Utc>) -> NaiveDate {
+        context.portfolio.trading_day.unwrap_or_else(|| match &self.market_calendar {
+            Some(calendar) => calendar.trading_day(now),
+            None => now.date_naive(),
+        })
+    }
+    
+    /// Period of the model evolution loop, taken from the live config
+    fn evolution_period(&self) -> Duration {
+        Duration::from_secs(self.config.model_update_interval_hours * 3600)
+    }
+    
     /// Perform model training and strategy evolution
     async fn evolve_models(&mut self) -> TradingResult<()> {
         info!("🧠 Evolving AI models and strategies...");
@@ -97,6 +145,8 @@ impl LearningEngineAgent {
         
         // Generate new strategies based on market conditions
         let new_strategies = self.generate_adaptive_strategies(&context).await?;
+        let session = self.session(&context, Utc::now());
+        self.track_strategies(&new_strategies, performance_analysis.score, session);
         
         // Update model parameters if needed
         if self.should_update_model(&performance_analysis).await? {
@@ -137,17 +187,82 @@ impl LearningEngineAgent {
         Ok(())
     }
     
+    /// Add generated strategies to the active pool, retiring the worst
+    /// performers once the configured cap is exceeded
+    fn track_strategies(&mut self, new_strategies: &[GeneratedStrategy], performance_score: f64, session: NaiveDate) {
+        for strategy in new_strategies {
+            // Redefinitions of active strategies don't grow the pool, so only new ones count
+            if !self.strategies.contains(&strategy.name) && !self.generation_quota.try_acquire(session) {
+                warn!(
+                    "🚫 Daily cap of {} new strategies reached, skipping {}",
+                    self.config.max_new_strategies_per_day, strategy.name
+                );
+                continue;
+            }
+            
+            let mut strategy = strategy.clone();
+            for (key, proposed, clamped) in clamp_parameters(&self.config.parameter_bounds, &mut strategy.parameters) {
+                warn!("📏 Clamped {}.{}: {} -> {}", strategy.name, key, proposed, clamped);
+            }
+            
+            let name = strategy.name.clone();
+            let is_new = !self.strategies.contains(&name);
+            for retired in self.strategies.add(strategy) {
+                info!("🗑️  Retired strategy {} (performance: {:.2})", retired.strategy.name, retired.performance);
+            }
+            let initial_state = self.config.new_strategy_state;
+            if is_new && initial_state != StrategyState::Generated {
+                if let Err(e) = self.strategies.transition(&name, initial_state) {
+                    warn!("⚠️  {}", e);
+                }
+            }
+            // Attribute the current cycle's performance to the strategies active in it
+            self.strategies.record_performance(&name, performance_score);
+        }
+    }
+    
+    /// Vote on a proposed signal from the system's track record: approve while
+    /// recent performance is at least neutral. Learning never vetoes.
+    pub async fn vote_on(&self, request: &VoteRequest) -> TradingResult<Vote> {
+        let context = self.base.get_system_context().await;
+        let score = performance_score(&context.performance_metrics);
+        let approve = score >= NEUTRAL_SCORE;
+        
+        Ok(Vote {
+            request_id: request.id,
+            voter: "learning_engine".to_string(),
+            approve,
+            confidence: if approve { score } else { 1.0 - score },
+            veto: false,
+            reason: format!("System performance score {:.2}", score),
+        })
+    }
+    
+    /// Answer a coordinator's vote request with a reply to the coordinator
+    async fn answer_vote_request(&self, message: &AgentMessage) -> TradingResult<()> {
+        let request: VoteRequest = serde_json::from_value(message.payload.clone())?;
+        let vote = self.vote_on(&request).await?;
+        
+        let reply = message.reply(self.base.id, crate::core::types::MessageType::VoteResponse, serde_json::to_value(&vote)?);
+        self.base.send_message(reply).await
+    }
+    
+    /// Train on a trade closed elsewhere in the system
+    async fn learn_from_closed_trade(&mut self, payload: serde_json::Value) -> TradingResult<()> {
+        let record: TradeRecord = serde_json::from_value(payload)?;
+        let Some(outcome) = trade_outcome(&record) else {
+            warn!("📚 Not learning from a closed trade recorded without its position value");
+            return Ok(());
+        };
+        self.learn_from_outcomes(&[outcome]).await?;
+        Ok(())
+    }
+    
     /// Analyze current system performance
     async fn analyze_performance(&self, context: &SystemContext) -> TradingResult<PerformanceAnalysis> {
         let metrics = &context.performance_metrics;
         
-        let performance_score = if metrics.total_trades > 0 {
-            metrics.win_rate * 0.4 + 
-            (metrics.profit_factor / 3.0).min(1.0) * 0.4 +
-            (1.0 / (metrics.average_execution_time_ms / 1000.0)).min(1.0) * 0.2
-        } else {
-            0.5 // Neutral score for no trades
-        };
+        let performance_score = performance_score(metrics);
         
         Ok(PerformanceAnalysis {
             score: performance_score,
@@ -293,6 +408,31 @@ impl LearningEngineAgent {
     }
 }
 
+/// Score neither good nor bad, used when there is nothing to judge
+const NEUTRAL_SCORE: f64 = 0.5;
+
+/// Blend win rate, profit factor and execution speed into a score in [0, 1].
+/// Components that cannot be computed, such as speed before any execution
+/// time is recorded, count as neutral instead of producing `inf` or `NaN`.
+fn performance_score(metrics: &PerformanceMetrics) -> f64 {
+    if metrics.total_trades == 0 {
+        return NEUTRAL_SCORE;
+    }
+
+    let ratio = |value: f64| if value.is_finite() { value.clamp(0.0, 1.0) } else { NEUTRAL_SCORE };
+    let win_rate = ratio(metrics.win_rate);
+    // An infinite profit factor (no losing trades) is as good as it gets
+    let profit = if metrics.profit_factor == f64::INFINITY { 1.0 } else { ratio(metrics.profit_factor / 3.0) };
+    let execution_secs = metrics.average_execution_time_ms / 1000.0;
+    let speed = if execution_secs > 0.0 && execution_secs.is_finite() {
+        (1.0 / execution_secs).min(1.0)
+    } else {
+        NEUTRAL_SCORE
+    };
+
+    win_rate * 0.4 + profit * 0.4 + speed * 0.2
+}
+
 /// Performance analysis result
 #[derive(Debug, Clone)]
 struct PerformanceAnalysis {
@@ -302,6 +442,14 @@ struct PerformanceAnalysis {
     recommendations: Vec<String>,
 }
 
+impl AgentWiring for LearningEngineAgent {
+    const NAME: &'static str = "learning_engine";
+    
+    fn base_mut(&mut self) -> &mut BaseAgent {
+        &mut self.base
+    }
+}
+
 #[async_trait]
 impl AutonomousAgent for LearningEngineAgent {
     async fn execute_mission(&self, context: &SystemContext) -> TradingResult<AgentResult> {
@@ -362,6 +510,26 @@ impl AutonomousAgent for LearningEngineAgent {
         })
     }
     
+    fn apply_parameters(&mut self, parameters: &HashMap<String, serde_json::Value>) -> TradingResult<Vec<ParameterChange>> {
+        let updates = validate_parameters(EVOLVABLE_PARAMETERS, parameters)?;
+        let mut applied = Vec::new();
+        
+        for (key, value) in updates {
+            let old = match key {
+                "model_update_interval_hours" => {
+                    info!("🔧 model_update_interval_hours: {} -> {}", self.config.model_update_interval_hours, value);
+                    let old = ParameterValue::Integer(self.config.model_update_interval_hours);
+                    self.config.model_update_interval_hours = value.as_u64();
+                    old
+                }
+                _ => continue,
+            };
+            applied.push(ParameterChange { key, old, new: value });
+        }
+        
+        Ok(applied)
+    }
+    
     fn capabilities(&self) -> Vec<AgentCapability> {
         self.base.capabilities.clone()
     }
@@ -370,12 +538,26 @@ impl AutonomousAgent for LearningEngineAgent {
         self.base.id
     }
     
+    fn change_log(&self) -> Option<&ConfigChangeLog> {
+        self.base.change_log.as_ref()
+    }
+    
+    fn evolution_cooldown(&self) -> Option<&EvolutionCooldown> {
+        self.base.evolution_cooldown.as_ref()
+    }
+    
     async fn run(&mut self) -> TradingResult<()> {
+        if !self.base.await_prerequisites().await? {
+            return Ok(());
+        }
         info!("🧠 Learning Engine starting execution loop...");
         
-        let mut evolution_interval = interval(Duration::from_secs(
-            self.config.model_update_interval_hours * 3600
-        ));
+        let mut evolution_interval = interval(self.evolution_period());
+        let mut events = self.base.subscribe_events();
+        let mut inbox = self.base.inbox().await;
+        let mut heartbeat = self.base.heartbeat_timer();
+        
+        self.base.mark_ready();
         
         loop {
             tokio::select! {
@@ -384,11 +566,21 @@ impl AutonomousAgent for LearningEngineAgent {
                         error!("Model evolution error: {}", e);
                     }
                 }
-                _ = tokio::time::sleep(Duration::from_millis(100)) => {
-                    if self.base.should_shutdown().await {
-                        break;
+                _ = heartbeat.tick() => self.base.send_heartbeat(),
+                Some(message) = next_message(&mut events, &mut inbox) => {
+                    let result = match message.message_type {
+                        crate::core::types::MessageType::VoteRequest => self.answer_vote_request(&message).await,
+                        crate::core::types::MessageType::TradeClosed => self.learn_from_closed_trade(message.payload).await,
+                        _ => self.handle_event(message).await,
+                    };
+                    if let Err(e) = result {
+                        error!("Learning Engine event error: {}", e);
+                    }
+                    if refresh_interval(&mut evolution_interval, self.evolution_period()) {
+                        info!("🧠 Model evolution interval now {:?}", evolution_interval.period());
                     }
                 }
+                _ = self.base.shutdown_requested() => break,
             }
         }
         
@@ -398,18 +590,41 @@ impl AutonomousAgent for LearningEngineAgent {
     
     async fn shutdown(&mut self) -> TradingResult<()> {
         info!("🛑 Learning Engine shutting down...");
-        self.base.request_shutdown().await;
+        self.base.request_shutdown();
         Ok(())
     }
 }
 
+/// Training outcome for a closed trade, with returns as fractions of the
+/// position's value. None for a trade recorded without that value.
+fn trade_outcome(record: &TradeRecord) -> Option<TradeOutcome> {
+    let notional = record.notional.abs();
+    if notional.is_zero() {
+        return None;
+    }
+    let fraction = |amount: Decimal| (amount / notional).to_f64().unwrap_or(0.0);
+    Some(TradeOutcome {
+        signal_strength: 0.0, // Closed trades don't carry the signal that opened them
+        // Gross of costs, which labeling takes off again
+        actual_return: fraction(record.pnl + record.costs),
+        execution_time_ms: record.execution_time_ms as u64,
+        slippage: 0.0, // Already in the fill prices behind the P&L
+        costs: fraction(record.costs),
+        holding_secs: record.holding_secs,
+        success: record.pnl > Decimal::ZERO,
+    })
+}
+
 #[async_trait]
 impl LearningAgent for LearningEngineAgent {
     async fn learn_from_outcomes(&mut self, outcomes: &[TradeOutcome]) -> TradingResult<LearningResult> {
         info!("📚 Learning from {} trade outcomes", outcomes.len());
         
+        // Train on labels from the configured rule, not the producer's flag
+        let outcomes = OutcomeLabeler::from_config(&self.config.outcome_labeling).label(outcomes);
+        
         // Store outcomes for future training
-        self.training_data.extend_from_slice(outcomes);
+        self.training_data.extend_from_slice(&outcomes);
         
         // Analyze outcomes
         let successful_trades = outcomes.iter().filter(|o| o.success).count();
@@ -464,3 +679,122 @@ impl LearningAgent for LearningEngineAgent {
         self.generate_adaptive_strategies(&context).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_zero_execution_time_scores_neutral_speed() {
+        let metrics = PerformanceMetrics {
+            total_trades: 4,
+            winning_trades: 2,
+            losing_trades: 2,
+            win_rate: 0.5,
+            average_win: Decimal::ZERO,
+            average_loss: Decimal::ZERO,
+            profit_factor: 1.5,
+            max_consecutive_wins: 1,
+            max_consecutive_losses: 1,
+            average_execution_time_ms: 0.0,
+        };
+
+        let score = performance_score(&metrics);
+        assert!(score.is_finite());
+        assert!((score - NEUTRAL_SCORE).abs() < 1e-9);
+
+        let no_losses = PerformanceMetrics { profit_factor: f64::INFINITY, average_execution_time_ms: f64::NAN, ..metrics };
+        assert!(performance_score(&no_losses).is_finite());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_idle_agent_waits_for_shutdown_without_polling() {
+        let config = crate::core::config::SystemConfig::default();
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let agent = LearningEngineAgent::new(
+            config.agents.learning_engine.clone(),
+            sender,
+            event_sender,
+            Arc::new(RwLock::new(SystemContext::initial(&config))),
+            AIThoughtBroadcaster::new(10),
+        ).await.unwrap();
+
+        let base = agent.base.clone();
+        let mut running = agent.clone();
+        let task = tokio::spawn(async move { running.run().await });
+
+        // The paused clock only moves to pending timers: half an hour passes
+        // with nothing but the hourly evolution timer to wake the loop
+        tokio::time::sleep(Duration::from_secs(1800)).await;
+        assert!(!task.is_finished());
+
+        base.request_shutdown();
+        let exited = tokio::time::timeout(Duration::from_millis(1), task).await;
+        assert!(exited.expect("agent should exit as soon as shutdown is requested").unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_closed_trade_is_learned_net_of_costs() {
+        let config = crate::core::config::SystemConfig::default();
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let mut agent = LearningEngineAgent::new(
+            config.agents.learning_engine.clone(),
+            sender,
+            event_sender,
+            Arc::new(RwLock::new(SystemContext::initial(&config))),
+            AIThoughtBroadcaster::new(10),
+        ).await.unwrap();
+        let record = |pnl: i64, notional: i64| TradeRecord {
+            timestamp: chrono::Utc::now(),
+            pnl: Decimal::from(pnl),
+            execution_time_ms: 12.0,
+            strategy: None,
+            order_id: None,
+            notional: Decimal::from(notional),
+            costs: Decimal::from(2),
+            holding_secs: 600,
+            source: None,
+        };
+
+        agent.learn_from_closed_trade(serde_json::to_value(record(8, 1_000)).unwrap()).await.unwrap();
+        assert_eq!(agent.training_data.len(), 1);
+        let outcome = &agent.training_data[0];
+        assert!((outcome.actual_return - 0.01).abs() < 1e-9);
+        assert!((outcome.costs - 0.002).abs() < 1e-9);
+        assert_eq!(outcome.holding_secs, 600);
+        assert_eq!(outcome.execution_time_ms, 12);
+
+        // Without the position's value there is no return to learn from
+        agent.learn_from_closed_trade(serde_json::to_value(record(8, 0)).unwrap()).await.unwrap();
+        assert_eq!(agent.training_data.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_generation_quota_follows_the_session_boundary() {
+        let config = crate::core::config::SystemConfig::default();
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let agent = LearningEngineAgent::new(
+            config.agents.learning_engine.clone(),
+            sender,
+            event_sender,
+            Arc::new(RwLock::new(SystemContext::initial(&config))),
+            AIThoughtBroadcaster::new(10),
+        ).await.unwrap()
+            .with_market_calendar(MarketCalendar::from_config(&config.trading.trading_hours).unwrap());
+        let mut context = SystemContext::initial(&config);
+        let day = |day: u32| NaiveDate::from_ymd_opt(2024, 3, day).unwrap();
+
+        // Past UTC midnight but before the New York open is still the previous session
+        let before_open = day(6).and_hms_opt(14, 29, 0).unwrap().and_utc();
+        assert_eq!(agent.session(&context, before_open), day(5));
+        assert_eq!(agent.session(&context, before_open + chrono::Duration::minutes(1)), day(6));
+
+        // Once the system has rolled the portfolio, its trading day is the session
+        context.portfolio.roll_trading_day(day(5));
+        assert_eq!(agent.session(&context, before_open + chrono::Duration::hours(1)), day(5));
+    }
+}
@@ -1,10 +1,19 @@
 //! Autonomous trading agents
 
+pub mod ab_test;
+pub mod change_log;
+pub mod consensus;
+pub mod cooldown;
 pub mod coordinator;
+pub mod decision_trace;
 pub mod intelligence;
+pub mod labeling;
 pub mod risk;
 pub mod execution;
 pub mod learning;
+pub mod parameters;
+pub mod startup;
+pub mod strategies;
 pub mod traits;
 
 pub use traits::AutonomousAgent;
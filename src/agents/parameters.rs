@@ -0,0 +1,246 @@
+//! Typed, validated parameter updates for self-evolving agents
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use tokio::time::{interval_at, Duration, Instant, Interval};
+
+use crate::core::config::ParameterBounds;
+use crate::core::errors::{TradingError, TradingResult};
+
+/// Numeric kind of an evolvable parameter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParameterKind {
+    Integer,
+    Float,
+}
+
+/// Declares a parameter an agent accepts from evolution, with its valid range
+#[derive(Debug, Clone, Copy)]
+pub struct ParameterSpec {
+    pub key: &'static str,
+    pub kind: ParameterKind,
+    pub min: f64,
+    pub max: f64,
+}
+
+/// A validated parameter value
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ParameterValue {
+    Integer(u64),
+    Float(f64),
+}
+
+/// A parameter an agent changed, with its value before and after
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterChange {
+    pub key: &'static str,
+    pub old: ParameterValue,
+    pub new: ParameterValue,
+}
+
+impl ParameterSpec {
+    /// Declare an unsigned integer parameter
+    pub const fn integer(key: &'static str, min: u64, max: u64) -> Self {
+        Self {
+            key,
+            kind: ParameterKind::Integer,
+            min: min as f64,
+            max: max as f64,
+        }
+    }
+
+    /// Declare a floating point parameter
+    pub const fn float(key: &'static str, min: f64, max: f64) -> Self {
+        Self {
+            key,
+            kind: ParameterKind::Float,
+            min,
+            max,
+        }
+    }
+
+    /// Check a raw JSON value against this spec
+    pub fn validate(&self, value: &serde_json::Value) -> TradingResult<ParameterValue> {
+        let parsed = match self.kind {
+            ParameterKind::Integer => value.as_u64().map(ParameterValue::Integer),
+            ParameterKind::Float => value.as_f64().map(ParameterValue::Float),
+        };
+
+        let parsed = parsed.ok_or_else(|| {
+            TradingError::strategy(format!(
+                "Parameter {} expects {:?}, got {}",
+                self.key, self.kind, value
+            ))
+        })?;
+
+        let numeric = parsed.as_f64();
+        if numeric < self.min || numeric > self.max {
+            return Err(TradingError::strategy(format!(
+                "Parameter {} = {} outside [{}, {}]",
+                self.key, parsed, self.min, self.max
+            )));
+        }
+
+        Ok(parsed)
+    }
+}
+
+impl ParameterValue {
+    /// Get the value as an unsigned integer (floats are truncated)
+    pub fn as_u64(&self) -> u64 {
+        match self {
+            ParameterValue::Integer(v) => *v,
+            ParameterValue::Float(v) => *v as u64,
+        }
+    }
+
+    /// Get the value as a float
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            ParameterValue::Integer(v) => *v as f64,
+            ParameterValue::Float(v) => *v,
+        }
+    }
+}
+
+impl fmt::Display for ParameterValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParameterValue::Integer(v) => write!(f, "{}", v),
+            ParameterValue::Float(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// Validate a full set of evolved parameters against an agent's specs.
+///
+/// Validation is all-or-nothing: an unknown key or invalid value rejects the
+/// whole update so the running config is never left half-applied.
+pub fn validate_parameters(
+    specs: &[ParameterSpec],
+    parameters: &HashMap<String, serde_json::Value>,
+) -> TradingResult<Vec<(&'static str, ParameterValue)>> {
+    parameters
+        .iter()
+        .map(|(key, value)| {
+            let spec = specs
+                .iter()
+                .find(|spec| spec.key == key)
+                .ok_or_else(|| TradingError::strategy(format!("Unknown parameter: {}", key)))?;
+            Ok((spec.key, spec.validate(value)?))
+        })
+        .collect()
+}
+
+/// Clamp evolved strategy parameters into their configured bounds.
+///
+/// Unlike agent parameters, strategy parameters are open-ended, so values are
+/// pulled back into range rather than rejected. Parameters without bounds or
+/// with non-numeric values are left untouched. Integer values stay integers.
+/// Returns the `(key, proposed, clamped)` triples that were adjusted.
+pub fn clamp_parameters(
+    bounds: &HashMap<String, ParameterBounds>,
+    parameters: &mut HashMap<String, serde_json::Value>,
+) -> Vec<(String, f64, f64)> {
+    let mut clamped = Vec::new();
+
+    for (key, value) in parameters.iter_mut() {
+        let (Some(range), Some(proposed)) = (bounds.get(key), value.as_f64()) else {
+            continue;
+        };
+
+        let bounded = proposed.clamp(range.min, range.max);
+        if bounded == proposed {
+            continue;
+        }
+
+        *value = if value.is_f64() {
+            serde_json::json!(bounded)
+        } else {
+            serde_json::json!(bounded.round() as i64)
+        };
+        clamped.push((key.clone(), proposed, bounded));
+    }
+
+    clamped
+}
+
+/// Rebuild `interval` if its period no longer matches `period`.
+/// Returns true if the interval was replaced.
+pub fn refresh_interval(interval: &mut Interval, period: Duration) -> bool {
+    if interval.period() == period {
+        return false;
+    }
+
+    *interval = interval_at(Instant::now() + period, period);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SPECS: &[ParameterSpec] = &[
+        ParameterSpec::integer("update_interval_ms", 10, 60_000),
+        ParameterSpec::float("max_portfolio_heat", 0.05, 1.0),
+    ];
+
+    #[test]
+    fn test_valid_parameters_are_typed() {
+        let mut parameters = HashMap::new();
+        parameters.insert("update_interval_ms".to_string(), json!(50));
+
+        let updates = validate_parameters(SPECS, &parameters).unwrap();
+        assert_eq!(updates, vec![("update_interval_ms", ParameterValue::Integer(50))]);
+    }
+
+    #[test]
+    fn test_invalid_parameters_rejected() {
+        let mut parameters = HashMap::new();
+        parameters.insert("update_interval_ms".to_string(), json!(0.5));
+        assert!(validate_parameters(SPECS, &parameters).is_err());
+
+        parameters.clear();
+        parameters.insert("max_portfolio_heat".to_string(), json!(1.5));
+        assert!(validate_parameters(SPECS, &parameters).is_err());
+
+        parameters.clear();
+        parameters.insert("unknown".to_string(), json!(1));
+        assert!(validate_parameters(SPECS, &parameters).is_err());
+    }
+
+    #[test]
+    fn test_out_of_bounds_multiplier_is_clamped() {
+        let bounds = HashMap::from([
+            ("position_size_multiplier".to_string(), ParameterBounds { min: 0.1, max: 3.0 }),
+            ("hold_time_seconds".to_string(), ParameterBounds { min: 1.0, max: 3600.0 }),
+        ]);
+        let mut parameters = HashMap::from([
+            ("position_size_multiplier".to_string(), json!(7.5)),
+            ("hold_time_seconds".to_string(), json!(0)),
+            ("trend_threshold".to_string(), json!(0.7)),
+        ]);
+
+        let mut clamped = clamp_parameters(&bounds, &mut parameters);
+        clamped.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(parameters["position_size_multiplier"], json!(3.0));
+        assert_eq!(parameters["hold_time_seconds"], json!(1));
+        assert_eq!(parameters["trend_threshold"], json!(0.7));
+        assert_eq!(clamped.len(), 2);
+        assert_eq!(clamped[1], ("position_size_multiplier".to_string(), 7.5, 3.0));
+    }
+
+    #[test]
+    fn test_inverted_or_nan_bounds_are_refused() {
+        for bounds in [ParameterBounds { min: 3.0, max: 0.1 }, ParameterBounds { min: f64::NAN, max: 1.0 }] {
+            let mut config = crate::core::config::SystemConfig::default();
+            config.agents.learning_engine.parameter_bounds.insert("position_size_multiplier".to_string(), bounds);
+            let error = config.validate().unwrap_err().to_string();
+            assert!(error.contains("Parameter bounds for 'position_size_multiplier'"), "{}", error);
+        }
+    }
+}
@@ -1,30 +1,62 @@
 //! Risk Management Agent - Portfolio risk monitoring and control
 
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{broadcast, mpsc, RwLock};
 use tokio::time::{interval, Duration};
 use tracing::{info, warn, error};
-use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 
+use crate::core::ai_thoughts::{AIAgent, AIThought, AIThoughtBroadcaster, ThoughtType};
 use crate::core::config::{RiskAgentConfig, RiskConfig};
-use crate::core::errors::TradingResult;
+use crate::core::errors::{TradingError, TradingResult};
+use crate::core::metrics::MetricsCollector;
+use crate::core::reload::ConfigUpdate;
 use crate::core::types::{
     AgentCapability, AgentId, AgentMessage, SystemContext, 
-    PerformanceMetrics, TradingSignal, RiskMetrics
+    PerformanceMetrics, TradingSignal, RiskMetrics, CloseReason, ClosingOrder, OrderRejection, ExecutionResult,
+    AlertSeverity, RiskAlertNotice
 };
+use crate::agents::change_log::ConfigChangeLog;
+use crate::agents::consensus::{Vote, VoteRequest};
+use crate::agents::cooldown::EvolutionCooldown;
+use crate::agents::decision_trace::{DecisionTraces, PipelineStage};
+use crate::agents::parameters::{validate_parameters, ParameterChange, ParameterSpec, ParameterValue};
+use crate::execution::take_profit::ProtectiveLevels;
+use crate::risk::audit::RiskAuditLog;
+use crate::risk::cache::RiskMetricsCache;
+use crate::risk::circuit_breaker::{BreakerState, CircuitBreaker};
+use crate::risk::exits::{PendingExits, UnrealizedLossMonitor};
+use crate::risk::heat::portfolio_heat;
+use crate::risk::monte_carlo::{self, MonteCarloResult, DEFAULT_DAILY_VOLATILITY};
+use crate::risk::var::ReturnsWindow;
 use crate::agents::traits::{
-    AutonomousAgent, BaseAgent, AgentResult, SystemFeedback, 
+    next_message, AgentWiring, AutonomousAgent, BaseAgent, AgentResult, SystemFeedback, 
     EvolutionResult, Requirements, CodeGeneration, RiskManager,
     RiskValidation, HedgeRecommendation
 };
 
+/// Parameters this agent accepts from strategy evolution
+const EVOLVABLE_PARAMETERS: &[ParameterSpec] = &[
+    ParameterSpec::float("max_portfolio_heat", 0.05, 1.0),
+];
+
 /// Risk Management Agent for portfolio risk monitoring
 #[derive(Clone)]
 pub struct RiskManagementAgent {
     base: BaseAgent,
     config: RiskAgentConfig,
     risk_config: RiskConfig,
+    loss_monitor: UnrealizedLossMonitor,
+    pending_exits: Arc<RwLock<PendingExits>>,
+    audit: Option<RiskAuditLog>,
+    decision_traces: Option<DecisionTraces>,
+    returns_window: Arc<RwLock<ReturnsWindow>>,
+    circuit_breaker: Arc<RwLock<CircuitBreaker>>,
+    metrics_cache: RiskMetricsCache,
+    thought_broadcaster: Option<AIThoughtBroadcaster>,
+    protective_levels: Option<ProtectiveLevels>, // Stops heat is measured against
 }
 
 impl RiskManagementAgent {
@@ -33,6 +65,7 @@ impl RiskManagementAgent {
         config: RiskAgentConfig,
         risk_config: RiskConfig,
         message_sender: mpsc::UnboundedSender<AgentMessage>,
+        event_sender: broadcast::Sender<AgentMessage>,
         system_context: Arc<RwLock<SystemContext>>,
     ) -> TradingResult<Self> {
         let capabilities = vec![
@@ -40,26 +73,104 @@ impl RiskManagementAgent {
             AgentCapability::EthicalReasoning,
         ];
         
-        let base = BaseAgent::new(capabilities, message_sender, system_context);
+        let base = BaseAgent::new(capabilities, message_sender, event_sender, system_context);
+        let loss_monitor = UnrealizedLossMonitor::from_config(&risk_config.max_unrealized_loss);
+        let audit = risk_config
+            .audit
+            .enabled
+            .then(|| RiskAuditLog::new(&risk_config.audit.path));
+        let returns_window = Arc::new(RwLock::new(ReturnsWindow::new(risk_config.var_window_days)));
+        let circuit_breaker = Arc::new(RwLock::new(CircuitBreaker::new(risk_config.circuit_breaker_cooldown_secs)));
+        let metrics_cache = RiskMetricsCache::from_config(&risk_config.validation_cache);
         
         Ok(Self {
             base,
             config,
             risk_config,
+            loss_monitor,
+            pending_exits: Arc::new(RwLock::new(PendingExits::new())),
+            audit,
+            decision_traces: None,
+            returns_window,
+            circuit_breaker,
+            metrics_cache,
+            thought_broadcaster: None,
+            protective_levels: None,
         })
     }
     
+    /// Record risk check verdicts in the given decision traces
+    pub fn with_decision_traces(mut self, traces: DecisionTraces) -> Self {
+        self.decision_traces = Some(traces);
+        self
+    }
+    
+    /// Publish risk thoughts to the given broadcaster
+    pub fn with_thought_broadcaster(mut self, thought_broadcaster: AIThoughtBroadcaster) -> Self {
+        self.thought_broadcaster = Some(thought_broadcaster);
+        self
+    }
+    
+    /// Measure stop risk against the stops execution keeps in `levels`
+    pub fn with_protective_levels(mut self, levels: ProtectiveLevels) -> Self {
+        self.protective_levels = Some(levels);
+        self
+    }
+    
+    /// Simulate next-day portfolio P&L over `monte_carlo_simulations` paths,
+    /// using the volatility of recorded daily returns once there is enough history
+    pub async fn run_monte_carlo(&self) -> TradingResult<MonteCarloResult> {
+        let context = self.base.get_system_context().await;
+        let positions: Vec<_> = context.portfolio.positions.values().cloned().collect();
+        let volatility = self.returns_window.read().await.volatility().unwrap_or(DEFAULT_DAILY_VOLATILITY);
+        let iterations = self.config.monte_carlo_simulations as usize;
+        
+        let started = std::time::Instant::now();
+        let result = monte_carlo::simulate(
+            &positions,
+            volatility,
+            self.risk_config.max_daily_loss,
+            iterations,
+            &mut rand::thread_rng(),
+        )?;
+        info!(
+            "🎲 Monte Carlo: {} paths in {:?}, P&L 5th/50th/95th ${}/${}/${}, {:.1}% chance of breaching the daily loss limit",
+            iterations, started.elapsed(), result.percentile_5, result.median, result.percentile_95, result.breach_probability * 100.0
+        );
+        
+        if let Some(broadcaster) = &self.thought_broadcaster {
+            broadcaster.broadcast_thought(
+                AIThought::new(
+                    AIAgent::RiskManager,
+                    ThoughtType::RiskCheck,
+                    format!(
+                        "Simulated {} one-day paths: P&L ranges ${} to ${} (5th-95th percentile), with a {:.1}% chance of losing more than ${}.",
+                        iterations, result.percentile_5, result.percentile_95, result.breach_probability * 100.0, self.risk_config.max_daily_loss
+                    ),
+                    0.8,
+                )
+                .with_data("monte_carlo".to_string(), serde_json::to_value(&result)?)
+                .with_symbols(positions.iter().map(|position| position.symbol.clone()).collect())
+                .with_tags(vec!["monte_carlo".to_string(), "risk".to_string()])
+            ).await;
+        }
+        
+        Ok(result)
+    }
+    
     /// Monitor portfolio risk continuously
     async fn monitor_risk(&self) -> TradingResult<()> {
         info!("🛡️  Monitoring portfolio risk...");
         
         let context = self.base.get_system_context().await;
+        self.record_daily_return(&context).await;
+        self.update_circuit_breaker(&context).await?;
         let risk_metrics = self.calculate_portfolio_risk(&context).await?;
         
         // Check for risk violations
         if risk_metrics.portfolio_heat > self.risk_config.max_portfolio_heat {
             warn!("⚠️  Portfolio heat exceeded: {:.2}", risk_metrics.portfolio_heat);
-            self.trigger_risk_alert("High portfolio heat").await?;
+            self.trigger_risk_alert("High portfolio heat", None, AlertSeverity::Warning).await?;
         }
         
         if context.portfolio.daily_pnl < -self.risk_config.max_daily_loss {
@@ -67,81 +178,258 @@ impl RiskManagementAgent {
             self.trigger_emergency_stop("Daily loss limit exceeded").await?;
         }
         
+        self.force_exits(&context).await?;
+        
         Ok(())
     }
     
+    /// Close positions whose unrealized loss is beyond the cap, even without a
+    /// stop order. A symbol with an exit still working isn't sent another.
+    async fn force_exits(&self, context: &SystemContext) -> TradingResult<()> {
+        let mut pending_exits = self.pending_exits.write().await;
+        pending_exits.retain_held(&context.portfolio);
+        for order in self.loss_monitor.exit_orders(&context.portfolio) {
+            if !pending_exits.begin(&order) {
+                continue;
+            }
+            warn!("✂️  Unrealized loss cap breached on {} - forcing market exit", order.symbol);
+            let reason = format!("Unrealized loss cap breached on {}", order.symbol);
+            self.trigger_risk_alert(&reason, Some(&order.symbol), AlertSeverity::Warning).await?;
+            
+            let order_id = order.id;
+            let message = AgentMessage::broadcast(
+                self.base.id,
+                crate::core::types::MessageType::OrderRequest,
+                serde_json::to_value(ClosingOrder { order, close_reason: Some(CloseReason::StopLoss) })?,
+            );
+            if let Err(e) = self.base.send_message(message).await {
+                pending_exits.resolve(order_id);
+                return Err(e);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Trip the circuit breaker when intraday drawdown crosses the threshold
+    async fn update_circuit_breaker(&self, context: &SystemContext) -> TradingResult<()> {
+        let now = chrono::Utc::now();
+        let day = context.portfolio.trading_day.unwrap_or_else(|| now.date_naive());
+        let tripped = self.circuit_breaker.write().await.observe(
+            day,
+            context.portfolio.total_value,
+            self.risk_config.circuit_breaker_threshold,
+            now,
+        );
+        let Some(drawdown) = tripped else {
+            return Ok(());
+        };
+        
+        MetricsCollector::record_circuit_breaker();
+        error!(
+            "🔌 Circuit breaker tripped: intraday drawdown {:.2}% - halting new trades for {}s",
+            drawdown * rust_decimal::Decimal::from(100),
+            self.risk_config.circuit_breaker_cooldown_secs
+        );
+        if let Some(broadcaster) = &self.thought_broadcaster {
+            broadcaster.broadcast_thought(
+                AIThought::new(
+                    AIAgent::RiskManager,
+                    ThoughtType::RiskCheck,
+                    format!(
+                        "Circuit breaker tripped: equity is down {:.2}% from today's high. Rejecting all new trades for {}s, then trading at reduced size until things settle.",
+                        drawdown * rust_decimal::Decimal::from(100),
+                        self.risk_config.circuit_breaker_cooldown_secs
+                    ),
+                    0.95,
+                )
+                .with_impact("High".to_string())
+                .with_tags(vec!["circuit_breaker".to_string(), "risk".to_string()])
+            ).await;
+        }
+        self.trigger_risk_alert("Circuit breaker tripped", None, AlertSeverity::Halt).await
+    }
+    
+    /// Sample today's portfolio return into the VaR window
+    async fn record_daily_return(&self, context: &SystemContext) {
+        let start_of_day = context.portfolio.total_value - context.portfolio.daily_pnl;
+        if start_of_day <= rust_decimal::Decimal::ZERO {
+            return;
+        }
+        if let Some(daily_return) = (context.portfolio.daily_pnl / start_of_day).to_f64() {
+            let day = context.portfolio.trading_day.unwrap_or_else(|| chrono::Utc::now().date_naive());
+            self.returns_window.write().await.record(day, daily_return);
+        }
+    }
+    
     /// Calculate portfolio risk metrics
     async fn calculate_portfolio_risk(&self, context: &SystemContext) -> TradingResult<RiskMetrics> {
-        // Simulate risk calculations
-        let portfolio_value = context.portfolio.total_value.to_f64().unwrap_or(0.0);
-        let daily_pnl = context.portfolio.daily_pnl.to_f64().unwrap_or(0.0);
-        
-        let portfolio_heat = if portfolio_value > 0.0 {
-            (daily_pnl.abs() / portfolio_value).min(1.0)
-        } else {
-            0.0
+        let stops = match &self.protective_levels {
+            Some(levels) => levels
+                .read()
+                .await
+                .iter()
+                .filter_map(|(symbol, levels)| Some((symbol.clone(), levels.stop_price()?)))
+                .collect(),
+            None => HashMap::new(),
         };
+        let portfolio_heat = portfolio_heat(&context.portfolio, &self.risk_config.portfolio_heat, &stops)?;
         
-        // Simple VaR calculation (95% confidence)
-        let var_95 = rust_decimal::Decimal::from_f64_retain(portfolio_value * 0.05).unwrap();
-        let var_99 = rust_decimal::Decimal::from_f64_retain(portfolio_value * 0.02).unwrap();
+        // Historical-simulation VaR over recorded daily returns, None until any
+        // history exists; var_95 is taken at the configured confidence level
+        let total_value = context.portfolio.total_value;
+        let window = self.returns_window.read().await;
+        let confidence = self.risk_config.var_confidence_level;
+        let to_value = |loss_fraction: Option<f64>| {
+            loss_fraction
+                .map(|fraction| {
+                    rust_decimal::Decimal::from_f64(fraction)
+                        .and_then(|fraction| total_value.checked_mul(fraction))
+                        .ok_or_else(|| TradingError::overflow("portfolio VaR"))
+                })
+                .transpose()
+        };
+        let var_95 = to_value(window.value_at_risk(confidence))?;
+        let var_99 = to_value(window.value_at_risk(confidence.max(0.99)))?;
+        let expected_shortfall = to_value(window.expected_shortfall(confidence))?;
         
         Ok(RiskMetrics {
             var_95,
             var_99,
-            expected_shortfall: var_95 * rust_decimal::Decimal::from_f64_retain(1.2).unwrap(),
+            expected_shortfall,
             max_position_size: self.risk_config.max_position_size,
             daily_loss_limit: self.risk_config.max_daily_loss,
             portfolio_heat,
         })
     }
     
+    /// Portfolio risk for trade validation, reusing a recent computation
+    async fn validation_risk(&self, context: &SystemContext) -> TradingResult<RiskMetrics> {
+        if let Some(metrics) = self.metrics_cache.get().await {
+            return Ok(metrics);
+        }
+        let metrics = self.calculate_portfolio_risk(context).await?;
+        self.metrics_cache.store(&metrics).await;
+        Ok(metrics)
+    }
+    
+    /// Handle a message from the bus
+    async fn on_message(&mut self, message: AgentMessage) -> TradingResult<()> {
+        match message.message_type {
+            crate::core::types::MessageType::VoteRequest => self.answer_vote_request(&message).await,
+            // Fills change the portfolio cached risk was computed from
+            crate::core::types::MessageType::OrderExecution => {
+                self.metrics_cache.invalidate().await;
+                if let Ok(result) = serde_json::from_value::<ExecutionResult>(message.payload.clone()) {
+                    self.resolve_exit(result.order_id).await;
+                }
+                self.handle_event(message).await
+            }
+            crate::core::types::MessageType::TradeClosed => {
+                self.metrics_cache.invalidate().await;
+                self.handle_event(message).await
+            }
+            crate::core::types::MessageType::OrderRejected => {
+                if let Ok(rejection) = serde_json::from_value::<OrderRejection>(message.payload.clone()) {
+                    if self.resolve_exit(rejection.order_id).await {
+                        warn!("✂️  Forced exit on {} was rejected: {}", rejection.symbol, rejection.reason);
+                    }
+                }
+                self.handle_event(message).await
+            }
+            _ => self.handle_event(message).await,
+        }
+    }
+    
+    /// Stop tracking a forced exit once execution has finished with it, so a
+    /// position still in breach gets a fresh exit. True if it was one of ours.
+    async fn resolve_exit(&self, order_id: uuid::Uuid) -> bool {
+        self.pending_exits.write().await.resolve(order_id).is_some()
+    }
+    
     /// Trigger risk alert
-    async fn trigger_risk_alert(&self, reason: &str) -> TradingResult<()> {
-        let message = AgentMessage {
-            from: self.base.id,
-            to: uuid::Uuid::nil(), // Broadcast
-            message_type: crate::core::types::MessageType::RiskAlert,
-            payload: serde_json::json!({ "reason": reason }),
-            timestamp: chrono::Utc::now(),
-        };
+    async fn trigger_risk_alert(&self, reason: &str, symbol: Option<&str>, severity: AlertSeverity) -> TradingResult<()> {
+        let alert = RiskAlertNotice { reason: reason.to_string(), symbol: symbol.map(str::to_string), severity };
+        let message = AgentMessage::broadcast(self.base.id, crate::core::types::MessageType::RiskAlert, serde_json::to_value(&alert)?);
         
         self.base.send_message(message).await?;
         Ok(())
     }
     
+    /// Vote on a proposed signal: approve it if it passes trade validation,
+    /// otherwise veto it
+    pub async fn vote_on(&self, request: &VoteRequest) -> TradingResult<Vote> {
+        let validation = self.validate_trade(&request.signal).await?;
+        let reason = if validation.warnings.is_empty() {
+            format!("Risk score {:.2} within limits", validation.risk_score)
+        } else {
+            validation.warnings.join("; ")
+        };
+        
+        Ok(Vote {
+            request_id: request.id,
+            voter: "risk_management".to_string(),
+            approve: validation.approved,
+            confidence: if validation.approved { (1.0 - validation.risk_score).clamp(0.0, 1.0) } else { 1.0 },
+            veto: !validation.approved,
+            reason,
+        })
+    }
+    
+    /// Answer a coordinator's vote request with a reply to the coordinator
+    async fn answer_vote_request(&self, message: &AgentMessage) -> TradingResult<()> {
+        let request: VoteRequest = serde_json::from_value(message.payload.clone())?;
+        let vote = self.vote_on(&request).await?;
+        
+        let reply = message.reply(self.base.id, crate::core::types::MessageType::VoteResponse, serde_json::to_value(&vote)?);
+        self.base.send_message(reply).await
+    }
+    
     /// Trigger emergency stop
     async fn trigger_emergency_stop(&self, reason: &str) -> TradingResult<()> {
         error!("🚨 EMERGENCY STOP: {}", reason);
         
-        let message = AgentMessage {
-            from: self.base.id,
-            to: uuid::Uuid::nil(), // Broadcast
-            message_type: crate::core::types::MessageType::EmergencyShutdown,
-            payload: serde_json::json!({ "reason": reason }),
-            timestamp: chrono::Utc::now(),
-        };
+        let message = AgentMessage::broadcast(self.base.id, crate::core::types::MessageType::EmergencyShutdown, serde_json::json!({ "reason": reason }));
         
         self.base.send_message(message).await?;
         Ok(())
     }
 }
 
+impl AgentWiring for RiskManagementAgent {
+    const NAME: &'static str = "risk_management";
+    
+    fn base_mut(&mut self) -> &mut BaseAgent {
+        &mut self.base
+    }
+}
+
 #[async_trait]
 impl AutonomousAgent for RiskManagementAgent {
     async fn execute_mission(&self, context: &SystemContext) -> TradingResult<AgentResult> {
         info!("🛡️  Risk Management executing mission...");
         
         let risk_metrics = self.calculate_portfolio_risk(context).await?;
+        let mut recommendations = vec![
+            format!("Portfolio heat: {:.2}%", risk_metrics.portfolio_heat * 100.0),
+            match risk_metrics.var_95 {
+                Some(var_95) => format!("VaR (95%): ${}", var_95),
+                None => "VaR (95%): insufficient data".to_string(),
+            },
+        ];
+        if self.config.stress_testing {
+            let simulation = self.run_monte_carlo().await?;
+            recommendations.push(format!(
+                "Monte Carlo daily loss breach probability: {:.1}%",
+                simulation.breach_probability * 100.0
+            ));
+        }
         
         Ok(AgentResult {
             success: true,
             signals: Vec::new(), // Risk agent doesn't generate trading signals
             metrics: context.performance_metrics.clone(),
-            recommendations: vec![
-                format!("Portfolio heat: {:.2}%", risk_metrics.portfolio_heat * 100.0),
-                format!("VaR (95%): ${}", risk_metrics.var_95),
-            ],
+            recommendations,
             errors: Vec::new(),
         })
     }
@@ -190,6 +478,43 @@ impl AutonomousAgent for RiskManagementAgent {
         })
     }
     
+    fn apply_parameters(&mut self, parameters: &HashMap<String, serde_json::Value>) -> TradingResult<Vec<ParameterChange>> {
+        let updates = validate_parameters(EVOLVABLE_PARAMETERS, parameters)?;
+        let mut applied = Vec::new();
+        
+        for (key, value) in updates {
+            let old = match key {
+                "max_portfolio_heat" => {
+                    info!("🔧 max_portfolio_heat: {} -> {}", self.risk_config.max_portfolio_heat, value);
+                    let old = ParameterValue::Float(self.risk_config.max_portfolio_heat);
+                    self.risk_config.max_portfolio_heat = value.as_f64();
+                    old
+                }
+                _ => continue,
+            };
+            applied.push(ParameterChange { key, old, new: value });
+        }
+        
+        Ok(applied)
+    }
+    
+    fn reconfigure(&mut self, update: &ConfigUpdate) -> TradingResult<Vec<String>> {
+        let mut risk_config = serde_json::to_value(&self.risk_config)?;
+        let mut applied = Vec::new();
+        
+        for (path, value) in &update.changes {
+            if let Some(field) = path.strip_prefix("risk.") {
+                risk_config[field] = value.clone();
+                applied.push(path.clone());
+            }
+        }
+        
+        if !applied.is_empty() {
+            self.risk_config = serde_json::from_value(risk_config)?;
+        }
+        Ok(applied)
+    }
+    
     fn capabilities(&self) -> Vec<AgentCapability> {
         self.base.capabilities.clone()
     }
@@ -198,10 +523,26 @@ impl AutonomousAgent for RiskManagementAgent {
         self.base.id
     }
     
+    fn change_log(&self) -> Option<&ConfigChangeLog> {
+        self.base.change_log.as_ref()
+    }
+    
+    fn evolution_cooldown(&self) -> Option<&EvolutionCooldown> {
+        self.base.evolution_cooldown.as_ref()
+    }
+    
     async fn run(&mut self) -> TradingResult<()> {
+        if !self.base.await_prerequisites().await? {
+            return Ok(());
+        }
         info!("🛡️  Risk Management starting execution loop...");
         
         let mut monitoring_interval = interval(Duration::from_millis(self.config.monitoring_interval_ms));
+        let mut events = self.base.subscribe_events();
+        let mut inbox = self.base.inbox().await;
+        let mut heartbeat = self.base.heartbeat_timer();
+        
+        self.base.mark_ready();
         
         loop {
             tokio::select! {
@@ -210,11 +551,13 @@ impl AutonomousAgent for RiskManagementAgent {
                         error!("Risk monitoring error: {}", e);
                     }
                 }
-                _ = tokio::time::sleep(Duration::from_millis(10)) => {
-                    if self.base.should_shutdown().await {
-                        break;
+                _ = heartbeat.tick() => self.base.send_heartbeat(),
+                Some(message) = next_message(&mut events, &mut inbox) => {
+                    if let Err(e) = self.on_message(message).await {
+                        error!("Risk Management event error: {}", e);
                     }
                 }
+                _ = self.base.shutdown_requested() => break,
             }
         }
         
@@ -224,7 +567,7 @@ impl AutonomousAgent for RiskManagementAgent {
     
     async fn shutdown(&mut self) -> TradingResult<()> {
         info!("🛑 Risk Management shutting down...");
-        self.base.request_shutdown().await;
+        self.base.request_shutdown();
         Ok(())
     }
 }
@@ -240,13 +583,13 @@ impl RiskManager for RiskManagementAgent {
         info!("🔍 Validating trade for {}", signal.symbol);
         
         let context = self.base.get_system_context().await;
-        let risk_metrics = self.calculate_portfolio_risk(&context).await?;
+        let risk_metrics = self.validation_risk(&context).await?;
         
         // Simple risk validation
         let approved = risk_metrics.portfolio_heat < self.risk_config.max_portfolio_heat;
         let risk_score = risk_metrics.portfolio_heat;
         
-        Ok(RiskValidation {
+        let mut validation = RiskValidation {
             approved,
             risk_score,
             position_size_adjustment: if approved { 1.0 } else { 0.5 },
@@ -256,7 +599,33 @@ impl RiskManager for RiskManagementAgent {
                 vec!["High portfolio heat - reducing position size".to_string()] 
             },
             required_hedges: Vec::new(),
-        })
+        };
+        
+        match self.circuit_breaker.read().await.state() {
+            BreakerState::Open { until } => {
+                validation.approved = false;
+                validation.position_size_adjustment = 0.0;
+                validation.warnings.push(format!("Circuit breaker open until {} - trading halted", until.format("%H:%M:%S UTC")));
+            }
+            BreakerState::HalfOpen { .. } => {
+                validation.position_size_adjustment *= 0.5;
+                validation.warnings.push("Circuit breaker half-open - trading at reduced size".to_string());
+            }
+            BreakerState::Closed => {}
+        }
+        
+        if let Some(audit) = &self.audit {
+            audit.record(signal, &validation, &context).await?;
+        }
+        if let Some(traces) = &self.decision_traces {
+            if validation.approved {
+                traces.pass(signal, PipelineStage::RiskCheck).await;
+            } else {
+                traces.fail(signal, PipelineStage::RiskCheck, validation.warnings.join("; ")).await;
+            }
+        }
+        
+        Ok(validation)
     }
     
     async fn generate_hedges(&self) -> TradingResult<Vec<HedgeRecommendation>> {
@@ -279,3 +648,289 @@ impl RiskManager for RiskManagementAgent {
         Ok(hedges)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::{SystemConfig, UnrealizedLossConfig};
+    use crate::core::types::{MessageType, OrderSide, OrderType, Position};
+    use crate::risk::audit::RiskAuditRecord;
+    use crate::risk::heat::HeatMethod;
+    use rust_decimal::Decimal;
+
+    #[tokio::test]
+    async fn test_poor_feedback_tightens_portfolio_heat() {
+        let config = SystemConfig::default();
+        let context = SystemContext::initial(&config);
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+
+        let mut agent = RiskManagementAgent::new(
+            config.agents.risk_management.clone(),
+            config.risk.clone(),
+            sender,
+            event_sender,
+            Arc::new(RwLock::new(context.clone())),
+        ).await.unwrap();
+        assert_eq!(agent.risk_config.max_portfolio_heat, 0.8);
+
+        let feedback = SystemFeedback {
+            performance_score: 0.3,
+            market_conditions: "Volatile".to_string(),
+            recent_trades: Vec::new(),
+            risk_metrics: context.risk_metrics.clone(),
+            suggestions: Vec::new(),
+        };
+        let message = AgentMessage::broadcast(uuid::Uuid::new_v4(), MessageType::SystemFeedback, serde_json::to_value(&feedback).unwrap());
+
+        agent.handle_event(message).await.unwrap();
+        assert_eq!(agent.risk_config.max_portfolio_heat, 0.6);
+    }
+
+    #[tokio::test]
+    async fn test_trade_validations_are_audited() {
+        let path = std::env::temp_dir().join(format!("risk-audit-{}.jsonl", uuid::Uuid::new_v4()));
+        let mut config = SystemConfig::default();
+        config.risk.audit.path = path.to_string_lossy().into_owned();
+        config.risk.portfolio_heat.method = HeatMethod::DailyLoss;
+        config.risk.validation_cache.enabled = false;
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+
+        let agent = RiskManagementAgent::new(
+            config.agents.risk_management.clone(),
+            config.risk.clone(),
+            sender,
+            event_sender,
+            system_context.clone(),
+        ).await.unwrap();
+
+        let signal = TradingSignal {
+            symbol: "AAPL".to_string(),
+            signal_type: crate::core::types::SignalType::Buy,
+            strength: 0.8,
+            confidence: 0.8,
+            timestamp: chrono::Utc::now(),
+            reasoning: "test".to_string(),
+            source: "test".to_string(),
+        };
+        assert!(agent.validate_trade(&signal).await.unwrap().approved);
+
+        // A 90% daily loss pushes portfolio heat past the limit
+        system_context.write().await.portfolio.daily_pnl = Decimal::from(-90);
+        assert!(!agent.validate_trade(&signal).await.unwrap().approved);
+
+        let contents = tokio::fs::read_to_string(&path).await.unwrap();
+        tokio::fs::remove_file(&path).await.unwrap();
+        let records: Vec<RiskAuditRecord> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(records.len(), 2);
+        assert!(records[0].validation.approved);
+        assert_eq!(records[0].validation.risk_score, 0.0);
+        assert!(!records[1].validation.approved);
+        assert!((records[1].validation.risk_score - 0.9).abs() < 1e-9);
+        assert_eq!(records[1].context.daily_pnl, Decimal::from(-90));
+        assert_eq!(records[1].signal.symbol, "AAPL");
+    }
+
+    #[tokio::test]
+    async fn test_unrealized_loss_breach_generates_closing_order() {
+        let mut config = SystemConfig::default();
+        config.risk.max_unrealized_loss = UnrealizedLossConfig {
+            max_loss: Some(Decimal::from(5)),
+            max_loss_pct: None,
+        };
+        let mut context = SystemContext::initial(&config);
+        for (symbol, unrealized_pnl) in [("AAPL", -6), ("MSFT", -4)] {
+            context.portfolio.positions.insert(symbol.to_string(), Position {
+                id: uuid::Uuid::new_v4(),
+                symbol: symbol.to_string(),
+                quantity: Decimal::from(2),
+                entry_price: Decimal::from(100),
+                current_price: Decimal::from(100) + Decimal::from(unrealized_pnl) / Decimal::from(2),
+                unrealized_pnl: Decimal::from(unrealized_pnl),
+                realized_pnl: Decimal::ZERO,
+                timestamp: chrono::Utc::now(),
+            });
+        }
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+
+        let agent = RiskManagementAgent::new(
+            config.agents.risk_management.clone(),
+            config.risk.clone(),
+            sender,
+            event_sender,
+            Arc::new(RwLock::new(context.clone())),
+        ).await.unwrap();
+        agent.force_exits(&context).await.unwrap();
+
+        let mut orders = Vec::new();
+        while let Ok(message) = receiver.try_recv() {
+            if let MessageType::OrderRequest = message.message_type {
+                let request: ClosingOrder = serde_json::from_value(message.payload).unwrap();
+                assert_eq!(request.close_reason, Some(CloseReason::StopLoss));
+                orders.push(request.order);
+            }
+        }
+
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].symbol, "AAPL");
+        assert!(matches!(orders[0].side, OrderSide::Sell));
+        assert!(matches!(orders[0].order_type, OrderType::Market));
+        assert_eq!(orders[0].quantity, Decimal::from(2));
+
+        let exit_requests = |receiver: &mut mpsc::UnboundedReceiver<AgentMessage>| {
+            std::iter::from_fn(|| receiver.try_recv().ok())
+                .filter(|message| matches!(message.message_type, MessageType::OrderRequest))
+                .count()
+        };
+        // Still in breach on the next tick, but the exit is already working
+        agent.force_exits(&context).await.unwrap();
+        assert_eq!(exit_requests(&mut receiver), 0);
+        assert!(agent.pending_exits.read().await.is_pending("AAPL"));
+
+        // Once execution rejects it, a fresh exit goes out
+        let rejected = AgentMessage::broadcast(
+            uuid::Uuid::new_v4(),
+            MessageType::OrderRejected,
+            serde_json::to_value(OrderRejection {
+                order_id: orders[0].id,
+                symbol: "AAPL".to_string(),
+                reason: "venue closed".to_string(),
+            }).unwrap(),
+        );
+        let mut agent = agent;
+        agent.on_message(rejected).await.unwrap();
+        agent.force_exits(&context).await.unwrap();
+        assert_eq!(exit_requests(&mut receiver), 1);
+
+        // A closed position clears its pending exit
+        context.portfolio.positions.remove("AAPL");
+        agent.force_exits(&context).await.unwrap();
+        assert!(!agent.pending_exits.read().await.is_pending("AAPL"));
+    }
+
+    #[tokio::test]
+    async fn test_monte_carlo_runs_configured_paths_and_broadcasts() {
+        let mut config = SystemConfig::default();
+        config.agents.risk_management.monte_carlo_simulations = 250;
+        let mut context = SystemContext::initial(&config);
+        let position = Position {
+            id: uuid::Uuid::new_v4(),
+            symbol: "AAPL".to_string(),
+            quantity: Decimal::from(10),
+            entry_price: Decimal::from(100),
+            current_price: Decimal::from(100),
+            unrealized_pnl: Decimal::ZERO,
+            realized_pnl: Decimal::ZERO,
+            timestamp: chrono::Utc::now(),
+        };
+        context.portfolio.positions.insert(position.symbol.clone(), position);
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let thoughts = AIThoughtBroadcaster::new(10);
+
+        let agent = RiskManagementAgent::new(
+            config.agents.risk_management.clone(),
+            config.risk.clone(),
+            sender,
+            event_sender,
+            Arc::new(RwLock::new(context)),
+        ).await.unwrap()
+            .with_thought_broadcaster(thoughts.clone());
+
+        let result = agent.run_monte_carlo().await.unwrap();
+        assert_eq!(result.iterations, 250);
+        assert!(result.percentile_5 <= result.median && result.median <= result.percentile_95);
+        assert!((0.0..=1.0).contains(&result.breach_probability));
+
+        let recent = thoughts.get_recent_thoughts(10).await;
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].symbols, vec!["AAPL".to_string()]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_validations_reuse_cached_risk_until_fill_or_ttl() {
+        let mut config = SystemConfig::default();
+        config.risk.audit.enabled = false;
+        config.risk.portfolio_heat.method = HeatMethod::DailyLoss;
+        config.risk.validation_cache = crate::core::config::RiskCacheConfig { enabled: true, ttl_ms: 500 };
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let mut agent = RiskManagementAgent::new(
+            config.agents.risk_management.clone(),
+            config.risk.clone(),
+            sender,
+            event_sender,
+            system_context.clone(),
+        ).await.unwrap();
+
+        let signal = TradingSignal {
+            symbol: "AAPL".to_string(),
+            signal_type: crate::core::types::SignalType::Buy,
+            strength: 0.8,
+            confidence: 0.8,
+            timestamp: chrono::Utc::now(),
+            reasoning: "test".to_string(),
+            source: "test".to_string(),
+        };
+        let set_daily_pnl = |pnl: i64| {
+            let system_context = system_context.clone();
+            async move { system_context.write().await.portfolio.daily_pnl = Decimal::from(pnl) }
+        };
+        assert_eq!(agent.validate_trade(&signal).await.unwrap().risk_score, 0.0);
+
+        // Within the TTL and without a fill the earlier computation is reused
+        set_daily_pnl(-50).await;
+        assert_eq!(agent.validate_trade(&signal).await.unwrap().risk_score, 0.0);
+
+        // A fill invalidates it
+        let fill = AgentMessage::broadcast(uuid::Uuid::new_v4(), MessageType::OrderExecution, serde_json::json!({}));
+        agent.on_message(fill).await.unwrap();
+        assert_eq!(agent.validate_trade(&signal).await.unwrap().risk_score, 0.5);
+
+        // So does the TTL lapsing
+        set_daily_pnl(-90).await;
+        assert_eq!(agent.validate_trade(&signal).await.unwrap().risk_score, 0.5);
+        tokio::time::advance(std::time::Duration::from_millis(500)).await;
+        assert_eq!(agent.validate_trade(&signal).await.unwrap().risk_score, 0.9);
+    }
+
+    #[tokio::test]
+    async fn test_var_is_unknown_until_returns_are_recorded() {
+        let mut config = SystemConfig::default();
+        config.risk.audit.enabled = false;
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let agent = RiskManagementAgent::new(
+            config.agents.risk_management.clone(),
+            config.risk.clone(),
+            sender,
+            event_sender,
+            system_context.clone(),
+        ).await.unwrap();
+
+        // No history reads as insufficient data, not as zero risk
+        let metrics = agent.calculate_risk().await.unwrap();
+        assert!(metrics.var_95.is_none() && metrics.var_99.is_none() && metrics.expected_shortfall.is_none());
+
+        {
+            let mut context = system_context.write().await;
+            context.portfolio.total_value = Decimal::from(9_500);
+            context.portfolio.daily_pnl = Decimal::from(-500);
+        }
+        agent.record_daily_return(&*system_context.read().await).await;
+        let metrics = agent.calculate_risk().await.unwrap();
+        // A 5% loss on the one recorded day, applied to today's value
+        let var_95 = metrics.var_95.unwrap();
+        assert!((var_95 - Decimal::from(475)).abs() < Decimal::new(1, 6), "{}", var_95);
+        assert!(metrics.expected_shortfall.is_some());
+    }
+}
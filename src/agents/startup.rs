@@ -0,0 +1,205 @@
+//! Agent startup ordering
+//!
+//! Agents declare the agents they depend on. The system starts them in
+//! dependency order, and each waits for its prerequisites to report ready
+//! before entering its main loop, giving up after the configured timeout.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tracing::info;
+
+use crate::core::errors::{TradingError, TradingResult};
+use crate::core::startup::startup_order;
+
+/// Flag an agent raises once it is ready for work, shared across its clones
+#[derive(Debug, Clone)]
+pub struct Readiness {
+    ready: Arc<watch::Sender<bool>>,
+}
+
+impl Readiness {
+    pub fn new() -> Self {
+        Self {
+            ready: Arc::new(watch::channel(false).0),
+        }
+    }
+
+    pub fn mark_ready(&self) {
+        self.ready.send_if_modified(|ready| !std::mem::replace(ready, true));
+    }
+
+    pub fn is_ready(&self) -> bool {
+        *self.ready.borrow()
+    }
+
+    /// Resolves once the agent has reported ready
+    pub async fn wait(&self) {
+        let mut ready = self.ready.subscribe();
+        let _ = ready.wait_for(|ready| *ready).await;
+    }
+}
+
+impl Default for Readiness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An agent's own readiness and that of the agents it waits for
+#[derive(Debug, Clone, Default)]
+pub struct StartupGate {
+    pub ready: Readiness,
+    prerequisites: Vec<(String, Readiness)>,
+    timeout: Option<Duration>,
+}
+
+impl StartupGate {
+    /// Resolves once every prerequisite has reported ready, or fails naming
+    /// those still pending when the timeout runs out first
+    pub async fn wait_for_prerequisites(&self) -> TradingResult<()> {
+        let wait = async {
+            for (name, readiness) in &self.prerequisites {
+                if !readiness.is_ready() {
+                    info!("⏳ Waiting for {} to be ready", name);
+                    readiness.wait().await;
+                }
+            }
+        };
+        let Some(timeout) = self.timeout else {
+            wait.await;
+            return Ok(());
+        };
+        if tokio::time::timeout(timeout, wait).await.is_err() {
+            let pending: Vec<&str> = self
+                .prerequisites
+                .iter()
+                .filter(|(_, readiness)| !readiness.is_ready())
+                .map(|(name, _)| name.as_str())
+                .collect();
+            return Err(TradingError::timeout(format!("{} to report ready after {:?}", pending.join(", "), timeout)));
+        }
+        Ok(())
+    }
+}
+
+/// Start order and gates for the enabled agents
+#[derive(Debug, Clone)]
+pub struct StartupPlan {
+    order: Vec<String>,
+    gates: HashMap<String, StartupGate>,
+}
+
+impl StartupPlan {
+    /// Plan the start of `enabled` agents so each follows its dependencies.
+    /// Dependencies on agents that aren't enabled are dropped, since they
+    /// would never report ready.
+    pub fn new(dependencies: &HashMap<String, Vec<String>>, enabled: &[&str]) -> TradingResult<Self> {
+        let order = startup_order(dependencies, enabled)?;
+        let readiness: HashMap<&str, Readiness> = order.iter().map(|agent| (agent.as_str(), Readiness::new())).collect();
+
+        let mut gates = HashMap::new();
+        for agent in &order {
+            let mut prerequisites = Vec::new();
+            for dependency in dependencies.get(agent).into_iter().flatten() {
+                match readiness.get(dependency.as_str()) {
+                    Some(ready) => prerequisites.push((dependency.clone(), ready.clone())),
+                    None => info!("⏳ {} starts without waiting for disabled {}", agent, dependency),
+                }
+            }
+            gates.insert(agent.clone(), StartupGate { ready: readiness[agent.as_str()].clone(), prerequisites, timeout: None });
+        }
+
+        Ok(Self { order, gates })
+    }
+
+    /// Give up waiting for prerequisites after `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        for gate in self.gates.values_mut() {
+            gate.timeout = Some(timeout);
+        }
+        self
+    }
+
+    /// Enabled agents in the order they should be started
+    pub fn order(&self) -> &[String] {
+        &self.order
+    }
+
+    /// Gate for `agent`; one with no prerequisites if it isn't planned
+    pub fn gate(&self, agent: &str) -> StartupGate {
+        self.gates.get(agent).cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::learning::LearningEngineAgent;
+    use crate::agents::traits::{AgentWiring, AutonomousAgent};
+    use crate::core::ai_thoughts::AIThoughtBroadcaster;
+    use crate::core::config::SystemConfig;
+    use crate::core::types::SystemContext;
+    use std::time::Duration;
+    use tokio::sync::{broadcast, mpsc, RwLock};
+
+    #[tokio::test(start_paused = true)]
+    async fn test_agent_waits_for_prerequisite_before_working() {
+        let dependencies = HashMap::from([("learning_engine".to_string(), vec!["risk_management".to_string()])]);
+        let plan = StartupPlan::new(&dependencies, &["learning_engine", "risk_management"]).unwrap();
+        assert_eq!(plan.order(), ["risk_management", "learning_engine"]);
+
+        let cycle = HashMap::from([
+            ("learning_engine".to_string(), vec!["risk_management".to_string()]),
+            ("risk_management".to_string(), vec!["learning_engine".to_string()]),
+        ]);
+        assert!(startup_order(&cycle, &["learning_engine", "risk_management"]).is_err());
+
+        let config = SystemConfig::default();
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (event_sender, _) = broadcast::channel(16);
+        let broadcaster = AIThoughtBroadcaster::new(10);
+        let learning = LearningEngineAgent::new(
+            config.agents.learning_engine.clone(),
+            sender,
+            event_sender,
+            Arc::new(RwLock::new(SystemContext::initial(&config))),
+            broadcaster.clone(),
+        ).await.unwrap()
+            .with_startup_gate(plan.gate("learning_engine"));
+        let mut thoughts = broadcaster.subscribe();
+        let task = tokio::spawn({
+            let mut agent = learning.clone();
+            async move { agent.run().await }
+        });
+
+        // Its first evolution cycle waits on risk management
+        assert!(tokio::time::timeout(Duration::from_secs(60), thoughts.recv()).await.is_err());
+        assert!(!plan.gate("learning_engine").ready.is_ready());
+
+        plan.gate("risk_management").ready.mark_ready();
+        let thought = tokio::time::timeout(Duration::from_secs(60), thoughts.recv()).await.unwrap().unwrap();
+        assert!(thought.tags.contains(&"evolution".to_string()));
+        assert!(plan.gate("learning_engine").ready.is_ready());
+
+        let mut learning = learning;
+        learning.shutdown().await.unwrap();
+        task.await.unwrap().unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_gate_gives_up_on_a_prerequisite_that_never_readies() {
+        let dependencies = HashMap::from([("execution_engine".to_string(), vec!["market_intelligence".to_string()])]);
+        let plan = StartupPlan::new(&dependencies, &["market_intelligence", "execution_engine"])
+            .unwrap()
+            .with_timeout(Duration::from_secs(30));
+
+        let error = plan.gate("execution_engine").wait_for_prerequisites().await.unwrap_err();
+        assert!(matches!(error, TradingError::Timeout { .. }));
+        assert!(error.to_string().contains("market_intelligence"), "{}", error);
+
+        plan.gate("market_intelligence").ready.mark_ready();
+        assert!(plan.gate("execution_engine").wait_for_prerequisites().await.is_ok());
+    }
+}
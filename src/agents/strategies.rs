@@ -0,0 +1,268 @@
+//! Bounded pool of generated strategies
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::agents::traits::GeneratedStrategy;
+use crate::core::errors::{TradingError, TradingResult};
+use crate::core::types::StrategyState;
+
+/// A generated strategy tracked with its observed performance
+#[derive(Debug, Clone)]
+pub struct ManagedStrategy {
+    pub strategy: GeneratedStrategy,
+    pub performance: f64,
+    pub created_at: DateTime<Utc>,
+    pub state: StrategyState,
+}
+
+/// Keeps the set of active generated strategies within a configured cap
+#[derive(Debug, Clone)]
+pub struct StrategyManager {
+    max_active: usize,
+    strategies: Vec<ManagedStrategy>,
+}
+
+impl StrategyManager {
+    /// Create a new strategy manager holding at most `max_active` strategies
+    pub fn new(max_active: usize) -> Self {
+        Self {
+            max_active,
+            strategies: Vec::new(),
+        }
+    }
+
+    /// Add a generated strategy, returning any strategies retired to stay
+    /// within the cap. A strategy with the same name replaces the existing
+    /// definition but keeps its recorded performance. The newly added
+    /// strategy is never retired in the same call, since it has no track
+    /// record yet.
+    pub fn add(&mut self, strategy: GeneratedStrategy) -> Vec<ManagedStrategy> {
+        if let Some(existing) = self.strategies.iter_mut().find(|s| s.strategy.name == strategy.name) {
+            existing.strategy = strategy;
+            return Vec::new();
+        }
+
+        let name = strategy.name.clone();
+        self.strategies.push(ManagedStrategy {
+            strategy,
+            performance: 0.0,
+            created_at: Utc::now(),
+            state: StrategyState::Generated,
+        });
+
+        let mut retired = Vec::new();
+        while self.strategies.len() > self.max_active.max(1) {
+            // Worst performer first, oldest breaks ties
+            let worst = self
+                .strategies
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| s.strategy.name != name)
+                .min_by(|(_, a), (_, b)| {
+                    a.performance
+                        .total_cmp(&b.performance)
+                        .then(a.created_at.cmp(&b.created_at))
+                })
+                .map(|(index, _)| index);
+
+            match worst {
+                Some(index) => {
+                    let mut strategy = self.strategies.remove(index);
+                    strategy.state = StrategyState::Retired;
+                    retired.push(strategy);
+                }
+                None => break,
+            }
+        }
+
+        retired
+    }
+
+    /// Record the latest performance score for a strategy
+    pub fn record_performance(&mut self, name: &str, performance: f64) {
+        if let Some(strategy) = self.strategies.iter_mut().find(|s| s.strategy.name == name) {
+            strategy.performance = performance;
+        }
+    }
+
+    /// Move a strategy to `next`, returning its previous state. Transitions
+    /// the lifecycle doesn't allow are rejected and leave the state unchanged.
+    /// Retired strategies leave the pool.
+    pub fn transition(&mut self, name: &str, next: StrategyState) -> TradingResult<StrategyState> {
+        let index = self
+            .strategies
+            .iter()
+            .position(|s| s.strategy.name == name)
+            .ok_or_else(|| TradingError::strategy(format!("Unknown strategy: {}", name)))?;
+
+        let previous = self.strategies[index].state;
+        if !previous.can_transition_to(next) {
+            return Err(TradingError::strategy(format!(
+                "Strategy {} cannot move from {:?} to {:?}",
+                name, previous, next
+            )));
+        }
+
+        if next == StrategyState::Retired {
+            self.strategies.remove(index);
+        } else {
+            self.strategies[index].state = next;
+        }
+        Ok(previous)
+    }
+
+    /// Current lifecycle state of a strategy in the pool
+    pub fn state(&self, name: &str) -> Option<StrategyState> {
+        self.strategies.iter().find(|s| s.strategy.name == name).map(|s| s.state)
+    }
+
+    /// Whether a strategy with this name is active
+    pub fn contains(&self, name: &str) -> bool {
+        self.strategies.iter().any(|s| s.strategy.name == name)
+    }
+
+    /// Get all active strategies
+    pub fn active(&self) -> &[ManagedStrategy] {
+        &self.strategies
+    }
+
+    /// Number of active strategies
+    pub fn len(&self) -> usize {
+        self.strategies.len()
+    }
+
+    /// Whether there are no active strategies
+    pub fn is_empty(&self) -> bool {
+        self.strategies.is_empty()
+    }
+}
+
+/// Caps how many new strategies may be generated per trading session.
+/// Sessions are trading days from `MarketCalendar::trading_day`, so the count
+/// turns over at the configured daily reset rather than at UTC midnight.
+#[derive(Debug, Clone)]
+pub struct GenerationQuota {
+    max_per_session: usize,
+    session: Option<NaiveDate>,
+    generated: usize,
+}
+
+impl GenerationQuota {
+    /// Allow at most `max_per_session` new strategies per session
+    pub fn new(max_per_session: usize) -> Self {
+        Self {
+            max_per_session,
+            session: None,
+            generated: 0,
+        }
+    }
+
+    /// Claim a slot for a new strategy in `session`, starting a fresh count
+    /// when the session changes. Returns false once the cap is reached.
+    pub fn try_acquire(&mut self, session: NaiveDate) -> bool {
+        if self.session != Some(session) {
+            self.session = Some(session);
+            self.generated = 0;
+        }
+        if self.generated >= self.max_per_session {
+            return false;
+        }
+        self.generated += 1;
+        true
+    }
+
+    /// New strategies generated in the current session
+    pub fn generated(&self) -> usize {
+        self.generated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::traits::PerformanceTargets;
+    use std::collections::HashMap;
+
+    fn strategy(name: &str) -> GeneratedStrategy {
+        GeneratedStrategy {
+            name: name.to_string(),
+            description: String::new(),
+            parameters: HashMap::new(),
+            expected_performance: PerformanceTargets {
+                max_latency_ms: 100,
+                min_accuracy: 0.7,
+                max_memory_mb: 50,
+                min_throughput: 100,
+            },
+            risk_profile: "moderate".to_string(),
+            market_conditions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_cap_retires_lowest_performer() {
+        let mut manager = StrategyManager::new(2);
+        assert!(manager.add(strategy("a")).is_empty());
+        assert!(manager.add(strategy("b")).is_empty());
+        manager.record_performance("a", 0.8);
+        manager.record_performance("b", 0.2);
+
+        let retired = manager.add(strategy("c"));
+        assert_eq!(retired.len(), 1);
+        assert_eq!(retired[0].strategy.name, "b");
+        assert_eq!(manager.len(), 2);
+
+        let names: Vec<&str> = manager.active().iter().map(|s| s.strategy.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "c"]);
+    }
+
+    #[test]
+    fn test_regenerated_strategy_keeps_performance() {
+        let mut manager = StrategyManager::new(2);
+        manager.add(strategy("a"));
+        manager.record_performance("a", 0.6);
+
+        assert!(manager.add(strategy("a")).is_empty());
+        assert_eq!(manager.len(), 1);
+        assert_eq!(manager.active()[0].performance, 0.6);
+    }
+
+    #[test]
+    fn test_generation_cap_resets_next_session() {
+        let mut quota = GenerationQuota::new(2);
+        let monday = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+        let tuesday = monday.succ_opt().unwrap();
+
+        assert!(quota.try_acquire(monday));
+        assert!(quota.try_acquire(monday));
+        assert!(!quota.try_acquire(monday));
+        assert_eq!(quota.generated(), 2);
+
+        assert!(quota.try_acquire(tuesday));
+        assert_eq!(quota.generated(), 1);
+    }
+
+    #[test]
+    fn test_lifecycle_transitions_are_enforced() {
+        let mut manager = StrategyManager::new(5);
+        manager.add(strategy("a"));
+        assert_eq!(manager.state("a"), Some(StrategyState::Generated));
+
+        assert_eq!(manager.transition("a", StrategyState::Shadow).unwrap(), StrategyState::Generated);
+        assert_eq!(manager.transition("a", StrategyState::Active).unwrap(), StrategyState::Shadow);
+        assert_eq!(manager.transition("a", StrategyState::Disabled).unwrap(), StrategyState::Active);
+
+        // A disabled strategy must prove itself in shadow again before trading
+        assert!(manager.transition("a", StrategyState::Active).is_err());
+        assert_eq!(manager.state("a"), Some(StrategyState::Disabled));
+        assert!(manager.transition("a", StrategyState::Disabled).is_err());
+
+        manager.transition("a", StrategyState::Retired).unwrap();
+        assert_eq!(manager.state("a"), None);
+        assert!(manager.transition("a", StrategyState::Active).is_err());
+
+        assert!(!StrategyState::Retired.can_transition_to(StrategyState::Active));
+        assert!(!StrategyState::Retired.can_transition_to(StrategyState::Shadow));
+        assert!(!StrategyState::Shadow.can_transition_to(StrategyState::Generated));
+    }
+}
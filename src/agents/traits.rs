@@ -1,14 +1,26 @@
 //! Common traits for autonomous trading agents
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{mpsc, RwLock};
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc, watch, Mutex, RwLock};
 
-use crate::core::errors::TradingResult;
+use crate::agents::change_log::ConfigChangeLog;
+use crate::agents::cooldown::EvolutionCooldown;
+use crate::agents::parameters::ParameterChange;
+use crate::agents::startup::StartupGate;
+use crate::core::errors::{TradingError, TradingResult};
+use crate::core::directory::AgentDirectory;
+use crate::core::reload::ConfigUpdate;
+use crate::core::replies::ReplyRouter;
 use crate::core::types::{
-    AgentCapability, AgentId, AgentMessage, SystemContext, 
+    AgentCapability, AgentId, AgentMessage, MessageType, SystemContext, 
     PerformanceMetrics, TradingSignal
 };
+use crate::infrastructure::locks::LockMonitor;
 
 /// Core trait for all autonomous agents
 #[async_trait]
@@ -25,6 +37,77 @@ pub trait AutonomousAgent: Send + Sync + Clone {
     /// Generate code for new capabilities (self-modification)
     async fn generate_code(&self, requirements: &Requirements) -> TradingResult<CodeGeneration>;
     
+    /// Apply evolved parameters to the running configuration, returning what changed
+    fn apply_parameters(&mut self, parameters: &HashMap<String, serde_json::Value>) -> TradingResult<Vec<ParameterChange>>;
+    
+    /// Log that applied parameter changes are recorded in, if any
+    fn change_log(&self) -> Option<&ConfigChangeLog> {
+        None
+    }
+    
+    /// Cooldown limiting how often evolved parameters are applied, if any
+    fn evolution_cooldown(&self) -> Option<&EvolutionCooldown> {
+        None
+    }
+    
+    /// Pick up reloaded config settings this agent reads while running,
+    /// returning the paths it applied
+    fn reconfigure(&mut self, _update: &ConfigUpdate) -> TradingResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+    
+    /// Handle a message fanned out by the system message bus
+    async fn handle_event(&mut self, message: AgentMessage) -> TradingResult<()> {
+        match message.message_type {
+            crate::core::types::MessageType::SystemFeedback => {
+                let feedback: SystemFeedback = serde_json::from_value(message.payload)?;
+                self.handle_feedback(&feedback).await?;
+            }
+            crate::core::types::MessageType::ConfigUpdate => {
+                let update: ConfigUpdate = serde_json::from_value(message.payload)?;
+                let applied = self.reconfigure(&update)?;
+                if !applied.is_empty() {
+                    tracing::info!("🔄 Agent {} reloaded config: {:?}", self.agent_id(), applied);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+    
+    /// Evolve from system feedback and apply the resulting parameters
+    async fn handle_feedback(&mut self, feedback: &SystemFeedback) -> TradingResult<EvolutionResult> {
+        if let Some(remaining) = self.evolution_cooldown().map(|cooldown| cooldown.remaining()) {
+            if let Some(remaining) = remaining.await {
+                tracing::info!("🧬 Agent {} skipped evolution: cooldown has {:?} left", self.agent_id(), remaining);
+                return Ok(EvolutionResult {
+                    strategy_updated: false,
+                    new_parameters: HashMap::new(),
+                    performance_improvement: 0.0,
+                    confidence: 0.0,
+                });
+            }
+        }
+        
+        let result = self.evolve_strategy(feedback).await?;
+        if result.strategy_updated {
+            let applied = self.apply_parameters(&result.new_parameters)?;
+            if let Some(cooldown) = self.evolution_cooldown() {
+                cooldown.record_applied().await;
+            }
+            let keys: Vec<&str> = applied.iter().map(|change| change.key).collect();
+            tracing::info!("🧬 Agent {} applied evolved parameters: {:?}", self.agent_id(), keys);
+            if let Some(log) = self.change_log() {
+                let reason = format!(
+                    "evolution: performance score {:.2} in {} market",
+                    feedback.performance_score, feedback.market_conditions
+                );
+                log.record(self.agent_id(), &applied, &reason).await;
+            }
+        }
+        Ok(result)
+    }
+    
     /// Get agent capabilities
     fn capabilities(&self) -> Vec<AgentCapability>;
     
@@ -49,7 +132,7 @@ pub struct AgentResult {
 }
 
 /// System feedback for agent evolution
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemFeedback {
     pub performance_score: f64,
     pub market_conditions: String,
@@ -59,13 +142,17 @@ pub struct SystemFeedback {
 }
 
 /// Trade outcome for learning
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeOutcome {
     pub signal_strength: f64,
     pub actual_return: f64,
     pub execution_time_ms: u64,
     pub slippage: f64,
-    pub success: bool,
+    #[serde(default)]
+    pub costs: f64, // Commissions and fees as a fraction of notional
+    #[serde(default)]
+    pub holding_secs: u64,
+    pub success: bool, // Label assigned by `OutcomeLabeler`
 }
 
 /// Result of strategy evolution
@@ -111,8 +198,18 @@ pub struct BaseAgent {
     pub id: AgentId,
     pub capabilities: Vec<AgentCapability>,
     pub message_sender: mpsc::UnboundedSender<AgentMessage>,
+    pub event_sender: broadcast::Sender<AgentMessage>,
     pub system_context: Arc<RwLock<SystemContext>>,
-    pub shutdown_signal: Arc<RwLock<bool>>,
+    pub shutdown_signal: Arc<watch::Sender<bool>>,
+    pub lock_monitor: LockMonitor,
+    pub change_log: Option<ConfigChangeLog>,
+    pub evolution_cooldown: Option<EvolutionCooldown>,
+    pub startup_gate: Option<StartupGate>,
+    pub replies: Option<ReplyRouter>,
+    pub directory: Option<AgentDirectory>,
+    pub heartbeat_interval: Duration,
+    inbox_sender: mpsc::UnboundedSender<AgentMessage>,
+    inbox: Arc<Mutex<mpsc::UnboundedReceiver<AgentMessage>>>, // Messages addressed to this agent alone
 }
 
 impl BaseAgent {
@@ -120,41 +217,229 @@ impl BaseAgent {
     pub fn new(
         capabilities: Vec<AgentCapability>,
         message_sender: mpsc::UnboundedSender<AgentMessage>,
+        event_sender: broadcast::Sender<AgentMessage>,
         system_context: Arc<RwLock<SystemContext>>,
     ) -> Self {
+        let (inbox_sender, inbox) = mpsc::unbounded_channel();
         Self {
             id: uuid::Uuid::new_v4(),
             capabilities,
             message_sender,
+            event_sender,
             system_context,
-            shutdown_signal: Arc::new(RwLock::new(false)),
+            shutdown_signal: Arc::new(watch::channel(false).0),
+            lock_monitor: LockMonitor::default(),
+            change_log: None,
+            evolution_cooldown: None,
+            startup_gate: None,
+            replies: None,
+            directory: None,
+            heartbeat_interval: Duration::from_secs(5),
+            inbox_sender,
+            inbox: Arc::new(Mutex::new(inbox)),
         }
     }
     
+    /// Make this agent reachable under `name` for messages addressed to it
+    pub fn register(&mut self, name: &str, directory: AgentDirectory) {
+        directory.register(name, self.id, self.inbox_sender.clone());
+        self.directory = Some(directory);
+    }
+    
+    /// Id of the agent registered under `name`, for addressing it directly
+    pub fn agent_id_of(&self, name: &str) -> Option<AgentId> {
+        self.directory.as_ref()?.id_of(name)
+    }
+    
+    /// This agent's inbox; the run loop holds it for as long as it runs
+    pub async fn inbox(&self) -> tokio::sync::OwnedMutexGuard<mpsc::UnboundedReceiver<AgentMessage>> {
+        self.inbox.clone().lock_owned().await
+    }
+    
     /// Send a message to other agents
     pub async fn send_message(&self, message: AgentMessage) -> TradingResult<()> {
         self.message_sender
             .send(message)
-            .map_err(|_| crate::core::errors::TradingError::agent_communication("Failed to send message"))?;
+            .map_err(|_| TradingError::agent_communication("Failed to send message"))?;
         Ok(())
     }
     
-    /// Check if shutdown has been requested
-    pub async fn should_shutdown(&self) -> bool {
-        let shutdown = self.shutdown_signal.read().await;
-        *shutdown
+    /// Send a request and wait up to `timeout` for the reply correlated with it
+    pub async fn request(&self, mut message: AgentMessage, timeout: Duration) -> TradingResult<AgentMessage> {
+        let replies = self.replies.as_ref()
+            .ok_or_else(|| TradingError::agent_communication("No reply router attached for requests"))?;
+        let correlation_id = uuid::Uuid::new_v4();
+        message.correlation_id = Some(correlation_id);
+        message.reply_to = Some(self.id);
+        let message_type = message.message_type.clone();
+        
+        let reply = replies.register(correlation_id).await;
+        if let Err(e) = self.send_message(message).await {
+            replies.cancel(&correlation_id).await;
+            return Err(e);
+        }
+        
+        match tokio::time::timeout(timeout, reply).await {
+            Ok(Ok(reply)) => Ok(reply),
+            Ok(Err(_)) => Err(TradingError::agent_communication("Reply channel closed")),
+            Err(_) => {
+                replies.cancel(&correlation_id).await;
+                Err(TradingError::timeout(format!("reply to {:?} request after {:?}", message_type, timeout)))
+            }
+        }
     }
     
-    /// Request shutdown
-    pub async fn request_shutdown(&self) {
-        let mut shutdown = self.shutdown_signal.write().await;
-        *shutdown = true;
+    /// Subscribe to messages fanned out by the system message bus
+    pub fn subscribe_events(&self) -> broadcast::Receiver<AgentMessage> {
+        self.event_sender.subscribe()
+    }
+    
+    /// Resolves once shutdown is requested. Run loops select on this next to
+    /// their intervals and events, so an idle agent is only woken by real work.
+    pub fn shutdown_requested(&self) -> impl Future<Output = ()> + Send + 'static {
+        let mut shutdown = self.shutdown_signal.subscribe();
+        async move {
+            // The sender lives as long as the agent; a dropped one means shut down too
+            let _ = shutdown.wait_for(|requested| *requested).await;
+        }
+    }
+    
+    /// Wait until the agents this one depends on report ready. Returns false
+    /// if shutdown was requested first, and fails if the gate times out.
+    pub async fn await_prerequisites(&self) -> TradingResult<bool> {
+        let Some(gate) = &self.startup_gate else { return Ok(true) };
+        tokio::select! {
+            ready = gate.wait_for_prerequisites() => ready.map(|_| true),
+            _ = self.shutdown_requested() => Ok(false),
+        }
+    }
+    
+    /// Report this agent ready; run loops call this once subscribed to events
+    pub fn mark_ready(&self) {
+        if let Some(gate) = &self.startup_gate {
+            gate.ready.mark_ready();
+        }
+    }
+    
+    /// Ticks at the heartbeat interval; run loops send a heartbeat on each tick
+    pub fn heartbeat_timer(&self) -> tokio::time::Interval {
+        let mut timer = tokio::time::interval(self.heartbeat_interval);
+        timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        timer
+    }
+    
+    /// Tell the system this agent's run loop is still alive
+    pub fn send_heartbeat(&self) {
+        let heartbeat = AgentMessage::broadcast(self.id, MessageType::Heartbeat, serde_json::Value::Null);
+        // The bus only closes on shutdown
+        let _ = self.message_sender.send(heartbeat);
+    }
+    
+    /// Request shutdown, waking any run loop awaiting `shutdown_requested`
+    pub fn request_shutdown(&self) {
+        self.shutdown_signal.send_replace(true);
     }
     
     /// Get current system context
     pub async fn get_system_context(&self) -> SystemContext {
-        let context = self.system_context.read().await;
-        context.clone()
+        match self.lock_monitor.read(&self.system_context, "system_context").await {
+            Ok(context) => context.clone(),
+            Err(e) => {
+                tracing::warn!("{}; waiting without a limit", e);
+                self.system_context.read().await.clone()
+            }
+        }
+    }
+    
+    /// Get current system context, refusing snapshots older than `max_age_secs` (0 disables the check)
+    pub async fn get_fresh_system_context(&self, max_age_secs: u64) -> TradingResult<SystemContext> {
+        let context = self.get_system_context().await;
+        if max_age_secs == 0 {
+            return Ok(context);
+        }
+        
+        let age = chrono::Utc::now() - context.last_updated;
+        if age > chrono::Duration::seconds(max_age_secs as i64) {
+            tracing::warn!("⏰ System context is stale ({}s old) - refusing to act on it", age.num_seconds());
+            return Err(TradingError::risk_management(format!(
+                "System context stale: last updated {}s ago (limit {}s)",
+                age.num_seconds(),
+                max_age_secs
+            )));
+        }
+        
+        Ok(context)
+    }
+}
+
+/// Builders for the wiring every agent keeps in its `BaseAgent`
+pub trait AgentWiring: Sized {
+    /// Name the agent is configured and addressed under
+    const NAME: &'static str;
+    
+    /// The agent's shared base state
+    fn base_mut(&mut self) -> &mut BaseAgent;
+    
+    /// Record applied parameter changes in the given log
+    fn with_change_log(mut self, change_log: ConfigChangeLog) -> Self {
+        self.base_mut().change_log = Some(change_log);
+        self
+    }
+    
+    /// Wait at least the cooldown between applied evolution updates
+    fn with_evolution_cooldown(mut self, cooldown: EvolutionCooldown) -> Self {
+        self.base_mut().evolution_cooldown = Some(cooldown);
+        self
+    }
+    
+    /// Wait for prerequisite agents before running, and report readiness
+    fn with_startup_gate(mut self, gate: StartupGate) -> Self {
+        self.base_mut().startup_gate = Some(gate);
+        self
+    }
+    
+    /// Match replies to this agent's requests through the given router
+    fn with_reply_router(mut self, replies: ReplyRouter) -> Self {
+        self.base_mut().replies = Some(replies);
+        self
+    }
+    
+    /// Register for messages addressed to this agent in the given directory
+    fn with_directory(mut self, directory: AgentDirectory) -> Self {
+        self.base_mut().register(Self::NAME, directory);
+        self
+    }
+    
+    /// Send a heartbeat from the run loop at the given interval
+    fn with_heartbeat_interval(mut self, interval: Duration) -> Self {
+        self.base_mut().heartbeat_interval = interval;
+        self
+    }
+    
+    /// Time this agent's shared-state locks against the given thresholds
+    fn with_lock_monitor(mut self, lock_monitor: LockMonitor) -> Self {
+        self.base_mut().lock_monitor = lock_monitor;
+        self
+    }
+}
+
+/// Next message for an agent, whether broadcast to all or addressed to it.
+/// Resolves to `None` only once both channels are closed.
+pub async fn next_message(
+    events: &mut broadcast::Receiver<AgentMessage>,
+    inbox: &mut mpsc::UnboundedReceiver<AgentMessage>,
+) -> Option<AgentMessage> {
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(message) => return Some(message),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Agent fell behind and missed {} broadcast messages", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => return inbox.recv().await,
+            },
+            Some(message) = inbox.recv() => return Some(message),
+        }
     }
 }
 
@@ -176,8 +461,9 @@ pub struct MarketAnalysis {
     pub trend_strength: f64,
     pub support_levels: Vec<f64>,
     pub resistance_levels: Vec<f64>,
-    pub sentiment_score: f64,
     pub volume_profile: VolumeProfile,
+    pub symbol_trends: HashMap<crate::core::types::Symbol, f64>, // Indicator trend (-1 to 1) per symbol with enough history
+    pub symbol_volatility: HashMap<crate::core::types::Symbol, f64>, // Annualized ATR volatility per symbol with enough history
 }
 
 /// Volume profile analysis
@@ -185,7 +471,6 @@ pub struct MarketAnalysis {
 pub struct VolumeProfile {
     pub total_volume: u64,
     pub average_volume: u64,
-    pub volume_trend: f64,
     pub high_volume_nodes: Vec<f64>,
 }
 
@@ -203,7 +488,7 @@ pub trait RiskManager: AutonomousAgent {
 }
 
 /// Risk validation result
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RiskValidation {
     pub approved: bool,
     pub risk_score: f64,
@@ -213,7 +498,7 @@ pub struct RiskValidation {
 }
 
 /// Hedge recommendation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HedgeRecommendation {
     pub instrument: String,
     pub action: String, // "buy", "sell"
@@ -243,6 +528,7 @@ pub struct ExecutionPlan {
     pub slice_size: f64,
     pub price_improvement_target: f64,
     pub contingency_plans: Vec<String>,
+    pub rationale: String, // Why the algorithm was chosen
 }
 
 /// Order status information
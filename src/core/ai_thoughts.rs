@@ -3,17 +3,21 @@
 //! This module provides complete transparency into AI decision-making process,
 //! allowing users to see exactly what their AI trader is thinking and why.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use tokio::sync::broadcast;
-use tracing::{info, warn};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+use crate::core::calendar::MarketCalendar;
+use crate::core::errors::TradingResult;
 use crate::core::types::AgentId;
 
 /// Types of AI thoughts for categorization
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum ThoughtType {
     /// Market pattern recognition and analysis
     Analysis,
@@ -38,7 +42,7 @@ pub enum ThoughtType {
 }
 
 /// AI agent types for thought attribution
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum AIAgent {
     MarketIntelligence,
     RiskManager,
@@ -100,6 +104,13 @@ pub struct AIThought {
     pub educational: bool,
     /// Follow-up actions the AI plans to take
     pub planned_actions: Vec<String>,
+    /// Times this thought was had in a row, when duplicates are coalesced
+    #[serde(default = "default_repeat_count")]
+    pub repeat_count: u32,
+}
+
+fn default_repeat_count() -> u32 {
+    1
 }
 
 impl AIThought {
@@ -125,6 +136,7 @@ impl AIThought {
             impact_level: "Medium".to_string(),
             educational: false,
             planned_actions: Vec::new(),
+            repeat_count: 1,
         }
     }
 
@@ -170,6 +182,50 @@ impl AIThought {
         self
     }
 
+    /// Cap the serialized size of `supporting_data` at `max_bytes`.
+    ///
+    /// Entries are kept in key order while they fit; the rest are dropped and
+    /// listed under a `_truncated` marker, which is not counted against the
+    /// cap. Returns whether anything was dropped.
+    pub fn cap_supporting_data(&mut self, max_bytes: usize) -> bool {
+        let total = serde_json::to_vec(&self.supporting_data).map(|bytes| bytes.len()).unwrap_or(usize::MAX);
+        if total <= max_bytes {
+            return false;
+        }
+
+        let mut entries: Vec<(String, serde_json::Value)> = self.supporting_data.drain().collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut used: usize = 2; // Enclosing braces
+        let mut dropped = Vec::new();
+        for (key, value) in entries {
+            // "key":value plus a separating comma
+            let size = serde_json::to_vec(&value).map(|bytes| bytes.len()).unwrap_or(usize::MAX)
+                .saturating_add(key.len() + 4);
+            if used.saturating_add(size) <= max_bytes {
+                used += size;
+                self.supporting_data.insert(key, value);
+            } else {
+                dropped.push(key);
+            }
+        }
+
+        self.supporting_data.insert(
+            "_truncated".to_string(),
+            serde_json::json!({ "original_bytes": total, "dropped_keys": dropped }),
+        );
+        true
+    }
+
+    /// Check whether this thought says the same thing as `other`: same agent,
+    /// type, message and symbols
+    pub fn is_repeat_of(&self, other: &AIThought) -> bool {
+        self.agent == other.agent
+            && self.thought_type == other.thought_type
+            && self.message == other.message
+            && self.symbols == other.symbols
+    }
+
     /// Generate user-friendly explanation
     pub fn to_user_explanation(&self) -> String {
         let emoji = match self.agent {
@@ -198,12 +254,269 @@ impl AIThought {
     }
 }
 
+/// Which thoughts a subscriber wants delivered
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum ThoughtSubscriptionMode {
+    /// Every thought
+    #[default]
+    All,
+    /// Only thoughts marked educational
+    EducationalOnly,
+}
+
+impl ThoughtSubscriptionMode {
+    /// Check whether a thought should be delivered in this mode
+    pub fn accepts(&self, thought: &AIThought) -> bool {
+        match self {
+            ThoughtSubscriptionMode::All => true,
+            ThoughtSubscriptionMode::EducationalOnly => thought.educational,
+        }
+    }
+}
+
+/// Receiver for the thought stream that skips thoughts outside its mode
+pub struct ThoughtSubscription {
+    receiver: broadcast::Receiver<AIThought>,
+    mode: ThoughtSubscriptionMode,
+}
+
+impl ThoughtSubscription {
+    /// Receive the next thought accepted by this subscription's mode
+    pub async fn recv(&mut self) -> Result<AIThought, broadcast::error::RecvError> {
+        loop {
+            let thought = self.receiver.recv().await?;
+            if self.mode.accepts(&thought) {
+                return Ok(thought);
+            }
+        }
+    }
+
+    /// Receive an accepted thought if one is already queued
+    pub fn try_recv(&mut self) -> Result<AIThought, broadcast::error::TryRecvError> {
+        loop {
+            let thought = self.receiver.try_recv()?;
+            if self.mode.accepts(&thought) {
+                return Ok(thought);
+            }
+        }
+    }
+}
+
+/// Criteria a thought must meet to reach a filtered subscriber.
+/// Empty type or agent sets match any type or agent.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ThoughtFilter {
+    pub thought_types: HashSet<ThoughtType>,
+    pub agents: HashSet<AIAgent>,
+    pub min_confidence: f64,
+    pub educational_only: bool,
+}
+
+impl ThoughtFilter {
+    /// Only deliver thoughts of these types
+    pub fn with_types<I: IntoIterator<Item = ThoughtType>>(mut self, thought_types: I) -> Self {
+        self.thought_types.extend(thought_types);
+        self
+    }
+
+    /// Only deliver thoughts from these agents
+    pub fn with_agents<I: IntoIterator<Item = AIAgent>>(mut self, agents: I) -> Self {
+        self.agents.extend(agents);
+        self
+    }
+
+    /// Only deliver thoughts at or above this confidence
+    pub fn with_min_confidence(mut self, min_confidence: f64) -> Self {
+        self.min_confidence = min_confidence;
+        self
+    }
+
+    /// Only deliver thoughts marked educational
+    pub fn educational_only(mut self) -> Self {
+        self.educational_only = true;
+        self
+    }
+
+    /// Check whether a thought meets every criterion
+    pub fn accepts(&self, thought: &AIThought) -> bool {
+        (self.thought_types.is_empty() || self.thought_types.contains(&thought.thought_type))
+            && (self.agents.is_empty() || self.agents.contains(&thought.agent))
+            && thought.confidence >= self.min_confidence
+            && (!self.educational_only || thought.educational)
+    }
+}
+
+/// File format for exported thought history
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThoughtExportFormat {
+    /// One JSON thought per line
+    Jsonl,
+    /// Timestamp, agent, type, confidence and message columns
+    Csv,
+}
+
+impl ThoughtExportFormat {
+    fn header(&self) -> Option<&'static str> {
+        match self {
+            ThoughtExportFormat::Jsonl => None,
+            ThoughtExportFormat::Csv => Some("timestamp,agent,thought_type,confidence,message\n"),
+        }
+    }
+
+    fn line(&self, thought: &AIThought) -> TradingResult<String> {
+        Ok(match self {
+            ThoughtExportFormat::Jsonl => format!("{}\n", serde_json::to_string(thought)?),
+            ThoughtExportFormat::Csv => format!(
+                "{},{:?},{:?},{},\"{}\"\n",
+                thought.timestamp.to_rfc3339(),
+                thought.agent,
+                thought.thought_type,
+                thought.confidence,
+                thought.message.replace('"', "\"\"")
+            ),
+        })
+    }
+}
+
+/// When the persisted thought log is rotated to a timestamped file
+#[derive(Debug, Clone)]
+pub struct ThoughtLogRotation {
+    /// Rotate before a write would take the log past this size
+    pub max_bytes: u64,
+    /// Rotate on the first write of each trading day
+    pub daily: bool,
+    /// Where trading days begin; UTC midnight when unset
+    pub calendar: Option<MarketCalendar>,
+}
+
+impl Default for ThoughtLogRotation {
+    fn default() -> Self {
+        Self {
+            max_bytes: 50 * 1024 * 1024,
+            daily: true,
+            calendar: None,
+        }
+    }
+}
+
+impl ThoughtLogRotation {
+    /// Trading day a write at `at` belongs to
+    fn day_of(&self, at: DateTime<Utc>) -> NaiveDate {
+        match &self.calendar {
+            Some(calendar) => calendar.trading_day(at),
+            None => at.date_naive(),
+        }
+    }
+}
+
+/// Appends thoughts to a newline-delimited JSON log, rotating it as configured
+struct ThoughtLogWriter {
+    path: PathBuf,
+    rotation: ThoughtLogRotation,
+    writer: Option<BufWriter<tokio::fs::File>>,
+    size: u64,
+    opened_on: NaiveDate,
+}
+
+impl ThoughtLogWriter {
+    fn new(path: PathBuf, rotation: ThoughtLogRotation) -> Self {
+        Self {
+            path,
+            writer: None,
+            size: 0,
+            opened_on: rotation.day_of(Utc::now()),
+            rotation,
+        }
+    }
+
+    /// Drain the channel until every sender is dropped, flushing after each burst
+    async fn run(mut self, mut receiver: mpsc::UnboundedReceiver<AIThought>) {
+        while let Some(thought) = receiver.recv().await {
+            self.write_logged(&thought).await;
+            while let Ok(thought) = receiver.try_recv() {
+                self.write_logged(&thought).await;
+            }
+            if let Some(writer) = &mut self.writer {
+                if let Err(e) = writer.flush().await {
+                    warn!("Failed to flush AI thought log {}: {}", self.path.display(), e);
+                }
+            }
+        }
+    }
+
+    async fn write_logged(&mut self, thought: &AIThought) {
+        if let Err(e) = self.write(thought).await {
+            warn!("Failed to persist AI thought {} to {}: {}", thought.id, self.path.display(), e);
+        }
+    }
+
+    async fn write(&mut self, thought: &AIThought) -> TradingResult<()> {
+        let line = format!("{}\n", serde_json::to_string(thought)?);
+
+        if self.writer.is_none() {
+            self.open().await?;
+        }
+        let oversized = self.size > 0 && self.size + line.len() as u64 > self.rotation.max_bytes;
+        let new_day = self.rotation.daily && self.rotation.day_of(Utc::now()) != self.opened_on;
+        if oversized || new_day {
+            self.rotate().await?;
+        }
+
+        if let Some(writer) = &mut self.writer {
+            writer.write_all(line.as_bytes()).await?;
+            self.size += line.len() as u64;
+        }
+        Ok(())
+    }
+
+    /// Open the log for appending, picking up the size and day of an existing file
+    async fn open(&mut self) -> TradingResult<()> {
+        if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let file = tokio::fs::OpenOptions::new().create(true).append(true).open(&self.path).await?;
+        let metadata = file.metadata().await?;
+        self.size = metadata.len();
+        self.opened_on = match metadata.modified() {
+            Ok(modified) if self.size > 0 => self.rotation.day_of(DateTime::<Utc>::from(modified)),
+            _ => self.rotation.day_of(Utc::now()),
+        };
+        self.writer = Some(BufWriter::new(file));
+        Ok(())
+    }
+
+    /// Move the current log aside under a timestamped name and start a new one
+    async fn rotate(&mut self) -> TradingResult<()> {
+        if let Some(mut writer) = self.writer.take() {
+            writer.flush().await?;
+        }
+        let rotated = rotated_log_path(&self.path, Utc::now());
+        tokio::fs::rename(&self.path, &rotated).await?;
+        info!("🗂️ Rotated AI thought log to {}", rotated.display());
+        self.open().await
+    }
+}
+
+/// Name a rotated log `<file name>.<timestamp>` so rotations sort chronologically
+fn rotated_log_path(path: &Path, at: DateTime<Utc>) -> PathBuf {
+    let file_name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    path.with_file_name(format!("{}.{}", file_name, at.format("%Y%m%d-%H%M%S%.6f")))
+}
+
+/// A filtered subscriber's criteria and dedicated channel
+type FilteredSubscriber = (ThoughtFilter, broadcast::Sender<AIThought>);
+
 /// AI Thought broadcaster for real-time streaming
 #[derive(Clone)]
 pub struct AIThoughtBroadcaster {
     sender: broadcast::Sender<AIThought>,
-    thought_history: std::sync::Arc<tokio::sync::RwLock<Vec<AIThought>>>,
+    thought_history: std::sync::Arc<tokio::sync::RwLock<VecDeque<AIThought>>>,
     max_history: usize,
+    max_supporting_data_bytes: Option<usize>,
+    export_chunk_size: usize,
+    coalesce_duplicates: bool,
+    persistence: Option<mpsc::UnboundedSender<AIThought>>,
+    filtered: std::sync::Arc<std::sync::Mutex<Vec<FilteredSubscriber>>>,
 }
 
 impl AIThoughtBroadcaster {
@@ -213,23 +526,90 @@ impl AIThoughtBroadcaster {
         
         Self {
             sender,
-            thought_history: std::sync::Arc::new(tokio::sync::RwLock::new(Vec::new())),
+            thought_history: std::sync::Arc::new(tokio::sync::RwLock::new(VecDeque::with_capacity(max_history))),
             max_history,
+            max_supporting_data_bytes: None,
+            export_chunk_size: 256,
+            coalesce_duplicates: false,
+            persistence: None,
+            filtered: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
         }
     }
 
+    /// Append every broadcast thought to `path` as JSON lines, rotating the file
+    /// as configured. Writes happen on a dedicated task so broadcasting never
+    /// waits on disk IO; must be called from within a Tokio runtime.
+    pub fn with_persistence<P: Into<PathBuf>>(mut self, path: P, rotation: ThoughtLogRotation) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(ThoughtLogWriter::new(path.into(), rotation).run(receiver));
+        self.persistence = Some(sender);
+        self
+    }
+
+    /// Copy at most `chunk_size` thoughts out of the history per lock acquisition when exporting
+    pub fn with_export_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.export_chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Truncate each thought's supporting data to at most `max_bytes` when serialized
+    pub fn with_supporting_data_cap(mut self, max_bytes: usize) -> Self {
+        self.max_supporting_data_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Fold a thought identical to the one before it into that history entry,
+    /// bumping its repeat count instead of storing and re-broadcasting it
+    pub fn with_duplicate_coalescing(mut self, enabled: bool) -> Self {
+        self.coalesce_duplicates = enabled;
+        self
+    }
+
     /// Broadcast a new AI thought
-    pub async fn broadcast_thought(&self, thought: AIThought) {
-        info!("🧠 AI Thought: {}", thought.to_user_explanation());
+    pub async fn broadcast_thought(&self, mut thought: AIThought) {
+        if let Some(max_bytes) = self.max_supporting_data_bytes {
+            if thought.cap_supporting_data(max_bytes) {
+                warn!("✂️ Truncated supporting data of thought {} to {} bytes", thought.id, max_bytes);
+            }
+        }
 
-        // Add to history
+        // Add to history; a repeat updates the last thought, which is then
+        // persisted and broadcast again with its new count
         {
             let mut history = self.thought_history.write().await;
-            history.push(thought.clone());
-            
-            // Keep only recent thoughts
-            if history.len() > self.max_history {
-                history.remove(0);
+            let repeated = history
+                .back_mut()
+                .filter(|last| self.coalesce_duplicates && thought.is_repeat_of(last));
+            if let Some(last) = repeated {
+                last.repeat_count = last.repeat_count.saturating_add(1);
+                last.timestamp = thought.timestamp;
+                debug!("🧠 AI Thought repeated ({}x): {}", last.repeat_count, last.to_user_explanation());
+                thought = last.clone();
+            } else {
+                info!("🧠 AI Thought: {}", thought.to_user_explanation());
+                history.push_back(thought.clone());
+                
+                // Keep only recent thoughts (O(1) eviction from the front)
+                while history.len() > self.max_history {
+                    history.pop_front();
+                }
+            }
+        }
+
+        if let Some(persistence) = &self.persistence {
+            if persistence.send(thought.clone()).is_err() {
+                warn!("AI thought log writer has stopped, thought {} not persisted", thought.id);
+            }
+        }
+
+        // Filtered subscribers each get their own channel, fed only matching thoughts
+        {
+            let mut filtered = self.filtered.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            filtered.retain(|(_, sender)| sender.receiver_count() > 0);
+            for (filter, sender) in filtered.iter() {
+                if filter.accepts(&thought) {
+                    let _ = sender.send(thought.clone());
+                }
             }
         }
 
@@ -239,20 +619,159 @@ impl AIThoughtBroadcaster {
         }
     }
 
+    /// Stream the thought history to `path` as JSON lines
+    pub async fn export_jsonl<P: AsRef<Path>>(&self, path: P) -> TradingResult<usize> {
+        self.export(path, ThoughtExportFormat::Jsonl).await
+    }
+
+    /// Stream the thought history to `path` as CSV
+    pub async fn export_csv<P: AsRef<Path>>(&self, path: P) -> TradingResult<usize> {
+        self.export(path, ThoughtExportFormat::Csv).await
+    }
+
+    /// Stream the history as it stood when the export began to `path`,
+    /// returning the number of thoughts written.
+    ///
+    /// Thoughts are copied out in bounded chunks and the history lock is
+    /// released while each chunk is written, so broadcasting is never blocked
+    /// for the length of the export. Thoughts evicted before their chunk is
+    /// reached are skipped; thoughts broadcast during the export are not included.
+    pub async fn export<P: AsRef<Path>>(&self, path: P, format: ThoughtExportFormat) -> TradingResult<usize> {
+        let mut writer = BufWriter::new(tokio::fs::File::create(path).await?);
+        if let Some(header) = format.header() {
+            writer.write_all(header.as_bytes()).await?;
+        }
+
+        let end = self.thought_history.read().await.back().map(|thought| thought.id.clone());
+        let mut last: Option<String> = None;
+        let mut exported = 0;
+
+        if let Some(end_id) = &end {
+            loop {
+                let chunk: Vec<AIThought> = {
+                    let history = self.thought_history.read().await;
+                    if !history.iter().any(|thought| &thought.id == end_id) {
+                        // Everything up to the end of the snapshot was exported or evicted
+                        break;
+                    }
+                    // Resume after the last exported thought, or from the oldest if it was evicted
+                    let start = last
+                        .as_ref()
+                        .and_then(|id| history.iter().position(|thought| &thought.id == id))
+                        .map_or(0, |index| index + 1);
+                    history.range(start..).take(self.export_chunk_size).cloned().collect()
+                };
+
+                let mut done = chunk.is_empty();
+                for thought in &chunk {
+                    writer.write_all(format.line(thought)?.as_bytes()).await?;
+                    exported += 1;
+                    if &thought.id == end_id {
+                        done = true;
+                        break;
+                    }
+                }
+                if done {
+                    break;
+                }
+                last = chunk.last().map(|thought| thought.id.clone());
+                tokio::task::yield_now().await;
+            }
+        }
+
+        writer.flush().await?;
+        Ok(exported)
+    }
+
+    /// Repopulate the history with the newest `limit` thoughts persisted at
+    /// `path`, reading rotated logs as needed. Loaded thoughts are placed
+    /// before any already in memory; unreadable lines are skipped. Returns
+    /// the number of thoughts loaded.
+    pub async fn load_history<P: AsRef<Path>>(&self, path: P, limit: usize) -> TradingResult<usize> {
+        let path = path.as_ref();
+        let file_name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        let directory = match path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            Some(parent) => parent.to_path_buf(),
+            None => PathBuf::from("."),
+        };
+
+        // The live log first, then rotated logs from newest to oldest
+        let mut files = vec![path.to_path_buf()];
+        if let Ok(mut entries) = tokio::fs::read_dir(&directory).await {
+            let prefix = format!("{}.", file_name);
+            let mut rotated = Vec::new();
+            while let Some(entry) = entries.next_entry().await? {
+                if entry.file_name().to_string_lossy().starts_with(&prefix) {
+                    rotated.push(entry.path());
+                }
+            }
+            rotated.sort();
+            files.extend(rotated.into_iter().rev());
+        }
+
+        let limit = limit.min(self.max_history);
+        let mut loaded: VecDeque<AIThought> = VecDeque::new();
+        for file in files {
+            if loaded.len() >= limit {
+                break;
+            }
+            let contents = match tokio::fs::read_to_string(&file).await {
+                Ok(contents) => contents,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            for line in contents.lines().rev() {
+                if loaded.len() >= limit {
+                    break;
+                }
+                match serde_json::from_str::<AIThought>(line) {
+                    Ok(thought) => loaded.push_front(thought),
+                    Err(e) => warn!("Skipping unreadable AI thought in {}: {}", file.display(), e),
+                }
+            }
+        }
+
+        let count = loaded.len();
+        let mut history = self.thought_history.write().await;
+        for thought in loaded.into_iter().rev() {
+            history.push_front(thought);
+        }
+        while history.len() > self.max_history {
+            history.pop_front();
+        }
+        Ok(count)
+    }
+
     /// Subscribe to AI thought stream
     pub fn subscribe(&self) -> broadcast::Receiver<AIThought> {
         self.sender.subscribe()
     }
 
+    /// Subscribe to the thought stream, delivering only thoughts matching `mode`
+    pub fn subscribe_with_mode(&self, mode: ThoughtSubscriptionMode) -> ThoughtSubscription {
+        ThoughtSubscription {
+            receiver: self.sender.subscribe(),
+            mode,
+        }
+    }
+
+    /// Subscribe to only the thoughts matching `filter`. Thoughts are matched
+    /// before they are sent, so non-matching thoughts never occupy this
+    /// receiver's buffer.
+    pub fn subscribe_filtered(&self, filter: ThoughtFilter) -> broadcast::Receiver<AIThought> {
+        let (sender, receiver) = broadcast::channel(1000);
+        self.filtered
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push((filter, sender));
+        receiver
+    }
+
     /// Get recent thought history
     pub async fn get_recent_thoughts(&self, limit: usize) -> Vec<AIThought> {
         let history = self.thought_history.read().await;
-        let start = if history.len() > limit {
-            history.len() - limit
-        } else {
-            0
-        };
-        history[start..].to_vec()
+        let start = history.len().saturating_sub(limit);
+        history.range(start..).cloned().collect()
     }
 
     /// Get thoughts by agent
@@ -422,4 +941,247 @@ mod tests {
         let received = receiver.recv().await.unwrap();
         assert_eq!(received.message, thought.message);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_consecutive_duplicate_thoughts_are_coalesced() {
+        let broadcaster = AIThoughtBroadcaster::new(10).with_duplicate_coalescing(true);
+        let mut subscriber = broadcaster.subscribe();
+        let ready = || AIThought::new(
+            AIAgent::ExecutionEngine,
+            ThoughtType::Execution,
+            "Execution engine healthy and ready for trades".to_string(),
+            0.9,
+        );
+
+        for _ in 0..3 {
+            broadcaster.broadcast_thought(ready()).await;
+        }
+        let recent = broadcaster.get_recent_thoughts(10).await;
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].repeat_count, 3);
+
+        // Subscribers see each repeat as the same thought with a rising count
+        let streamed: Vec<_> = std::iter::from_fn(|| subscriber.try_recv().ok()).collect();
+        assert_eq!(streamed.iter().map(|t| t.repeat_count).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!(streamed.iter().all(|t| t.id == recent[0].id));
+
+        // A different thought breaks the run, so the next repeat starts afresh
+        broadcaster.broadcast_thought(AIThought::new(
+            AIAgent::ExecutionEngine,
+            ThoughtType::Execution,
+            "Order filled".to_string(),
+            0.9,
+        )).await;
+        broadcaster.broadcast_thought(ready()).await;
+        let counts: Vec<_> = broadcaster.get_recent_thoughts(10).await.iter().map(|t| t.repeat_count).collect();
+        assert_eq!(counts, vec![3, 1, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_history_cap_and_ordering() {
+        let broadcaster = AIThoughtBroadcaster::new(100);
+
+        for i in 0..10_000 {
+            broadcaster.broadcast_thought(AIThought::new(
+                AIAgent::ExecutionEngine,
+                ThoughtType::Execution,
+                format!("thought {}", i),
+                0.5,
+            )).await;
+        }
+
+        let recent = broadcaster.get_recent_thoughts(1_000).await;
+        assert_eq!(recent.len(), 100);
+        assert_eq!(recent.first().unwrap().message, "thought 9900");
+        assert_eq!(recent.last().unwrap().message, "thought 9999");
+
+        let last_three = broadcaster.get_recent_thoughts(3).await;
+        let messages: Vec<_> = last_three.iter().map(|t| t.message.as_str()).collect();
+        assert_eq!(messages, vec!["thought 9997", "thought 9998", "thought 9999"]);
+    }
+
+    #[tokio::test]
+    async fn test_educational_only_subscription() {
+        let broadcaster = AIThoughtBroadcaster::new(10);
+        let mut subscription = broadcaster.subscribe_with_mode(ThoughtSubscriptionMode::EducationalOnly);
+
+        broadcaster.broadcast_thought(AIThought::new(
+            AIAgent::ExecutionEngine,
+            ThoughtType::Execution,
+            "internal".to_string(),
+            0.5,
+        )).await;
+        broadcaster.broadcast_thought(AIThought::new(
+            AIAgent::LearningEngine,
+            ThoughtType::Learning,
+            "lesson".to_string(),
+            0.8,
+        ).educational()).await;
+
+        let received = subscription.recv().await.unwrap();
+        assert_eq!(received.message, "lesson");
+        assert!(subscription.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_oversized_supporting_data_is_truncated() {
+        let broadcaster = AIThoughtBroadcaster::new(10).with_supporting_data_cap(256);
+        let thought = |key: &str| AIThought::new(
+            AIAgent::MarketIntelligence,
+            ThoughtType::Analysis,
+            "Data".to_string(),
+            0.5,
+        ).with_data(key.to_string(), serde_json::json!(0.42));
+
+        let small = thought("rsi");
+        broadcaster.broadcast_thought(small.clone()).await;
+
+        let large = thought("rsi").with_data("blob".to_string(), serde_json::json!("x".repeat(1024)));
+        broadcaster.broadcast_thought(large).await;
+
+        let recent = broadcaster.get_recent_thoughts(2).await;
+        assert_eq!(recent[0].supporting_data, small.supporting_data);
+
+        let capped = &recent[1].supporting_data;
+        assert_eq!(capped["rsi"], serde_json::json!(0.42));
+        assert!(!capped.contains_key("blob"));
+        assert_eq!(capped["_truncated"]["dropped_keys"], serde_json::json!(["blob"]));
+        assert!(serde_json::to_vec(capped).unwrap().len() <= 256);
+    }
+
+    #[tokio::test]
+    async fn test_export_releases_lock_between_chunks() {
+        let broadcaster = AIThoughtBroadcaster::new(20_000).with_export_chunk_size(100);
+        for i in 0..10_000 {
+            broadcaster.broadcast_thought(AIThought::new(
+                AIAgent::LearningEngine,
+                ThoughtType::Learning,
+                format!("Thought {}", i),
+                0.5,
+            )).await;
+        }
+
+        let path = std::env::temp_dir().join(format!("thoughts-{}.jsonl", Uuid::new_v4()));
+        let export = tokio::spawn({
+            let broadcaster = broadcaster.clone();
+            let path = path.clone();
+            async move { broadcaster.export_jsonl(&path).await }
+        });
+
+        // Wait until the export has started writing, then broadcast mid-export
+        while !tokio::fs::metadata(&path).await.map(|m| m.len() > 0).unwrap_or(false) {
+            tokio::task::yield_now().await;
+        }
+        broadcaster.broadcast_thought(AIThought::new(
+            AIAgent::RiskManager,
+            ThoughtType::RiskCheck,
+            "Broadcast during export".to_string(),
+            0.9,
+        )).await;
+        assert!(!export.is_finished(), "broadcast waited for the whole export");
+
+        // The export covers the history as it stood when it began
+        assert_eq!(export.await.unwrap().unwrap(), 10_000);
+        let contents = tokio::fs::read_to_string(&path).await.unwrap();
+        tokio::fs::remove_file(&path).await.unwrap();
+
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 10_000);
+        let first: AIThought = serde_json::from_str(lines[0]).unwrap();
+        let last: AIThought = serde_json::from_str(lines[9_999]).unwrap();
+        assert_eq!(first.message, "Thought 0");
+        assert_eq!(last.message, "Thought 9999");
+    }
+
+    #[tokio::test]
+    async fn test_persisted_thoughts_rotate_and_reload() {
+        let directory = std::env::temp_dir().join(format!("thought-log-{}", Uuid::new_v4()));
+        let path = directory.join("thoughts.jsonl");
+        let rotation = ThoughtLogRotation { max_bytes: 1024, daily: true, calendar: None };
+        let broadcaster = AIThoughtBroadcaster::new(100).with_persistence(&path, rotation);
+
+        for i in 0..20 {
+            broadcaster.broadcast_thought(AIThought::new(
+                AIAgent::RiskManager,
+                ThoughtType::RiskCheck,
+                format!("Thought {}", i),
+                0.5,
+            )).await;
+        }
+
+        // Wait for the writer task to drain the channel
+        let log_files = || async {
+            let mut files = Vec::new();
+            let mut entries = tokio::fs::read_dir(&directory).await.unwrap();
+            while let Some(entry) = entries.next_entry().await.unwrap() {
+                files.push(entry.path());
+            }
+            files
+        };
+        let mut persisted = 0;
+        for _ in 0..500 {
+            persisted = 0;
+            if tokio::fs::metadata(&directory).await.is_ok() {
+                for file in log_files().await {
+                    persisted += tokio::fs::read_to_string(&file).await.unwrap().lines().count();
+                }
+            }
+            if persisted == 20 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(persisted, 20);
+
+        let files = log_files().await;
+        assert!(files.len() > 1, "log was never rotated");
+        for file in &files {
+            assert!(tokio::fs::metadata(file).await.unwrap().len() <= 1024);
+        }
+
+        // A fresh broadcaster picks up the newest thoughts across rotated files, in order
+        let restarted = AIThoughtBroadcaster::new(100);
+        assert_eq!(restarted.load_history(&path, 12).await.unwrap(), 12);
+        let messages: Vec<String> = restarted.get_recent_thoughts(100).await.into_iter().map(|t| t.message).collect();
+        let expected: Vec<String> = (8..20).map(|i| format!("Thought {}", i)).collect();
+        assert_eq!(messages, expected);
+
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_filtered_subscription_only_receives_matches() {
+        let broadcaster = AIThoughtBroadcaster::new(10);
+        let mut dashboard = broadcaster.subscribe_filtered(
+            ThoughtFilter::default()
+                .with_types([ThoughtType::Decision, ThoughtType::RiskCheck])
+                .with_min_confidence(0.7),
+        );
+        let mut risk_lessons = broadcaster.subscribe_filtered(
+            ThoughtFilter::default().with_agents([AIAgent::RiskManager]).educational_only(),
+        );
+
+        let thought = |agent: AIAgent, thought_type: ThoughtType, confidence: f64| {
+            AIThought::new(agent, thought_type, format!("{:.1}", confidence), confidence)
+        };
+        broadcaster.broadcast_thought(thought(AIAgent::MasterCoordinator, ThoughtType::Decision, 0.9)).await;
+        broadcaster.broadcast_thought(thought(AIAgent::MasterCoordinator, ThoughtType::Decision, 0.5)).await;
+        broadcaster.broadcast_thought(thought(AIAgent::MarketIntelligence, ThoughtType::Analysis, 0.95)).await;
+        broadcaster.broadcast_thought(thought(AIAgent::RiskManager, ThoughtType::RiskCheck, 0.8).educational()).await;
+
+        let received = dashboard.recv().await.unwrap();
+        assert_eq!((received.thought_type, received.message), (ThoughtType::Decision, "0.9".to_string()));
+        let received = dashboard.recv().await.unwrap();
+        assert_eq!((received.thought_type, received.message), (ThoughtType::RiskCheck, "0.8".to_string()));
+        // Nothing else was ever queued for this subscriber
+        assert_eq!(dashboard.len(), 0);
+
+        assert_eq!(risk_lessons.recv().await.unwrap().agent, AIAgent::RiskManager);
+        assert_eq!(risk_lessons.len(), 0);
+
+        // Dropped subscribers are pruned on the next broadcast
+        drop(risk_lessons);
+        broadcaster.broadcast_thought(thought(AIAgent::RiskManager, ThoughtType::RiskCheck, 0.8)).await;
+        assert_eq!(broadcaster.filtered.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,291 @@
+//! Market calendar with regular and extended trading sessions
+//!
+//! `status_at` takes exchange-local times (see `TradingHours::timezone`);
+//! `status_for` converts UTC timestamps into the exchange time zone, daylight
+//! saving included, and honours
+//! the session clock override used by backtests and simulations.
+//! `trading_day` tells which day daily P&L counts towards.
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::core::config::{ExtendedHoursConfig, SessionWindow, TradingHours};
+use crate::core::errors::{TradingError, TradingResult};
+
+/// Which trading session a point in time falls into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionType {
+    PreMarket,
+    Regular,
+    AfterHours,
+    Closed,
+}
+
+/// Which time session checks are made against
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionClock {
+    /// The current wall-clock time
+    #[default]
+    WallClock,
+    /// The timestamp of the data being acted on, for backtests and replays
+    DataTimestamp,
+    /// Treat the market as always in its regular session
+    AlwaysOpen,
+}
+
+/// When daily P&L and other daily counters roll over to a new trading day
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DailyResetBoundary {
+    /// At the regular session open in exchange-local time
+    #[default]
+    SessionOpen,
+    /// At the configured pre-market start in exchange-local time
+    PreMarketOpen,
+    /// At midnight UTC
+    UtcMidnight,
+}
+
+/// Trading status of the market at a point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarketStatus {
+    pub session: SessionType,
+    /// Whether trading is allowed in this session under the current configuration
+    pub is_open: bool,
+    /// Set during pre-market and after-hours, where thinner liquidity calls for tighter guards
+    pub extended_session: bool,
+}
+
+impl MarketStatus {
+    /// Whether a strategy may trade now, given whether it opted into extended sessions
+    pub fn permits(&self, strategy_allows_extended: bool) -> bool {
+        self.is_open && (!self.extended_session || strategy_allows_extended)
+    }
+}
+
+/// Parsed trading hours for session lookups
+#[derive(Debug, Clone)]
+pub struct MarketCalendar {
+    regular: (NaiveTime, NaiveTime),
+    pre_market: Option<(NaiveTime, NaiveTime)>,
+    after_hours: Option<(NaiveTime, NaiveTime)>,
+    trading_days: Vec<Weekday>,
+    extended: ExtendedHoursConfig,
+    clock: SessionClock,
+    timezone: Tz,
+    daily_reset: Option<NaiveTime>,
+}
+
+impl MarketCalendar {
+    /// Build a calendar from configured trading hours
+    pub fn from_config(hours: &TradingHours) -> TradingResult<Self> {
+        let trading_days = hours
+            .trading_days
+            .iter()
+            .map(|day| {
+                day.parse::<Weekday>()
+                    .map_err(|_| TradingError::Config(anyhow::anyhow!("Invalid trading day: {}", day)))
+            })
+            .collect::<TradingResult<Vec<_>>>()?;
+
+        Ok(Self {
+            regular: (parse_time(&hours.market_open)?, parse_time(&hours.market_close)?),
+            pre_market: enabled_window(&hours.extended.pre_market)?,
+            after_hours: enabled_window(&hours.extended.after_hours)?,
+            trading_days,
+            extended: hours.extended.clone(),
+            clock: hours.clock,
+            daily_reset: match hours.daily_reset {
+                DailyResetBoundary::SessionOpen => Some(parse_time(&hours.market_open)?),
+                DailyResetBoundary::PreMarketOpen => Some(parse_time(&hours.extended.pre_market.start)?),
+                DailyResetBoundary::UtcMidnight => None,
+            },
+            timezone: hours.timezone.parse::<Tz>().map_err(|e| {
+                TradingError::Config(anyhow::anyhow!("Invalid timezone {}: {}", hours.timezone, e))
+            })?,
+        })
+    }
+
+    /// Replace the configured session clock
+    pub fn with_clock(mut self, clock: SessionClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Report the trading status for data stamped `data_time`, under the
+    /// configured session clock
+    pub fn status_for(&self, data_time: DateTime<Utc>) -> MarketStatus {
+        let at = match self.clock {
+            SessionClock::WallClock => Utc::now(),
+            SessionClock::DataTimestamp => data_time,
+            SessionClock::AlwaysOpen => {
+                return MarketStatus {
+                    session: SessionType::Regular,
+                    is_open: true,
+                    extended_session: false,
+                }
+            }
+        };
+        self.status_at(at.with_timezone(&self.timezone).naive_local())
+    }
+
+    /// Report the session and trading status at an exchange-local time
+    pub fn status_at(&self, local: NaiveDateTime) -> MarketStatus {
+        let session = self.session_at(local);
+        MarketStatus {
+            session,
+            is_open: session != SessionType::Closed,
+            extended_session: matches!(session, SessionType::PreMarket | SessionType::AfterHours),
+        }
+    }
+
+    /// Trading day `at` counts towards. Days begin at the configured daily
+    /// reset boundary, so times before it belong to the previous day.
+    pub fn trading_day(&self, at: DateTime<Utc>) -> NaiveDate {
+        let Some(reset) = self.daily_reset else {
+            return at.date_naive();
+        };
+        let local = at.with_timezone(&self.timezone).naive_local();
+        if local.time() < reset {
+            local.date().pred_opt().unwrap_or(local.date())
+        } else {
+            local.date()
+        }
+    }
+
+    /// Check a quote's spread against the extended-session limit
+    pub fn spread_acceptable(&self, status: &MarketStatus, bid: Decimal, ask: Decimal) -> bool {
+        if !status.extended_session {
+            return true;
+        }
+
+        let mid = (bid + ask) / Decimal::from(2);
+        if mid <= Decimal::ZERO {
+            return false;
+        }
+        let spread_bps = ((ask - bid) / mid * Decimal::from(10_000)).to_f64().unwrap_or(f64::MAX);
+        spread_bps <= self.extended.max_spread_bps
+    }
+
+    /// Scale a position size down for the thinner liquidity of extended sessions
+    pub fn session_size_multiplier(&self, status: &MarketStatus) -> f64 {
+        if status.extended_session {
+            self.extended.position_size_multiplier
+        } else {
+            1.0
+        }
+    }
+
+    fn session_at(&self, local: NaiveDateTime) -> SessionType {
+        if !self.trading_days.contains(&local.weekday()) {
+            return SessionType::Closed;
+        }
+
+        let time = local.time();
+        let within = |(start, end): (NaiveTime, NaiveTime)| time >= start && time < end;
+
+        if within(self.regular) {
+            SessionType::Regular
+        } else if self.pre_market.is_some_and(within) {
+            SessionType::PreMarket
+        } else if self.after_hours.is_some_and(within) {
+            SessionType::AfterHours
+        } else {
+            SessionType::Closed
+        }
+    }
+}
+
+fn parse_time(value: &str) -> TradingResult<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H:%M:%S")
+        .map_err(|e| TradingError::Config(anyhow::anyhow!("Invalid session time {}: {}", value, e)))
+}
+
+fn enabled_window(window: &SessionWindow) -> TradingResult<Option<(NaiveTime, NaiveTime)>> {
+    if !window.enabled {
+        return Ok(None);
+    }
+    Ok(Some((parse_time(&window.start)?, parse_time(&window.end)?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::SystemConfig;
+    use chrono::NaiveDate;
+
+    fn at(hour: u32, minute: u32) -> NaiveDateTime {
+        // 2024-03-12 is a Tuesday
+        NaiveDate::from_ymd_opt(2024, 3, 12).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_pre_market_open_when_extended_hours_enabled() {
+        let mut hours = SystemConfig::default().trading.trading_hours;
+
+        let calendar = MarketCalendar::from_config(&hours).unwrap();
+        let status = calendar.status_at(at(7, 30));
+        assert_eq!(status.session, SessionType::Closed);
+        assert!(!status.is_open);
+
+        hours.extended.pre_market.enabled = true;
+        let calendar = MarketCalendar::from_config(&hours).unwrap();
+
+        let status = calendar.status_at(at(7, 30));
+        assert_eq!(status.session, SessionType::PreMarket);
+        assert!(status.is_open);
+        assert!(status.extended_session);
+        assert!(status.permits(true));
+        assert!(!status.permits(false));
+
+        let regular = calendar.status_at(at(10, 0));
+        assert_eq!(regular.session, SessionType::Regular);
+        assert!(!regular.extended_session);
+    }
+
+    #[test]
+    fn test_session_open_resets_daily_pnl_but_keeps_total() {
+        let config = SystemConfig::default();
+        let calendar = MarketCalendar::from_config(&config.trading.trading_hours).unwrap();
+        let mut portfolio = crate::core::types::SystemContext::initial(&config).portfolio;
+        // 09:30 New York (EST, UTC-5) is 14:30 UTC
+        let utc = |day: u32, hour: u32, minute: u32| {
+            NaiveDate::from_ymd_opt(2024, 3, day).unwrap().and_hms_opt(hour, minute, 0).unwrap().and_utc()
+        };
+
+        assert!(!portfolio.roll_trading_day(calendar.trading_day(utc(5, 15, 0))));
+        portfolio.apply_realized_pnl(Decimal::from(5)).unwrap();
+
+        // After the close and past UTC midnight, but before the next open: same day
+        assert_eq!(calendar.trading_day(utc(6, 14, 29)), NaiveDate::from_ymd_opt(2024, 3, 5).unwrap());
+        assert!(!portfolio.roll_trading_day(calendar.trading_day(utc(6, 14, 29))));
+        assert_eq!(portfolio.daily_pnl, Decimal::from(5));
+
+        assert!(portfolio.roll_trading_day(calendar.trading_day(utc(6, 14, 30))));
+        assert_eq!(portfolio.daily_pnl, Decimal::ZERO);
+        assert_eq!(portfolio.total_pnl, Decimal::from(5));
+        assert_eq!(portfolio.trading_day, NaiveDate::from_ymd_opt(2024, 3, 6));
+    }
+
+    #[test]
+    fn test_sessions_follow_daylight_saving() {
+        let mut hours = SystemConfig::default().trading.trading_hours;
+        hours.clock = SessionClock::DataTimestamp;
+        let calendar = MarketCalendar::from_config(&hours).unwrap();
+        let utc = |month: u32, day: u32, hour: u32, minute: u32| {
+            NaiveDate::from_ymd_opt(2024, month, day).unwrap().and_hms_opt(hour, minute, 0).unwrap().and_utc()
+        };
+
+        // 09:30 New York is 14:30 UTC in winter (EST) and 13:30 UTC in summer (EDT)
+        assert_eq!(calendar.status_for(utc(3, 8, 14, 30)).session, SessionType::Regular);
+        assert_eq!(calendar.status_for(utc(3, 12, 13, 30)).session, SessionType::Regular);
+        assert_eq!(calendar.status_for(utc(3, 12, 20, 0)).session, SessionType::Closed);
+        assert_eq!(calendar.trading_day(utc(7, 2, 13, 29)), NaiveDate::from_ymd_opt(2024, 7, 1).unwrap());
+        assert_eq!(calendar.trading_day(utc(7, 2, 13, 30)), NaiveDate::from_ymd_opt(2024, 7, 2).unwrap());
+
+        hours.timezone = "Mars/Olympus_Mons".to_string();
+        assert!(MarketCalendar::from_config(&hours).is_err());
+    }
+}
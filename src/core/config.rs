@@ -5,10 +5,21 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::env;
+use std::net::{IpAddr, Ipv4Addr};
 use std::path::Path;
 use tokio::fs;
+use tokio::sync::watch;
+use tokio::time::{interval, Duration};
+use tracing::{info, warn};
 
-use crate::core::types::{AgentCapability, StrategyConfig};
+use crate::core::startup::{startup_order, AGENT_NAMES};
+use crate::core::calendar::{DailyResetBoundary, SessionClock};
+use crate::core::types::{AgentCapability, DataSource, StrategyConfig, StrategyState};
+use crate::execution::algorithm::ALGORITHMS;
+use crate::execution::orders::OrderConflictPolicy;
+use crate::execution::sizing::SubMinimumPolicy;
+use crate::intelligence::replay::ReplaySpeed;
+use crate::risk::heat::PortfolioHeatConfig;
 
 /// Main system configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,90 +30,935 @@ pub struct SystemConfig {
     pub api: ApiConfig,
     pub monitoring: MonitoringConfig,
     pub strategies: Vec<StrategyConfig>,
+    #[serde(default)]
+    pub interfaces: InterfacesConfig,
+    #[serde(default)]
+    pub replay: ReplayConfig,
+    #[serde(default)]
+    pub ab_test: AbTestConfig,
+    #[serde(default)]
+    pub hot_reload: HotReloadConfig,
+    #[serde(default)]
+    pub runtime: RuntimeConfig,
+    #[serde(default)]
+    pub supervision: SupervisionConfig,
+    #[serde(default)]
+    pub heartbeat: HeartbeatConfig,
+}
+
+/// Tokio runtime sizing and agent concurrency for constrained hosts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuntimeConfig {
+    pub worker_threads: usize,       // Runtime worker threads (0 = one per CPU core)
+    pub max_concurrent_agents: usize, // Agents allowed to run; each holds a slot for its lifetime (0 = unlimited)
+}
+
+/// Restarting agents whose task fails or panics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupervisionConfig {
+    pub max_restarts: u32, // Per agent; once exhausted the system is shut down
+    pub initial_backoff_ms: u64, // Delay before the first restart, doubling up to the maximum
+    pub max_backoff_ms: u64,
+    pub stable_after_ms: u64, // An agent running this long before failing starts over with all its restarts
+}
+
+impl Default for SupervisionConfig {
+    fn default() -> Self {
+        Self {
+            max_restarts: 3,
+            initial_backoff_ms: 1000,
+            max_backoff_ms: 30_000,
+            stable_after_ms: 600_000,
+        }
+    }
+}
+
+/// Agent heartbeats and how long one may go quiet before health degrades
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeartbeatConfig {
+    pub interval_ms: u64, // How often each agent's run loop reports in
+    pub timeout_ms: u64,  // Silence after which the agent counts as stalled
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: 5000,
+            timeout_ms: 15_000,
+        }
+    }
+}
+
+/// Watching the config file for edits applied without a restart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotReloadConfig {
+    pub enabled: bool,
+    pub poll_interval_ms: u64, // How often the file's modification time is checked
+}
+
+impl Default for HotReloadConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            poll_interval_ms: 1000,
+        }
+    }
+}
+
+/// Live A/B comparison between two configured strategies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AbTestConfig {
+    pub enabled: bool,
+    pub variant_a: String,  // Strategy name for variant A
+    pub variant_b: String,  // Strategy name for variant B
+    pub split: f64,         // Share of signals/capital given to variant A
+    pub min_trades: u64,    // Trades each variant needs before a winner is declared
+    pub min_z_score: f64,   // Significance threshold on the t statistic (1.96 ~ 95%)
+}
+
+impl Default for AbTestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            variant_a: String::new(),
+            variant_b: String::new(),
+            split: 0.5,
+            min_trades: 30,
+            min_z_score: 1.96,
+        }
+    }
 }
 
 /// Trading-specific configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradingConfig {
     pub initial_capital: Decimal,
-    pub target_daily_return: f64, // 0.25 for 25%
+    pub target_daily_return: f64, // 0.25 for 25% - informational only, never used for sizing
+    #[serde(default = "default_aggressiveness")]
+    pub aggressiveness: f64, // Position sizing multiplier, bounded to AGGRESSIVENESS_RANGE
     pub max_positions: u32,
     pub default_position_size: Decimal,
     pub commission_rate: Decimal,
+    #[serde(default)]
+    pub commission: CommissionConfig,
     pub slippage_tolerance: Decimal,
     pub trading_hours: TradingHours,
+    #[serde(default = "default_max_quote_age_secs")]
+    pub max_quote_age_secs: u64, // Skip symbols without a newer quote (0 disables)
+    #[serde(default)]
+    pub position_import: PositionImportConfig,
+}
+
+/// How commissions are rounded and floored, as on broker statements
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommissionConfig {
+    pub round_to_cents: bool,
+    pub minimum_per_trade: Decimal, // Charged when the rate works out lower
+}
+
+impl Default for CommissionConfig {
+    fn default() -> Self {
+        Self {
+            round_to_cents: true,
+            minimum_per_trade: Decimal::ZERO,
+        }
+    }
+}
+
+/// Startup import of positions already held at the broker
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PositionImportConfig {
+    pub enabled: bool,
+    #[serde(default)]
+    pub file: Option<String>, // JSON position list, used in paper trading instead of the gateway
+}
+
+fn default_max_quote_age_secs() -> u64 {
+    5
+}
+
+fn default_aggressiveness() -> f64 {
+    1.0
+}
+
+/// Allowed range for `TradingConfig::aggressiveness`
+pub const AGGRESSIVENESS_RANGE: (f64, f64) = (0.1, 2.0);
+
+/// Daily returns above this are treated as implausible and warned about at startup
+pub const PLAUSIBLE_DAILY_RETURN: f64 = 0.05;
+
+impl TradingConfig {
+    /// Multiplier applied to position sizes, clamped to the safe range.
+    /// Sizing deliberately ignores `target_daily_return`.
+    pub fn sizing_multiplier(&self) -> f64 {
+        self.aggressiveness.clamp(AGGRESSIVENESS_RANGE.0, AGGRESSIVENESS_RANGE.1)
+    }
+
+    /// Warning for a daily return target too high to be realistic
+    pub fn target_return_warning(&self) -> Option<String> {
+        (self.target_daily_return > PLAUSIBLE_DAILY_RETURN).then(|| {
+            format!(
+                "Target daily return of {:.1}% is implausibly high; it is informational only and does not affect position sizing (aggressiveness {:.2})",
+                self.target_daily_return * 100.0,
+                self.sizing_multiplier()
+            )
+        })
+    }
+}
+
+/// Trading hours configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradingHours {
+    pub market_open: String,  // "09:30:00"
+    pub market_close: String, // "16:00:00"
+    pub timezone: String,     // "America/New_York"
+    pub trading_days: Vec<String>, // ["Monday", "Tuesday", ...]
+    #[serde(default)]
+    pub extended: ExtendedHoursConfig,
+    #[serde(default)]
+    pub clock: SessionClock, // WallClock, DataTimestamp (backtests/replays), or AlwaysOpen
+    #[serde(default)]
+    pub daily_reset: DailyResetBoundary, // SessionOpen, PreMarketOpen, or UtcMidnight
+}
+
+/// Pre-market and after-hours sessions, each opted into separately
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtendedHoursConfig {
+    pub pre_market: SessionWindow,
+    pub after_hours: SessionWindow,
+    pub max_spread_bps: f64,            // Skip quotes with wider spreads outside regular hours
+    pub position_size_multiplier: f64,  // Scale positions down for thinner liquidity
+}
+
+/// A configurable trading session window in exchange-local time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionWindow {
+    pub enabled: bool,
+    pub start: String, // "04:00:00"
+    pub end: String,   // "09:30:00"
+}
+
+impl Default for ExtendedHoursConfig {
+    fn default() -> Self {
+        Self {
+            pre_market: SessionWindow {
+                enabled: false,
+                start: "04:00:00".to_string(),
+                end: "09:30:00".to_string(),
+            },
+            after_hours: SessionWindow {
+                enabled: false,
+                start: "16:00:00".to_string(),
+                end: "20:00:00".to_string(),
+            },
+            max_spread_bps: 50.0,
+            position_size_multiplier: 0.5,
+        }
+    }
+}
+
+/// Risk management configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskConfig {
+    pub max_daily_loss: Decimal,
+    pub max_position_size: Decimal,
+    pub var_confidence_level: f64, // 0.95 for 95%
+    #[serde(default = "default_var_window_days")]
+    pub var_window_days: usize,    // Daily returns kept for historical VaR
+    pub max_portfolio_heat: f64,   // 0.8 for 80%
+    #[serde(default)]
+    pub portfolio_heat: PortfolioHeatConfig,
+    pub circuit_breaker_threshold: Decimal,
+    #[serde(default = "default_circuit_breaker_cooldown_secs")]
+    pub circuit_breaker_cooldown_secs: u64, // Trading halt after a trip, then the same again at reduced size
+    pub emergency_stop_loss: Decimal,
+    pub correlation_limit: f64,
+    #[serde(default)]
+    pub reconciliation: ReconciliationConfig,
+    #[serde(default)]
+    pub max_unrealized_loss: UnrealizedLossConfig,
+    #[serde(default)]
+    pub min_cash_buffer: CashBufferConfig,
+    #[serde(default)]
+    pub audit: RiskAuditConfig,
+    #[serde(default)]
+    pub compliance: ComplianceConfig,
+    #[serde(default)]
+    pub validation_cache: RiskCacheConfig,
+}
+
+/// Reuse of recently computed portfolio risk across trade validations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskCacheConfig {
+    pub enabled: bool,
+    pub ttl_ms: u64, // Metrics are recomputed after this, or on the next fill
+}
+
+impl Default for RiskCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_ms: 500,
+        }
+    }
+}
+
+/// Pre-trade compliance checks that can veto new orders
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceConfig {
+    pub enabled: bool,
+    pub restricted_list_path: String, // One symbol per line; `#` starts a comment
+}
+
+impl Default for ComplianceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            restricted_list_path: "config/restricted_symbols.txt".to_string(),
+        }
+    }
+}
+
+/// JSONL audit trail of every risk decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskAuditConfig {
+    pub enabled: bool,
+    pub path: String,
+}
+
+impl Default for RiskAuditConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: "logs/risk_audit.jsonl".to_string(),
+        }
+    }
+}
+
+/// Free cash that orders may not consume, reserved for fees and slippage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CashBufferConfig {
+    pub min_cash: Decimal,     // Absolute USD floor
+    pub min_cash_pct: f64,     // Fraction of equity; the larger reserve applies
+}
+
+impl Default for CashBufferConfig {
+    fn default() -> Self {
+        Self {
+            min_cash: Decimal::from(1),
+            min_cash_pct: 0.05,
+        }
+    }
+}
+
+/// Per-position unrealized loss caps that force a market exit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnrealizedLossConfig {
+    #[serde(default)]
+    pub max_loss: Option<Decimal>,  // Absolute USD loss per position
+    #[serde(default)]
+    pub max_loss_pct: Option<f64>,  // Loss as a fraction of the position's cost basis
+}
+
+impl Default for UnrealizedLossConfig {
+    fn default() -> Self {
+        Self {
+            max_loss: None,
+            max_loss_pct: Some(0.15),
+        }
+    }
+}
+
+/// Portfolio accounting reconciliation configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationConfig {
+    pub enabled: bool,
+    pub tolerance: Decimal, // Absolute USD tolerance
+}
+
+impl Default for ReconciliationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            tolerance: Decimal::new(1, 2), // $0.01
+        }
+    }
+}
+
+/// Agent system configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentConfig {
+    pub master_coordinator: CoordinatorConfig,
+    pub market_intelligence: IntelligenceConfig,
+    pub risk_management: RiskAgentConfig,
+    pub execution_engine: ExecutionConfig,
+    pub learning_engine: LearningConfig,
+    #[serde(default)]
+    pub evolution_cooldowns: EvolutionCooldownConfig,
+    #[serde(default)]
+    pub startup: StartupConfig,
+}
+
+impl AgentConfig {
+    /// Config sections of the enabled agents, in the default start order
+    pub fn enabled(&self) -> Vec<&'static str> {
+        [
+            ("master_coordinator", self.master_coordinator.enabled),
+            ("market_intelligence", self.market_intelligence.enabled),
+            ("risk_management", self.risk_management.enabled),
+            ("execution_engine", self.execution_engine.enabled),
+            ("learning_engine", self.learning_engine.enabled),
+        ]
+        .into_iter()
+        .filter_map(|(agent, enabled)| enabled.then_some(agent))
+        .collect()
+    }
+}
+
+/// Agents each agent waits for before entering its main loop, and which
+/// of them the system cannot start without
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartupConfig {
+    pub dependencies: HashMap<String, Vec<String>>, // Keyed by agent section, e.g. "execution_engine"
+    #[serde(default = "default_critical_agents")]
+    pub critical: Vec<String>, // Failing to start aborts startup; other agents are skipped
+    #[serde(default = "default_ready_timeout_secs")]
+    pub ready_timeout_secs: u64, // How long an agent waits for its prerequisites to report ready
+}
+
+fn default_ready_timeout_secs() -> u64 {
+    120
+}
+
+fn default_critical_agents() -> Vec<String> {
+    vec!["risk_management".to_string(), "execution_engine".to_string()]
+}
+
+impl Default for StartupConfig {
+    fn default() -> Self {
+        let dependencies = [
+            ("execution_engine", vec!["market_intelligence", "risk_management"]),
+            ("master_coordinator", vec!["risk_management", "learning_engine"]),
+        ];
+        Self {
+            dependencies: dependencies
+                .into_iter()
+                .map(|(agent, needs)| (agent.to_string(), needs.into_iter().map(String::from).collect()))
+                .collect(),
+            critical: default_critical_agents(),
+            ready_timeout_secs: default_ready_timeout_secs(),
+        }
+    }
+}
+
+/// Minimum time between applied evolution updates, per agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvolutionCooldownConfig {
+    pub default_secs: u64, // 0 applies every evolution
+    #[serde(default)]
+    pub per_agent: HashMap<String, u64>, // Overrides keyed by agent section, e.g. "risk_management"
+}
+
+impl EvolutionCooldownConfig {
+    /// Cooldown for the agent configured under `agent`
+    pub fn cooldown_secs(&self, agent: &str) -> u64 {
+        self.per_agent.get(agent).copied().unwrap_or(self.default_secs)
+    }
+}
+
+impl Default for EvolutionCooldownConfig {
+    fn default() -> Self {
+        Self {
+            default_secs: 600,
+            per_agent: HashMap::new(),
+        }
+    }
+}
+
+/// Master coordinator agent configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoordinatorConfig {
+    pub enabled: bool,
+    pub decision_timeout_ms: u64,
+    pub consensus_threshold: f64,
+    pub capabilities: Vec<AgentCapability>,
+    pub strategic_planning_interval_hours: u64,
+    #[serde(default = "default_feedback_interval_secs")]
+    pub feedback_interval_secs: u64, // How often aggregated system feedback is sent to agents
+    #[serde(default)]
+    pub reliability: ReliabilityConfig,
+    #[serde(default = "default_voters")]
+    pub voters: Vec<String>, // Agents asked to vote on proposed signals before execution
+}
+
+fn default_feedback_interval_secs() -> u64 {
+    300
+}
+
+fn default_voters() -> Vec<String> {
+    vec!["risk_management".to_string(), "learning_engine".to_string()]
+}
+
+/// Per-source reliability weighting of confidences in consensus
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReliabilityConfig {
+    pub enabled: bool,
+    pub learning_rate: f64, // Weight given to each realized outcome when updating accuracy
+    pub min_weight: f64,    // Floor so an unreliable source is discounted, never silenced
+}
+
+impl Default for ReliabilityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            learning_rate: 0.1,
+            min_weight: 0.1,
+        }
+    }
+}
+
+/// Market intelligence agent configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntelligenceConfig {
+    pub enabled: bool,
+    pub data_sources: Vec<DataSourceConfig>, // In failover priority order
+    pub update_interval_ms: u64,
+    pub technical_indicators: Vec<String>,
+    pub sentiment_analysis: bool,
+    pub pattern_recognition: bool,
+    #[serde(default = "default_coalesce_quotes")]
+    pub coalesce_quotes: bool, // Keep only the latest quote per symbol per analysis interval
+    #[serde(default)]
+    pub adaptive_interval: AdaptiveIntervalConfig,
+    #[serde(default)]
+    pub compute_budget: ComputeBudgetConfig,
+    #[serde(default = "default_watchlist")]
+    pub watchlist: Vec<String>, // Symbols to quote and analyze
+    #[serde(default = "default_signal_cooldown_secs")]
+    pub signal_cooldown_secs: u64, // Suppress repeat signals for a symbol unless direction flips (0 disables)
+    #[serde(default)]
+    pub live_feed: LiveFeedConfig,
+}
+
+/// Market data provider quotes may be taken from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataSourceConfig {
+    pub provider: String,
+    pub feed: String,
+    pub max_age_secs: u64, // Source is stale after this long without a quote (0 = never)
+}
+
+impl DataSourceConfig {
+    /// Tag carried by quotes from this source
+    pub fn source(&self) -> DataSource {
+        DataSource::new(&self.provider, &self.feed)
+    }
+}
+
+/// Per-cycle analysis budget, deferring lower-priority symbols when exceeded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComputeBudgetConfig {
+    pub enabled: bool,
+    pub max_cycle_ms: u64,                  // Analysis time allowed per cycle
+    pub initial_cost_per_symbol_us: u64,    // Starting estimate, refined from observed timings
+}
+
+impl Default for ComputeBudgetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_cycle_ms: 50,
+            initial_cost_per_symbol_us: 500,
+        }
+    }
+}
+
+/// Volatility-driven scaling of the analysis interval
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdaptiveIntervalConfig {
+    pub enabled: bool,
+    pub reference_volatility: f64, // Volatility at which update_interval_ms applies unchanged
+    pub min_interval_ms: u64,
+    pub max_interval_ms: u64,
+}
+
+impl Default for AdaptiveIntervalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            reference_volatility: 0.3,
+            min_interval_ms: 20,
+            max_interval_ms: 1000,
+        }
+    }
+}
+
+fn default_coalesce_quotes() -> bool {
+    true
+}
+
+fn default_signal_cooldown_secs() -> u64 {
+    60
+}
+
+fn default_watchlist() -> Vec<String> {
+    ["AAPL", "TSLA", "MSFT", "GOOGL"].iter().map(|symbol| symbol.to_string()).collect()
+}
+
+/// Streaming quotes over a WebSocket subscription instead of polling
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiveFeedConfig {
+    pub enabled: bool,
+    pub provider: Option<String>, // Name in api.data_providers relaying JSON quotes; required when enabled
+    pub initial_backoff_ms: u64, // First reconnect delay, grown per api.rate_limits.backoff_strategy
+    pub max_backoff_ms: u64,
+}
+
+impl Default for LiveFeedConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: None,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 30_000,
+        }
+    }
+}
+
+/// Risk management agent configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskAgentConfig {
+    pub enabled: bool,
+    pub monitoring_interval_ms: u64,
+    pub stress_testing: bool,
+    pub monte_carlo_simulations: u32,
+    pub dynamic_hedging: bool,
+}
+
+/// Execution engine configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionConfig {
+    pub enabled: bool,
+    pub max_latency_ms: u64,
+    pub order_routing_optimization: bool,
+    pub slippage_optimization: bool,
+    pub execution_algorithms: Vec<String>,
+    #[serde(default)]
+    pub tick_sizes: TickSizeConfig,
+    #[serde(default = "default_max_context_age_secs")]
+    pub max_context_age_secs: u64, // Refuse to trade on older context snapshots (0 disables)
+    #[serde(default = "default_deterministic_signal_order")]
+    pub deterministic_signal_order: bool, // Sort signal batches before execution
+    #[serde(default = "default_fill_timeout_ms")]
+    pub fill_timeout_ms: u64, // Late fills are held for reconciliation after this
+    #[serde(default = "default_resync_on_reconnect")]
+    pub resync_on_reconnect: bool, // Query broker order states after a gateway reconnect
+    #[serde(default = "default_simulated_fill_slices")]
+    pub simulated_fill_slices: u32, // Partial fills each simulated order is split into
+    #[serde(default = "default_partial_fill_resubmits")]
+    pub partial_fill_resubmits: u32, // Times a partial fill's remainder is re-sent before it is cancelled
+    #[serde(default = "default_max_retries_per_order")]
+    pub max_retries_per_order: u32, // Retries shared by the gateway, re-submission and reconciliation layers
+    #[serde(default)]
+    pub canary: CanaryConfig,
+    #[serde(default)]
+    pub slippage_monitor: SlippageMonitorConfig,
+    #[serde(default)]
+    pub slippage_attribution: SlippageAttributionConfig,
+    #[serde(default)]
+    pub protective_levels: ProtectiveLevelsConfig,
+    #[serde(default)]
+    pub venue_routing: VenueRoutingConfig,
+    #[serde(default)]
+    pub algorithm_selection: AlgorithmSelectionConfig,
+    #[serde(default)]
+    pub position_sizing: PositionSizingConfig,
+    #[serde(default)]
+    pub order_conflict_policy: OrderConflictPolicy, // Handling of new orders opposing an open order on the same symbol
+    #[serde(default)]
+    pub journal: TradeJournalConfig,
+    #[serde(default)]
+    pub min_order_quantity: Decimal, // Orders must be positive and at least this large to be sent
+    #[serde(default)]
+    pub order_minimum: OrderMinimumConfig,
+    #[serde(default)]
+    pub multi_leg: MultiLegConfig,
+}
+
+/// Orders spanning several instruments, such as option spreads
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiLegConfig {
+    pub all_or_none: bool, // Fill every leg or none; otherwise fillable legs go through alone
+}
+
+impl Default for MultiLegConfig {
+    fn default() -> Self {
+        Self { all_or_none: true }
+    }
+}
+
+/// Smallest order worth sending, so a tiny account doesn't request untradable amounts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderMinimumConfig {
+    pub min_notional: Decimal,    // USD; smaller orders fall under the policy
+    pub policy: SubMinimumPolicy, // "Skip" drops them; "Accumulate" holds them until the minimum is reached
+}
+
+impl Default for OrderMinimumConfig {
+    fn default() -> Self {
+        Self {
+            min_notional: Decimal::ONE,
+            policy: SubMinimumPolicy::Skip,
+        }
+    }
+}
+
+/// JSONL journal of executed and rejected orders
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeJournalConfig {
+    pub enabled: bool,
+    pub path: String,
+    pub record_rejections: bool, // Write rejected orders with their structured reason
+}
+
+impl Default for TradeJournalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: "logs/trade_journal.jsonl".to_string(),
+            record_rejections: true,
+        }
+    }
+}
+
+/// Adaptive venue preference from per-venue fill quality
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VenueRoutingConfig {
+    pub venue: String,                // Venue orders are sent to and recorded against
+    pub min_orders: u64,              // Orders seen before a venue's statistics are trusted
+    pub slippage_weight: f64,         // Score per basis point of average slippage
+    pub latency_weight_per_ms: f64,   // Score per millisecond of average fill latency
+    pub fill_rate_weight: f64,        // Score for a venue that never fills
+}
+
+impl Default for VenueRoutingConfig {
+    fn default() -> Self {
+        Self {
+            venue: "moomoo".to_string(),
+            min_orders: 10,
+            slippage_weight: 1.0,
+            latency_weight_per_ms: 0.1,
+            fill_rate_weight: 100.0,
+        }
+    }
+}
+
+/// Order size from signal strength, scaled by the signal's confidence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionSizingConfig {
+    pub base_size: Decimal, // Size of a full-strength signal at the reference confidence
+    pub confidence_weight: f64, // 0 ignores confidence, 1 scales size in proportion to it
+    pub reference_confidence: f64, // Confidence that leaves the size unscaled
+    pub max_confidence_multiplier: f64, // Cap on the confidence scaling
+}
+
+impl Default for PositionSizingConfig {
+    fn default() -> Self {
+        Self {
+            base_size: Decimal::from(10),
+            confidence_weight: 0.5,
+            reference_confidence: 0.7,
+            max_confidence_multiplier: 1.5,
+        }
+    }
+}
+
+/// Rules choosing an execution algorithm per order. The first rule whose
+/// conditions all hold wins; unset conditions match anything.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlgorithmSelectionConfig {
+    pub default_algorithm: String, // Used when no rule matches
+    pub rules: Vec<AlgorithmRule>,
+    #[serde(default = "default_twap_horizon_ms")]
+    pub twap_horizon_ms: u64, // Span a TWAP order's slices are spread across
+}
+
+/// One algorithm selection rule
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AlgorithmRule {
+    pub algorithm: String,
+    #[serde(default)]
+    pub urgent: Option<bool>,          // Stop, trailing-stop, end-of-day and emergency exits
+    #[serde(default)]
+    pub high_volatility: Option<bool>, // Market regime is HighVolatility
+    #[serde(default)]
+    pub above_quantity: Option<Decimal>, // Only orders larger than this
+    #[serde(default)]
+    pub max_quantity: Option<Decimal>,  // Only orders up to this size
+    #[serde(default)]
+    pub max_spread_bps: Option<f64>,   // Only with a quote at most this wide
+}
+
+impl Default for AlgorithmSelectionConfig {
+    fn default() -> Self {
+        Self {
+            default_algorithm: "MARKET".to_string(),
+            rules: vec![
+                AlgorithmRule { algorithm: "MARKET".to_string(), urgent: Some(true), ..AlgorithmRule::default() },
+                AlgorithmRule { algorithm: "MARKET".to_string(), high_volatility: Some(true), ..AlgorithmRule::default() },
+                AlgorithmRule {
+                    algorithm: "TWAP".to_string(),
+                    above_quantity: Some(Decimal::from(100)),
+                    ..AlgorithmRule::default()
+                },
+            ],
+            twap_horizon_ms: default_twap_horizon_ms(),
+        }
+    }
+}
+
+/// Persistence of per-position stops and take-profit plans across restarts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtectiveLevelsConfig {
+    pub enabled: bool,
+    pub path: String, // JSON snapshot re-armed at startup
+}
+
+impl Default for ProtectiveLevelsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: "state/protective_levels.json".to_string(),
+        }
+    }
+}
+
+/// Alerting on fills persistently worse than the modeled slippage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlippageMonitorConfig {
+    pub enabled: bool,
+    pub window: usize,              // Rolling number of fills averaged
+    pub alert_threshold_bps: f64,   // Average realized-minus-modeled slippage that raises an alert
+}
+
+impl Default for SlippageMonitorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window: 20,
+            alert_threshold_bps: 5.0,
+        }
+    }
+}
+
+/// Splitting realized slippage into spread, impact and timing components
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlippageAttributionConfig {
+    pub enabled: bool,
+    pub impact_bps_per_unit: f64, // Modeled market impact per unit of order quantity
+}
+
+impl Default for SlippageAttributionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            impact_bps_per_unit: 0.1,
+        }
+    }
+}
+
+/// Scale-out plan: close part of a position at each profit level, then trail the rest
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TakeProfitConfig {
+    pub levels: Vec<TakeProfitLevel>,
+    #[serde(default)]
+    pub trail_pct: Option<f64>, // Trailing stop for the remainder once all levels fill
+    #[serde(default)]
+    pub stop_loss_pct: Option<f64>, // Protective stop on the remaining quantity, 0.05 for -5% from entry
+}
+
+/// One take-profit tranche
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TakeProfitLevel {
+    pub gain_pct: f64,  // 0.02 for +2% from entry
+    pub fraction: f64,  // Share of the original quantity to close
+}
+
+/// Live canary sub-account mirroring the paper book at reduced size
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanaryConfig {
+    pub enabled: bool,
+    pub size_fraction: f64,           // Canary order size relative to the paper order
+    pub max_slippage_bps: f64,        // Live fills further than this from paper count as breaches
+    pub min_clean_fills: u64,         // Clean fills required before promotion to full live
+}
+
+impl Default for CanaryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            size_fraction: 0.01,
+            max_slippage_bps: 25.0,
+            min_clean_fills: 50,
+        }
+    }
+}
+
+fn default_max_context_age_secs() -> u64 {
+    30
+}
+
+fn default_deterministic_signal_order() -> bool {
+    true
+}
+
+fn default_fill_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_twap_horizon_ms() -> u64 {
+    60_000
+}
+
+fn default_resync_on_reconnect() -> bool {
+    true
 }
 
-/// Trading hours configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TradingHours {
-    pub market_open: String,  // "09:30:00"
-    pub market_close: String, // "16:00:00"
-    pub timezone: String,     // "America/New_York"
-    pub trading_days: Vec<String>, // ["Monday", "Tuesday", ...]
+fn default_circuit_breaker_cooldown_secs() -> u64 {
+    900
 }
 
-/// Risk management configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RiskConfig {
-    pub max_daily_loss: Decimal,
-    pub max_position_size: Decimal,
-    pub var_confidence_level: f64, // 0.95 for 95%
-    pub max_portfolio_heat: f64,   // 0.8 for 80%
-    pub circuit_breaker_threshold: Decimal,
-    pub emergency_stop_loss: Decimal,
-    pub correlation_limit: f64,
+fn default_var_window_days() -> usize {
+    250
 }
 
-/// Agent system configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AgentConfig {
-    pub master_coordinator: CoordinatorConfig,
-    pub market_intelligence: IntelligenceConfig,
-    pub risk_management: RiskAgentConfig,
-    pub execution_engine: ExecutionConfig,
-    pub learning_engine: LearningConfig,
+fn default_max_retries_per_order() -> u32 {
+    3
 }
 
-/// Master coordinator agent configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CoordinatorConfig {
-    pub enabled: bool,
-    pub decision_timeout_ms: u64,
-    pub consensus_threshold: f64,
-    pub capabilities: Vec<AgentCapability>,
-    pub strategic_planning_interval_hours: u64,
+fn default_partial_fill_resubmits() -> u32 {
+    2
 }
 
-/// Market intelligence agent configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct IntelligenceConfig {
-    pub enabled: bool,
-    pub data_sources: Vec<String>,
-    pub update_interval_ms: u64,
-    pub technical_indicators: Vec<String>,
-    pub sentiment_analysis: bool,
-    pub pattern_recognition: bool,
+fn default_simulated_fill_slices() -> u32 {
+    1
 }
 
-/// Risk management agent configuration
+/// Price tick size configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RiskAgentConfig {
-    pub enabled: bool,
-    pub monitoring_interval_ms: u64,
-    pub stress_testing: bool,
-    pub monte_carlo_simulations: u32,
-    pub dynamic_hedging: bool,
+pub struct TickSizeConfig {
+    pub default_tick: Decimal,
+    #[serde(default)]
+    pub symbols: HashMap<String, Decimal>, // Per-symbol overrides
 }
 
-/// Execution engine configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ExecutionConfig {
-    pub enabled: bool,
-    pub max_latency_ms: u64,
-    pub order_routing_optimization: bool,
-    pub slippage_optimization: bool,
-    pub execution_algorithms: Vec<String>,
+impl Default for TickSizeConfig {
+    fn default() -> Self {
+        Self {
+            default_tick: Decimal::new(1, 2), // $0.01
+            symbols: HashMap::new(),
+        }
+    }
 }
 
 /// Learning engine configuration
@@ -113,6 +969,51 @@ pub struct LearningConfig {
     pub online_learning: bool,
     pub ensemble_models: bool,
     pub strategy_generation: bool,
+    #[serde(default = "default_max_active_strategies")]
+    pub max_active_strategies: usize, // Worst performers are retired beyond this
+    #[serde(default = "default_max_new_strategies_per_day")]
+    pub max_new_strategies_per_day: usize, // Further generated strategies are dropped until the next session
+    #[serde(default = "default_new_strategy_state")]
+    pub new_strategy_state: StrategyState, // State newly generated strategies are moved into
+    #[serde(default = "default_parameter_bounds")]
+    pub parameter_bounds: HashMap<String, ParameterBounds>, // Evolved strategy parameters are clamped to these
+    #[serde(default)]
+    pub outcome_labeling: OutcomeLabelingConfig,
+}
+
+/// Rules labeling trade outcomes as successes for training
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OutcomeLabelingConfig {
+    pub min_net_return: f64, // Return net of slippage and costs must exceed this
+    #[serde(default)]
+    pub max_holding_secs: Option<u64>, // Trades held longer are labeled failures
+}
+
+/// Inclusive range an evolved strategy parameter is clamped to
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ParameterBounds {
+    pub min: f64,
+    pub max: f64,
+}
+
+fn default_max_active_strategies() -> usize {
+    20
+}
+
+fn default_new_strategy_state() -> StrategyState {
+    StrategyState::Shadow
+}
+
+fn default_max_new_strategies_per_day() -> usize {
+    10
+}
+
+fn default_parameter_bounds() -> HashMap<String, ParameterBounds> {
+    HashMap::from([
+        ("position_size_multiplier".to_string(), ParameterBounds { min: 0.1, max: 3.0 }),
+        ("stop_loss_threshold".to_string(), ParameterBounds { min: 0.005, max: 0.2 }),
+        ("hold_time_seconds".to_string(), ParameterBounds { min: 1.0, max: 86_400.0 }),
+    ])
 }
 
 /// API configuration for external services
@@ -121,6 +1022,28 @@ pub struct ApiConfig {
     pub moomoo: MoomooConfig,
     pub data_providers: Vec<DataProviderConfig>,
     pub rate_limits: RateLimitConfig,
+    #[serde(default)]
+    pub http: HttpClientConfig,
+}
+
+/// Pool and timeouts of the HTTP client shared by outbound integrations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpClientConfig {
+    pub timeout_ms: u64,              // Whole request, connect to last body byte
+    pub connect_timeout_ms: u64,
+    pub pool_max_idle_per_host: usize, // Idle keep-alive connections kept per host
+    pub pool_idle_timeout_secs: u64,   // Idle connections are closed after this
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout_ms: 10_000,
+            connect_timeout_ms: 3_000,
+            pool_max_idle_per_host: 8,
+            pool_idle_timeout_secs: 90,
+        }
+    }
 }
 
 /// Moomoo API configuration
@@ -132,6 +1055,8 @@ pub struct MoomooConfig {
     pub paper_trading: bool,
     pub timeout_ms: u64,
     pub retry_attempts: u32,
+    #[serde(default)]
+    pub use_gateway: bool, // Send orders to the OpenD gateway at base_url instead of the simulator
 }
 
 /// Data provider configuration
@@ -157,9 +1082,169 @@ pub struct RateLimitConfig {
 pub struct MonitoringConfig {
     pub metrics_enabled: bool,
     pub prometheus_port: u16,
+    #[serde(default = "default_metrics_bind_address")]
+    pub metrics_bind_address: IpAddr, // Interface the exporter listens on; 0.0.0.0 exposes it to the network
     pub log_level: String,
     pub performance_tracking: bool,
     pub alerts: AlertConfig,
+    #[serde(default)]
+    pub equity_curve: EquityCurveConfig,
+    #[serde(default)]
+    pub performance_window: PerformanceWindowConfig,
+    #[serde(default)]
+    pub daily_report: ReportConfig,
+    #[serde(default)]
+    pub lock_contention: LockContentionConfig,
+    #[serde(default = "default_max_thought_data_bytes")]
+    pub max_thought_data_bytes: usize, // Serialized size cap on each AI thought's supporting data
+    #[serde(default = "default_max_decode_failures")]
+    pub max_decode_failures: u64, // Consecutive undecodable payloads before health is Degraded
+    #[serde(default = "default_max_config_changes")]
+    pub max_config_changes: usize, // Evolved parameter changes kept in the audit log
+    #[serde(default = "default_thought_export_chunk_size")]
+    pub thought_export_chunk_size: usize, // Thoughts copied per history lock acquisition when exporting
+    #[serde(default = "default_coalesce_duplicate_thoughts")]
+    pub coalesce_duplicate_thoughts: bool, // Fold consecutive identical thoughts into one with a repeat count
+    #[serde(default)]
+    pub thought_log: ThoughtLogConfig,
+    #[serde(default = "default_per_strategy_metrics")]
+    pub per_strategy_metrics: bool, // Export win rate, P&L and trade count labeled by strategy
+    #[serde(default)]
+    pub decision_trace: DecisionTraceConfig,
+}
+
+/// Per-signal verdicts of each decision pipeline stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionTraceConfig {
+    pub enabled: bool,
+    pub max_traces: usize, // Signals whose traces are kept; the oldest are dropped first
+}
+
+impl Default for DecisionTraceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_traces: 1000,
+        }
+    }
+}
+
+fn default_per_strategy_metrics() -> bool {
+    true
+}
+
+/// On-disk AI thought history, kept across restarts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThoughtLogConfig {
+    pub enabled: bool,
+    pub path: String,               // Newline-delimited JSON log; rotated files get a timestamp suffix
+    pub max_file_bytes: u64,        // Rotate before the log grows past this
+    pub rotate_daily: bool,         // Also rotate at the first write of each trading day
+    pub replay_on_startup: usize,   // Most recent thoughts reloaded into memory at startup
+}
+
+impl Default for ThoughtLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: "logs/ai_thoughts.jsonl".to_string(),
+            max_file_bytes: 50 * 1024 * 1024,
+            rotate_daily: true,
+            replay_on_startup: 1000,
+        }
+    }
+}
+
+fn default_max_thought_data_bytes() -> usize {
+    16 * 1024
+}
+
+fn default_metrics_bind_address() -> IpAddr {
+    IpAddr::V4(Ipv4Addr::LOCALHOST)
+}
+
+fn default_max_decode_failures() -> u64 {
+    5
+}
+
+fn default_max_config_changes() -> usize {
+    1000
+}
+
+fn default_thought_export_chunk_size() -> usize {
+    256
+}
+
+fn default_coalesce_duplicate_thoughts() -> bool {
+    true
+}
+
+/// Shared-state lock contention diagnostics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockContentionConfig {
+    pub warn_threshold_ms: u64,       // Log waits or holds longer than this
+    #[serde(default)]
+    pub max_wait_ms: Option<u64>,     // Optionally give up on acquisitions slower than this
+}
+
+impl Default for LockContentionConfig {
+    fn default() -> Self {
+        Self {
+            warn_threshold_ms: 50,
+            max_wait_ms: None,
+        }
+    }
+}
+
+/// End-of-session daily report configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportConfig {
+    pub enabled: bool,
+    pub top_thoughts: usize,          // Highest-confidence thoughts included
+    pub deliver_via_alerts: bool,     // Also send through the alert channels
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            top_thoughts: 5,
+            deliver_via_alerts: false,
+        }
+    }
+}
+
+/// Rolling window over which performance metrics are computed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerformanceWindowConfig {
+    pub max_trades: usize,            // Only the last N closed trades count
+    #[serde(default)]
+    pub max_age_secs: Option<u64>,    // Optionally also drop trades older than this
+}
+
+impl Default for PerformanceWindowConfig {
+    fn default() -> Self {
+        Self {
+            max_trades: 100,
+            max_age_secs: None,
+        }
+    }
+}
+
+/// Equity curve sampling configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquityCurveConfig {
+    pub granularity_secs: u64,
+    pub max_points: usize,
+}
+
+impl Default for EquityCurveConfig {
+    fn default() -> Self {
+        Self {
+            granularity_secs: 60,
+            max_points: 10_000,
+        }
+    }
 }
 
 /// Alert configuration
@@ -172,6 +1257,60 @@ pub struct AlertConfig {
     pub performance_degradation_threshold: f64,
 }
 
+/// Historical data replay configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplayConfig {
+    #[serde(default)]
+    pub speed: ReplaySpeed, // Instant, RealTime, or { Multiplier = N }
+}
+
+/// External interface configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InterfacesConfig {
+    #[serde(default)]
+    pub http: HttpApiConfig,
+    #[serde(default)]
+    pub thought_stream: ThoughtStreamConfig,
+}
+
+/// WebSocket stream of AI thoughts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThoughtStreamConfig {
+    pub enabled: bool,
+    pub bind_address: String,
+    pub port: u16,
+    pub history_on_connect: usize, // Recent matching thoughts replayed to each new client
+}
+
+impl Default for ThoughtStreamConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: "127.0.0.1".to_string(),
+            port: 8081,
+            history_on_connect: 50,
+        }
+    }
+}
+
+/// HTTP API configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpApiConfig {
+    pub enabled: bool,
+    pub bind_address: String,
+    pub port: u16,
+}
+
+impl Default for HttpApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: "127.0.0.1".to_string(),
+            port: 8080,
+        }
+    }
+}
+
 impl SystemConfig {
     /// Load configuration from environment variables and config file
     pub async fn load() -> Result<Self> {
@@ -207,6 +1346,111 @@ impl SystemConfig {
         }
     }
 
+    /// Load the config file and keep watching it for edits.
+    ///
+    /// The file's modification time is polled every `hot_reload.poll_interval_ms`
+    /// (taken from the initial load). Edits that fail to parse or validate are
+    /// logged and skipped, so receivers only ever see valid configurations.
+    /// The watcher stops once every receiver is dropped.
+    pub async fn watch<P: AsRef<Path>>(path: P) -> Result<watch::Receiver<SystemConfig>> {
+        let path = path.as_ref().to_path_buf();
+        let mut config = Self::load_from_file(&path).await?;
+        config.apply_env_overrides();
+        config.validate()?;
+
+        let poll_interval = Duration::from_millis(config.hot_reload.poll_interval_ms.max(1));
+        let mut modified = fs::metadata(&path).await?.modified().ok();
+        let (sender, receiver) = watch::channel(config);
+
+        tokio::spawn(async move {
+            let mut ticker = interval(poll_interval);
+            while !sender.is_closed() {
+                ticker.tick().await;
+
+                let current = match fs::metadata(&path).await.and_then(|m| m.modified()) {
+                    Ok(time) => Some(time),
+                    Err(e) => {
+                        warn!("⚠️  Cannot stat {}: {}", path.display(), e);
+                        continue;
+                    }
+                };
+                if current == modified {
+                    continue;
+                }
+                modified = current;
+
+                let reloaded = Self::load_from_file(&path).await.and_then(|mut config| {
+                    config.apply_env_overrides();
+                    config.validate()?;
+                    Ok(config)
+                });
+                match reloaded {
+                    Ok(config) => {
+                        info!("🔄 Read edited configuration from {}", path.display());
+                        sender.send_replace(config);
+                    }
+                    Err(e) => warn!("⚠️  Ignoring invalid edit to {}: {}", path.display(), e),
+                }
+            }
+        });
+
+        Ok(receiver)
+    }
+
+    /// Human-readable summary of what this configuration would run
+    pub fn summary(&self) -> String {
+        let moomoo = &self.api.moomoo;
+        let mode = if moomoo.paper_trading { "PAPER" } else { "LIVE (real money)" };
+        let hours = &self.trading.trading_hours;
+        let agents = &self.agents;
+
+        let mut lines = vec![
+            format!("Trading mode:     {}", mode),
+            format!("Broker gateway:   {}", moomoo.base_url),
+            format!("Initial capital:  ${}", self.trading.initial_capital),
+            format!("Max positions:    {}", self.trading.max_positions),
+            format!(
+                "Trading hours:    {} - {} {} ({})",
+                hours.market_open,
+                hours.market_close,
+                hours.timezone,
+                hours.trading_days.join(", ")
+            ),
+            "Risk limits:".to_string(),
+            format!("  max daily loss:        ${}", self.risk.max_daily_loss),
+            format!("  max position size:     ${}", self.risk.max_position_size),
+            format!("  max portfolio heat:    {:.0}%", self.risk.max_portfolio_heat * 100.0),
+            format!("  circuit breaker:       {}", self.risk.circuit_breaker_threshold),
+            format!("  emergency stop loss:   {}", self.risk.emergency_stop_loss),
+            "Agents:".to_string(),
+        ];
+
+        for (name, enabled) in [
+            ("master_coordinator", agents.master_coordinator.enabled),
+            ("market_intelligence", agents.market_intelligence.enabled),
+            ("risk_management", agents.risk_management.enabled),
+            ("execution_engine", agents.execution_engine.enabled),
+            ("learning_engine", agents.learning_engine.enabled),
+        ] {
+            lines.push(format!("  {:<22} {}", name, if enabled { "enabled" } else { "disabled" }));
+        }
+
+        lines.push("Data providers:".to_string());
+        if self.api.data_providers.is_empty() {
+            lines.push("  (none)".to_string());
+        }
+        for provider in &self.api.data_providers {
+            lines.push(format!(
+                "  {:<22} {} (priority {})",
+                provider.name,
+                if provider.enabled { "enabled" } else { "disabled" },
+                provider.priority
+            ));
+        }
+
+        lines.join("\n")
+    }
+
     /// Create default configuration with environment variable overrides
     pub fn default_with_env() -> Self {
         let mut config = Self::default();
@@ -273,6 +1517,21 @@ impl SystemConfig {
             anyhow::bail!("Target daily return must be between 0 and 1");
         }
 
+        let (min_aggressiveness, max_aggressiveness) = AGGRESSIVENESS_RANGE;
+        if !(min_aggressiveness..=max_aggressiveness).contains(&self.trading.aggressiveness) {
+            anyhow::bail!(
+                "Aggressiveness must be between {} and {}",
+                min_aggressiveness, max_aggressiveness
+            );
+        }
+
+        crate::core::calendar::MarketCalendar::from_config(&self.trading.trading_hours)
+            .map_err(|e| anyhow::anyhow!("Invalid trading hours: {}", e))?;
+
+        if self.trading.commission_rate < Decimal::ZERO || self.trading.commission.minimum_per_trade < Decimal::ZERO {
+            anyhow::bail!("Commission rate and minimum cannot be negative");
+        }
+
         // Validate risk configuration
         if self.risk.max_daily_loss >= self.trading.initial_capital {
             anyhow::bail!("Max daily loss cannot exceed initial capital");
@@ -282,6 +1541,148 @@ impl SystemConfig {
             anyhow::bail!("VaR confidence level must be between 0 and 1");
         }
 
+        // The canary validates live trading before promotion, so the main book must stay on paper
+        let canary = &self.agents.execution_engine.canary;
+        if canary.enabled {
+            if !self.api.moomoo.paper_trading {
+                anyhow::bail!("Canary mode requires the main book to use paper trading");
+            }
+            if canary.size_fraction <= 0.0 || canary.size_fraction >= 1.0 {
+                anyhow::bail!("Canary size fraction must be between 0 and 1");
+            }
+        }
+
+        let sizing = &self.agents.execution_engine.position_sizing;
+        if !(0.0..=1.0).contains(&sizing.confidence_weight) {
+            anyhow::bail!("Position sizing confidence weight must be between 0 and 1");
+        }
+        if sizing.reference_confidence <= 0.0 || sizing.max_confidence_multiplier < 1.0 {
+            anyhow::bail!("Position sizing needs a positive reference confidence and a multiplier cap of at least 1");
+        }
+
+        let routing = &self.agents.execution_engine.venue_routing;
+        if routing.venue.trim().is_empty() {
+            anyhow::bail!("Execution venue name cannot be empty");
+        }
+        if routing.slippage_weight < 0.0 || routing.latency_weight_per_ms < 0.0 || routing.fill_rate_weight < 0.0 {
+            anyhow::bail!("Venue routing weights cannot be negative");
+        }
+
+        let ab_test = &self.ab_test;
+        if ab_test.enabled {
+            for variant in [&ab_test.variant_a, &ab_test.variant_b] {
+                if !self.strategies.iter().any(|strategy| &strategy.name == variant) {
+                    anyhow::bail!("A/B test variant '{}' is not a configured strategy", variant);
+                }
+            }
+            if ab_test.variant_a == ab_test.variant_b {
+                anyhow::bail!("A/B test variants must be different strategies");
+            }
+            if ab_test.split <= 0.0 || ab_test.split >= 1.0 {
+                anyhow::bail!("A/B test split must be between 0 and 1");
+            }
+        }
+
+        if self.agents.learning_engine.new_strategy_state == StrategyState::Retired {
+            anyhow::bail!("New strategies cannot start out retired");
+        }
+        for (key, bounds) in &self.agents.learning_engine.parameter_bounds {
+            if !bounds.min.is_finite() || !bounds.max.is_finite() || bounds.min > bounds.max {
+                anyhow::bail!("Parameter bounds for '{}' need finite min <= max, got {}..{}", key, bounds.min, bounds.max);
+            }
+        }
+
+        let reliability = &self.agents.master_coordinator.reliability;
+        if reliability.enabled {
+            if reliability.learning_rate <= 0.0 || reliability.learning_rate > 1.0 {
+                anyhow::bail!("Reliability learning rate must be in (0, 1]");
+            }
+            if reliability.min_weight <= 0.0 || reliability.min_weight > 1.0 {
+                anyhow::bail!("Reliability minimum weight must be in (0, 1]");
+            }
+        }
+
+        let selection = &self.agents.execution_engine.algorithm_selection;
+        let algorithms = std::iter::once(&selection.default_algorithm).chain(selection.rules.iter().map(|rule| &rule.algorithm));
+        for algorithm in algorithms {
+            if !ALGORITHMS.contains(&algorithm.as_str()) {
+                anyhow::bail!("Unknown execution algorithm '{}', expected one of {}", algorithm, ALGORITHMS.join(", "));
+            }
+        }
+
+        if self.agents.execution_engine.order_minimum.min_notional < Decimal::ZERO {
+            anyhow::bail!("Minimum order notional cannot be negative");
+        }
+
+        if let ReplaySpeed::Multiplier(factor) = self.replay.speed {
+            if !factor.is_finite() || factor <= 0.0 {
+                anyhow::bail!("Replay speed multiplier must be a positive number, got {}", factor);
+            }
+        }
+
+        if self.heartbeat.interval_ms == 0 || self.heartbeat.timeout_ms <= self.heartbeat.interval_ms {
+            anyhow::bail!("Heartbeat timeout must exceed a non-zero heartbeat interval");
+        }
+
+        // Agents hold their slot until they exit, so any over the cap would never start
+        let enabled = self.agents.enabled().len();
+        if self.runtime.max_concurrent_agents > 0 && self.runtime.max_concurrent_agents < enabled {
+            anyhow::bail!(
+                "runtime.max_concurrent_agents ({}) is below the {} enabled agents, which run for the system's lifetime",
+                self.runtime.max_concurrent_agents,
+                enabled
+            );
+        }
+
+        startup_order(&self.agents.startup.dependencies, AGENT_NAMES)?;
+        if self.agents.startup.ready_timeout_secs == 0 {
+            anyhow::bail!("Startup ready timeout must be positive");
+        }
+        if let Some(unknown) = self.agents.startup.critical.iter().find(|agent| !AGENT_NAMES.contains(&agent.as_str())) {
+            anyhow::bail!("Unknown critical agent '{}'", unknown);
+        }
+        if let Some(unknown) = self.agents.evolution_cooldowns.per_agent.keys().find(|agent| !AGENT_NAMES.contains(&agent.as_str())) {
+            anyhow::bail!("Unknown agent '{}' in evolution cooldowns", unknown);
+        }
+
+        let intelligence = &self.agents.market_intelligence;
+        if intelligence.enabled && intelligence.watchlist.iter().all(|symbol| symbol.trim().is_empty()) {
+            anyhow::bail!("Market intelligence watchlist cannot be empty");
+        }
+
+        let adaptive = &intelligence.adaptive_interval;
+        if adaptive.enabled {
+            if !adaptive.reference_volatility.is_finite() || adaptive.reference_volatility <= 0.0 {
+                anyhow::bail!("Adaptive interval reference volatility must be positive, got {}", adaptive.reference_volatility);
+            }
+            if adaptive.min_interval_ms == 0 {
+                anyhow::bail!("Adaptive interval minimum must be positive");
+            }
+        }
+
+        // OpenD's API port speaks protobuf, so the feed needs a relay serving JSON quotes
+        let live_feed = &intelligence.live_feed;
+        if live_feed.enabled {
+            let Some(name) = &live_feed.provider else {
+                anyhow::bail!("The live feed needs a provider from api.data_providers");
+            };
+            let provider = self.api.data_providers
+                .iter()
+                .find(|provider| provider.enabled && provider.name == *name)
+                .ok_or_else(|| anyhow::anyhow!("Live feed provider '{}' is not an enabled data provider", name))?;
+            if !["http://", "https://", "ws://", "wss://"].iter().any(|scheme| provider.url.starts_with(scheme)) {
+                anyhow::bail!("Live feed provider '{}' needs an http(s) or ws(s) URL", name);
+            }
+            if provider.url.trim_end_matches('/') == self.api.moomoo.base_url.trim_end_matches('/') {
+                anyhow::bail!("Live feed provider '{}' points at the OpenD API port, which does not serve the JSON quote feed", name);
+            }
+        }
+
+        let thought_log = &self.monitoring.thought_log;
+        if thought_log.enabled && (thought_log.path.trim().is_empty() || thought_log.max_file_bytes == 0) {
+            anyhow::bail!("AI thought log needs a path and a non-zero max file size");
+        }
+
         // Validate API configuration for Moomoo's session-based architecture
         // Moomoo uses OpenD local gateway - authentication is handled externally
         if self.api.moomoo.base_url.contains("localhost") || self.api.moomoo.base_url.contains("127.0.0.1") {
@@ -321,9 +1722,11 @@ impl Default for SystemConfig {
             trading: TradingConfig {
                 initial_capital: Decimal::from(100), // $100 starting capital
                 target_daily_return: 0.25,           // 25% daily target
+                aggressiveness: 1.0,
                 max_positions: 10,
                 default_position_size: Decimal::from(10), // $10 per position
                 commission_rate: Decimal::from_f64_retain(0.001).unwrap(), // 0.1%
+                commission: CommissionConfig::default(),
                 slippage_tolerance: Decimal::from_f64_retain(0.0005).unwrap(), // 0.05%
                 trading_hours: TradingHours {
                     market_open: "09:30:00".to_string(),
@@ -336,16 +1739,30 @@ impl Default for SystemConfig {
                         "Thursday".to_string(),
                         "Friday".to_string(),
                     ],
+                    extended: ExtendedHoursConfig::default(),
+                    clock: SessionClock::default(),
+                    daily_reset: DailyResetBoundary::default(),
                 },
+                max_quote_age_secs: default_max_quote_age_secs(),
+                position_import: PositionImportConfig::default(),
             },
             risk: RiskConfig {
                 max_daily_loss: Decimal::from(2), // $2 max daily loss (2%)
                 max_position_size: Decimal::from(20), // $20 max position
                 var_confidence_level: 0.95,
+                var_window_days: default_var_window_days(),
                 max_portfolio_heat: 0.8,
+                portfolio_heat: PortfolioHeatConfig::default(),
                 circuit_breaker_threshold: Decimal::from_f64_retain(0.05).unwrap(), // 5%
+                circuit_breaker_cooldown_secs: default_circuit_breaker_cooldown_secs(),
                 emergency_stop_loss: Decimal::from_f64_retain(0.10).unwrap(), // 10%
                 correlation_limit: 0.7,
+                reconciliation: ReconciliationConfig::default(),
+                max_unrealized_loss: UnrealizedLossConfig::default(),
+                min_cash_buffer: CashBufferConfig::default(),
+                audit: RiskAuditConfig::default(),
+                compliance: ComplianceConfig::default(),
+                validation_cache: RiskCacheConfig::default(),
             },
             agents: AgentConfig {
                 master_coordinator: CoordinatorConfig {
@@ -358,19 +1775,33 @@ impl Default for SystemConfig {
                         AgentCapability::EthicalReasoning,
                     ],
                     strategic_planning_interval_hours: 1,
+                    feedback_interval_secs: default_feedback_interval_secs(),
+                    reliability: ReliabilityConfig::default(),
+                    voters: default_voters(),
                 },
                 market_intelligence: IntelligenceConfig {
                     enabled: true,
-                    data_sources: vec!["moomoo".to_string()],
+                    data_sources: vec![DataSourceConfig {
+                        provider: "moomoo".to_string(),
+                        feed: "level1".to_string(),
+                        max_age_secs: 5,
+                    }],
                     update_interval_ms: 100,
                     technical_indicators: vec![
                         "sma".to_string(),
                         "ema".to_string(),
                         "rsi".to_string(),
                         "macd".to_string(),
+                        "atr".to_string(),
                     ],
                     sentiment_analysis: true,
                     pattern_recognition: true,
+                    coalesce_quotes: default_coalesce_quotes(),
+                    adaptive_interval: AdaptiveIntervalConfig::default(),
+                    compute_budget: ComputeBudgetConfig::default(),
+                    watchlist: default_watchlist(),
+                    signal_cooldown_secs: default_signal_cooldown_secs(),
+                    live_feed: LiveFeedConfig::default(),
                 },
                 risk_management: RiskAgentConfig {
                     enabled: true,
@@ -385,6 +1816,26 @@ impl Default for SystemConfig {
                     order_routing_optimization: true,
                     slippage_optimization: true,
                     execution_algorithms: vec!["twap".to_string(), "vwap".to_string()],
+                    tick_sizes: TickSizeConfig::default(),
+                    max_context_age_secs: default_max_context_age_secs(),
+                    deterministic_signal_order: default_deterministic_signal_order(),
+                    fill_timeout_ms: default_fill_timeout_ms(),
+                    resync_on_reconnect: default_resync_on_reconnect(),
+                    simulated_fill_slices: default_simulated_fill_slices(),
+                    partial_fill_resubmits: default_partial_fill_resubmits(),
+                    max_retries_per_order: default_max_retries_per_order(),
+                    canary: CanaryConfig::default(),
+                    slippage_monitor: SlippageMonitorConfig::default(),
+                    slippage_attribution: SlippageAttributionConfig::default(),
+                    protective_levels: ProtectiveLevelsConfig::default(),
+                    venue_routing: VenueRoutingConfig::default(),
+                    algorithm_selection: AlgorithmSelectionConfig::default(),
+                    position_sizing: PositionSizingConfig::default(),
+                    order_conflict_policy: OrderConflictPolicy::default(),
+                    journal: TradeJournalConfig::default(),
+                    min_order_quantity: Decimal::ZERO,
+                    order_minimum: OrderMinimumConfig::default(),
+                    multi_leg: MultiLegConfig::default(),
                 },
                 learning_engine: LearningConfig {
                     enabled: true,
@@ -392,7 +1843,14 @@ impl Default for SystemConfig {
                     online_learning: true,
                     ensemble_models: true,
                     strategy_generation: true,
+                    max_active_strategies: default_max_active_strategies(),
+                    max_new_strategies_per_day: default_max_new_strategies_per_day(),
+                    new_strategy_state: default_new_strategy_state(),
+                    parameter_bounds: default_parameter_bounds(),
+                    outcome_labeling: OutcomeLabelingConfig::default(),
                 },
+                evolution_cooldowns: EvolutionCooldownConfig::default(),
+                startup: StartupConfig::default(),
             },
             api: ApiConfig {
                 moomoo: MoomooConfig {
@@ -402,6 +1860,7 @@ impl Default for SystemConfig {
                     paper_trading: true, // Start with paper trading
                     timeout_ms: 5000,
                     retry_attempts: 3,
+                    use_gateway: false,
                 },
                 data_providers: vec![],
                 rate_limits: RateLimitConfig {
@@ -409,10 +1868,12 @@ impl Default for SystemConfig {
                     burst_limit: 200,
                     backoff_strategy: "exponential".to_string(),
                 },
+                http: HttpClientConfig::default(),
             },
             monitoring: MonitoringConfig {
                 metrics_enabled: true,
                 prometheus_port: 9090,
+                metrics_bind_address: default_metrics_bind_address(),
                 log_level: "info".to_string(),
                 performance_tracking: true,
                 alerts: AlertConfig {
@@ -422,6 +1883,18 @@ impl Default for SystemConfig {
                     critical_loss_threshold: Decimal::from(5), // $5 critical loss
                     performance_degradation_threshold: 0.5,
                 },
+                equity_curve: EquityCurveConfig::default(),
+                performance_window: PerformanceWindowConfig::default(),
+                daily_report: ReportConfig::default(),
+                lock_contention: LockContentionConfig::default(),
+                max_thought_data_bytes: default_max_thought_data_bytes(),
+                max_decode_failures: default_max_decode_failures(),
+                max_config_changes: default_max_config_changes(),
+                thought_export_chunk_size: default_thought_export_chunk_size(),
+                coalesce_duplicate_thoughts: default_coalesce_duplicate_thoughts(),
+                thought_log: ThoughtLogConfig::default(),
+                per_strategy_metrics: default_per_strategy_metrics(),
+                decision_trace: DecisionTraceConfig::default(),
             },
             strategies: vec![
                 StrategyConfig {
@@ -430,6 +1903,8 @@ impl Default for SystemConfig {
                     risk_limit: Decimal::from(1),
                     max_position_size: Decimal::from(10),
                     parameters: HashMap::new(),
+                    extended_hours: false,
+                    take_profit: None,
                 },
                 StrategyConfig {
                     name: "mean_reversion".to_string(),
@@ -437,8 +1912,45 @@ impl Default for SystemConfig {
                     risk_limit: Decimal::from(1),
                     max_position_size: Decimal::from(10),
                     parameters: HashMap::new(),
+                    extended_hours: false,
+                    take_profit: None,
                 },
             ],
+            interfaces: InterfacesConfig::default(),
+            replay: ReplayConfig::default(),
+            ab_test: AbTestConfig::default(),
+            hot_reload: HotReloadConfig::default(),
+            runtime: RuntimeConfig::default(),
+            supervision: SupervisionConfig::default(),
+            heartbeat: HeartbeatConfig::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_distinguishes_paper_and_live() {
+        let mut config = SystemConfig::default();
+        config.agents.learning_engine.enabled = false;
+        config.api.data_providers.push(DataProviderConfig {
+            name: "polygon".to_string(),
+            url: "https://api.polygon.io".to_string(),
+            api_key: None,
+            enabled: true,
+            priority: 1,
+        });
+
+        let summary = config.summary();
+        assert!(summary.contains("Trading mode:     PAPER"));
+        assert!(summary.contains("Initial capital:  $100"));
+        assert!(summary.contains("learning_engine        disabled"));
+        assert!(summary.contains("risk_management        enabled"));
+        assert!(summary.contains("polygon                enabled (priority 1)"));
+
+        config.api.moomoo.paper_trading = false;
+        assert!(config.summary().contains("Trading mode:     LIVE (real money)"));
+    }
+}
@@ -0,0 +1,63 @@
+//! Agents reachable for point-to-point messages
+//!
+//! Each agent registers its inbound channel under its id. Messages addressed
+//! to a specific agent are delivered to that channel alone; messages to the
+//! nil id are still broadcast to everyone.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tokio::sync::mpsc;
+
+use crate::core::errors::{TradingError, TradingResult};
+use crate::core::types::{AgentId, AgentMessage};
+
+#[derive(Debug, Clone)]
+struct Entry {
+    name: String,
+    inbox: mpsc::UnboundedSender<AgentMessage>,
+}
+
+/// Registry of agent inboxes, keyed by agent id
+#[derive(Debug, Clone, Default)]
+pub struct AgentDirectory {
+    agents: Arc<RwLock<HashMap<AgentId, Entry>>>,
+}
+
+impl AgentDirectory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an agent's inbox under its id and config section name
+    pub fn register(&self, name: &str, id: AgentId, inbox: mpsc::UnboundedSender<AgentMessage>) {
+        let entry = Entry { name: name.to_string(), inbox };
+        self.agents.write().expect("agent directory lock poisoned").insert(id, entry);
+    }
+
+    /// Id of the agent registered under `name`
+    pub fn id_of(&self, name: &str) -> Option<AgentId> {
+        self.agents
+            .read()
+            .expect("agent directory lock poisoned")
+            .iter()
+            .find(|(_, entry)| entry.name == name)
+            .map(|(id, _)| *id)
+    }
+
+    /// Remove the agent registered under `name`, such as one that failed to start
+    pub fn deregister(&self, name: &str) {
+        self.agents.write().expect("agent directory lock poisoned").retain(|_, entry| entry.name != name);
+    }
+
+    /// Deliver a message to the inbox of the agent it is addressed to
+    pub fn deliver(&self, message: AgentMessage) -> TradingResult<()> {
+        let agents = self.agents.read().expect("agent directory lock poisoned");
+        let entry = agents
+            .get(&message.to)
+            .ok_or_else(|| TradingError::agent_communication(format!("No agent registered as {}", message.to)))?;
+        entry
+            .inbox
+            .send(message)
+            .map_err(|_| TradingError::agent_communication(format!("Inbox of {} is closed", entry.name)))
+    }
+}
@@ -0,0 +1,123 @@
+//! Equity curve tracking and export
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// A single point on the equity curve
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EquityPoint {
+    pub timestamp: DateTime<Utc>,
+    pub total_value: Decimal,
+    pub cash_balance: Decimal,
+}
+
+/// Time series of portfolio equity sampled at a configurable granularity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquityCurve {
+    points: Vec<EquityPoint>,
+    granularity_secs: u64,
+    max_points: usize,
+}
+
+impl EquityCurve {
+    /// Create a new equity curve
+    pub fn new(granularity_secs: u64, max_points: usize) -> Self {
+        Self {
+            points: Vec::new(),
+            granularity_secs,
+            max_points,
+        }
+    }
+
+    /// Record an equity sample, skipping it if it falls within the granularity
+    /// window of the previous point. Returns true if the point was stored.
+    pub fn record(&mut self, timestamp: DateTime<Utc>, total_value: Decimal, cash_balance: Decimal) -> bool {
+        if let Some(last) = self.points.last() {
+            let elapsed = (timestamp - last.timestamp).num_seconds();
+            if elapsed < self.granularity_secs as i64 {
+                return false;
+            }
+        }
+
+        self.points.push(EquityPoint {
+            timestamp,
+            total_value,
+            cash_balance,
+        });
+
+        // Keep only the most recent points
+        if self.points.len() > self.max_points {
+            let excess = self.points.len() - self.max_points;
+            self.points.drain(..excess);
+        }
+
+        true
+    }
+
+    /// Get all recorded points in chronological order
+    pub fn points(&self) -> &[EquityPoint] {
+        &self.points
+    }
+
+    /// Export the curve as CSV with a header row
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("timestamp,total_value,cash_balance\n");
+        for point in &self.points {
+            csv.push_str(&format!(
+                "{},{},{}\n",
+                point.timestamp.to_rfc3339(),
+                point.total_value,
+                point.cash_balance
+            ));
+        }
+        csv
+    }
+}
+
+impl Default for EquityCurve {
+    fn default() -> Self {
+        Self::new(60, 10_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_equity_export_order() {
+        let mut curve = EquityCurve::new(60, 100);
+        let start = Utc::now();
+
+        assert!(curve.record(start, Decimal::from(100), Decimal::from(100)));
+        assert!(curve.record(start + Duration::seconds(60), Decimal::from(105), Decimal::from(50)));
+        assert!(curve.record(start + Duration::seconds(120), Decimal::from(98), Decimal::from(40)));
+
+        let json = serde_json::to_value(curve.points()).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 3);
+        assert_eq!(curve.points()[1].timestamp, start + Duration::seconds(60));
+        assert_eq!(curve.points()[1].total_value, Decimal::from(105));
+
+        let csv = curve.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "timestamp,total_value,cash_balance");
+        assert_eq!(lines[1], format!("{},100,100", start.to_rfc3339()));
+        assert_eq!(lines[3], format!("{},98,40", (start + Duration::seconds(120)).to_rfc3339()));
+    }
+
+    #[test]
+    fn test_equity_granularity_and_cap() {
+        let mut curve = EquityCurve::new(60, 2);
+        let start = Utc::now();
+
+        assert!(curve.record(start, Decimal::from(100), Decimal::from(100)));
+        assert!(!curve.record(start + Duration::seconds(10), Decimal::from(101), Decimal::from(100)));
+        assert!(curve.record(start + Duration::seconds(60), Decimal::from(102), Decimal::from(100)));
+        assert!(curve.record(start + Duration::seconds(120), Decimal::from(103), Decimal::from(100)));
+
+        assert_eq!(curve.points().len(), 2);
+        assert_eq!(curve.points()[0].total_value, Decimal::from(102));
+    }
+}
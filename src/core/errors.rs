@@ -35,6 +35,15 @@ pub enum TradingError {
     #[error("Strategy error: {message}")]
     Strategy { message: String },
 
+    #[error("Interface error: {message}")]
+    Interface { message: String },
+
+    #[error("Arithmetic overflow: {operation}")]
+    ArithmeticOverflow { operation: String },
+
+    #[error("Timed out: {operation}")]
+    Timeout { operation: String },
+
     #[error("System shutdown requested")]
     Shutdown,
 
@@ -84,6 +93,27 @@ impl TradingError {
         }
     }
 
+    /// Create a new interface error
+    pub fn interface<S: Into<String>>(message: S) -> Self {
+        Self::Interface {
+            message: message.into(),
+        }
+    }
+
+    /// Create a new arithmetic overflow error
+    pub fn overflow<S: Into<String>>(operation: S) -> Self {
+        Self::ArithmeticOverflow {
+            operation: operation.into(),
+        }
+    }
+
+    /// Create a new timeout error
+    pub fn timeout<S: Into<String>>(operation: S) -> Self {
+        Self::Timeout {
+            operation: operation.into(),
+        }
+    }
+
     /// Create a new circuit breaker error
     pub fn circuit_breaker<S: Into<String>>(reason: S) -> Self {
         Self::CircuitBreaker {
@@ -0,0 +1,81 @@
+//! System health from independent conditions
+//!
+//! Checks such as heartbeats and portfolio reconciliation each raise a
+//! condition while their problem persists and clear it once it's gone. The
+//! system health is recomputed from whatever is still raised, so it recovers
+//! on its own instead of sticking at the worst state ever seen.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tracing::info;
+
+use crate::core::types::{SystemContext, SystemHealth};
+
+/// Conditions currently degrading system health, shared across clones
+#[derive(Debug, Clone, Default)]
+pub struct HealthConditions {
+    active: Arc<RwLock<HashMap<&'static str, SystemHealth>>>,
+}
+
+impl HealthConditions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hold health at `health` or worse until `condition` is cleared
+    pub fn raise(&self, condition: &'static str, health: SystemHealth) {
+        self.active.write().expect("health lock poisoned").insert(condition, health);
+    }
+
+    /// Stop counting `condition`. True if it was raised.
+    pub fn clear(&self, condition: &'static str) -> bool {
+        self.active.write().expect("health lock poisoned").remove(condition).is_some()
+    }
+
+    /// Worst health among the raised conditions, Healthy if there are none
+    pub fn health(&self) -> SystemHealth {
+        self.active
+            .read()
+            .expect("health lock poisoned")
+            .values()
+            .max()
+            .cloned()
+            .unwrap_or(SystemHealth::Healthy)
+    }
+
+    /// Set the context's health from the raised conditions
+    pub fn apply(&self, context: &mut SystemContext) {
+        let health = self.health();
+        if context.system_health != health {
+            info!("🏥 System health {:?} -> {:?}", context.system_health, health);
+            context.system_health = health;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::SystemConfig;
+
+    #[test]
+    fn test_health_recovers_as_conditions_clear() {
+        let conditions = HealthConditions::new();
+        let mut context = SystemContext::initial(&SystemConfig::default());
+
+        conditions.raise("reconciliation", SystemHealth::Warning);
+        conditions.raise("heartbeat", SystemHealth::Degraded);
+        conditions.apply(&mut context);
+        assert_eq!(context.system_health, SystemHealth::Degraded);
+
+        // The milder condition still holding is what remains
+        assert!(conditions.clear("heartbeat"));
+        conditions.apply(&mut context);
+        assert_eq!(context.system_health, SystemHealth::Warning);
+
+        assert!(conditions.clear("reconciliation"));
+        assert!(!conditions.clear("reconciliation"));
+        conditions.apply(&mut context);
+        assert_eq!(context.system_health, SystemHealth::Healthy);
+    }
+}
@@ -0,0 +1,156 @@
+//! Agent liveness tracking
+//!
+//! Each running agent sends a heartbeat on the message bus every few seconds.
+//! The system records when each watched agent was last seen and degrades its
+//! health when one goes quiet for longer than the configured timeout, until
+//! every agent is beating again.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tracing::{error, info};
+
+use crate::core::config::HeartbeatConfig;
+use crate::core::health::HealthConditions;
+use crate::core::metrics::MetricsCollector;
+use crate::core::types::{AgentId, SystemHealth};
+
+/// Health condition raised while any watched agent is stalled
+const STALLED_AGENTS: &str = "stalled_agents";
+
+#[derive(Debug, Clone)]
+struct Entry {
+    name: String,
+    last_seen: DateTime<Utc>,
+}
+
+/// Liveness of one agent as of a point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentLiveness {
+    pub agent_id: AgentId,
+    pub name: String,
+    pub last_seen: DateTime<Utc>,
+    pub alive: bool,
+}
+
+/// Last heartbeat of each watched agent, shared across clones
+#[derive(Debug, Clone)]
+pub struct HeartbeatMonitor {
+    timeout: chrono::Duration,
+    agents: Arc<RwLock<HashMap<AgentId, Entry>>>,
+}
+
+impl HeartbeatMonitor {
+    pub fn new(timeout: chrono::Duration) -> Self {
+        Self {
+            timeout,
+            agents: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub fn from_config(config: &HeartbeatConfig) -> Self {
+        Self::new(chrono::Duration::milliseconds(config.timeout_ms as i64))
+    }
+
+    /// Expect heartbeats from an agent, counting it as seen at `now`. Agents
+    /// are watched once they report ready, since they only beat from then on.
+    pub fn watch(&self, name: &str, id: AgentId, now: DateTime<Utc>) {
+        let entry = Entry { name: name.to_string(), last_seen: now };
+        self.agents.write().expect("heartbeat lock poisoned").insert(id, entry);
+    }
+
+    /// Record a heartbeat; ones from agents that aren't watched are ignored
+    pub fn beat(&self, id: AgentId, at: DateTime<Utc>) {
+        if let Some(entry) = self.agents.write().expect("heartbeat lock poisoned").get_mut(&id) {
+            entry.last_seen = entry.last_seen.max(at);
+        }
+    }
+
+    /// Liveness of every watched agent, by name
+    pub fn statuses(&self, now: DateTime<Utc>) -> Vec<AgentLiveness> {
+        let mut statuses: Vec<AgentLiveness> = self
+            .agents
+            .read()
+            .expect("heartbeat lock poisoned")
+            .iter()
+            .map(|(id, entry)| AgentLiveness {
+                agent_id: *id,
+                name: entry.name.clone(),
+                last_seen: entry.last_seen,
+                alive: now - entry.last_seen <= self.timeout,
+            })
+            .collect();
+        statuses.sort_by(|a, b| a.name.cmp(&b.name));
+        statuses
+    }
+
+    /// Names of watched agents that haven't beaten within the timeout
+    pub fn stalled(&self, now: DateTime<Utc>) -> Vec<String> {
+        self.statuses(now)
+            .into_iter()
+            .filter(|status| !status.alive)
+            .map(|status| status.name)
+            .collect()
+    }
+
+    /// Hold the system at Degraded while any watched agent has stalled,
+    /// returning those agents. The condition clears once none are stalled.
+    pub fn check(&self, health: &HealthConditions, now: DateTime<Utc>) -> Vec<String> {
+        let stalled = self.stalled(now);
+        if !stalled.is_empty() {
+            error!(
+                "💔 No heartbeat from {} within {}ms - marking system Degraded",
+                stalled.join(", "),
+                self.timeout.num_milliseconds()
+            );
+            MetricsCollector::record_system_error();
+            health.raise(STALLED_AGENTS, SystemHealth::Degraded);
+        } else if health.clear(STALLED_AGENTS) {
+            info!("💓 Every agent is beating again");
+        }
+        stalled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::SystemConfig;
+    use crate::core::types::SystemContext;
+
+    #[test]
+    fn test_stalled_agent_degrades_health() {
+        let monitor = HeartbeatMonitor::new(chrono::Duration::seconds(15));
+        let health = HealthConditions::new();
+        let mut context = SystemContext::initial(&SystemConfig::default());
+        let start = Utc::now();
+        let (risk, execution) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        monitor.watch("risk_management", risk, start);
+        monitor.watch("execution_engine", execution, start);
+
+        monitor.beat(risk, start + chrono::Duration::seconds(10));
+        monitor.beat(execution, start + chrono::Duration::seconds(10));
+        assert!(monitor.check(&health, start + chrono::Duration::seconds(20)).is_empty());
+        health.apply(&mut context);
+        assert!(matches!(context.system_health, SystemHealth::Healthy));
+
+        // Only risk management keeps beating
+        monitor.beat(risk, start + chrono::Duration::seconds(25));
+        let now = start + chrono::Duration::seconds(30);
+        assert_eq!(monitor.check(&health, now), ["execution_engine"]);
+        health.apply(&mut context);
+        assert!(matches!(context.system_health, SystemHealth::Degraded));
+
+        let statuses = monitor.statuses(now);
+        assert_eq!(statuses.len(), 2);
+        assert!(statuses.iter().any(|status| status.name == "risk_management" && status.alive));
+        assert!(statuses.iter().any(|status| status.name == "execution_engine" && !status.alive));
+
+        // Health recovers once execution beats again
+        monitor.beat(execution, start + chrono::Duration::seconds(35));
+        assert!(monitor.check(&health, start + chrono::Duration::seconds(40)).is_empty());
+        health.apply(&mut context);
+        assert!(matches!(context.system_health, SystemHealth::Healthy));
+    }
+}
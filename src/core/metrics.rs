@@ -1,16 +1,33 @@
 //! Performance metrics and monitoring
 
 use lazy_static::lazy_static;
-use prometheus::{Counter, Histogram, Gauge, register_counter, register_histogram, register_gauge};
-use std::time::Instant;
+use prometheus::{
+    Counter, CounterVec, Histogram, HistogramVec, Gauge, GaugeVec,
+    register_counter, register_counter_vec, register_histogram, register_histogram_vec, register_gauge, register_gauge_vec
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
 
 lazy_static! {
+    // Symbols given their own label; anything else is reported as `other`
+    static ref SYMBOL_LABELS: RwLock<HashSet<String>> = RwLock::new(HashSet::new());
+    
+    // Labels `position_pnl_usd` currently has a series for
+    static ref POSITION_PNL_LABELS: RwLock<HashSet<String>> = RwLock::new(HashSet::new());
+    
     // Trading metrics
-    pub static ref TRADES_EXECUTED: Counter = register_counter!(
+    pub static ref TRADES_EXECUTED: CounterVec = register_counter_vec!(
         "trades_executed_total", 
-        "Total number of trades executed"
+        "Total number of trades executed per symbol",
+        &["symbol"]
+    ).unwrap();
+    
+    pub static ref TRADES_EXECUTED_ALL: Counter = register_counter!(
+        "trades_executed_all_symbols_total", 
+        "Total number of trades executed across all symbols"
     ).unwrap();
     
     pub static ref TRADES_SUCCESSFUL: Counter = register_counter!(
@@ -24,9 +41,15 @@ lazy_static! {
     ).unwrap();
     
     // Execution performance metrics
-    pub static ref EXECUTION_LATENCY: Histogram = register_histogram!(
+    pub static ref EXECUTION_LATENCY: HistogramVec = register_histogram_vec!(
         "trade_execution_latency_seconds", 
-        "Trade execution latency in seconds"
+        "Trade execution latency in seconds per symbol",
+        &["symbol"]
+    ).unwrap();
+    
+    pub static ref EXECUTION_LATENCY_ALL: Histogram = register_histogram!(
+        "trade_execution_latency_all_symbols_seconds", 
+        "Trade execution latency in seconds across all symbols"
     ).unwrap();
     
     pub static ref ORDER_PROCESSING_TIME: Histogram = register_histogram!(
@@ -34,6 +57,11 @@ lazy_static! {
         "Time to process orders in seconds"
     ).unwrap();
     
+    pub static ref MARKET_DATA_TICKS_COALESCED: Counter = register_counter!(
+        "market_data_ticks_coalesced_total", 
+        "Total number of market data ticks coalesced into a newer quote"
+    ).unwrap();
+    
     pub static ref MARKET_DATA_LATENCY: Histogram = register_histogram!(
         "market_data_latency_seconds", 
         "Market data processing latency in seconds"
@@ -50,6 +78,12 @@ lazy_static! {
         "Daily profit and loss in USD"
     ).unwrap();
     
+    pub static ref POSITION_PNL: GaugeVec = register_gauge_vec!(
+        "position_pnl_usd", 
+        "Profit and loss in USD of each symbol's open position",
+        &["symbol"]
+    ).unwrap();
+    
     pub static ref TOTAL_PNL: Gauge = register_gauge!(
         "total_pnl_usd", 
         "Total profit and loss in USD"
@@ -92,6 +126,21 @@ lazy_static! {
         "Total number of system errors"
     ).unwrap();
     
+    pub static ref LOCK_CONTENTION_EVENTS: Counter = register_counter!(
+        "lock_contention_events_total", 
+        "Total number of shared lock acquisitions or holds beyond the contention threshold"
+    ).unwrap();
+    
+    pub static ref MESSAGE_DECODE_FAILURES: Counter = register_counter!(
+        "message_decode_failures_total", 
+        "Total number of inter-agent message payloads that failed to deserialize"
+    ).unwrap();
+    
+    pub static ref ANALYSIS_DEFERRED_SYMBOLS: Counter = register_counter!(
+        "analysis_deferred_symbols_total", 
+        "Total number of symbol analyses deferred to a later cycle by the compute budget"
+    ).unwrap();
+    
     pub static ref CIRCUIT_BREAKER_TRIPS: Counter = register_counter!(
         "circuit_breaker_trips_total", 
         "Total number of circuit breaker activations"
@@ -108,29 +157,86 @@ lazy_static! {
         "Current model accuracy ratio"
     ).unwrap();
     
+    pub static ref ROLLING_WIN_RATE: Gauge = register_gauge!(
+        "rolling_win_rate_ratio", 
+        "Win rate over the configured performance window"
+    ).unwrap();
+    
+    pub static ref ROLLING_PROFIT_FACTOR: Gauge = register_gauge!(
+        "rolling_profit_factor_ratio", 
+        "Profit factor over the configured performance window"
+    ).unwrap();
+    
     pub static ref STRATEGY_PERFORMANCE: Gauge = register_gauge!(
         "strategy_performance_ratio", 
         "Current strategy performance ratio"
     ).unwrap();
+    
+    pub static ref STRATEGY_WIN_RATE: GaugeVec = register_gauge_vec!(
+        "strategy_win_rate_ratio", 
+        "Win rate of closed trades attributed to each strategy",
+        &["strategy"]
+    ).unwrap();
+    
+    pub static ref STRATEGY_PNL: GaugeVec = register_gauge_vec!(
+        "strategy_pnl_usd", 
+        "Realized profit and loss of closed trades attributed to each strategy",
+        &["strategy"]
+    ).unwrap();
+    
+    pub static ref STRATEGY_TRADES: GaugeVec = register_gauge_vec!(
+        "strategy_trades_count", 
+        "Number of closed trades attributed to each strategy",
+        &["strategy"]
+    ).unwrap();
+}
+
+/// Convert a Decimal to a finite gauge value, reporting 0.0 if it cannot be represented
+fn gauge_value(value: Decimal) -> f64 {
+    match value.to_f64() {
+        Some(v) if v.is_finite() => v,
+        _ => {
+            tracing::warn!("Metric value {} not representable as f64", value);
+            0.0
+        }
+    }
+}
+
+/// Label value for `symbol`: itself if it is on the watchlist, otherwise
+/// `other`, so a mistyped symbol can't add series without bound
+pub fn symbol_label(symbol: &str) -> &str {
+    let labels = SYMBOL_LABELS.read().expect("symbol labels lock poisoned");
+    if labels.contains(symbol) {
+        symbol
+    } else {
+        "other"
+    }
 }
 
 /// Timer for measuring execution latency
 pub struct LatencyTimer {
     start: Instant,
-    histogram: &'static Histogram,
+    histograms: Vec<Histogram>,
 }
 
 impl LatencyTimer {
-    pub fn new(histogram: &'static Histogram) -> Self {
+    pub fn new(histogram: &Histogram) -> Self {
+        Self::observing(vec![histogram.clone()])
+    }
+    
+    /// Timer that records into each of `histograms`
+    pub fn observing(histograms: Vec<Histogram>) -> Self {
         Self {
             start: Instant::now(),
-            histogram,
+            histograms,
         }
     }
     
     pub fn observe(self) {
         let duration = self.start.elapsed();
-        self.histogram.observe(duration.as_secs_f64());
+        for histogram in &self.histograms {
+            histogram.observe(duration.as_secs_f64());
+        }
     }
 }
 
@@ -138,6 +244,12 @@ impl LatencyTimer {
 pub struct MetricsCollector;
 
 impl MetricsCollector {
+    /// Give the symbols of the watchlist their own label on per-symbol metrics
+    pub fn limit_symbols(watchlist: &[String]) {
+        let mut labels = SYMBOL_LABELS.write().expect("symbol labels lock poisoned");
+        *labels = watchlist.iter().map(|symbol| symbol.trim().to_string()).filter(|symbol| !symbol.is_empty()).collect();
+    }
+    
     /// Update portfolio metrics
     pub fn update_portfolio_metrics(
         total_value: Decimal,
@@ -146,10 +258,10 @@ impl MetricsCollector {
         total_pnl: Decimal,
         active_positions: u32,
     ) {
-        PORTFOLIO_VALUE.set(total_value.to_f64().unwrap_or(0.0));
-        CASH_BALANCE.set(cash_balance.to_f64().unwrap_or(0.0));
-        DAILY_PNL.set(daily_pnl.to_f64().unwrap_or(0.0));
-        TOTAL_PNL.set(total_pnl.to_f64().unwrap_or(0.0));
+        PORTFOLIO_VALUE.set(gauge_value(total_value));
+        CASH_BALANCE.set(gauge_value(cash_balance));
+        DAILY_PNL.set(gauge_value(daily_pnl));
+        TOTAL_PNL.set(gauge_value(total_pnl));
         ACTIVE_POSITIONS.set(active_positions as f64);
     }
     
@@ -157,25 +269,53 @@ impl MetricsCollector {
     pub fn update_risk_metrics(
         portfolio_heat: f64,
         max_drawdown: Decimal,
-        var_95: Decimal,
+        var_95: Option<Decimal>,
     ) {
         PORTFOLIO_HEAT.set(portfolio_heat);
-        MAX_DRAWDOWN.set(max_drawdown.to_f64().unwrap_or(0.0));
-        VAR_95.set(var_95.to_f64().unwrap_or(0.0));
+        MAX_DRAWDOWN.set(gauge_value(max_drawdown));
+        // NaN rather than zero while there's too little history for a VaR
+        VAR_95.set(var_95.map_or(f64::NAN, gauge_value));
+    }
+    
+    /// Set the per-symbol P&L gauges to those of the open positions, dropping
+    /// the series of symbols no longer held. Symbols sharing the `other`
+    /// label are summed.
+    pub fn update_symbol_pnl<'a>(positions: impl IntoIterator<Item = (&'a str, Decimal)>) {
+        let mut totals: HashMap<String, f64> = HashMap::new();
+        for (symbol, pnl) in positions {
+            *totals.entry(symbol_label(symbol).to_string()).or_default() += gauge_value(pnl);
+        }
+        
+        let mut labels = POSITION_PNL_LABELS.write().expect("position P&L labels lock poisoned");
+        for closed in labels.iter().filter(|label| !totals.contains_key(*label)) {
+            let _ = POSITION_PNL.remove_label_values(&[closed]);
+        }
+        for (label, pnl) in &totals {
+            POSITION_PNL.with_label_values(&[label]).set(*pnl);
+        }
+        *labels = totals.into_keys().collect();
     }
     
     /// Record a successful trade
-    pub fn record_successful_trade() {
-        TRADES_EXECUTED.inc();
+    pub fn record_successful_trade(symbol: &str) {
+        TRADES_EXECUTED.with_label_values(&[symbol_label(symbol)]).inc();
+        TRADES_EXECUTED_ALL.inc();
         TRADES_SUCCESSFUL.inc();
     }
     
     /// Record a failed trade
-    pub fn record_failed_trade() {
-        TRADES_EXECUTED.inc();
+    pub fn record_failed_trade(symbol: &str) {
+        TRADES_EXECUTED.with_label_values(&[symbol_label(symbol)]).inc();
+        TRADES_EXECUTED_ALL.inc();
         TRADES_FAILED.inc();
     }
     
+    /// Record how long a trade in `symbol` took to execute
+    pub fn record_execution_latency(symbol: &str, latency: Duration) {
+        EXECUTION_LATENCY.with_label_values(&[symbol_label(symbol)]).observe(latency.as_secs_f64());
+        EXECUTION_LATENCY_ALL.observe(latency.as_secs_f64());
+    }
+    
     /// Record system error
     pub fn record_system_error() {
         SYSTEM_ERRORS.inc();
@@ -191,6 +331,26 @@ impl MetricsCollector {
         AGENT_MESSAGES.inc();
     }
     
+    /// Record a market data tick coalesced into a newer quote
+    pub fn record_coalesced_tick() {
+        MARKET_DATA_TICKS_COALESCED.inc();
+    }
+    
+    /// Record a slow shared lock acquisition or long hold
+    pub fn record_lock_contention() {
+        LOCK_CONTENTION_EVENTS.inc();
+    }
+    
+    /// Record a message payload that failed to deserialize
+    pub fn record_decode_failure() {
+        MESSAGE_DECODE_FAILURES.inc();
+    }
+    
+    /// Record symbols whose analysis was deferred by the compute budget
+    pub fn record_deferred_analysis(count: usize) {
+        ANALYSIS_DEFERRED_SYMBOLS.inc_by(count as f64);
+    }
+    
     /// Record model prediction
     pub fn record_model_prediction() {
         MODEL_PREDICTIONS.inc();
@@ -201,14 +361,30 @@ impl MetricsCollector {
         MODEL_ACCURACY.set(accuracy);
     }
     
+    /// Update windowed performance gauges
+    pub fn update_rolling_performance(win_rate: f64, profit_factor: f64) {
+        ROLLING_WIN_RATE.set(win_rate);
+        ROLLING_PROFIT_FACTOR.set(profit_factor);
+    }
+    
     /// Update strategy performance
     pub fn update_strategy_performance(performance: f64) {
         STRATEGY_PERFORMANCE.set(performance);
     }
     
-    /// Start measuring execution latency
-    pub fn start_execution_timer() -> LatencyTimer {
-        LatencyTimer::new(&EXECUTION_LATENCY)
+    /// Update the labeled gauges of one strategy
+    pub fn update_strategy_metrics(strategy: &str, win_rate: f64, pnl: Decimal, trades: u64) {
+        STRATEGY_WIN_RATE.with_label_values(&[strategy]).set(win_rate);
+        STRATEGY_PNL.with_label_values(&[strategy]).set(gauge_value(pnl));
+        STRATEGY_TRADES.with_label_values(&[strategy]).set(trades as f64);
+    }
+    
+    /// Start measuring execution latency of a trade in `symbol`
+    pub fn start_execution_timer(symbol: &str) -> LatencyTimer {
+        LatencyTimer::observing(vec![
+            EXECUTION_LATENCY.with_label_values(&[symbol_label(symbol)]),
+            EXECUTION_LATENCY_ALL.clone(),
+        ])
     }
     
     /// Start measuring order processing time
@@ -244,7 +420,10 @@ impl PerformanceCalculator {
                 0.0
             }
         } else {
-            (total_profit / total_loss.abs()).to_f64().unwrap_or(0.0)
+            total_profit
+                .checked_div(total_loss.abs())
+                .and_then(|ratio| ratio.to_f64())
+                .unwrap_or(f64::INFINITY)
         }
     }
     
@@ -298,3 +477,49 @@ impl PerformanceCalculator {
         max_drawdown
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Label values of `symbol` across the series of one metric family
+    fn symbol_labels(family: &str) -> Vec<String> {
+        prometheus::gather()
+            .iter()
+            .filter(|metrics| metrics.get_name() == family)
+            .flat_map(|metrics| metrics.get_metric().iter())
+            .flat_map(|metric| metric.get_label().iter())
+            .filter(|label| label.get_name() == "symbol")
+            .map(|label| label.get_value().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_symbol_labels_are_bounded_to_watchlist() {
+        MetricsCollector::limit_symbols(&["AAPL".to_string(), "MSFT".to_string()]);
+
+        let before = TRADES_EXECUTED_ALL.get();
+        MetricsCollector::record_successful_trade("AAPL");
+        MetricsCollector::record_failed_trade("APPL"); // Typo
+        MetricsCollector::record_execution_latency("APPL", Duration::from_millis(5));
+        MetricsCollector::update_symbol_pnl([("MSFT", Decimal::from(3)), ("XYZ", Decimal::from(2)), ("QQQ", Decimal::from(-5))]);
+
+        assert!(TRADES_EXECUTED.with_label_values(&["AAPL"]).get() >= 1.0);
+        assert!(TRADES_EXECUTED.with_label_values(&["other"]).get() >= 1.0);
+        assert!(TRADES_EXECUTED_ALL.get() >= before + 2.0);
+        assert!(EXECUTION_LATENCY.with_label_values(&["other"]).get_sample_count() >= 1);
+        assert_eq!(POSITION_PNL.with_label_values(&["MSFT"]).get(), 3.0);
+        assert_eq!(POSITION_PNL.with_label_values(&["other"]).get(), -3.0);
+
+        // Closed symbols lose their series; held ones are updated in place
+        MetricsCollector::update_symbol_pnl([("MSFT", Decimal::from(4))]);
+        assert_eq!(symbol_labels("position_pnl_usd"), ["MSFT"]);
+        assert_eq!(POSITION_PNL.with_label_values(&["MSFT"]).get(), 4.0);
+
+        // Symbols off the watchlist never get a series of their own
+        for family in ["trades_executed_total", "trade_execution_latency_seconds", "position_pnl_usd"] {
+            let labels = symbol_labels(family);
+            assert!(!labels.iter().any(|label| ["APPL", "XYZ", "QQQ"].contains(&label.as_str())), "{}: {:?}", family, labels);
+        }
+    }
+}
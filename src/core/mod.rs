@@ -1,8 +1,19 @@
 //! Core system components for the autonomous trading platform
 
 pub mod ai_thoughts;
+pub mod calendar;
 pub mod config;
+pub mod directory;
+pub mod equity;
+pub mod health;
+pub mod heartbeat;
 pub mod system;
 pub mod types;
 pub mod errors;
 pub mod metrics;
+pub mod performance;
+pub mod reload;
+pub mod replies;
+pub mod report;
+pub mod runtime;
+pub mod startup;
@@ -0,0 +1,223 @@
+//! Rolling-window trade performance statistics
+
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+use crate::core::config::PerformanceWindowConfig;
+use crate::core::metrics::PerformanceCalculator;
+use crate::core::types::{OrderId, PerformanceMetrics};
+
+/// A closed trade's contribution to performance statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeRecord {
+    pub timestamp: DateTime<Utc>,
+    pub pnl: Decimal,
+    pub execution_time_ms: f64,
+    #[serde(default)]
+    pub strategy: Option<String>, // Strategy the trade is attributed to, if any
+    #[serde(default)]
+    pub order_id: Option<OrderId>, // Order that opened the position, scored for its A/B variant
+    #[serde(default)]
+    pub notional: Decimal, // Value of the position when it was opened
+    #[serde(default)]
+    pub costs: Decimal, // Commissions and fees, already deducted from pnl
+    #[serde(default)]
+    pub holding_secs: u64, // Time from opening the position to closing it
+    #[serde(default)]
+    pub source: Option<String>, // Source of the signal that opened the position
+}
+
+/// Cumulative results of the trades attributed to one strategy
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StrategyTally {
+    pub trades: u64,
+    pub wins: u64,
+    pub pnl: Decimal,
+}
+
+impl StrategyTally {
+    /// Share of trades that closed with a profit
+    pub fn win_rate(&self) -> f64 {
+        PerformanceCalculator::calculate_win_rate(self.wins, self.trades)
+    }
+}
+
+/// Per-strategy tallies of closed trades
+#[derive(Debug, Clone, Default)]
+pub struct StrategyPerformance {
+    tallies: HashMap<String, StrategyTally>,
+}
+
+impl StrategyPerformance {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a closed trade to its strategy's tally, returning the updated
+    /// tally, or `None` if the trade is not attributed to a strategy
+    pub fn record(&mut self, trade: &TradeRecord) -> Option<&StrategyTally> {
+        let strategy = trade.strategy.as_ref()?;
+        let tally = self.tallies.entry(strategy.clone()).or_default();
+        tally.trades += 1;
+        if trade.pnl > Decimal::ZERO {
+            tally.wins += 1;
+        }
+        tally.pnl += trade.pnl;
+        Some(tally)
+    }
+
+    /// The tally for one strategy
+    pub fn get(&self, strategy: &str) -> Option<&StrategyTally> {
+        self.tallies.get(strategy)
+    }
+}
+
+/// Performance metrics computed over the last N trades and/or a time window,
+/// smoothing out per-trade noise in win rate and profit factor
+#[derive(Debug, Clone)]
+pub struct RollingPerformance {
+    trades: VecDeque<TradeRecord>,
+    max_trades: usize,
+    max_age: Option<Duration>,
+}
+
+impl RollingPerformance {
+    /// Create a rolling window from configuration
+    pub fn from_config(config: &PerformanceWindowConfig) -> Self {
+        Self {
+            trades: VecDeque::with_capacity(config.max_trades),
+            max_trades: config.max_trades.max(1),
+            max_age: config.max_age_secs.map(|secs| Duration::seconds(secs as i64)),
+        }
+    }
+
+    /// Add a closed trade, evicting trades that fall outside the count window
+    pub fn record(&mut self, trade: TradeRecord) {
+        self.trades.push_back(trade);
+        while self.trades.len() > self.max_trades {
+            self.trades.pop_front();
+        }
+    }
+
+    /// Compute metrics over the trades inside the window as of `now`
+    pub fn metrics(&mut self, now: DateTime<Utc>) -> PerformanceMetrics {
+        if let Some(max_age) = self.max_age {
+            while self.trades.front().is_some_and(|t| now - t.timestamp > max_age) {
+                self.trades.pop_front();
+            }
+        }
+
+        let mut winning_trades = 0u64;
+        let mut losing_trades = 0u64;
+        let mut total_profit = Decimal::ZERO;
+        let mut total_loss = Decimal::ZERO;
+        let mut wins = 0u32;
+        let mut losses = 0u32;
+        let mut max_consecutive_wins = 0u32;
+        let mut max_consecutive_losses = 0u32;
+        let mut total_execution_ms = 0.0;
+
+        for trade in &self.trades {
+            total_execution_ms += trade.execution_time_ms;
+            if trade.pnl > Decimal::ZERO {
+                winning_trades += 1;
+                total_profit += trade.pnl;
+                wins += 1;
+                losses = 0;
+            } else {
+                losing_trades += 1;
+                total_loss += trade.pnl.abs();
+                losses += 1;
+                wins = 0;
+            }
+            max_consecutive_wins = max_consecutive_wins.max(wins);
+            max_consecutive_losses = max_consecutive_losses.max(losses);
+        }
+
+        let total_trades = self.trades.len() as u64;
+        PerformanceMetrics {
+            total_trades,
+            winning_trades,
+            losing_trades,
+            win_rate: PerformanceCalculator::calculate_win_rate(winning_trades, total_trades),
+            average_win: average(total_profit, winning_trades),
+            average_loss: average(total_loss, losing_trades),
+            profit_factor: PerformanceCalculator::calculate_profit_factor(total_profit, total_loss),
+            max_consecutive_wins,
+            max_consecutive_losses,
+            average_execution_time_ms: if total_trades > 0 {
+                total_execution_ms / total_trades as f64
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+fn average(total: Decimal, count: u64) -> Decimal {
+    if count == 0 {
+        Decimal::ZERO
+    } else {
+        total / Decimal::from(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(pnl: i64, timestamp: DateTime<Utc>) -> TradeRecord {
+        TradeRecord {
+            timestamp,
+            pnl: Decimal::from(pnl),
+            execution_time_ms: 1.0,
+            strategy: None,
+            order_id: None,
+            notional: Decimal::ZERO,
+            costs: Decimal::ZERO,
+            holding_secs: 0,
+            source: None,
+        }
+    }
+
+    #[test]
+    fn test_win_rate_uses_last_n_trades() {
+        let mut window = RollingPerformance::from_config(&PerformanceWindowConfig {
+            max_trades: 4,
+            max_age_secs: None,
+        });
+        let now = Utc::now();
+
+        // Six early losses fall out of the window
+        for _ in 0..6 {
+            window.record(trade(-10, now));
+        }
+        for pnl in [20, 30, -10, 40] {
+            window.record(trade(pnl, now));
+        }
+
+        let metrics = window.metrics(now);
+        assert_eq!(metrics.total_trades, 4);
+        assert_eq!(metrics.winning_trades, 3);
+        assert!((metrics.win_rate - 0.75).abs() < 1e-9);
+        assert!((metrics.profit_factor - 9.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_time_window_expires_old_trades() {
+        let mut window = RollingPerformance::from_config(&PerformanceWindowConfig {
+            max_trades: 100,
+            max_age_secs: Some(60),
+        });
+        let now = Utc::now();
+
+        window.record(trade(-10, now - Duration::seconds(120)));
+        window.record(trade(10, now));
+
+        let metrics = window.metrics(now);
+        assert_eq!(metrics.total_trades, 1);
+        assert_eq!(metrics.win_rate, 1.0);
+    }
+}
@@ -0,0 +1,99 @@
+//! Planning of live configuration reloads
+//!
+//! A reloaded config is validated and diffed against the running one. Only
+//! settings agents re-read while running are applied; anything wired into a
+//! component at startup needs a restart, so it is reported and left pending.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::core::config::SystemConfig;
+use crate::core::errors::{TradingError, TradingResult};
+
+/// Config paths that running agents pick up without a restart
+pub const HOT_RELOADABLE: &[&str] = &[
+    "risk.max_daily_loss",
+    "risk.max_position_size",
+    "risk.var_confidence_level",
+    "risk.max_portfolio_heat",
+    "risk.circuit_breaker_threshold",
+    "risk.emergency_stop_loss",
+    "risk.correlation_limit",
+];
+
+/// Changed settings broadcast to agents, keyed by dotted config path
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfigUpdate {
+    pub changes: BTreeMap<String, serde_json::Value>,
+    /// Changed paths that only take effect after a restart
+    #[serde(default)]
+    pub restart_required: Vec<String>,
+}
+
+impl ConfigUpdate {
+    /// Whether nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.restart_required.is_empty()
+    }
+
+    /// `config` with the hot-reloadable changes applied. Settings that need a
+    /// restart keep their running values, so later reloads still report them.
+    pub fn apply_to(&self, config: &SystemConfig) -> TradingResult<SystemConfig> {
+        let mut value = serde_json::to_value(config)?;
+        for (path, change) in &self.changes {
+            let leaf = path.split('.').try_fold(&mut value, |node, key| node.get_mut(key));
+            match leaf {
+                Some(leaf) => *leaf = change.clone(),
+                None => return Err(TradingError::Config(anyhow::anyhow!("Unknown config path {}", path))),
+            }
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+/// Validate `new` and work out the update that takes `old` to it.
+///
+/// Fails if `new` is invalid. Changes outside [`HOT_RELOADABLE`] are listed
+/// in `restart_required` rather than applied.
+pub fn plan_reload(old: &SystemConfig, new: &SystemConfig) -> TradingResult<ConfigUpdate> {
+    new.validate().map_err(TradingError::Config)?;
+
+    let old = serde_json::to_value(old)?;
+    let new = serde_json::to_value(new)?;
+    let mut changes = BTreeMap::new();
+    diff("", &old, &new, &mut changes);
+
+    let (changes, restart): (BTreeMap<_, _>, BTreeMap<_, _>) = changes
+        .into_iter()
+        .partition(|(path, _)| HOT_RELOADABLE.contains(&path.as_str()));
+
+    Ok(ConfigUpdate {
+        changes,
+        restart_required: restart.into_keys().collect(),
+    })
+}
+
+/// Collect the dotted paths of leaves that differ, with their new values
+fn diff(
+    path: &str,
+    old: &serde_json::Value,
+    new: &serde_json::Value,
+    changes: &mut BTreeMap<String, serde_json::Value>,
+) {
+    match (old, new) {
+        (serde_json::Value::Object(old), serde_json::Value::Object(new)) => {
+            for (key, value) in new {
+                let child = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                diff(&child, old.get(key).unwrap_or(&serde_json::Value::Null), value, changes);
+            }
+            for key in old.keys().filter(|key| !new.contains_key(*key)) {
+                let child = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                changes.insert(child, serde_json::Value::Null);
+            }
+        }
+        (old, new) if old != new => {
+            changes.insert(path.to_string(), new.clone());
+        }
+        _ => {}
+    }
+}
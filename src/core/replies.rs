@@ -0,0 +1,50 @@
+//! Request/reply correlation over the message bus
+//!
+//! A requester registers a oneshot under a fresh correlation id before
+//! sending; the system hands the matching reply back to it as the message is
+//! routed. Requests that time out are deregistered, so late replies are dropped.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{oneshot, RwLock};
+use uuid::Uuid;
+
+use crate::core::types::AgentMessage;
+
+/// Requests awaiting a reply, keyed by correlation id
+#[derive(Debug, Clone, Default)]
+pub struct ReplyRouter {
+    pending: Arc<RwLock<HashMap<Uuid, oneshot::Sender<AgentMessage>>>>,
+}
+
+impl ReplyRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Await the reply correlated with `correlation_id`
+    pub async fn register(&self, correlation_id: Uuid) -> oneshot::Receiver<AgentMessage> {
+        let (sender, receiver) = oneshot::channel();
+        self.pending.write().await.insert(correlation_id, sender);
+        receiver
+    }
+
+    /// Stop waiting for a reply, e.g. once the request has timed out
+    pub async fn cancel(&self, correlation_id: &Uuid) {
+        self.pending.write().await.remove(correlation_id);
+    }
+
+    /// Hand a reply to its requester. Returns false if nobody is waiting for it.
+    pub async fn deliver(&self, reply: AgentMessage) -> bool {
+        let Some(correlation_id) = reply.correlation_id else { return false };
+        match self.pending.write().await.remove(&correlation_id) {
+            Some(requester) => requester.send(reply).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Number of requests still awaiting a reply
+    pub async fn pending(&self) -> usize {
+        self.pending.read().await.len()
+    }
+}
@@ -0,0 +1,251 @@
+//! End-of-session daily report
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::core::ai_thoughts::AIThought;
+use crate::core::config::{AlertConfig, ReportConfig};
+use crate::core::errors::TradingResult;
+use crate::core::types::{ExecutionResult, PerformanceMetrics};
+
+/// A timestamped notable event recorded during the session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionEvent {
+    pub timestamp: DateTime<Utc>,
+    pub description: String,
+}
+
+/// Everything worth reporting that happened during a trading session
+#[derive(Debug, Clone, Default)]
+pub struct SessionLog {
+    trades: Vec<ExecutionResult>,
+    risk_events: Vec<SessionEvent>,
+    strategy_changes: Vec<SessionEvent>,
+    trading_day: Option<NaiveDate>,
+}
+
+impl SessionLog {
+    /// Create an empty session log
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an executed trade
+    pub fn record_trade(&mut self, trade: ExecutionResult) {
+        self.trades.push(trade);
+    }
+
+    /// Record a risk alert or limit breach
+    pub fn record_risk_event<S: Into<String>>(&mut self, description: S) {
+        self.risk_events.push(SessionEvent {
+            timestamp: Utc::now(),
+            description: description.into(),
+        });
+    }
+
+    /// Record a strategy or parameter change
+    pub fn record_strategy_change<S: Into<String>>(&mut self, description: S) {
+        self.strategy_changes.push(SessionEvent {
+            timestamp: Utc::now(),
+            description: description.into(),
+        });
+    }
+
+    /// Clear the log for the next session
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Move the log to `day`, clearing it when that starts a new trading day.
+    /// Returns whether it was cleared.
+    pub fn roll_trading_day(&mut self, day: NaiveDate) -> bool {
+        let previous = self.trading_day.replace(day);
+        if previous.is_some_and(|previous| previous != day) {
+            self.reset();
+            self.trading_day = Some(day);
+            return true;
+        }
+        false
+    }
+}
+
+/// Structured end-of-day digest for operators
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyReport {
+    pub date: NaiveDate,
+    pub generated_at: DateTime<Utc>,
+    pub performance: PerformanceMetrics,
+    pub trade_count: usize,
+    pub trades: Vec<ExecutionResult>,
+    pub top_thoughts: Vec<AIThought>,
+    pub risk_events: Vec<SessionEvent>,
+    pub strategy_changes: Vec<SessionEvent>,
+}
+
+impl DailyReport {
+    /// Compile a report from the session log, current metrics and thought history
+    pub fn generate(
+        session: &SessionLog,
+        performance: &PerformanceMetrics,
+        thoughts: &[AIThought],
+        config: &ReportConfig,
+    ) -> Self {
+        let mut top_thoughts = thoughts.to_vec();
+        top_thoughts.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        top_thoughts.truncate(config.top_thoughts);
+
+        let generated_at = Utc::now();
+        Self {
+            date: session.trading_day.unwrap_or_else(|| generated_at.date_naive()),
+            generated_at,
+            performance: performance.clone(),
+            trade_count: session.trades.len(),
+            trades: session.trades.clone(),
+            top_thoughts,
+            risk_events: session.risk_events.clone(),
+            strategy_changes: session.strategy_changes.clone(),
+        }
+    }
+
+    /// Serialize the report as pretty-printed JSON
+    pub fn to_json(&self) -> TradingResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Render the report as human-readable text
+    pub fn to_text(&self) -> String {
+        let successful = self.trades.iter().filter(|t| t.success).count();
+        let mut text = format!("Daily Report - {}\n\n", self.date);
+
+        text.push_str("== Performance ==\n");
+        text.push_str(&format!(
+            "Win rate: {:.1}% | Profit factor: {:.2} | Avg execution: {:.1}ms\n\n",
+            self.performance.win_rate * 100.0,
+            self.performance.profit_factor,
+            self.performance.average_execution_time_ms
+        ));
+
+        text.push_str("== Trades ==\n");
+        text.push_str(&format!("{} executed, {} successful\n\n", self.trade_count, successful));
+
+        text.push_str("== Top Thoughts ==\n");
+        for thought in &self.top_thoughts {
+            text.push_str(&format!("- {}\n", thought.to_user_explanation()));
+        }
+
+        text.push_str("\n== Risk Events ==\n");
+        for event in &self.risk_events {
+            text.push_str(&format!("- {} {}\n", event.timestamp.format("%H:%M:%S"), event.description));
+        }
+
+        text.push_str("\n== Strategy Changes ==\n");
+        for change in &self.strategy_changes {
+            text.push_str(&format!("- {} {}\n", change.timestamp.format("%H:%M:%S"), change.description));
+        }
+
+        text
+    }
+
+    /// Send the text report through the configured alert channels
+    pub async fn deliver(&self, alerts: &AlertConfig, client: &reqwest::Client) -> TradingResult<()> {
+        if !alerts.enabled {
+            return Ok(());
+        }
+
+        if let Some(webhook) = alerts.slack_webhook.as_deref().filter(|w| !w.is_empty()) {
+            client
+                .post(webhook)
+                .json(&serde_json::json!({ "text": self.to_text() }))
+                .send()
+                .await?
+                .error_for_status()?;
+            info!("📬 Daily report delivered to Slack");
+        }
+
+        if alerts.email_notifications {
+            info!("📬 Email delivery is not configured; daily report logged only");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ai_thoughts::{AIAgent, ThoughtType};
+    use crate::core::config::PerformanceWindowConfig;
+    use crate::core::performance::RollingPerformance;
+    use rust_decimal::Decimal;
+
+    fn trade(success: bool) -> ExecutionResult {
+        ExecutionResult {
+            order_id: uuid::Uuid::new_v4(),
+            executed_quantity: Decimal::from(1),
+            executed_price: Decimal::from(100),
+            execution_time_ms: 2,
+            slippage: Decimal::ZERO,
+            commission: Decimal::ZERO,
+            success,
+            error_message: None,
+            fills: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_report_sections_and_trade_count() {
+        let mut session = SessionLog::new();
+        session.record_trade(trade(true));
+        session.record_trade(trade(true));
+        session.record_trade(trade(false));
+        session.record_risk_event("Daily loss limit approached");
+        session.record_strategy_change("max_portfolio_heat 0.8 -> 0.6");
+
+        let thoughts: Vec<AIThought> = [0.4, 0.9, 0.7]
+            .iter()
+            .map(|c| AIThought::new(AIAgent::RiskManager, ThoughtType::RiskCheck, format!("confidence {}", c), *c))
+            .collect();
+
+        let config = ReportConfig {
+            top_thoughts: 2,
+            ..ReportConfig::default()
+        };
+        let performance = RollingPerformance::from_config(&PerformanceWindowConfig::default()).metrics(Utc::now());
+        let report = DailyReport::generate(&session, &performance, &thoughts, &config);
+
+        assert_eq!(report.trade_count, 3);
+        assert_eq!(report.top_thoughts.len(), 2);
+        assert_eq!(report.top_thoughts[0].confidence, 0.9);
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        for section in ["performance", "trades", "top_thoughts", "risk_events", "strategy_changes"] {
+            assert!(json.get(section).is_some(), "missing section {}", section);
+        }
+
+        let text = report.to_text();
+        assert!(text.contains("3 executed, 2 successful"));
+        assert!(text.contains("Daily loss limit approached"));
+        assert!(text.contains("max_portfolio_heat 0.8 -> 0.6"));
+    }
+
+    #[test]
+    fn test_session_log_clears_on_new_trading_day() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+        let mut session = SessionLog::new();
+        assert!(!session.roll_trading_day(day(5)));
+        session.record_trade(trade(true));
+        assert!(!session.roll_trading_day(day(5)));
+
+        let config = ReportConfig::default();
+        let performance = RollingPerformance::from_config(&PerformanceWindowConfig::default()).metrics(Utc::now());
+        let report = DailyReport::generate(&session, &performance, &[], &config);
+        assert_eq!(report.date, day(5));
+        assert_eq!(report.trade_count, 1);
+
+        assert!(session.roll_trading_day(day(6)));
+        let report = DailyReport::generate(&session, &performance, &[], &config);
+        assert_eq!(report.date, day(6));
+        assert_eq!(report.trade_count, 0);
+    }
+}
@@ -0,0 +1,246 @@
+//! Tokio runtime construction, agent concurrency limits and supervision
+//!
+//! The runtime is sized from `[runtime]` before the system starts. Agent
+//! tasks are spawned through an [`AgentLimiter`], so at most
+//! `max_concurrent_agents` run at once. Agents run for the system's lifetime,
+//! so config validation refuses a cap below the number of enabled agents;
+//! short-lived tasks over the cap wait for a slot. Each task runs under an
+//! [`AgentSupervisor`], which restarts an agent that fails or panics. An
+//! agent that ran stably before failing gets its full restart budget back.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::{Builder, Runtime};
+use tokio::sync::{mpsc, Semaphore};
+use tokio::task::JoinHandle;
+use tracing::error;
+
+use crate::core::config::{RuntimeConfig, SupervisionConfig};
+use crate::core::errors::{TradingError, TradingResult};
+use crate::core::metrics::MetricsCollector;
+use crate::core::types::{AgentMessage, MessageType};
+use crate::infrastructure::backoff::Backoff;
+
+/// Build the multi-threaded runtime the system runs on
+pub fn build_runtime(config: &RuntimeConfig) -> std::io::Result<Runtime> {
+    let mut builder = Builder::new_multi_thread();
+    builder.enable_all().thread_name("trading-worker");
+    if config.worker_threads > 0 {
+        builder.worker_threads(config.worker_threads);
+    }
+    builder.build()
+}
+
+/// Caps how many agent tasks run at the same time
+#[derive(Debug, Clone)]
+pub struct AgentLimiter {
+    permits: Option<Arc<Semaphore>>,
+}
+
+impl AgentLimiter {
+    /// Allow `max_concurrent` agents at once; 0 means unlimited
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            permits: (max_concurrent > 0).then(|| Arc::new(Semaphore::new(max_concurrent))),
+        }
+    }
+
+    /// Spawn an agent task that starts once a slot is free and holds it until it exits
+    pub fn spawn<F>(&self, agent: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let permits = self.permits.clone();
+        tokio::spawn(async move {
+            let _permit = match permits {
+                Some(permits) => Some(permits.acquire_owned().await.expect("agent limiter is never closed")),
+                None => None,
+            };
+            agent.await
+        })
+    }
+}
+
+/// Restarts agents whose task returns an error or panics, backing off
+/// between attempts, and requests an emergency shutdown once an agent has
+/// used up its restarts. Restarts only count against the budget while the
+/// agent keeps failing within `stable_after_ms` of being started.
+#[derive(Debug, Clone)]
+pub struct AgentSupervisor {
+    config: SupervisionConfig,
+    bus: mpsc::UnboundedSender<AgentMessage>,
+}
+
+impl AgentSupervisor {
+    pub fn new(config: &SupervisionConfig, bus: mpsc::UnboundedSender<AgentMessage>) -> Self {
+        Self {
+            config: config.clone(),
+            bus,
+        }
+    }
+
+    /// Run the agent `start` launches until it exits cleanly, relaunching it
+    /// after each failure while restarts remain
+    pub async fn supervise<F, Fut>(self, name: &'static str, mut start: F) -> TradingResult<()>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = TradingResult<()>> + Send + 'static,
+    {
+        let mut backoff = Backoff::new(
+            "exponential",
+            Duration::from_millis(self.config.initial_backoff_ms),
+            Duration::from_millis(self.config.max_backoff_ms),
+        );
+        let stable_after = Duration::from_millis(self.config.stable_after_ms);
+        let mut restarts = 0;
+
+        loop {
+            let started = tokio::time::Instant::now();
+            // A separate task, so a panic surfaces as a join error instead of unwinding here
+            let failure = match tokio::spawn(start()).await {
+                Ok(Ok(())) => return Ok(()),
+                Ok(Err(e)) => e.to_string(),
+                Err(e) if e.is_panic() => "panicked".to_string(),
+                Err(e) => e.to_string(),
+            };
+            MetricsCollector::record_system_error();
+
+            // Failures far apart aren't a crash loop
+            if started.elapsed() >= stable_after {
+                restarts = 0;
+                backoff.reset();
+            }
+            if restarts >= self.config.max_restarts {
+                let reason = format!("Agent {} failed after {} restarts: {}", name, restarts, failure);
+                error!("💀 {}", reason);
+                let message = AgentMessage::broadcast(uuid::Uuid::nil(), MessageType::EmergencyShutdown, serde_json::json!({ "reason": reason }));
+                let _ = self.bus.send(message);
+                return Err(TradingError::emergency_stop(reason));
+            }
+
+            restarts += 1;
+            let delay = backoff.next_delay();
+            error!("💥 Agent {} failed: {}; restart {}/{} in {:?}", name, failure, restarts, self.config.max_restarts, delay);
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn test_configured_runtime_runs_capped_agents_to_completion() {
+        let config = RuntimeConfig { worker_threads: 2, max_concurrent_agents: 2 };
+        let runtime = build_runtime(&config).unwrap();
+        assert_eq!(runtime.metrics().num_workers(), 2);
+
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let completed = runtime.block_on(async {
+            let limiter = AgentLimiter::new(config.max_concurrent_agents);
+            let agents: Vec<_> = (0..5)
+                .map(|id| {
+                    let running = running.clone();
+                    let peak = peak.clone();
+                    limiter.spawn(async move {
+                        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        id
+                    })
+                })
+                .collect();
+            futures::future::join_all(agents).await
+        });
+
+        let completed: Vec<usize> = completed.into_iter().map(Result::unwrap).collect();
+        assert_eq!(completed, vec![0, 1, 2, 3, 4]);
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+
+        // Agents never give their slot back, so a cap below the enabled agents is refused
+        let mut system = crate::core::config::SystemConfig::default();
+        system.api.moomoo.api_key = "test".to_string();
+        system.runtime = config;
+        assert!(system.validate().is_err());
+        system.runtime.max_concurrent_agents = system.agents.enabled().len();
+        assert!(system.validate().is_ok());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_supervisor_restarts_failed_agents_then_shuts_down() {
+        use crate::core::metrics::SYSTEM_ERRORS;
+
+        let config = SupervisionConfig { max_restarts: 2, initial_backoff_ms: 100, max_backoff_ms: 1000, stable_after_ms: 60_000 };
+        let (bus, mut messages) = mpsc::unbounded_channel();
+        let supervisor = AgentSupervisor::new(&config, bus);
+        let errors_before = SYSTEM_ERRORS.get();
+
+        // A panic and an error are both restarted, and a clean exit ends supervision
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let result = supervisor.clone().supervise("flaky", {
+            let attempts = attempts.clone();
+            move || {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    match attempt {
+                        0 => panic!("agent bug"),
+                        1 => Err(TradingError::agent_communication("lost connection")),
+                        _ => Ok(()),
+                    }
+                }
+            }
+        }).await;
+        assert!(result.is_ok());
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert!(messages.try_recv().is_err());
+
+        // Once restarts run out the system is told to shut down
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let result = supervisor.supervise("broken", {
+            let attempts = attempts.clone();
+            move || {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                async { Err(TradingError::agent_communication("still broken")) }
+            }
+        }).await;
+        assert!(matches!(result, Err(TradingError::EmergencyStop { .. })));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        let shutdown = messages.try_recv().unwrap();
+        assert!(matches!(shutdown.message_type, MessageType::EmergencyShutdown));
+        assert!(shutdown.payload["reason"].as_str().unwrap().contains("broken"));
+        assert!(SYSTEM_ERRORS.get() >= errors_before + 5.0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stable_run_restores_the_restart_budget() {
+        let config = SupervisionConfig { max_restarts: 1, initial_backoff_ms: 100, max_backoff_ms: 1000, stable_after_ms: 60_000 };
+        let (bus, mut messages) = mpsc::unbounded_channel();
+        let supervisor = AgentSupervisor::new(&config, bus);
+
+        // Each failure comes after an hour of healthy running, so none exhausts the budget
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let result = supervisor.supervise("occasional", {
+            let attempts = attempts.clone();
+            move || {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    tokio::time::sleep(Duration::from_secs(3600)).await;
+                    match attempt {
+                        0..=2 => Err(TradingError::agent_communication("dropped")),
+                        _ => Ok(()),
+                    }
+                }
+            }
+        }).await;
+        assert!(result.is_ok());
+        assert_eq!(attempts.load(Ordering::SeqCst), 4);
+        assert!(messages.try_recv().is_err());
+    }
+}
@@ -0,0 +1,50 @@
+//! Agent start order
+//!
+//! The agents the system knows by configuration section, and the order they
+//! start in given the dependencies declared between them.
+
+use std::collections::HashMap;
+
+use crate::core::errors::{TradingError, TradingResult};
+
+/// Agents by their configuration section, in the default start order
+pub const AGENT_NAMES: &[&str] = &[
+    "master_coordinator",
+    "market_intelligence",
+    "risk_management",
+    "execution_engine",
+    "learning_engine",
+];
+
+/// Order `agents` so each comes after its dependencies among them, keeping
+/// the given order otherwise. Fails on unknown agent names or a cycle.
+pub fn startup_order(dependencies: &HashMap<String, Vec<String>>, agents: &[&str]) -> TradingResult<Vec<String>> {
+    for (agent, needs) in dependencies {
+        if let Some(unknown) = std::iter::once(agent).chain(needs).find(|name| !AGENT_NAMES.contains(&name.as_str())) {
+            return Err(TradingError::Config(anyhow::anyhow!("Unknown agent '{}' in startup dependencies", unknown)));
+        }
+    }
+
+    let mut order: Vec<String> = Vec::with_capacity(agents.len());
+    while order.len() < agents.len() {
+        let next = agents.iter().find(|agent| {
+            !order.iter().any(|placed| placed == *agent)
+                && dependencies
+                    .get(**agent)
+                    .into_iter()
+                    .flatten()
+                    .all(|dependency| !agents.contains(&dependency.as_str()) || order.contains(dependency))
+        });
+        match next {
+            Some(agent) => order.push(agent.to_string()),
+            None => {
+                let waiting: Vec<&str> = agents.iter().copied().filter(|agent| !order.iter().any(|placed| placed == agent)).collect();
+                return Err(TradingError::Config(anyhow::anyhow!(
+                    "Startup dependencies form a cycle among {}",
+                    waiting.join(", ")
+                )));
+            }
+        }
+    }
+    Ok(order)
+}
@@ -1,25 +1,65 @@
 //! Main trading system orchestrator
 
 use anyhow::Result;
+use rust_decimal::prelude::ToPrimitive;
+use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{broadcast, mpsc, watch, Mutex, RwLock};
 use tokio::time::{interval, Duration};
 use tracing::{info, warn, error};
 
-use crate::core::ai_thoughts::AIThoughtBroadcaster;
+use crate::core::calendar::MarketCalendar;
+use crate::core::ai_thoughts::{AIThoughtBroadcaster, ThoughtLogRotation};
 use crate::core::config::SystemConfig;
+use crate::core::directory::AgentDirectory;
 use crate::core::errors::{TradingError, TradingResult};
 use crate::core::types::{
-    AgentId, AgentMessage, AgentType, SystemContext, SystemHealth, 
-    Portfolio, RiskMetrics, PerformanceMetrics, MarketRegime
+    AgentId, AgentMessage, AgentType, ExecutionResult, SystemContext, SystemHealth, PerformanceMetrics
 };
+use crate::core::health::HealthConditions;
+use crate::core::heartbeat::HeartbeatMonitor;
 use crate::core::metrics::MetricsCollector;
+use crate::core::performance::{RollingPerformance, StrategyPerformance, TradeRecord};
+use crate::core::replies::ReplyRouter;
+use crate::core::reload::{plan_reload, ConfigUpdate};
+use crate::core::report::{DailyReport, SessionLog};
+use crate::core::runtime::{AgentLimiter, AgentSupervisor};
+use crate::agents::ab_test::{AbReport, AbTest};
+use crate::agents::change_log::ConfigChangeLog;
+use crate::agents::cooldown::EvolutionCooldown;
+use crate::agents::decision_trace::DecisionTraces;
 use crate::agents::coordinator::MasterCoordinatorAgent;
 use crate::agents::intelligence::MarketIntelligenceAgent;
 use crate::agents::risk::RiskManagementAgent;
+use crate::agents::startup::{Readiness, StartupPlan};
 use crate::agents::execution::ExecutionEngineAgent;
 use crate::agents::learning::LearningEngineAgent;
-use crate::agents::traits::AutonomousAgent;
+use crate::agents::traits::{AgentWiring, AutonomousAgent, SystemFeedback};
+use crate::execution::api::ApiClient;
+use crate::execution::canary::CanaryRouter;
+use crate::execution::commission::CommissionModel;
+use crate::execution::import::{FilePositionSource, PositionSource};
+use crate::execution::journal::TradeJournal;
+use crate::execution::protection::ProtectionStore;
+use crate::execution::slippage::SlippageMonitor;
+use crate::execution::take_profit::ProtectiveLevels;
+use crate::infrastructure::faults::FaultInjector;
+use crate::infrastructure::http::HttpClientFactory;
+use crate::infrastructure::metrics_server::MetricsServer;
+use crate::infrastructure::locks::LockMonitor;
+use crate::intelligence::quotes::QuoteBook;
+use crate::interfaces::http::HttpApiServer;
+use crate::interfaces::ws::ThoughtStreamServer;
+use crate::risk::cash::CashBuffer;
+use crate::risk::compliance::{CompliancePipeline, RestrictedList};
+use crate::risk::reconciliation::PortfolioReconciler;
+
+/// Health condition raised while the portfolio fails reconciliation
+const RECONCILIATION_FAILED: &str = "reconciliation_failed";
+
+/// Health condition raised while message payloads keep failing to decode
+const UNDECODABLE_PAYLOADS: &str = "undecodable_payloads";
 
 /// Main trading system that orchestrates all agents
 pub struct TradingSystem {
@@ -29,6 +69,20 @@ pub struct TradingSystem {
     system_context: Arc<RwLock<SystemContext>>,
     shutdown_signal: Arc<RwLock<bool>>,
     thought_broadcaster: AIThoughtBroadcaster,
+    quote_book: QuoteBook,
+    protective_levels: ProtectiveLevels, // Execution's stops, read by risk for heat
+    faults: FaultInjector,
+    http: HttpClientFactory, // Pooled client shared by HTTP integrations
+    session: Arc<RwLock<SessionLog>>,
+    ab_test: Option<Arc<RwLock<AbTest>>>,
+    change_log: ConfigChangeLog,
+    decision_traces: Option<DecisionTraces>,
+    live_config: Arc<RwLock<SystemConfig>>, // Last config applied, startup or reloaded
+    config_updates: Option<watch::Receiver<SystemConfig>>,
+    heartbeats: HeartbeatMonitor,
+    health: HealthConditions, // Conditions the system health is recomputed from
+    lock_monitor: LockMonitor, // Shared by agents and the health monitor
+    metrics_server: Option<tokio::task::JoinHandle<()>>, // Prometheus exporter, once agents have started
 }
 
 /// Registry of all active agents
@@ -38,12 +92,18 @@ struct AgentRegistry {
     risk_management: Option<RiskManagementAgent>,
     execution: Option<ExecutionEngineAgent>,
     learning: Option<LearningEngineAgent>,
+    start_order: Vec<String>, // Enabled agents by config section, prerequisites first
+    readiness: HashMap<String, Readiness>, // Raised by each started agent once it is ready
+    degraded: Vec<String>,    // Non-critical agents skipped because they failed to start
 }
 
 /// Message bus for inter-agent communication
 struct MessageBus {
     sender: mpsc::UnboundedSender<AgentMessage>,
     receiver: Arc<RwLock<mpsc::UnboundedReceiver<AgentMessage>>>,
+    events: broadcast::Sender<AgentMessage>,
+    replies: ReplyRouter, // Requests awaiting a correlated reply
+    directory: AgentDirectory, // Inboxes for messages addressed to one agent
 }
 
 impl TradingSystem {
@@ -53,51 +113,26 @@ impl TradingSystem {
         
         // Validate configuration
         config.validate().map_err(TradingError::Config)?;
+        if let Some(warning) = config.trading.target_return_warning() {
+            warn!("⚠️  {}", warning);
+        }
+        
+        // Only watchlist symbols get their own series on per-symbol metrics
+        MetricsCollector::limit_symbols(&config.agents.market_intelligence.watchlist);
         
         // Create message bus
         let (sender, receiver) = mpsc::unbounded_channel();
+        let (events, _) = broadcast::channel(1024);
         let message_bus = MessageBus {
             sender,
             receiver: Arc::new(RwLock::new(receiver)),
+            events,
+            replies: ReplyRouter::new(),
+            directory: AgentDirectory::new(),
         };
         
         // Initialize system context
-        let system_context = Arc::new(RwLock::new(SystemContext {
-            market_regime: MarketRegime::Sideways,
-            portfolio: Portfolio {
-                total_value: config.trading.initial_capital,
-                cash_balance: config.trading.initial_capital,
-                positions: Default::default(),
-                daily_pnl: Default::default(),
-                total_pnl: Default::default(),
-                max_drawdown: Default::default(),
-                sharpe_ratio: None,
-                last_updated: chrono::Utc::now(),
-            },
-            risk_metrics: RiskMetrics {
-                var_95: Default::default(),
-                var_99: Default::default(),
-                expected_shortfall: Default::default(),
-                max_position_size: config.risk.max_position_size,
-                daily_loss_limit: config.risk.max_daily_loss,
-                portfolio_heat: 0.0,
-            },
-            performance_metrics: PerformanceMetrics {
-                total_trades: 0,
-                winning_trades: 0,
-                losing_trades: 0,
-                win_rate: 0.0,
-                average_win: Default::default(),
-                average_loss: Default::default(),
-                profit_factor: 0.0,
-                max_consecutive_wins: 0,
-                max_consecutive_losses: 0,
-                average_execution_time_ms: 0.0,
-            },
-            active_positions: 0,
-            available_capital: config.trading.initial_capital,
-            system_health: SystemHealth::Healthy,
-        }));
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
         
         // Initialize agent registry
         let agents = AgentRegistry {
@@ -106,10 +141,40 @@ impl TradingSystem {
             risk_management: None,
             execution: None,
             learning: None,
+            start_order: Vec::new(),
+            readiness: HashMap::new(),
+            degraded: Vec::new(),
         };
         
         // Initialize AI thought broadcaster
-        let thought_broadcaster = AIThoughtBroadcaster::new(1000); // Keep 1000 recent thoughts
+        let mut thought_broadcaster = AIThoughtBroadcaster::new(1000) // Keep 1000 recent thoughts
+            .with_supporting_data_cap(config.monitoring.max_thought_data_bytes)
+            .with_export_chunk_size(config.monitoring.thought_export_chunk_size)
+            .with_duplicate_coalescing(config.monitoring.coalesce_duplicate_thoughts);
+        let thought_log = &config.monitoring.thought_log;
+        if thought_log.enabled {
+            // Replay before attaching persistence so reloaded thoughts are not written twice
+            match thought_broadcaster.load_history(&thought_log.path, thought_log.replay_on_startup).await {
+                Ok(loaded) => info!("🧠 Reloaded {} AI thoughts from {}", loaded, thought_log.path),
+                Err(e) => warn!("⚠️ Could not reload AI thoughts from {}: {}", thought_log.path, e),
+            }
+            let rotation = ThoughtLogRotation {
+                max_bytes: thought_log.max_file_bytes,
+                daily: thought_log.rotate_daily,
+                calendar: Some(MarketCalendar::from_config(&config.trading.trading_hours)?),
+            };
+            thought_broadcaster = thought_broadcaster.with_persistence(&thought_log.path, rotation);
+        }
+        let ab_test = config.ab_test.enabled
+            .then(|| Arc::new(RwLock::new(AbTest::from_config(&config.ab_test))));
+        let change_log = ConfigChangeLog::new(config.monitoring.max_config_changes);
+        let http = HttpClientFactory::from_config(&config.api.http)?;
+        let decision_traces = config.monitoring.decision_trace.enabled
+            .then(|| DecisionTraces::from_config(&config.monitoring.decision_trace));
+        let live_config = Arc::new(RwLock::new(config.clone()));
+        let heartbeats = HeartbeatMonitor::from_config(&config.heartbeat);
+        let lock_monitor = LockMonitor::new(&config.monitoring.lock_contention);
+        let quote_book = QuoteBook::with_sources(config.agents.market_intelligence.data_sources.clone());
 
         let system = Self {
             config,
@@ -118,6 +183,20 @@ impl TradingSystem {
             system_context,
             shutdown_signal: Arc::new(RwLock::new(false)),
             thought_broadcaster,
+            quote_book,
+            protective_levels: ProtectiveLevels::default(),
+            faults: FaultInjector::new(),
+            http,
+            session: Arc::new(RwLock::new(SessionLog::new())),
+            ab_test,
+            change_log,
+            decision_traces,
+            live_config,
+            config_updates: None,
+            metrics_server: None,
+            heartbeats,
+            health: HealthConditions::new(),
+            lock_monitor,
         };
         
         info!("✅ Trading system initialized successfully");
@@ -126,66 +205,337 @@ impl TradingSystem {
     
     /// Start all enabled agents
     pub async fn start(&mut self) -> TradingResult<()> {
+        self.import_positions().await?;
+        
         info!("🚀 Starting trading system agents...");
         
+        let plan = StartupPlan::new(&self.config.agents.startup.dependencies, &self.config.agents.enabled())?
+            .with_timeout(Duration::from_secs(self.config.agents.startup.ready_timeout_secs));
+        info!("🚦 Agent start order: {}", plan.order().join(" → "));
+        
         // Start master coordinator if enabled
         if self.config.agents.master_coordinator.enabled {
             info!("🎯 Starting Master Coordinator Agent...");
-            let coordinator = MasterCoordinatorAgent::new(
-                self.config.agents.master_coordinator.clone(),
-                self.message_bus.sender.clone(),
-                self.system_context.clone(),
-            ).await?;
-            self.agents.coordinator = Some(coordinator);
+            match self.build_coordinator(&plan).await {
+                Ok(agent) => self.agents.coordinator = Some(agent),
+                Err(e) => self.skip_failed_agent("master_coordinator", e, &plan).await?,
+            }
         }
         
         // Start market intelligence agent if enabled
         if self.config.agents.market_intelligence.enabled {
             info!("📊 Starting Market Intelligence Agent...");
-            let intelligence = MarketIntelligenceAgent::new(
-                self.config.agents.market_intelligence.clone(),
-                self.config.api.clone(),
-                self.message_bus.sender.clone(),
-            ).await?;
-            self.agents.intelligence = Some(intelligence);
+            match self.build_intelligence(&plan).await {
+                Ok(agent) => self.agents.intelligence = Some(agent),
+                Err(e) => self.skip_failed_agent("market_intelligence", e, &plan).await?,
+            }
         }
         
         // Start risk management agent if enabled
         if self.config.agents.risk_management.enabled {
             info!("🛡️  Starting Risk Management Agent...");
-            let risk_agent = RiskManagementAgent::new(
-                self.config.agents.risk_management.clone(),
-                self.config.risk.clone(),
-                self.message_bus.sender.clone(),
-                self.system_context.clone(),
-            ).await?;
-            self.agents.risk_management = Some(risk_agent);
+            match self.build_risk_agent(&plan).await {
+                Ok(agent) => self.agents.risk_management = Some(agent),
+                Err(e) => self.skip_failed_agent("risk_management", e, &plan).await?,
+            }
         }
         
         // Start execution engine if enabled
         if self.config.agents.execution_engine.enabled {
             info!("⚡ Starting Execution Engine Agent...");
-            let execution = ExecutionEngineAgent::new(
-                self.config.agents.execution_engine.clone(),
-                self.config.api.clone(),
-                self.message_bus.sender.clone(),
-            ).await?;
-            self.agents.execution = Some(execution);
+            match self.build_execution(&plan).await {
+                Ok(agent) => self.agents.execution = Some(agent),
+                Err(e) => self.skip_failed_agent("execution_engine", e, &plan).await?,
+            }
         }
         
         // Start learning engine if enabled
         if self.config.agents.learning_engine.enabled {
             info!("🧠 Starting Learning Engine Agent...");
-            let learning = LearningEngineAgent::new(
-                self.config.agents.learning_engine.clone(),
-                self.message_bus.sender.clone(),
-                self.system_context.clone(),
-                self.thought_broadcaster.clone(),
-            ).await?;
-            self.agents.learning = Some(learning);
+            match self.build_learning(&plan).await {
+                Ok(agent) => self.agents.learning = Some(agent),
+                Err(e) => self.skip_failed_agent("learning_engine", e, &plan).await?,
+            }
+        }
+        
+        self.agents.start_order = plan.order().iter().filter(|name| !self.agents.degraded.contains(name)).cloned().collect();
+        self.agents.readiness = self.agents.start_order.iter().map(|name| (name.clone(), plan.gate(name).ready)).collect();
+        if self.agents.degraded.is_empty() {
+            info!("✅ All agents started successfully");
+        } else {
+            warn!("⚠️ Agents started in a degraded state without: {}", self.agents.degraded.join(", "));
+        }
+        self.start_metrics_server();
+        Ok(())
+    }
+    
+    /// Serve Prometheus metrics; a busy port costs observability, not trading
+    fn start_metrics_server(&mut self) {
+        let monitoring = &self.config.monitoring;
+        if !monitoring.metrics_enabled || self.metrics_server.is_some() {
+            return;
+        }
+        let address = SocketAddr::new(monitoring.metrics_bind_address, monitoring.prometheus_port);
+        match MetricsServer::with_address(address).bind() {
+            Ok((_, server)) => {
+                self.metrics_server = Some(tokio::spawn(async move {
+                    if let Err(e) = server.await {
+                        error!("Metrics exporter error: {}", e);
+                    }
+                }));
+            }
+            Err(e) => error!("📈 Prometheus metrics unavailable: {}", e),
+        }
+    }
+    
+    /// Non-critical agents that failed to start and were skipped
+    pub fn degraded_agents(&self) -> &[String] {
+        &self.agents.degraded
+    }
+    
+    /// Abort startup if `agent` is critical; otherwise skip it and list it as degraded
+    async fn skip_failed_agent(&mut self, agent: &str, error: TradingError, plan: &StartupPlan) -> TradingResult<()> {
+        if self.config.agents.startup.critical.iter().any(|critical| critical == agent) {
+            error!("❌ Critical agent {} failed to start: {}", agent, error);
+            return Err(error);
         }
         
-        info!("✅ All agents started successfully");
+        warn!("⚠️ Skipping {} after it failed to start: {}", agent, error);
+        MetricsCollector::record_system_error();
+        // Agents waiting on it would otherwise never start
+        plan.gate(agent).ready.mark_ready();
+        for (dependent, prerequisites) in &self.config.agents.startup.dependencies {
+            if prerequisites.iter().any(|prerequisite| prerequisite == agent) && plan.order().contains(dependent) {
+                warn!("⚠️ {} will start without its prerequisite {}", dependent, agent);
+            }
+        }
+        self.message_bus.directory.deregister(agent);
+        self.agents.degraded.push(agent.to_string());
+        Ok(())
+    }
+    
+    /// Construct the master coordinator
+    async fn build_coordinator(&self, plan: &StartupPlan) -> TradingResult<MasterCoordinatorAgent> {
+        let coordinator = MasterCoordinatorAgent::new(
+            self.config.agents.master_coordinator.clone(),
+            self.message_bus.sender.clone(),
+            self.message_bus.events.clone(),
+            self.system_context.clone(),
+        ).await?
+            .with_change_log(self.change_log.clone())
+            .with_evolution_cooldown(self.evolution_cooldown("master_coordinator"))
+            .with_startup_gate(plan.gate("master_coordinator"))
+            .with_reply_router(self.message_bus.replies.clone())
+            .with_directory(self.message_bus.directory.clone())
+            .with_heartbeat_interval(Duration::from_millis(self.config.heartbeat.interval_ms))
+            .with_lock_monitor(self.lock_monitor.clone());
+        let coordinator = match &self.decision_traces {
+            Some(traces) => coordinator.with_decision_traces(traces.clone()),
+            None => coordinator,
+        };
+        Ok(coordinator)
+    }
+    
+    /// Construct the market intelligence agent
+    async fn build_intelligence(&self, plan: &StartupPlan) -> TradingResult<MarketIntelligenceAgent> {
+        let intelligence = MarketIntelligenceAgent::new(
+            self.config.agents.market_intelligence.clone(),
+            self.config.api.clone(),
+            self.message_bus.sender.clone(),
+            self.message_bus.events.clone(),
+        ).await?
+            .with_fault_injector(self.faults.clone())
+            .with_quote_guard(self.quote_book.clone(), self.config.trading.max_quote_age_secs)
+            .with_thought_broadcaster(self.thought_broadcaster.clone())
+            .with_change_log(self.change_log.clone())
+            .with_evolution_cooldown(self.evolution_cooldown("market_intelligence"))
+            .with_startup_gate(plan.gate("market_intelligence"))
+            .with_reply_router(self.message_bus.replies.clone())
+            .with_directory(self.message_bus.directory.clone())
+            .with_heartbeat_interval(Duration::from_millis(self.config.heartbeat.interval_ms))
+            .with_lock_monitor(self.lock_monitor.clone());
+        let intelligence = match &self.decision_traces {
+            Some(traces) => intelligence.with_decision_traces(traces.clone()),
+            None => intelligence,
+        };
+        Ok(intelligence)
+    }
+    
+    /// Construct the risk management agent
+    async fn build_risk_agent(&self, plan: &StartupPlan) -> TradingResult<RiskManagementAgent> {
+        let risk_agent = RiskManagementAgent::new(
+            self.config.agents.risk_management.clone(),
+            self.config.risk.clone(),
+            self.message_bus.sender.clone(),
+            self.message_bus.events.clone(),
+            self.system_context.clone(),
+        ).await?
+            .with_thought_broadcaster(self.thought_broadcaster.clone())
+            .with_change_log(self.change_log.clone())
+            .with_evolution_cooldown(self.evolution_cooldown("risk_management"))
+            .with_startup_gate(plan.gate("risk_management"))
+            .with_reply_router(self.message_bus.replies.clone())
+            .with_directory(self.message_bus.directory.clone())
+            .with_heartbeat_interval(Duration::from_millis(self.config.heartbeat.interval_ms))
+            .with_lock_monitor(self.lock_monitor.clone())
+            .with_protective_levels(self.protective_levels.clone());
+        let risk_agent = match &self.decision_traces {
+            Some(traces) => risk_agent.with_decision_traces(traces.clone()),
+            None => risk_agent,
+        };
+        Ok(risk_agent)
+    }
+    
+    /// Construct the execution engine
+    async fn build_execution(&self, plan: &StartupPlan) -> TradingResult<ExecutionEngineAgent> {
+        let execution = ExecutionEngineAgent::new(
+            self.config.agents.execution_engine.clone(),
+            self.config.api.clone(),
+            self.message_bus.sender.clone(),
+            self.message_bus.events.clone(),
+            self.system_context.clone(),
+        ).await?
+            .with_fault_injector(self.faults.clone())
+            .with_quote_guard(self.quote_book.clone(), self.config.trading.max_quote_age_secs)
+            .with_protective_levels(self.protective_levels.clone())
+            .with_thought_broadcaster(self.thought_broadcaster.clone())
+            .with_cash_buffer(CashBuffer::from_config(&self.config.risk.min_cash_buffer))
+            .with_aggressiveness(self.config.trading.sizing_multiplier())
+            .with_commission_model(CommissionModel::from_config(
+                self.config.trading.commission_rate,
+                &self.config.trading.commission,
+            ))
+            .with_market_calendar(MarketCalendar::from_config(&self.config.trading.trading_hours)?)
+            .with_extended_hours(self.config.strategies.iter().any(|strategy| strategy.enabled && strategy.extended_hours))
+            .with_change_log(self.change_log.clone())
+            .with_evolution_cooldown(self.evolution_cooldown("execution_engine"))
+            .with_startup_gate(plan.gate("execution_engine"))
+            .with_reply_router(self.message_bus.replies.clone())
+            .with_directory(self.message_bus.directory.clone())
+            .with_heartbeat_interval(Duration::from_millis(self.config.heartbeat.interval_ms))
+            .with_lock_monitor(self.lock_monitor.clone());
+        let compliance = &self.config.risk.compliance;
+        let execution = if compliance.enabled {
+            let restricted = RestrictedList::load(&compliance.restricted_list_path).await?;
+            info!("🚫 Loaded {} restricted symbols", restricted.len());
+            execution.with_compliance(CompliancePipeline::new().with_check(restricted))
+        } else {
+            execution
+        };
+        let execution = match &self.ab_test {
+            Some(ab_test) => execution.with_ab_test(ab_test.clone()),
+            None => execution,
+        };
+        let execution = match &self.config.agents.execution_engine.slippage_monitor {
+            monitor if monitor.enabled => {
+                let modeled = self.config.trading.slippage_tolerance.to_f64().unwrap_or(0.0);
+                execution.with_slippage_monitor(SlippageMonitor::new(modeled, monitor))
+            }
+            _ => execution,
+        };
+        let execution = match &self.decision_traces {
+            Some(traces) => execution.with_decision_traces(traces.clone()),
+            None => execution,
+        };
+        let moomoo = &self.config.api.moomoo;
+        let execution = if moomoo.use_gateway {
+            let api_client = ApiClient::new(moomoo, self.http.client());
+            info!("📨 Routing orders to OpenD at {} ({:?})", moomoo.base_url, api_client.environment());
+            execution
+                .with_order_status_source(Arc::new(api_client.clone()))
+                .with_option_quotes(Arc::new(api_client.clone()))
+                .with_api_client(api_client)
+        } else {
+            execution
+        };
+        let execution = match &self.config.agents.execution_engine.journal {
+            journal if journal.enabled => execution.with_trade_journal(TradeJournal::from_config(journal)),
+            _ => execution,
+        };
+        let take_profit = self.config.strategies.iter().find(|strategy| strategy.enabled && strategy.take_profit.is_some());
+        let execution = match take_profit {
+            Some(strategy) => {
+                info!("🎯 Scaling out of held positions per the {} take-profit plan", strategy.name);
+                execution.with_take_profit_plan(strategy.take_profit.clone().unwrap_or_default())
+            }
+            None => execution,
+        };
+        // Validation keeps the main book on paper, so the canary is the only live flow
+        let canary = &self.config.agents.execution_engine.canary;
+        let execution = if canary.enabled {
+            warn!(
+                "🐤 Canary mirroring {:.1}% of each paper order to the live account at {}",
+                canary.size_fraction * 100.0, moomoo.base_url
+            );
+            let venue = ApiClient::live(moomoo, self.http.client());
+            execution.with_canary(CanaryRouter::new(canary.clone(), Arc::new(venue)))
+        } else {
+            execution
+        };
+        let protective_levels = &self.config.agents.execution_engine.protective_levels;
+        let execution = if protective_levels.enabled {
+            let execution = execution.with_protection_store(ProtectionStore::new(&protective_levels.path));
+            let restored = execution.restore_protective_levels().await?;
+            if restored > 0 {
+                info!("🛡️ Re-armed protective levels for {} positions", restored);
+            }
+            execution
+        } else {
+            execution
+        };
+        Ok(execution)
+    }
+    
+    /// Construct the learning engine
+    async fn build_learning(&self, plan: &StartupPlan) -> TradingResult<LearningEngineAgent> {
+        let learning = LearningEngineAgent::new(
+            self.config.agents.learning_engine.clone(),
+            self.message_bus.sender.clone(),
+            self.message_bus.events.clone(),
+            self.system_context.clone(),
+            self.thought_broadcaster.clone(),
+        ).await?
+            .with_market_calendar(MarketCalendar::from_config(&self.config.trading.trading_hours)?)
+            .with_change_log(self.change_log.clone())
+            .with_evolution_cooldown(self.evolution_cooldown("learning_engine"))
+            .with_startup_gate(plan.gate("learning_engine"))
+            .with_reply_router(self.message_bus.replies.clone())
+            .with_directory(self.message_bus.directory.clone())
+            .with_heartbeat_interval(Duration::from_millis(self.config.heartbeat.interval_ms))
+            .with_lock_monitor(self.lock_monitor.clone());
+        Ok(learning)
+    }
+    
+    /// Evolution cooldown for the agent configured under `agent`
+    fn evolution_cooldown(&self, agent: &str) -> EvolutionCooldown {
+        EvolutionCooldown::new(Duration::from_secs(self.config.agents.evolution_cooldowns.cooldown_secs(agent)))
+    }
+    
+    /// Seed the portfolio with positions already held, so they are managed from the start
+    async fn import_positions(&self) -> TradingResult<()> {
+        let import = &self.config.trading.position_import;
+        if !import.enabled {
+            return Ok(());
+        }
+        
+        let moomoo = &self.config.api.moomoo;
+        let source: Box<dyn PositionSource> = match &import.file {
+            Some(file) if moomoo.paper_trading => Box::new(FilePositionSource::new(file)),
+            _ if moomoo.use_gateway => {
+                info!("📥 Importing positions held at OpenD");
+                Box::new(ApiClient::new(moomoo, self.http.client()))
+            }
+            _ => {
+                warn!("📥 Position import enabled but neither an import file nor the OpenD gateway is configured");
+                return Ok(());
+            }
+        };
+        
+        let positions = source.fetch_positions().await?;
+        let mut context = self.system_context.write().await;
+        let imported = context.portfolio.import_positions(positions)?;
+        context.active_positions = context.portfolio.positions.len() as u32;
+        info!("📥 Imported {} existing positions", imported);
         Ok(())
     }
     
@@ -197,61 +547,112 @@ impl TradingSystem {
         let message_receiver = self.message_bus.receiver.clone();
         let system_context = self.system_context.clone();
         let shutdown_signal = self.shutdown_signal.clone();
+        let faults = self.faults.clone();
+        let routing = RoutingState {
+            performance: RollingPerformance::from_config(&self.config.monitoring.performance_window),
+            strategies: self.config.monitoring.per_strategy_metrics.then(StrategyPerformance::new),
+            decode_failures: DecodeFailures::new(self.config.monitoring.max_decode_failures, self.health.clone()),
+            replies: self.message_bus.replies.clone(),
+            events: self.message_bus.events.clone(),
+            directory: self.message_bus.directory.clone(),
+            heartbeats: self.heartbeats.clone(),
+            ab_test: self.ab_test.clone(),
+        };
+        let session = self.session.clone();
         
         let message_task = tokio::spawn(async move {
-            Self::process_messages(message_receiver, system_context, shutdown_signal).await
+            Self::process_messages(message_receiver, system_context, shutdown_signal, faults, routing, session).await
         });
         
         // Start system monitoring task
         let monitoring_task = tokio::spawn({
             let system_context = self.system_context.clone();
             let shutdown_signal = self.shutdown_signal.clone();
+            let lock_monitor = self.lock_monitor.clone();
+            let calendar = MarketCalendar::from_config(&self.config.trading.trading_hours)?;
+            let session = self.session.clone();
+            let checks = HealthChecks {
+                reconciler: self.config.risk.reconciliation.enabled
+                    .then(|| PortfolioReconciler::new(self.config.risk.reconciliation.tolerance)),
+                heartbeats: self.heartbeats.clone(),
+                conditions: self.health.clone(),
+            };
             async move {
-                Self::monitor_system_health(system_context, shutdown_signal).await
+                Self::monitor_system_health(system_context, shutdown_signal, checks, lock_monitor, calendar, session).await
             }
         });
         
-        // Start agents
-        let mut agent_tasks = Vec::new();
-        
-        if let Some(ref mut coordinator) = self.agents.coordinator {
-            let task = tokio::spawn({
-                let mut agent = coordinator.clone();
-                async move { agent.run().await }
+        // Apply config edits as the watcher reports them
+        if let Some(mut updates) = self.config_updates.take() {
+            let live_config = self.live_config.clone();
+            let sender = self.message_bus.sender.clone();
+            tokio::spawn(async move {
+                while updates.changed().await.is_ok() {
+                    let config = updates.borrow_and_update().clone();
+                    if let Err(e) = Self::reload_config(&live_config, &sender, config).await {
+                        error!("❌ Config reload rejected: {}", e);
+                    }
+                }
             });
-            agent_tasks.push(task);
         }
         
-        if let Some(ref mut intelligence) = self.agents.intelligence {
-            let task = tokio::spawn({
-                let mut agent = intelligence.clone();
-                async move { agent.run().await }
+        // Start HTTP API if enabled
+        if self.config.interfaces.http.enabled {
+            let server = HttpApiServer::new(
+                self.config.interfaces.http.clone(),
+                self.system_context.clone(),
+            )
+                .with_change_log(self.change_log.clone())
+                .with_heartbeats(self.heartbeats.clone())
+                .with_degraded_agents(self.agents.degraded.clone());
+            tokio::spawn(async move {
+                if let Err(e) = server.serve().await {
+                    error!("HTTP API error: {}", e);
+                }
             });
-            agent_tasks.push(task);
         }
         
-        if let Some(ref mut risk_agent) = self.agents.risk_management {
-            let task = tokio::spawn({
-                let mut agent = risk_agent.clone();
-                async move { agent.run().await }
+        // Stream AI thoughts to dashboards if enabled
+        if self.config.interfaces.thought_stream.enabled {
+            let server = ThoughtStreamServer::new(
+                self.config.interfaces.thought_stream.clone(),
+                self.thought_broadcaster.clone(),
+            );
+            tokio::spawn(async move {
+                if let Err(e) = server.serve().await {
+                    error!("AI thought stream error: {}", e);
+                }
             });
-            agent_tasks.push(task);
         }
         
-        if let Some(ref mut execution) = self.agents.execution {
-            let task = tokio::spawn({
-                let mut agent = execution.clone();
-                async move { agent.run().await }
-            });
-            agent_tasks.push(task);
+        // Start agents, at most `runtime.max_concurrent_agents` at once, restarting any that fail
+        let limiter = AgentLimiter::new(self.config.runtime.max_concurrent_agents);
+        let supervisor = AgentSupervisor::new(&self.config.supervision, self.message_bus.sender.clone());
+        let mut agent_tasks = Vec::new();
+        
+        // Expect heartbeats from each agent once it reports ready; until then
+        // it may still be waiting on its prerequisites
+        for name in &self.agents.start_order {
+            if let (Some(id), Some(readiness)) = (self.message_bus.directory.id_of(name), self.agents.readiness.get(name)) {
+                let (heartbeats, readiness, name) = (self.heartbeats.clone(), readiness.clone(), name.clone());
+                tokio::spawn(async move {
+                    readiness.wait().await;
+                    heartbeats.watch(&name, id, chrono::Utc::now());
+                });
+            }
         }
         
-        if let Some(ref mut learning) = self.agents.learning {
-            let task = tokio::spawn({
-                let mut agent = learning.clone();
-                async move { agent.run().await }
-            });
-            agent_tasks.push(task);
+        // Spawn prerequisites first; each agent still waits for their readiness
+        for name in &self.agents.start_order {
+            let task = match name.as_str() {
+                "master_coordinator" => self.agents.coordinator.clone().map(|agent| limiter.spawn(supervised(&supervisor, "master_coordinator", agent, self.faults.clone()))),
+                "market_intelligence" => self.agents.intelligence.clone().map(|agent| limiter.spawn(supervised(&supervisor, "market_intelligence", agent, self.faults.clone()))),
+                "risk_management" => self.agents.risk_management.clone().map(|agent| limiter.spawn(supervised(&supervisor, "risk_management", agent, self.faults.clone()))),
+                "execution_engine" => self.agents.execution.clone().map(|agent| limiter.spawn(supervised(&supervisor, "execution_engine", agent, self.faults.clone()))),
+                "learning_engine" => self.agents.learning.clone().map(|agent| limiter.spawn(supervised(&supervisor, "learning_engine", agent, self.faults.clone()))),
+                _ => None,
+            };
+            agent_tasks.extend(task);
         }
         
         // Wait for shutdown signal or task completion
@@ -301,6 +702,37 @@ impl TradingSystem {
             coordinator.shutdown().await?;
         }
         
+        if let Some(metrics_server) = self.metrics_server.take() {
+            metrics_server.abort();
+        }
+        
+        let report_config = &self.config.monitoring.daily_report;
+        if report_config.enabled {
+            let report = self.generate_daily_report().await;
+            info!("📋 {}", report.to_text());
+            if report_config.deliver_via_alerts {
+                if let Err(e) = report.deliver(&self.config.monitoring.alerts, &self.http.client()).await {
+                    warn!("Failed to deliver daily report: {}", e);
+                }
+            }
+        }
+        
+        if let Some(report) = self.ab_test_report().await {
+            info!(
+                "🧪 A/B test {} vs {}: {} vs {} trades, mean P&L {:.2} vs {:.2}, winner: {}",
+                report.variant_a,
+                report.variant_b,
+                report.scorecard_a.trades,
+                report.scorecard_b.trades,
+                report.scorecard_a.mean_pnl(),
+                report.scorecard_b.mean_pnl(),
+                match report.winner {
+                    Some(variant) => format!("{:?}", variant),
+                    None => "not yet significant".to_string(),
+                }
+            );
+        }
+        
         info!("✅ System shutdown completed");
         Ok(())
     }
@@ -310,11 +742,83 @@ impl TradingSystem {
         &self.thought_broadcaster
     }
     
+    /// Per-signal pipeline verdicts, when decision tracing is enabled
+    pub fn decision_traces(&self) -> Option<&DecisionTraces> {
+        self.decision_traces.as_ref()
+    }
+    
+    /// Compile the daily report for the current session
+    pub async fn generate_daily_report(&self) -> DailyReport {
+        let config = &self.config.monitoring.daily_report;
+        let performance = self.system_context.read().await.performance_metrics.clone();
+        let thoughts = self.thought_broadcaster.get_recent_thoughts(1000).await;
+        let session = self.session.read().await;
+        DailyReport::generate(&session, &performance, &thoughts, config)
+    }
+    
+    /// Apply configs produced by `SystemConfig::watch` while the system runs
+    pub fn watch_config(&mut self, updates: watch::Receiver<SystemConfig>) {
+        self.config_updates = Some(updates);
+    }
+    
+    /// Validate a new config, diff it against the running one and propagate
+    /// the changed settings to agents over the message bus.
+    ///
+    /// Nothing is applied if the config is invalid. Changed settings that
+    /// require a restart are logged and returned in `restart_required`, and
+    /// keep their running values until then.
+    pub async fn apply_config(&self, new: SystemConfig) -> TradingResult<ConfigUpdate> {
+        Self::reload_config(&self.live_config, &self.message_bus.sender, new).await
+    }
+    
+    async fn reload_config(
+        live_config: &Arc<RwLock<SystemConfig>>,
+        sender: &mpsc::UnboundedSender<AgentMessage>,
+        new: SystemConfig,
+    ) -> TradingResult<ConfigUpdate> {
+        // Held throughout so concurrent reloads are diffed against each other's result
+        let mut live = live_config.write().await;
+        let update = plan_reload(&live, &new)?;
+        if !update.restart_required.is_empty() {
+            warn!("⚠️  Config changes not applied until restart: {}", update.restart_required.join(", "));
+        }
+        if update.changes.is_empty() {
+            return Ok(update);
+        }
+        
+        let applied = update.apply_to(&live)?;
+        let message = AgentMessage::broadcast(uuid::Uuid::nil(), crate::core::types::MessageType::ConfigUpdate, serde_json::to_value(&update)?);
+        sender
+            .send(message)
+            .map_err(|_| TradingError::agent_communication("Failed to send config update"))?;
+        
+        info!("🔄 Applied config changes: {:?}", update.changes.keys().collect::<Vec<_>>());
+        MetricsCollector::limit_symbols(&applied.agents.market_intelligence.watchlist);
+        *live = applied;
+        Ok(update)
+    }
+    
+    /// Current A/B comparison, when a test is running
+    pub async fn ab_test_report(&self) -> Option<AbReport> {
+        match &self.ab_test {
+            Some(ab_test) => Some(ab_test.read().await.report()),
+            None => None,
+        }
+    }
+    
+    /// Get the fault injector shared with agents, for resilience testing
+    pub fn fault_injector(&self) -> FaultInjector {
+        self.faults.clone()
+    }
+    
     /// Process inter-agent messages
     async fn process_messages(
         receiver: Arc<RwLock<mpsc::UnboundedReceiver<AgentMessage>>>,
         system_context: Arc<RwLock<SystemContext>>,
         shutdown_signal: Arc<RwLock<bool>>,
+        faults: FaultInjector,
+        mut routing: RoutingState,
+        session: Arc<RwLock<SessionLog>>,
     ) -> TradingResult<()> {
         info!("📨 Starting message processing loop...");
         
@@ -334,6 +838,9 @@ impl TradingSystem {
             };
             
             match message {
+                Some(msg) if faults.should_drop_message() => {
+                    warn!("🧪 Fault injection dropped {:?} message", msg.message_type);
+                }
                 Some(msg) => {
                     MetricsCollector::record_agent_message();
                     
@@ -346,7 +853,7 @@ impl TradingSystem {
                         }
                         _ => {
                             // Route message to appropriate handler
-                            Self::route_message(msg, &system_context).await?;
+                            Self::route_message(msg, &system_context, &mut routing, &session).await?;
                         }
                     }
                 }
@@ -365,18 +872,81 @@ impl TradingSystem {
     async fn route_message(
         message: AgentMessage,
         system_context: &Arc<RwLock<SystemContext>>,
+        routing: &mut RoutingState,
+        session: &Arc<RwLock<SessionLog>>,
     ) -> TradingResult<()> {
+        // Heartbeats are for the system alone
+        if let crate::core::types::MessageType::Heartbeat = message.message_type {
+            routing.heartbeats.beat(message.from, message.timestamp);
+            return Ok(());
+        }
+        
+        // Addressed messages go to that agent alone; nil means everyone
+        if message.to.is_nil() {
+            // No subscribers is not an error
+            let _ = routing.events.send(message.clone());
+        } else if let Err(e) = routing.directory.deliver(message.clone()) {
+            warn!("📨 Dropped {:?} message: {}", message.message_type, e);
+        }
+        
+        // Replies reach their requester and are then routed like any other message
+        if message.is_reply() && !routing.replies.deliver(message.clone()).await {
+            warn!("📨 No request awaiting {:?} reply {:?}", message.message_type, message.correlation_id);
+        }
+        
         match message.message_type {
             crate::core::types::MessageType::PerformanceUpdate => {
                 // Update system context with performance data
-                if let Ok(metrics) = serde_json::from_value::<PerformanceMetrics>(message.payload) {
-                    let mut context = system_context.write().await;
-                    context.performance_metrics = metrics;
+                match serde_json::from_value::<PerformanceMetrics>(message.payload) {
+                    Ok(metrics) => {
+                        let mut context = system_context.write().await;
+                        routing.decode_failures.reset(&mut context);
+                        context.performance_metrics = metrics;
+                        context.last_updated = chrono::Utc::now();
+                    }
+                    Err(e) => routing.decode_failures.record("PerformanceUpdate", e, system_context).await,
+                }
+            }
+            crate::core::types::MessageType::TradeClosed => {
+                // Recompute windowed metrics so gauges move smoothly rather than per trade
+                match serde_json::from_value::<TradeRecord>(message.payload) {
+                    Ok(trade) => {
+                        routing.decode_failures.reset(&mut *system_context.write().await);
+                        if let Some(strategies) = &mut routing.strategies {
+                            if let (Some(name), Some(tally)) = (&trade.strategy, strategies.record(&trade)) {
+                                MetricsCollector::update_strategy_metrics(name, tally.win_rate(), tally.pnl, tally.trades);
+                            }
+                        }
+                        routing.record_ab_outcome(&trade).await;
+                        routing.performance.record(trade);
+                        let metrics = routing.performance.metrics(chrono::Utc::now());
+                        MetricsCollector::update_rolling_performance(metrics.win_rate, metrics.profit_factor);
+                        
+                        let mut context = system_context.write().await;
+                        context.performance_metrics = metrics;
+                        context.last_updated = chrono::Utc::now();
+                    }
+                    Err(e) => routing.decode_failures.record("TradeClosed", e, system_context).await,
+                }
+            }
+            crate::core::types::MessageType::OrderExecution => {
+                if let Ok(result) = serde_json::from_value::<ExecutionResult>(message.payload) {
+                    session.write().await.record_trade(result);
                 }
             }
             crate::core::types::MessageType::RiskAlert => {
                 warn!("🚨 Risk alert received: {:?}", message.payload);
                 MetricsCollector::record_system_error();
+                session.write().await.record_risk_event(message.payload.to_string());
+            }
+            crate::core::types::MessageType::SystemFeedback => {
+                if let Ok(feedback) = serde_json::from_value::<SystemFeedback>(message.payload) {
+                    session.write().await.record_strategy_change(format!(
+                        "Evolution feedback (score {:.2}): {}",
+                        feedback.performance_score,
+                        feedback.suggestions.join("; ")
+                    ));
+                }
             }
             _ => {
                 // Handle other message types as needed
@@ -390,6 +960,10 @@ impl TradingSystem {
     async fn monitor_system_health(
         system_context: Arc<RwLock<SystemContext>>,
         shutdown_signal: Arc<RwLock<bool>>,
+        checks: HealthChecks,
+        lock_monitor: LockMonitor,
+        calendar: MarketCalendar,
+        session: Arc<RwLock<SessionLog>>,
     ) -> TradingResult<()> {
         info!("🏥 Starting system health monitoring...");
         
@@ -408,7 +982,13 @@ impl TradingSystem {
             
             // Update metrics
             {
-                let context = system_context.read().await;
+                let context = match lock_monitor.read(&system_context, "system_context").await {
+                    Ok(context) => context,
+                    Err(e) => {
+                        warn!("Skipping health check: {}", e);
+                        continue;
+                    }
+                };
                 
                 MetricsCollector::update_portfolio_metrics(
                     context.portfolio.total_value,
@@ -418,12 +998,61 @@ impl TradingSystem {
                     context.active_positions,
                 );
                 
+                MetricsCollector::update_symbol_pnl(context.portfolio.positions.values().map(|position| {
+                    (position.symbol.as_str(), position.unrealized_pnl + position.realized_pnl)
+                }));
+                
                 MetricsCollector::update_risk_metrics(
                     context.risk_metrics.portfolio_heat,
                     context.portfolio.max_drawdown,
                     context.risk_metrics.var_95,
                 );
             }
+            
+            // Agents that stopped beating hold the system at Degraded
+            checks.heartbeats.check(&checks.conditions, chrono::Utc::now());
+            
+            // Sample the equity curve and mark the context as fresh
+            {
+                let mut context = match lock_monitor.write(&system_context, "system_context").await {
+                    Ok(context) => context,
+                    Err(e) => {
+                        warn!("Skipping equity sample: {}", e);
+                        continue;
+                    }
+                };
+                let now = chrono::Utc::now();
+                let trading_day = calendar.trading_day(now);
+                if context.portfolio.roll_trading_day(trading_day) {
+                    info!("🌅 New trading day {}: daily P&L reset, total P&L {}", trading_day, context.portfolio.total_pnl);
+                }
+                if session.write().await.roll_trading_day(trading_day) {
+                    info!("📋 Session log started for {}", trading_day);
+                }
+                let total_value = context.portfolio.total_value;
+                let cash_balance = context.portfolio.cash_balance;
+                context.equity_curve.record(now, total_value, cash_balance);
+                context.last_updated = now;
+            }
+            
+            // Verify portfolio accounting invariant, then recompute health so
+            // conditions that have cleared stop counting against it
+            let Ok(mut context) = lock_monitor.write(&system_context, "system_context").await else {
+                continue;
+            };
+            if let Some(ref reconciler) = checks.reconciler {
+                match reconciler.check(&context.portfolio) {
+                    Ok(()) => {
+                        checks.conditions.clear(RECONCILIATION_FAILED);
+                    }
+                    Err(violation) => {
+                        error!("🚨 Portfolio reconciliation failed: {}", violation);
+                        MetricsCollector::record_system_error();
+                        checks.conditions.raise(RECONCILIATION_FAILED, SystemHealth::Warning);
+                    }
+                }
+            }
+            checks.conditions.apply(&mut context);
         }
         
         info!("🏥 System health monitoring ended");
@@ -431,6 +1060,99 @@ impl TradingSystem {
     }
 }
 
+/// Checks the health monitor runs each tick, and the conditions they raise
+struct HealthChecks {
+    reconciler: Option<PortfolioReconciler>,
+    heartbeats: HeartbeatMonitor,
+    conditions: HealthConditions,
+}
+
+/// State carried across routed messages
+struct RoutingState {
+    performance: RollingPerformance,
+    strategies: Option<StrategyPerformance>,
+    decode_failures: DecodeFailures,
+    replies: ReplyRouter,
+    events: broadcast::Sender<AgentMessage>, // Broadcast to all subscribed agents
+    directory: AgentDirectory,
+    heartbeats: HeartbeatMonitor,
+    ab_test: Option<Arc<RwLock<AbTest>>>, // Scored as trades close
+}
+
+impl RoutingState {
+    /// Score a closed trade against the A/B variant its order was allocated
+    /// to, dropping the assignment once scored
+    async fn record_ab_outcome(&self, trade: &TradeRecord) {
+        if let (Some(ab_test), Some(order_id)) = (&self.ab_test, &trade.order_id) {
+            if let Some(variant) = ab_test.write().await.record_outcome(order_id, trade.pnl) {
+                info!("🧪 Scored order {} for variant {:?}: {}", order_id, variant, trade.pnl);
+            }
+        }
+    }
+}
+
+/// Consecutive message payloads that failed to deserialize. A schema mismatch
+/// would otherwise stop metrics updating without any sign of trouble.
+#[derive(Debug, Clone)]
+struct DecodeFailures {
+    threshold: u64,
+    consecutive: u64,
+    health: HealthConditions,
+}
+
+impl DecodeFailures {
+    fn new(threshold: u64, health: HealthConditions) -> Self {
+        Self { threshold: threshold.max(1), consecutive: 0, health }
+    }
+    
+    /// A payload decoded successfully, lifting any degradation from earlier failures
+    fn reset(&mut self, context: &mut SystemContext) {
+        self.consecutive = 0;
+        if self.health.clear(UNDECODABLE_PAYLOADS) {
+            info!("✅ Message payloads are decoding again");
+            self.health.apply(context);
+        }
+    }
+    
+    /// Count a failed payload, degrading system health once the threshold is reached
+    async fn record(&mut self, kind: &str, error: serde_json::Error, system_context: &Arc<RwLock<SystemContext>>) {
+        self.consecutive += 1;
+        MetricsCollector::record_decode_failure();
+        warn!("Failed to decode {} payload: {}", kind, error);
+        
+        if self.consecutive >= self.threshold {
+            error!(
+                "🚨 {} consecutive message payloads failed to decode (latest {}: {}) - marking system Degraded",
+                self.consecutive, kind, error
+            );
+            self.health.raise(UNDECODABLE_PAYLOADS, SystemHealth::Degraded);
+            self.health.apply(&mut *system_context.write().await);
+        }
+    }
+}
+
+/// Run `agent` under the supervisor, relaunching the same instance after each
+/// failure. Whatever it picked up while running, such as reloaded limits,
+/// evolved parameters and generated strategies, carries over to the restart.
+fn supervised<A: AutonomousAgent + 'static>(
+    supervisor: &AgentSupervisor,
+    name: &'static str,
+    agent: A,
+    faults: FaultInjector,
+) -> impl std::future::Future<Output = TradingResult<()>> + Send + 'static {
+    let agent = Arc::new(Mutex::new(agent));
+    supervisor.clone().supervise(name, move || {
+        let (agent, faults) = (agent.clone(), faults.clone());
+        async move {
+            let mut agent = agent.lock_owned().await;
+            tokio::select! {
+                result = agent.run() => result,
+                _ = faults.agent_crashed(name) => panic!("Injected crash of agent {}", name),
+            }
+        }
+    })
+}
+
 impl MessageBus {
     /// Send a message to all agents
     pub fn broadcast(&self, message: AgentMessage) -> TradingResult<()> {
@@ -439,3 +1161,322 @@ impl MessageBus {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_repeated_malformed_payloads_degrade_health() {
+        let config = SystemConfig::default();
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let session = Arc::new(RwLock::new(SessionLog::new()));
+        let mut routing = RoutingState {
+            performance: RollingPerformance::from_config(&config.monitoring.performance_window),
+            strategies: None,
+            decode_failures: DecodeFailures::new(3, HealthConditions::new()),
+            replies: ReplyRouter::new(),
+            events: broadcast::channel(16).0,
+            directory: AgentDirectory::new(),
+            heartbeats: HeartbeatMonitor::new(chrono::Duration::seconds(15)),
+            ab_test: None,
+        };
+        let malformed = || AgentMessage::broadcast(uuid::Uuid::new_v4(), crate::core::types::MessageType::PerformanceUpdate, serde_json::json!({ "win_rate": "not a number" }));
+
+        for expected in 1..=2 {
+            TradingSystem::route_message(malformed(), &system_context, &mut routing, &session).await.unwrap();
+            assert_eq!(routing.decode_failures.consecutive, expected);
+            assert!(matches!(system_context.read().await.system_health, SystemHealth::Healthy));
+        }
+
+        TradingSystem::route_message(malformed(), &system_context, &mut routing, &session).await.unwrap();
+        assert_eq!(routing.decode_failures.consecutive, 3);
+        assert!(matches!(system_context.read().await.system_health, SystemHealth::Degraded));
+
+        // A payload that decodes again brings health back
+        let valid = AgentMessage::broadcast(
+            uuid::Uuid::new_v4(),
+            crate::core::types::MessageType::PerformanceUpdate,
+            serde_json::to_value(SystemContext::initial(&config).performance_metrics).unwrap(),
+        );
+        TradingSystem::route_message(valid, &system_context, &mut routing, &session).await.unwrap();
+        assert_eq!(routing.decode_failures.consecutive, 0);
+        assert!(matches!(system_context.read().await.system_health, SystemHealth::Healthy));
+    }
+
+    #[tokio::test]
+    async fn test_closed_trades_update_per_strategy_gauges() {
+        use crate::core::metrics::{STRATEGY_PNL, STRATEGY_TRADES, STRATEGY_WIN_RATE};
+        use rust_decimal::Decimal;
+        
+        let config = SystemConfig::default();
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let session = Arc::new(RwLock::new(SessionLog::new()));
+        let mut routing = RoutingState {
+            performance: RollingPerformance::from_config(&config.monitoring.performance_window),
+            strategies: Some(StrategyPerformance::new()),
+            decode_failures: DecodeFailures::new(3, HealthConditions::new()),
+            replies: ReplyRouter::new(),
+            events: broadcast::channel(16).0,
+            directory: AgentDirectory::new(),
+            heartbeats: HeartbeatMonitor::new(chrono::Duration::seconds(15)),
+            ab_test: Some(Arc::new(RwLock::new(AbTest::from_config(&config.ab_test)))),
+        };
+        let opened = uuid::Uuid::new_v4();
+        if let Some(ab_test) = &routing.ab_test {
+            ab_test.write().await.assign(opened, crate::agents::ab_test::Variant::B);
+        }
+        let closed = |strategy: &str, pnl: i64| AgentMessage::broadcast(
+            uuid::Uuid::new_v4(),
+            crate::core::types::MessageType::TradeClosed,
+            serde_json::to_value(TradeRecord {
+                timestamp: chrono::Utc::now(),
+                pnl: Decimal::from(pnl),
+                execution_time_ms: 1.0,
+                strategy: Some(strategy.to_string()),
+                order_id: Some(opened),
+                notional: Decimal::ZERO,
+                costs: Decimal::ZERO,
+                holding_secs: 0,
+                source: None,
+            }).unwrap(),
+        );
+        
+        // Labels unique to this test, since the registry is process-wide
+        for (strategy, pnl) in [("gauge_test_momentum", 30), ("gauge_test_reversion", -10), ("gauge_test_momentum", -10)] {
+            TradingSystem::route_message(closed(strategy, pnl), &system_context, &mut routing, &session).await.unwrap();
+        }
+        
+        assert_eq!(STRATEGY_TRADES.with_label_values(&["gauge_test_momentum"]).get(), 2.0);
+        assert_eq!(STRATEGY_WIN_RATE.with_label_values(&["gauge_test_momentum"]).get(), 0.5);
+        assert_eq!(STRATEGY_PNL.with_label_values(&["gauge_test_momentum"]).get(), 20.0);
+        assert_eq!(STRATEGY_TRADES.with_label_values(&["gauge_test_reversion"]).get(), 1.0);
+        assert_eq!(STRATEGY_WIN_RATE.with_label_values(&["gauge_test_reversion"]).get(), 0.0);
+        assert_eq!(STRATEGY_PNL.with_label_values(&["gauge_test_reversion"]).get(), -10.0);
+        
+        // The opening order's variant is scored once, on the first close
+        let ab_test = routing.ab_test.as_ref().unwrap().read().await;
+        assert_eq!(ab_test.scorecard(crate::agents::ab_test::Variant::B).trades, 1);
+    }
+    
+    #[tokio::test]
+    async fn test_config_reload_validates_before_propagating() {
+        use crate::agents::traits::RiskManager;
+        use rust_decimal::Decimal;
+        
+        let mut config = SystemConfig::default();
+        config.api.moomoo.api_key = "test".to_string();
+        let system = TradingSystem::new(config.clone()).await.unwrap();
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (events, _) = broadcast::channel(16);
+        let mut risk = RiskManagementAgent::new(
+            config.agents.risk_management.clone(),
+            config.risk.clone(),
+            sender,
+            events,
+            system.system_context.clone(),
+        ).await.unwrap();
+        
+        // An edit that fails validation is rejected outright, even alongside a reloadable change
+        let mut invalid = config.clone();
+        invalid.risk.max_daily_loss = Decimal::from(5);
+        invalid.trading.initial_capital = Decimal::ZERO;
+        assert!(system.apply_config(invalid).await.is_err());
+        
+        // Settings wired in at startup are reported as needing a restart and
+        // keep their running values; the reloadable edit alongside still applies
+        let mut edited = config.clone();
+        edited.risk.max_daily_loss = Decimal::from(5);
+        edited.agents.market_intelligence.enabled = false;
+        let update = system.apply_config(edited.clone()).await.unwrap();
+        assert_eq!(update.changes.keys().collect::<Vec<_>>(), vec!["risk.max_daily_loss"]);
+        assert_eq!(update.restart_required, vec!["agents.market_intelligence.enabled"]);
+        let live = system.live_config.read().await.clone();
+        assert_eq!(live.risk.max_daily_loss, Decimal::from(5));
+        assert!(live.agents.market_intelligence.enabled);
+        
+        // The pending setting is reported again until the system restarts
+        let update = system.apply_config(edited).await.unwrap();
+        assert!(update.changes.is_empty());
+        assert_eq!(update.restart_required, vec!["agents.market_intelligence.enabled"]);
+        
+        // Only the accepted edit reached the bus, and the risk agent picks it up
+        let mut receiver = system.message_bus.receiver.write().await;
+        let message = receiver.try_recv().unwrap();
+        assert!(receiver.try_recv().is_err());
+        assert!(matches!(message.message_type, crate::core::types::MessageType::ConfigUpdate));
+        
+        assert_eq!(risk.calculate_risk().await.unwrap().daily_loss_limit, config.risk.max_daily_loss);
+        risk.handle_event(message).await.unwrap();
+        assert_eq!(risk.calculate_risk().await.unwrap().daily_loss_limit, Decimal::from(5));
+        
+        // Re-applying the running config is a no-op
+        let running = system.live_config.read().await.clone();
+        assert!(system.apply_config(running).await.unwrap().is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_request_resolves_with_routed_reply_or_times_out() {
+        use crate::agents::traits::BaseAgent;
+        use crate::core::types::MessageType;
+        
+        let config = SystemConfig::default();
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let session = Arc::new(RwLock::new(SessionLog::new()));
+        let replies = ReplyRouter::new();
+        let mut routing = RoutingState {
+            performance: RollingPerformance::from_config(&config.monitoring.performance_window),
+            strategies: None,
+            decode_failures: DecodeFailures::new(3, HealthConditions::new()),
+            replies: replies.clone(),
+            events: broadcast::channel(16).0,
+            directory: AgentDirectory::new(),
+            heartbeats: HeartbeatMonitor::new(chrono::Duration::seconds(15)),
+            ab_test: None,
+        };
+        let (sender, mut bus) = mpsc::unbounded_channel();
+        let (events, _) = broadcast::channel(16);
+        let mut requester = BaseAgent::new(Vec::new(), sender, events, system_context.clone());
+        requester.replies = Some(replies.clone());
+        let request = |payload| AgentMessage::broadcast(requester.id, MessageType::OrderRequest, payload);
+        
+        // The reply routed through the system reaches the waiting requester
+        let pending = tokio::spawn({
+            let requester = requester.clone();
+            let message = request(serde_json::json!({ "order": 1 }));
+            async move { requester.request(message, Duration::from_secs(5)).await }
+        });
+        let sent = bus.recv().await.unwrap();
+        assert_eq!(sent.reply_to, Some(requester.id));
+        let reply = sent.reply(uuid::Uuid::new_v4(), MessageType::OrderExecution, serde_json::json!({ "ack": 1 }));
+        assert_eq!(reply.to, requester.id);
+        TradingSystem::route_message(reply, &system_context, &mut routing, &session).await.unwrap();
+        let received = pending.await.unwrap().unwrap();
+        assert_eq!(received.correlation_id, sent.correlation_id);
+        assert_eq!(received.payload["ack"], 1);
+        
+        // Without a reply the request times out and stops waiting
+        let error = requester.request(request(serde_json::json!({ "order": 2 })), Duration::from_millis(20)).await.unwrap_err();
+        assert!(matches!(error, TradingError::Timeout { .. }), "{}", error);
+        assert_eq!(replies.pending().await, 0);
+        
+        // A reply arriving after the timeout finds nobody waiting
+        let late = bus.recv().await.unwrap().reply(uuid::Uuid::new_v4(), MessageType::OrderExecution, serde_json::json!({}));
+        assert!(!replies.deliver(late).await);
+    }
+    
+    #[tokio::test]
+    async fn test_addressed_message_reaches_only_its_agent() {
+        use crate::agents::traits::BaseAgent;
+        use crate::core::types::MessageType;
+        
+        let config = SystemConfig::default();
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let session = Arc::new(RwLock::new(SessionLog::new()));
+        let (events, mut broadcasts) = broadcast::channel(16);
+        let directory = AgentDirectory::new();
+        let mut routing = RoutingState {
+            performance: RollingPerformance::from_config(&config.monitoring.performance_window),
+            strategies: None,
+            decode_failures: DecodeFailures::new(3, HealthConditions::new()),
+            replies: ReplyRouter::new(),
+            events: events.clone(),
+            directory: directory.clone(),
+            heartbeats: HeartbeatMonitor::new(chrono::Duration::seconds(15)),
+            ab_test: None,
+        };
+        let (sender, _bus) = mpsc::unbounded_channel();
+        let mut risk = BaseAgent::new(Vec::new(), sender.clone(), events.clone(), system_context.clone());
+        let mut learning = BaseAgent::new(Vec::new(), sender, events, system_context.clone());
+        risk.register("risk_management", directory.clone());
+        learning.register("learning_engine", directory.clone());
+        assert_eq!(learning.agent_id_of("risk_management"), Some(risk.id));
+        let message = |to| AgentMessage::new(learning.id, to, MessageType::SystemCommand, serde_json::json!("rebalance"));
+        
+        TradingSystem::route_message(message(risk.id), &system_context, &mut routing, &session).await.unwrap();
+        assert_eq!(risk.inbox().await.try_recv().unwrap().to, risk.id);
+        assert!(learning.inbox().await.try_recv().is_err());
+        assert!(broadcasts.try_recv().is_err());
+        
+        // The nil id still reaches every agent through the broadcast
+        TradingSystem::route_message(message(uuid::Uuid::nil()), &system_context, &mut routing, &session).await.unwrap();
+        assert!(broadcasts.try_recv().unwrap().to.is_nil());
+        assert!(risk.inbox().await.try_recv().is_err());
+        assert!(learning.inbox().await.try_recv().is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_non_critical_agent_failing_to_start_degrades_system() {
+        let mut config = SystemConfig::default();
+        config.api.moomoo.api_key = "test".to_string();
+        config.agents.master_coordinator.enabled = false;
+        config.agents.market_intelligence.enabled = false;
+        config.agents.learning_engine.enabled = false;
+        // The execution engine can't load its restricted list
+        config.risk.compliance.enabled = true;
+        config.risk.compliance.restricted_list_path = "/nonexistent/restricted_symbols.txt".to_string();
+        config.agents.startup.critical = vec!["risk_management".to_string()];
+        config.monitoring.metrics_enabled = false;
+        
+        let mut system = TradingSystem::new(config.clone()).await.unwrap();
+        system.start().await.unwrap();
+        assert!(system.agents.risk_management.is_some());
+        assert!(system.agents.execution.is_none());
+        assert_eq!(system.degraded_agents(), ["execution_engine"]);
+        assert_eq!(system.agents.start_order, ["risk_management"]);
+        // Health reports combine the skipped agents, leaving the shared health to its own writers
+        assert!(matches!(system.system_context.read().await.system_health, SystemHealth::Healthy));
+        
+        // The same failure aborts startup once the agent is critical
+        config.agents.startup.critical.push("execution_engine".to_string());
+        let mut system = TradingSystem::new(config).await.unwrap();
+        assert!(system.start().await.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_restarted_agent_keeps_reloaded_risk_limits() {
+        use crate::agents::risk::RiskManagementAgent;
+        use crate::core::config::SupervisionConfig;
+        use crate::core::types::MessageType;
+        use rust_decimal::Decimal;
+        
+        let mut config = SystemConfig::default();
+        config.risk.max_daily_loss = Decimal::from(1000);
+        let system_context = Arc::new(RwLock::new(SystemContext::initial(&config)));
+        let (sender, mut bus) = mpsc::unbounded_channel();
+        let (events, _) = broadcast::channel(16);
+        let risk = RiskManagementAgent::new(
+            config.agents.risk_management.clone(),
+            config.risk.clone(),
+            sender.clone(),
+            events.clone(),
+            system_context.clone(),
+        ).await.unwrap();
+        let supervision = SupervisionConfig { max_restarts: 1, initial_backoff_ms: 100, max_backoff_ms: 100, stable_after_ms: 60_000 };
+        let faults = FaultInjector::new();
+        let task = tokio::spawn(supervised(&AgentSupervisor::new(&supervision, sender), "risk_management", risk, faults.clone()));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        
+        // Tighten the loss limit while the agent runs, then crash it
+        let update = ConfigUpdate {
+            changes: [("risk.max_daily_loss".to_string(), serde_json::to_value(Decimal::from(5)).unwrap())].into(),
+            restart_required: Vec::new(),
+        };
+        events.send(AgentMessage::broadcast(uuid::Uuid::nil(), MessageType::ConfigUpdate, serde_json::to_value(&update).unwrap())).unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        faults.crash_agent("risk_management");
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(!task.is_finished());
+        
+        // The restarted agent enforces the reloaded limit, not the startup one
+        system_context.write().await.portfolio.daily_pnl = Decimal::from(-10);
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let mut stopped = false;
+        while let Ok(message) = bus.try_recv() {
+            stopped |= matches!(message.message_type, MessageType::EmergencyShutdown)
+                && message.payload["reason"] == "Daily loss limit exceeded";
+        }
+        assert!(stopped);
+        task.abort();
+    }
+}
@@ -1,11 +1,15 @@
 //! Core types and data structures for the trading system
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+use crate::core::config::SystemConfig;
+use crate::core::equity::EquityCurve;
+use crate::core::errors::{TradingError, TradingResult};
+
 /// Unique identifier for trading agents
 pub type AgentId = Uuid;
 
@@ -52,6 +56,27 @@ pub struct MarketData {
     pub ask: Option<Decimal>,
     pub bid_size: Option<u64>,
     pub ask_size: Option<u64>,
+    #[serde(default)]
+    pub source: DataSource,
+}
+
+/// Provider and feed a quote was received from
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct DataSource {
+    pub provider: String,
+    pub feed: String,
+}
+
+impl DataSource {
+    pub fn new(provider: impl Into<String>, feed: impl Into<String>) -> Self {
+        Self { provider: provider.into(), feed: feed.into() }
+    }
+}
+
+impl Default for DataSource {
+    fn default() -> Self {
+        Self::new("unknown", "unknown")
+    }
 }
 
 /// Trading order representation
@@ -93,6 +118,69 @@ pub enum OrderStatus {
     Rejected,
 }
 
+/// Why a position (or part of one) was closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloseReason {
+    TakeProfit,
+    StopLoss,
+    TrailingStop,
+    EodFlatten,
+    Manual,
+    Rebalance,
+    Emergency,
+}
+
+/// Order requested by another agent, tagged with why it closes a position.
+/// A bare `Order` payload deserializes with no close reason.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClosingOrder {
+    #[serde(flatten)]
+    pub order: Order,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub close_reason: Option<CloseReason>,
+}
+
+/// Order the execution engine refused or could not fill at all
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderRejection {
+    pub order_id: OrderId,
+    pub symbol: Symbol,
+    pub reason: String,
+}
+
+/// Order that reached a terminal state, and how much of it filled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderCompletion {
+    pub order_id: OrderId,
+    pub symbol: Symbol,
+    pub status: OrderStatus,
+    pub filled_quantity: Decimal,
+    pub remaining_quantity: Decimal, // Left unfilled when the order ended
+    pub average_price: Decimal,
+    pub execution_time_ms: u64,
+}
+
+/// How widely a risk alert applies
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AlertSeverity {
+    /// Needs attention; work on the alert's symbol, if any, is stopped
+    #[default]
+    Warning,
+    /// New trading is halted; everything in flight is stopped
+    Halt,
+}
+
+/// Payload of a `RiskAlert` message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskAlertNotice {
+    pub reason: String,
+    #[serde(default)]
+    pub symbol: Option<Symbol>, // None when the alert concerns the whole book
+    #[serde(default)]
+    pub severity: AlertSeverity,
+}
+
 /// Trading position
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Position {
@@ -106,6 +194,25 @@ pub struct Position {
     pub timestamp: DateTime<Utc>,
 }
 
+impl Position {
+    /// Market value of the position at the current price
+    pub fn market_value(&self) -> TradingResult<Decimal> {
+        self.quantity
+            .checked_mul(self.current_price)
+            .ok_or_else(|| TradingError::overflow(format!("market value of {}", self.symbol)))
+    }
+}
+
+/// Part of a position closed by a fill
+#[derive(Debug, Clone)]
+pub struct ClosedFill {
+    pub opened_by: OrderId, // Order that opened the position
+    pub quantity: Decimal,  // Shares closed, unsigned
+    pub entry_price: Decimal,
+    pub pnl: Decimal, // Realized on the closed shares, net of the fill's commission
+    pub opened_at: DateTime<Utc>,
+}
+
 /// Trading signal generated by AI models
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradingSignal {
@@ -115,6 +222,21 @@ pub struct TradingSignal {
     pub confidence: f64, // 0.0 to 1.0
     pub timestamp: DateTime<Utc>,
     pub reasoning: String,
+    #[serde(default)]
+    pub source: String, // Producing agent, used to break ordering ties
+}
+
+impl TradingSignal {
+    /// Sort a batch of signals into a deterministic processing order:
+    /// timestamp, then symbol, then source
+    pub fn sort_deterministic(signals: &mut [TradingSignal]) {
+        signals.sort_by(|a, b| {
+            a.timestamp
+                .cmp(&b.timestamp)
+                .then_with(|| a.symbol.cmp(&b.symbol))
+                .then_with(|| a.source.cmp(&b.source))
+        });
+    }
 }
 
 /// Types of trading signals
@@ -138,14 +260,154 @@ pub struct Portfolio {
     pub max_drawdown: Decimal,
     pub sharpe_ratio: Option<f64>,
     pub last_updated: DateTime<Utc>,
+    #[serde(default)]
+    pub trading_day: Option<NaiveDate>, // Day daily_pnl accumulates for
+}
+
+impl Portfolio {
+    /// Total market value of all open positions
+    pub fn positions_value(&self) -> TradingResult<Decimal> {
+        self.positions.values().try_fold(Decimal::ZERO, |total, position| {
+            total
+                .checked_add(position.market_value()?)
+                .ok_or_else(|| TradingError::overflow("portfolio positions value"))
+        })
+    }
+
+    /// Move to trading day `day`, zeroing daily P&L when it differs from the
+    /// current one. Realized P&L is already booked into `total_pnl`, which is
+    /// kept. Returns whether a reset happened.
+    pub fn roll_trading_day(&mut self, day: NaiveDate) -> bool {
+        let previous = self.trading_day.replace(day);
+        if previous.is_none_or(|previous| previous == day) {
+            return false;
+        }
+        self.daily_pnl = Decimal::ZERO;
+        self.last_updated = Utc::now();
+        true
+    }
+
+    /// Book a fill of signed `quantity` (negative sells) at `price` for
+    /// `order_id`. Cash pays for the fill and the commission; additions
+    /// average into the entry price, while reductions realize P&L against it.
+    /// A fill that crosses through flat opens a new position for the rest.
+    /// Returns the closed part when the fill reduced a held position; nothing
+    /// is modified if any of the updated totals would overflow.
+    pub fn apply_fill(
+        &mut self,
+        symbol: &str,
+        order_id: OrderId,
+        quantity: Decimal,
+        price: Decimal,
+        commission: Decimal,
+        at: DateTime<Utc>,
+    ) -> TradingResult<Option<ClosedFill>> {
+        let overflow = || TradingError::overflow(format!("fill of {}", symbol));
+        let existing = self.positions.get(symbol).cloned();
+        let held = existing.as_ref().map_or(Decimal::ZERO, |position| position.quantity);
+        let remaining = held.checked_add(quantity).ok_or_else(overflow)?;
+
+        let closed = match &existing {
+            Some(position) if held.is_sign_positive() != quantity.is_sign_positive() => {
+                let closed_quantity = quantity.abs().min(held.abs());
+                let per_share = if held.is_sign_positive() { price - position.entry_price } else { position.entry_price - price };
+                let gross = per_share.checked_mul(closed_quantity).ok_or_else(overflow)?;
+                Some(ClosedFill {
+                    opened_by: position.id,
+                    quantity: closed_quantity,
+                    entry_price: position.entry_price,
+                    pnl: gross.checked_sub(commission).ok_or_else(overflow)?,
+                    opened_at: position.timestamp,
+                })
+            }
+            _ => None,
+        };
+
+        let position = match existing {
+            _ if remaining.is_zero() => None,
+            // Reduced but still on the same side: the entry price stands
+            Some(position) if closed.is_some() && remaining.is_sign_positive() == held.is_sign_positive() => Some(position),
+            // Added to: average the fill into the entry price
+            Some(position) if closed.is_none() => {
+                let cost = held
+                    .checked_mul(position.entry_price)
+                    .and_then(|cost| cost.checked_add(quantity.checked_mul(price)?))
+                    .ok_or_else(overflow)?;
+                Some(Position { entry_price: cost / remaining, ..position })
+            }
+            // Newly opened, or flipped through flat
+            _ => Some(Position {
+                id: order_id,
+                symbol: symbol.to_string(),
+                quantity: Decimal::ZERO,
+                entry_price: price,
+                current_price: price,
+                unrealized_pnl: Decimal::ZERO,
+                realized_pnl: Decimal::ZERO,
+                timestamp: at,
+            }),
+        };
+        let position = match position {
+            Some(position) => {
+                let realized = match &closed {
+                    Some(closed) => closed.pnl.checked_add(commission).ok_or_else(overflow)?,
+                    None => Decimal::ZERO,
+                };
+                let unrealized = (price - position.entry_price).checked_mul(remaining).ok_or_else(overflow)?;
+                Some(Position {
+                    quantity: remaining,
+                    current_price: price,
+                    unrealized_pnl: unrealized,
+                    realized_pnl: position.realized_pnl.checked_add(realized).ok_or_else(overflow)?,
+                    ..position
+                })
+            }
+            None => None,
+        };
+
+        let pnl = closed.as_ref().map_or(-commission, |closed| closed.pnl);
+        let cash_balance = quantity
+            .checked_mul(price)
+            .and_then(|cost| self.cash_balance.checked_sub(cost)?.checked_sub(commission))
+            .ok_or_else(overflow)?;
+        let daily_pnl = self.daily_pnl.checked_add(pnl).ok_or_else(overflow)?;
+        let total_pnl = self.total_pnl.checked_add(pnl).ok_or_else(overflow)?;
+        let mut positions = self.positions.clone();
+        match position {
+            Some(position) => positions.insert(symbol.to_string(), position),
+            None => positions.remove(symbol),
+        };
+        let booked = Portfolio { cash_balance, daily_pnl, total_pnl, positions, last_updated: at, ..self.clone() };
+        let total_value = booked.positions_value()?.checked_add(cash_balance).ok_or_else(overflow)?;
+
+        *self = Portfolio { total_value, ..booked };
+        Ok(closed)
+    }
+
+    /// Book realized P&L into the running totals. Nothing is modified if any
+    /// of the updated totals would overflow.
+    pub fn apply_realized_pnl(&mut self, pnl: Decimal) -> TradingResult<()> {
+        let overflow = || TradingError::overflow("realized P&L accumulation");
+        let daily_pnl = self.daily_pnl.checked_add(pnl).ok_or_else(overflow)?;
+        let total_pnl = self.total_pnl.checked_add(pnl).ok_or_else(overflow)?;
+        let cash_balance = self.cash_balance.checked_add(pnl).ok_or_else(overflow)?;
+        let total_value = self.total_value.checked_add(pnl).ok_or_else(overflow)?;
+
+        self.daily_pnl = daily_pnl;
+        self.total_pnl = total_pnl;
+        self.cash_balance = cash_balance;
+        self.total_value = total_value;
+        self.last_updated = Utc::now();
+        Ok(())
+    }
 }
 
 /// Risk metrics and limits
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RiskMetrics {
-    pub var_95: Decimal, // Value at Risk (95% confidence)
-    pub var_99: Decimal, // Value at Risk (99% confidence)
-    pub expected_shortfall: Decimal,
+    pub var_95: Option<Decimal>, // Value at Risk (95% confidence), None without return history
+    pub var_99: Option<Decimal>, // Value at Risk (99% confidence)
+    pub expected_shortfall: Option<Decimal>,
     pub max_position_size: Decimal,
     pub daily_loss_limit: Decimal,
     pub portfolio_heat: f64, // 0.0 to 1.0
@@ -162,6 +424,30 @@ pub enum MarketRegime {
     Crisis,
 }
 
+/// Lifecycle state of a generated strategy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StrategyState {
+    Generated, // Created by evolution, not yet evaluated
+    Shadow,    // Producing signals that are tracked but not traded
+    Active,    // Trading
+    Disabled,  // Switched off, may be re-evaluated in shadow
+    Retired,   // Permanently removed
+}
+
+impl StrategyState {
+    /// Whether a strategy in this state may move to `next`
+    pub fn can_transition_to(self, next: StrategyState) -> bool {
+        use StrategyState::*;
+        matches!(
+            (self, next),
+            (Generated, Shadow | Active | Disabled | Retired)
+                | (Shadow, Active | Disabled | Retired)
+                | (Active, Shadow | Disabled | Retired)
+                | (Disabled, Shadow | Retired)
+        )
+    }
+}
+
 /// System performance metrics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceMetrics {
@@ -185,6 +471,48 @@ pub struct AgentMessage {
     pub message_type: MessageType,
     pub payload: serde_json::Value,
     pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub correlation_id: Option<Uuid>, // Shared by a request and its reply
+    #[serde(default)]
+    pub reply_to: Option<AgentId>, // Set on requests that await a reply
+}
+
+impl AgentMessage {
+    /// Message from one agent to another
+    pub fn new(from: AgentId, to: AgentId, message_type: MessageType, payload: serde_json::Value) -> Self {
+        Self {
+            from,
+            to,
+            message_type,
+            payload,
+            timestamp: Utc::now(),
+            correlation_id: None,
+            reply_to: None,
+        }
+    }
+
+    /// Message for every agent on the bus
+    pub fn broadcast(from: AgentId, message_type: MessageType, payload: serde_json::Value) -> Self {
+        Self::new(from, Uuid::nil(), message_type, payload)
+    }
+
+    /// Reply to this message, addressed to its requester under the same correlation id
+    pub fn reply(&self, from: AgentId, message_type: MessageType, payload: serde_json::Value) -> Self {
+        Self {
+            from,
+            to: self.reply_to.unwrap_or(self.from),
+            message_type,
+            payload,
+            timestamp: Utc::now(),
+            correlation_id: self.correlation_id,
+            reply_to: None,
+        }
+    }
+
+    /// Whether this answers a request that may be awaiting it
+    pub fn is_reply(&self) -> bool {
+        self.correlation_id.is_some() && self.reply_to.is_none()
+    }
 }
 
 /// Types of messages between agents
@@ -192,11 +520,22 @@ pub struct AgentMessage {
 pub enum MessageType {
     MarketUpdate,
     TradingSignal,
+    SignalProposal,
+    VoteRequest,
+    VoteResponse,
     RiskAlert,
+    OrderRequest,
+    MultiLegOrderRequest,
     OrderExecution,
+    OrderRejected,
+    OrderCompleted,
     PerformanceUpdate,
+    TradeClosed,
     SystemCommand,
+    SystemFeedback,
+    ConfigUpdate,
     EmergencyShutdown,
+    Heartbeat,
 }
 
 /// System context for agent decision making
@@ -209,13 +548,66 @@ pub struct SystemContext {
     pub active_positions: u32,
     pub available_capital: Decimal,
     pub system_health: SystemHealth,
+    #[serde(default)]
+    pub equity_curve: EquityCurve,
+    #[serde(default = "Utc::now")]
+    pub last_updated: DateTime<Utc>,
 }
 
-/// System health status
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl SystemContext {
+    /// Create the initial context for a freshly started system
+    pub fn initial(config: &SystemConfig) -> Self {
+        Self {
+            market_regime: MarketRegime::Sideways,
+            portfolio: Portfolio {
+                total_value: config.trading.initial_capital,
+                cash_balance: config.trading.initial_capital,
+                positions: Default::default(),
+                daily_pnl: Default::default(),
+                total_pnl: Default::default(),
+                max_drawdown: Default::default(),
+                sharpe_ratio: None,
+                last_updated: Utc::now(),
+                trading_day: None,
+            },
+            risk_metrics: RiskMetrics {
+                var_95: Default::default(),
+                var_99: Default::default(),
+                expected_shortfall: Default::default(),
+                max_position_size: config.risk.max_position_size,
+                daily_loss_limit: config.risk.max_daily_loss,
+                portfolio_heat: 0.0,
+            },
+            performance_metrics: PerformanceMetrics {
+                total_trades: 0,
+                winning_trades: 0,
+                losing_trades: 0,
+                win_rate: 0.0,
+                average_win: Default::default(),
+                average_loss: Default::default(),
+                profit_factor: 0.0,
+                max_consecutive_wins: 0,
+                max_consecutive_losses: 0,
+                average_execution_time_ms: 0.0,
+            },
+            active_positions: 0,
+            available_capital: config.trading.initial_capital,
+            system_health: SystemHealth::Healthy,
+            equity_curve: EquityCurve::new(
+                config.monitoring.equity_curve.granularity_secs,
+                config.monitoring.equity_curve.max_points,
+            ),
+            last_updated: Utc::now(),
+        }
+    }
+}
+
+/// System health status, from best to worst
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum SystemHealth {
     Healthy,
     Warning,
+    Degraded,
     Critical,
     Emergency,
 }
@@ -228,6 +620,27 @@ pub struct StrategyConfig {
     pub risk_limit: Decimal,
     pub max_position_size: Decimal,
     pub parameters: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub extended_hours: bool, // Opt into pre-market/after-hours sessions
+    #[serde(default)]
+    pub take_profit: Option<crate::core::config::TakeProfitConfig>,
+}
+
+/// Whether a fill added liquidity to the book or took it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Liquidity {
+    Maker,
+    Taker,
+}
+
+/// A single execution against an order; an order may fill in several parts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fill {
+    pub order_id: OrderId,
+    pub quantity: Decimal,
+    pub price: Decimal,
+    pub timestamp: DateTime<Utc>,
+    pub liquidity: Liquidity,
 }
 
 /// Execution result for trades
@@ -235,10 +648,149 @@ pub struct StrategyConfig {
 pub struct ExecutionResult {
     pub order_id: OrderId,
     pub executed_quantity: Decimal,
-    pub executed_price: Decimal,
+    pub executed_price: Decimal, // Volume-weighted over `fills`
     pub execution_time_ms: u64,
     pub slippage: Decimal,
     pub commission: Decimal,
     pub success: bool,
     pub error_message: Option<String>,
+    #[serde(default)]
+    pub fills: Vec<Fill>,
+}
+
+impl ExecutionResult {
+    /// Aggregate an order's fills: total quantity at the volume-weighted price
+    pub fn from_fills(
+        order_id: OrderId,
+        fills: Vec<Fill>,
+        slippage: Decimal,
+        commission: Decimal,
+        execution_time_ms: u64,
+    ) -> TradingResult<Self> {
+        let overflow = || TradingError::overflow(format!("fills of order {}", order_id));
+        let mut executed_quantity = Decimal::ZERO;
+        let mut notional = Decimal::ZERO;
+        for fill in &fills {
+            executed_quantity = executed_quantity.checked_add(fill.quantity).ok_or_else(overflow)?;
+            notional = fill
+                .price
+                .checked_mul(fill.quantity)
+                .and_then(|value| notional.checked_add(value))
+                .ok_or_else(overflow)?;
+        }
+        let executed_price = if executed_quantity.is_zero() {
+            Decimal::ZERO
+        } else {
+            notional / executed_quantity
+        };
+
+        Ok(Self {
+            order_id,
+            executed_quantity,
+            executed_price,
+            execution_time_ms,
+            slippage,
+            commission,
+            success: !executed_quantity.is_zero(),
+            error_message: None,
+            fills,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(quantity: Decimal, price: Decimal) -> Position {
+        Position {
+            id: Uuid::new_v4(),
+            symbol: "AAPL".to_string(),
+            quantity,
+            entry_price: price,
+            current_price: price,
+            unrealized_pnl: Decimal::ZERO,
+            realized_pnl: Decimal::ZERO,
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_partial_fills_aggregate_to_weighted_price() {
+        let order_id = Uuid::new_v4();
+        let fill = |quantity: i64, price: i64, liquidity| Fill {
+            order_id,
+            quantity: Decimal::from(quantity),
+            price: Decimal::from(price),
+            timestamp: Utc::now(),
+            liquidity,
+        };
+
+        let result = ExecutionResult::from_fills(
+            order_id,
+            vec![fill(2, 100, Liquidity::Maker), fill(3, 110, Liquidity::Taker)],
+            Decimal::ZERO,
+            Decimal::ZERO,
+            5,
+        ).unwrap();
+
+        assert_eq!(result.fills.len(), 2);
+        assert_eq!(result.executed_quantity, Decimal::from(5));
+        assert_eq!(result.executed_price, Decimal::from(106));
+        assert!(result.success);
+    }
+
+    #[test]
+    fn test_extreme_values_return_overflow_error() {
+        let huge = position(Decimal::MAX, Decimal::from(2));
+        assert!(matches!(huge.market_value(), Err(TradingError::ArithmeticOverflow { .. })));
+
+        let config = SystemConfig::default();
+        let mut portfolio = SystemContext::initial(&config).portfolio;
+        portfolio.positions.insert("AAPL".to_string(), huge);
+        assert!(matches!(portfolio.positions_value(), Err(TradingError::ArithmeticOverflow { .. })));
+
+        portfolio.total_pnl = Decimal::MAX;
+        let before = portfolio.daily_pnl;
+        assert!(matches!(
+            portfolio.apply_realized_pnl(Decimal::MAX),
+            Err(TradingError::ArithmeticOverflow { .. })
+        ));
+        assert_eq!(portfolio.daily_pnl, before);
+        assert_eq!(portfolio.total_pnl, Decimal::MAX);
+    }
+
+    #[test]
+    fn test_fills_average_in_and_realize_on_reduction() {
+        let mut config = SystemConfig::default();
+        config.trading.initial_capital = Decimal::from(10_000);
+        let mut portfolio = SystemContext::initial(&config).portfolio;
+        let (opening, now) = (Uuid::new_v4(), Utc::now());
+
+        assert!(portfolio.apply_fill("AAPL", opening, Decimal::from(10), Decimal::from(100), Decimal::ONE, now).unwrap().is_none());
+        portfolio.apply_fill("AAPL", Uuid::new_v4(), Decimal::from(10), Decimal::from(110), Decimal::ONE, now).unwrap();
+        assert_eq!(portfolio.positions["AAPL"].entry_price, Decimal::from(105));
+        assert_eq!(portfolio.cash_balance, Decimal::from(7_898));
+
+        // Selling 15 closes three quarters of the position at a 15 per-share gain
+        let later = now + chrono::Duration::minutes(5);
+        let closed = portfolio
+            .apply_fill("AAPL", Uuid::new_v4(), Decimal::from(-15), Decimal::from(120), Decimal::from(2), later)
+            .unwrap()
+            .unwrap();
+        assert_eq!((closed.opened_by, closed.quantity, closed.opened_at), (opening, Decimal::from(15), now));
+        assert_eq!(closed.pnl, Decimal::from(223));
+        assert_eq!(portfolio.positions["AAPL"].quantity, Decimal::from(5));
+        assert_eq!(portfolio.positions["AAPL"].entry_price, Decimal::from(105));
+        assert_eq!(portfolio.total_pnl, Decimal::from(221));
+        assert_eq!(portfolio.total_value, portfolio.cash_balance + portfolio.positions_value().unwrap());
+
+        // Selling through flat opens a short at the fill price
+        let flipped = Uuid::new_v4();
+        let closed = portfolio.apply_fill("AAPL", flipped, Decimal::from(-8), Decimal::from(100), Decimal::ZERO, later).unwrap().unwrap();
+        assert_eq!(closed.quantity, Decimal::from(5));
+        let short = &portfolio.positions["AAPL"];
+        assert_eq!((short.id, short.quantity, short.entry_price), (flipped, Decimal::from(-3), Decimal::from(100)));
+        assert_eq!(portfolio.total_value, portfolio.cash_balance + portfolio.positions_value().unwrap());
+    }
 }
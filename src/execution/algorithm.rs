@@ -0,0 +1,157 @@
+//! Rule-based choice of execution algorithm
+//!
+//! Rules from `AlgorithmSelectionConfig` are tried in order against the
+//! order's size, urgency, the market regime and the quoted spread. The choice
+//! carries a rationale naming the rule and the conditions that matched.
+
+use rust_decimal::Decimal;
+
+use crate::core::config::{AlgorithmRule, AlgorithmSelectionConfig};
+use crate::execution::twap::TWAP_ALGORITHM;
+
+/// Send the whole order at once
+pub const MARKET_ALGORITHM: &str = "MARKET";
+
+/// Algorithms the execution engine knows how to run
+pub const ALGORITHMS: &[&str] = &[MARKET_ALGORITHM, TWAP_ALGORITHM];
+
+/// What the algorithm choice is made on
+#[derive(Debug, Clone)]
+pub struct ExecutionConditions {
+    pub quantity: Decimal,
+    pub urgent: bool,
+    pub high_volatility: bool,
+    pub spread_bps: Option<f64>,
+}
+
+/// Chosen algorithm and why
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlgorithmChoice {
+    pub algorithm: String,
+    pub rationale: String,
+}
+
+/// Picks an execution algorithm by the first matching configured rule
+#[derive(Debug, Clone)]
+pub struct AlgorithmSelector {
+    config: AlgorithmSelectionConfig,
+}
+
+impl AlgorithmSelector {
+    pub fn from_config(config: &AlgorithmSelectionConfig) -> Self {
+        Self { config: config.clone() }
+    }
+
+    pub fn select(&self, conditions: &ExecutionConditions) -> AlgorithmChoice {
+        for (i, rule) in self.config.rules.iter().enumerate() {
+            if let Some(reasons) = matches(rule, conditions) {
+                return AlgorithmChoice {
+                    algorithm: rule.algorithm.clone(),
+                    rationale: format!("rule {} ({}): {}", i + 1, rule.algorithm, reasons.join(", ")),
+                };
+            }
+        }
+
+        AlgorithmChoice {
+            algorithm: self.config.default_algorithm.clone(),
+            rationale: format!(
+                "no rule matched quantity {}{}, default {}",
+                conditions.quantity,
+                if conditions.urgent { " (urgent)" } else { "" },
+                self.config.default_algorithm
+            ),
+        }
+    }
+}
+
+/// The conditions a rule matched on, or `None` if any of them fails
+fn matches(rule: &AlgorithmRule, conditions: &ExecutionConditions) -> Option<Vec<String>> {
+    let mut reasons = Vec::new();
+
+    if let Some(urgent) = rule.urgent {
+        if urgent != conditions.urgent {
+            return None;
+        }
+        reasons.push(if urgent { "urgent exit" } else { "not urgent" }.to_string());
+    }
+    if let Some(high_volatility) = rule.high_volatility {
+        if high_volatility != conditions.high_volatility {
+            return None;
+        }
+        reasons.push(if high_volatility { "high volatility" } else { "calm market" }.to_string());
+    }
+    if let Some(above) = rule.above_quantity {
+        if conditions.quantity <= above {
+            return None;
+        }
+        reasons.push(format!("quantity {} > {}", conditions.quantity, above));
+    }
+    if let Some(max) = rule.max_quantity {
+        if conditions.quantity > max {
+            return None;
+        }
+        reasons.push(format!("quantity {} <= {}", conditions.quantity, max));
+    }
+    if let Some(max_spread) = rule.max_spread_bps {
+        // Without a quote the spread can't be shown to be narrow enough
+        let spread = conditions.spread_bps.filter(|spread| *spread <= max_spread)?;
+        reasons.push(format!("spread {:.1}bps <= {:.1}bps", spread, max_spread));
+    }
+
+    if reasons.is_empty() {
+        reasons.push("matches any order".to_string());
+    }
+    Some(reasons)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::SystemConfig;
+
+    fn conditions(quantity: i64, urgent: bool) -> ExecutionConditions {
+        ExecutionConditions {
+            quantity: Decimal::from(quantity),
+            urgent,
+            high_volatility: false,
+            spread_bps: Some(2.0),
+        }
+    }
+
+    #[test]
+    fn test_urgent_orders_go_to_market_and_large_calm_orders_to_twap() {
+        let selector = AlgorithmSelector::from_config(&AlgorithmSelectionConfig::default());
+
+        let urgent = selector.select(&conditions(500, true));
+        assert_eq!(urgent.algorithm, "MARKET");
+        assert_eq!(urgent.rationale, "rule 1 (MARKET): urgent exit");
+
+        let large = selector.select(&conditions(500, false));
+        assert_eq!(large.algorithm, "TWAP");
+        assert_eq!(large.rationale, "rule 3 (TWAP): quantity 500 > 100");
+        // Only orders larger than 100 are sliced
+        assert_eq!(selector.select(&conditions(100, false)).algorithm, "MARKET");
+
+        let volatile = selector.select(&ExecutionConditions { high_volatility: true, ..conditions(500, false) });
+        assert_eq!(volatile.algorithm, "MARKET");
+        assert!(volatile.rationale.contains("high volatility"));
+
+        let small = selector.select(&conditions(10, false));
+        assert_eq!(small.algorithm, "MARKET");
+        assert!(small.rationale.starts_with("no rule matched"));
+
+        // A spread condition needs a quote narrow enough to satisfy it
+        let mut config = AlgorithmSelectionConfig::default();
+        config.rules[2].max_spread_bps = Some(1.0);
+        let selector = AlgorithmSelector::from_config(&config);
+        assert_eq!(selector.select(&conditions(500, false)).algorithm, "MARKET");
+    }
+
+    #[test]
+    fn test_unknown_algorithm_names_are_refused() {
+        let mut config = SystemConfig::default();
+        config.agents.execution_engine.algorithm_selection.rules[2].algorithm = "twap".to_string();
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("Unknown execution algorithm 'twap'"), "{}", error);
+    }
+}
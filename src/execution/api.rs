@@ -1,12 +1,428 @@
-//! API integration for trade execution
+//! Moomoo OpenD gateway client for order placement and on-demand quotes
+//!
+//! Orders are sent as JSON over HTTP to the gateway at `MoomooConfig.base_url`,
+//! using the client order id as the gateway's idempotency key so retried
+//! placements cannot double-fill. With `paper_trading` set every request
+//! targets the simulated trading environment; only `paper_trading = false`
+//! reaches the real account.
 
-use crate::core::errors::TradingResult;
+use async_trait::async_trait;
+use reqwest::{RequestBuilder, Response, StatusCode};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
 
-/// Placeholder for API integration
-pub struct ApiClient;
+use crate::agents::traits::OrderStatus as OrderStatusReport;
+use crate::core::config::MoomooConfig;
+use crate::core::errors::{TradingError, TradingResult};
+use crate::core::types::{ExecutionResult, Fill, Liquidity, MarketData, Order, OrderId, OrderSide, OrderStatus, OrderType};
+use crate::execution::canary::LiveVenue;
+use crate::execution::import::{ImportedPosition, PositionSource};
+use crate::execution::multi_leg::QuoteSource;
+use crate::execution::orders::OrderStatusSource;
+use crate::execution::retry::RetryBudget;
+
+/// First retry waits this long, doubling on each further attempt
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(100);
+
+/// OpenD trading environment an order is placed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TradingEnvironment {
+    Simulate,
+    Real,
+}
+
+#[derive(Debug, Serialize)]
+struct PlaceOrderRequest<'a> {
+    client_order_id: OrderId,
+    code: &'a str,
+    side: &'a OrderSide,
+    order_type: &'a OrderType,
+    quantity: Decimal,
+    price: Option<Decimal>,
+    trd_env: TradingEnvironment,
+}
+
+#[derive(Debug, Serialize)]
+struct ModifyOrderRequest {
+    quantity: Decimal,
+    trd_env: TradingEnvironment,
+}
+
+/// Order state as reported by the gateway
+#[derive(Debug, Deserialize)]
+struct GatewayOrder {
+    status: OrderStatus,
+    filled_quantity: Decimal,
+    average_price: Decimal,
+    remaining_quantity: Decimal,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// Client for placing, cancelling and querying orders through OpenD
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    http: reqwest::Client,
+    base_url: String,
+    api_key: String,
+    timeout: Duration,
+    retry_attempts: u32,
+    environment: TradingEnvironment,
+    connected: Arc<AtomicBool>,
+}
 
 impl ApiClient {
-    pub fn new() -> Self {
-        Self
+    /// Create a client for the configured gateway, sending requests through `http`
+    pub fn new(config: &MoomooConfig, http: reqwest::Client) -> Self {
+        let environment = if config.paper_trading {
+            TradingEnvironment::Simulate
+        } else {
+            TradingEnvironment::Real
+        };
+
+        Self {
+            http,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            api_key: config.api_key.clone(),
+            timeout: Duration::from_millis(config.timeout_ms),
+            retry_attempts: config.retry_attempts,
+            environment,
+            connected: Arc::new(AtomicBool::new(true)),
+        }
+    }
+
+    /// Client for the real account behind the same gateway, whatever
+    /// `paper_trading` says; the canary mirrors paper orders through it
+    pub fn live(config: &MoomooConfig, http: reqwest::Client) -> Self {
+        Self {
+            environment: TradingEnvironment::Real,
+            ..Self::new(config, http)
+        }
+    }
+
+    /// Environment orders from this client are placed in
+    pub fn environment(&self) -> TradingEnvironment {
+        self.environment
+    }
+
+    /// Place an order and report what the gateway filled. Retries are drawn
+    /// from the order's `budget` as well as capped by `retry_attempts`.
+    pub async fn place_order(&self, order: &Order, budget: &RetryBudget) -> TradingResult<ExecutionResult> {
+        let started = Instant::now();
+        let body = PlaceOrderRequest {
+            client_order_id: order.id,
+            code: &order.symbol,
+            side: &order.side,
+            order_type: &order.order_type,
+            quantity: order.quantity,
+            price: order.price,
+            trd_env: self.environment,
+        };
+
+        let url = format!("{}/orders", self.base_url);
+        let response = self.send(|| self.http.post(&url).json(&body), budget, false).await?;
+        let report: GatewayOrder = response.error_for_status()?.json().await?;
+        info!("📨 OpenD {:?} order {} for {}: {:?}", self.environment, order.id, order.symbol, report.status);
+
+        Ok(self.execution_result(order, report, started.elapsed()))
+    }
+
+    /// Cancel an open order
+    pub async fn cancel_order(&self, order_id: OrderId) -> TradingResult<()> {
+        let url = format!("{}/orders/{}", self.base_url, order_id);
+        let budget = RetryBudget::new(self.retry_attempts);
+        let response = self.send(|| self.http.delete(&url).query(&[("trd_env", self.environment)]), &budget, true).await?;
+        response.error_for_status()?;
+        info!("📨 Cancelled OpenD order {}", order_id);
+        Ok(())
+    }
+
+    /// Change the total quantity of an open order. The new quantity is
+    /// absolute, so resending the request is safe.
+    pub async fn modify_order(&self, order_id: OrderId, quantity: Decimal) -> TradingResult<()> {
+        let url = format!("{}/orders/{}", self.base_url, order_id);
+        let body = ModifyOrderRequest { quantity, trd_env: self.environment };
+        let budget = RetryBudget::new(self.retry_attempts);
+        let response = self.send(|| self.http.patch(&url).json(&body), &budget, true).await?;
+        response.error_for_status()?;
+        info!("📨 Amended OpenD order {} to {}", order_id, quantity);
+        Ok(())
+    }
+
+    /// Current status of an order, or `None` if the gateway doesn't know it
+    pub async fn query_order_status(&self, order_id: OrderId) -> TradingResult<Option<OrderStatusReport>> {
+        let url = format!("{}/orders/{}", self.base_url, order_id);
+        let budget = RetryBudget::new(self.retry_attempts);
+        let response = self.send(|| self.http.get(&url).query(&[("trd_env", self.environment)]), &budget, true).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let report: GatewayOrder = response.error_for_status()?.json().await?;
+        Ok(Some(OrderStatusReport {
+            order_id,
+            status: report.status,
+            filled_quantity: report.filled_quantity,
+            average_price: report.average_price,
+            remaining_quantity: report.remaining_quantity,
+            estimated_completion: None,
+        }))
+    }
+
+    /// Positions held in this client's trading environment
+    pub async fn positions(&self) -> TradingResult<Vec<ImportedPosition>> {
+        let url = format!("{}/positions", self.base_url);
+        let budget = RetryBudget::new(self.retry_attempts);
+        let response = self.send(|| self.http.get(&url).query(&[("trd_env", self.environment)]), &budget, true).await?;
+        let positions: Vec<ImportedPosition> = response.error_for_status()?.json().await?;
+        info!("📨 OpenD reports {} {:?} positions", positions.len(), self.environment);
+        Ok(positions)
+    }
+
+    /// Latest quote for `symbol`, or `None` if the gateway doesn't quote it
+    pub async fn quote(&self, symbol: &str) -> TradingResult<Option<MarketData>> {
+        let url = format!("{}/quotes/{}", self.base_url, symbol);
+        let budget = RetryBudget::new(self.retry_attempts);
+        let response = self.send(|| self.http.get(&url), &budget, true).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Ok(Some(response.error_for_status()?.json().await?))
+    }
+
+    /// Send a request, retrying failures up to `retry_attempts` times with
+    /// exponential backoff while `budget` lasts. Connection failures and 503s
+    /// never reached the gateway and are always retried; timeouts and other
+    /// 5xx responses only when the request is `idempotent`, since a placement
+    /// may already be working at the broker.
+    async fn send(&self, request: impl Fn() -> RequestBuilder, budget: &RetryBudget, idempotent: bool) -> TradingResult<Response> {
+        let mut attempt = 0;
+        loop {
+            let mut builder = request().timeout(self.timeout);
+            if !self.api_key.is_empty() {
+                builder = builder.bearer_auth(&self.api_key);
+            }
+
+            let outcome = builder.send().await;
+            let retryable = match &outcome {
+                Ok(response) => {
+                    response.status() == reqwest::StatusCode::SERVICE_UNAVAILABLE
+                        || (idempotent && response.status().is_server_error())
+                }
+                Err(e) => e.is_connect() || (idempotent && e.is_timeout()),
+            };
+            self.connected.store(!matches!(&outcome, Err(e) if e.is_connect()), Ordering::SeqCst);
+
+            if !retryable || attempt >= self.retry_attempts || !budget.try_consume() {
+                return Ok(outcome?);
+            }
+            attempt += 1;
+            let delay = RETRY_BASE_DELAY * 2u32.saturating_pow(attempt - 1);
+            warn!("🔁 OpenD request failed, retrying in {:?} ({}/{})", delay, attempt, self.retry_attempts);
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    fn execution_result(&self, order: &Order, report: GatewayOrder, elapsed: Duration) -> ExecutionResult {
+        let filled = !report.filled_quantity.is_zero() && !matches!(report.status, OrderStatus::Rejected);
+        let liquidity = if matches!(order.order_type, OrderType::Market) {
+            Liquidity::Taker
+        } else {
+            Liquidity::Maker
+        };
+        let fills = if filled {
+            vec![Fill {
+                order_id: order.id,
+                quantity: report.filled_quantity,
+                price: report.average_price,
+                timestamp: chrono::Utc::now(),
+                liquidity,
+            }]
+        } else {
+            Vec::new()
+        };
+
+        ExecutionResult {
+            order_id: order.id,
+            executed_quantity: if filled { report.filled_quantity } else { Decimal::ZERO },
+            executed_price: if filled { report.average_price } else { Decimal::ZERO },
+            execution_time_ms: elapsed.as_millis() as u64,
+            slippage: order
+                .price
+                .filter(|_| filled)
+                .map(|price| (report.average_price - price).abs())
+                .unwrap_or(Decimal::ZERO),
+            commission: Decimal::ZERO,
+            success: filled,
+            error_message: if filled {
+                None
+            } else {
+                Some(report.message.unwrap_or_else(|| format!("Order {:?} by gateway", report.status)))
+            },
+            fills,
+        }
+    }
+}
+
+#[async_trait]
+impl OrderStatusSource for ApiClient {
+    fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    async fn order_status(&self, order_id: OrderId) -> TradingResult<Option<OrderStatusReport>> {
+        self.query_order_status(order_id).await
+    }
+}
+
+#[async_trait]
+impl PositionSource for ApiClient {
+    async fn fetch_positions(&self) -> TradingResult<Vec<ImportedPosition>> {
+        self.positions().await
+    }
+}
+
+#[async_trait]
+impl QuoteSource for ApiClient {
+    async fn quote(&self, symbol: &str) -> TradingResult<Option<MarketData>> {
+        ApiClient::quote(self, symbol).await
+    }
+}
+
+#[async_trait]
+impl LiveVenue for ApiClient {
+    async fn submit(&self, order: &Order) -> TradingResult<ExecutionResult> {
+        if self.environment != TradingEnvironment::Real {
+            return Err(TradingError::execution("Live venue requires paper_trading = false"));
+        }
+        self.place_order(order, &RetryBudget::new(self.retry_attempts)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::service::{make_service_fn, service_fn};
+    use hyper::{Body, Method, Request, Server};
+    use std::convert::Infallible;
+    use std::sync::atomic::AtomicUsize;
+
+    /// Fake gateway that fails the first placement with a 503 and fills the retry
+    async fn fake_gateway(placements: Arc<AtomicUsize>) -> String {
+        let make_service = make_service_fn(move |_| {
+            let placements = placements.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                    let placements = placements.clone();
+                    async move {
+                        let response = match (request.method(), request.uri().path()) {
+                            (&Method::POST, "/orders") => {
+                                let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                                let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                                assert_eq!(body["trd_env"], "SIMULATE");
+                                let placement = placements.fetch_add(1, Ordering::SeqCst);
+                                if placement == 0 {
+                                    hyper::Response::builder().status(503).body(Body::empty()).unwrap()
+                                } else if placement == 2 {
+                                    hyper::Response::builder().status(500).body(Body::empty()).unwrap()
+                                } else {
+                                    let filled = serde_json::json!({
+                                        "status": "Filled",
+                                        "filled_quantity": body["quantity"],
+                                        "average_price": "101.5",
+                                        "remaining_quantity": "0",
+                                    });
+                                    hyper::Response::new(Body::from(filled.to_string()))
+                                }
+                            }
+                            (&Method::GET, "/positions") => {
+                                assert_eq!(request.uri().query(), Some("trd_env=SIMULATE"));
+                                let held = serde_json::json!([
+                                    {"symbol": "AAPL", "quantity": "3", "average_cost": "150.25", "current_price": "155"},
+                                ]);
+                                hyper::Response::new(Body::from(held.to_string()))
+                            }
+                            _ => hyper::Response::builder().status(404).body(Body::empty()).unwrap(),
+                        };
+                        Ok::<_, Infallible>(response)
+                    }
+                }))
+            }
+        });
+        let server = Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(make_service);
+        let url = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+        url
+    }
+
+    #[tokio::test]
+    async fn test_paper_order_is_retried_and_filled_by_gateway() {
+        let placements = Arc::new(AtomicUsize::new(0));
+        let config = MoomooConfig {
+            base_url: fake_gateway(placements.clone()).await,
+            api_key: "test".to_string(),
+            secret_key: String::new(),
+            paper_trading: true,
+            timeout_ms: 1000,
+            retry_attempts: 2,
+            use_gateway: true,
+        };
+        let client = ApiClient::new(&config, reqwest::Client::new());
+        assert_eq!(client.environment(), TradingEnvironment::Simulate);
+
+        let order = Order {
+            id: uuid::Uuid::new_v4(),
+            symbol: "AAPL".to_string(),
+            order_type: OrderType::Limit,
+            side: OrderSide::Buy,
+            quantity: Decimal::from(10),
+            price: Some(Decimal::from(101)),
+            timestamp: chrono::Utc::now(),
+            status: OrderStatus::Pending,
+        };
+        let budget = RetryBudget::new(5);
+        let result = client.place_order(&order, &budget).await.unwrap();
+        assert_eq!(placements.load(Ordering::SeqCst), 2);
+        assert_eq!(budget.remaining(), 4);
+        assert!(result.success);
+        assert_eq!(result.executed_quantity, Decimal::from(10));
+        assert_eq!(result.executed_price, Decimal::new(1015, 1));
+        assert_eq!(result.slippage, Decimal::new(5, 1));
+
+        // A 500 may have come after the order was accepted, so the placement
+        // isn't sent again
+        assert!(client.place_order(&order, &budget).await.is_err());
+        assert_eq!(placements.load(Ordering::SeqCst), 3);
+
+        assert!(client.query_order_status(order.id).await.unwrap().is_none());
+        assert!(client.is_connected());
+        // Paper clients never place real orders, even as a live venue
+        assert!(client.submit(&order).await.is_err());
+        assert_eq!(ApiClient::live(&config, reqwest::Client::new()).environment(), TradingEnvironment::Real);
+    }
+
+    #[tokio::test]
+    async fn test_positions_are_fetched_from_gateway() {
+        let config = MoomooConfig {
+            base_url: fake_gateway(Arc::new(AtomicUsize::new(0))).await,
+            api_key: String::new(),
+            secret_key: String::new(),
+            paper_trading: true,
+            timeout_ms: 1000,
+            retry_attempts: 0,
+            use_gateway: true,
+        };
+        let positions = ApiClient::new(&config, reqwest::Client::new()).fetch_positions().await.unwrap();
+        assert_eq!(positions, vec![ImportedPosition {
+            symbol: "AAPL".to_string(),
+            quantity: Decimal::from(3),
+            average_cost: Decimal::new(15025, 2),
+            current_price: Some(Decimal::from(155)),
+        }]);
     }
 }
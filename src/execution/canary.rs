@@ -0,0 +1,133 @@
+//! Canary live validation
+//!
+//! While the main book trades on paper, a canary mirrors each order at a
+//! small fraction of its size to a live sub-account and compares the real
+//! fill with the simulated one. Going fully live should only happen once the
+//! canary has built up a clean record.
+
+use async_trait::async_trait;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::core::config::CanaryConfig;
+use crate::core::errors::{TradingError, TradingResult};
+use crate::core::types::{ExecutionResult, Order};
+
+/// A venue that executes real orders
+#[async_trait]
+pub trait LiveVenue: Send + Sync {
+    async fn submit(&self, order: &Order) -> TradingResult<ExecutionResult>;
+}
+
+/// Running comparison of canary fills against paper expectations
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CanaryStats {
+    pub fills: u64,
+    pub failures: u64,
+    pub slippage_breaches: u64,
+    pub max_deviation_bps: f64,
+}
+
+impl CanaryStats {
+    /// No failed submissions and no fills outside the slippage tolerance
+    pub fn is_clean(&self) -> bool {
+        self.failures == 0 && self.slippage_breaches == 0
+    }
+}
+
+/// Mirrors paper orders to a live sub-account at reduced size
+#[derive(Clone)]
+pub struct CanaryRouter {
+    config: CanaryConfig,
+    venue: Arc<dyn LiveVenue>,
+    stats: Arc<RwLock<CanaryStats>>,
+}
+
+impl CanaryRouter {
+    /// Create a canary routing to `venue`
+    pub fn new(config: CanaryConfig, venue: Arc<dyn LiveVenue>) -> Self {
+        Self {
+            config,
+            venue,
+            stats: Arc::new(RwLock::new(CanaryStats::default())),
+        }
+    }
+
+    /// Build the reduced-size live counterpart of a paper order
+    pub fn canary_order(&self, order: &Order) -> TradingResult<Order> {
+        let fraction = Decimal::from_f64(self.config.size_fraction)
+            .ok_or_else(|| TradingError::execution("Invalid canary size fraction"))?;
+        let quantity = order
+            .quantity
+            .checked_mul(fraction)
+            .ok_or_else(|| TradingError::overflow("canary order quantity"))?;
+
+        Ok(Order {
+            id: uuid::Uuid::new_v4(),
+            quantity,
+            timestamp: chrono::Utc::now(),
+            ..order.clone()
+        })
+    }
+
+    /// Send the canary order live and compare its fill against the paper fill
+    pub async fn mirror(&self, order: &Order, expected: &ExecutionResult) -> TradingResult<ExecutionResult> {
+        let canary = self.canary_order(order)?;
+        let fill = match self.venue.submit(&canary).await {
+            Ok(fill) if fill.success => fill,
+            Ok(fill) => {
+                self.stats.write().await.failures += 1;
+                return Err(TradingError::execution(format!(
+                    "Canary order {} rejected: {}",
+                    canary.id,
+                    fill.error_message.unwrap_or_default()
+                )));
+            }
+            Err(e) => {
+                self.stats.write().await.failures += 1;
+                return Err(e);
+            }
+        };
+
+        let deviation_bps = deviation_bps(fill.executed_price, expected.executed_price);
+        let mut stats = self.stats.write().await;
+        stats.fills += 1;
+        stats.max_deviation_bps = stats.max_deviation_bps.max(deviation_bps);
+        if deviation_bps > self.config.max_slippage_bps {
+            stats.slippage_breaches += 1;
+            warn!(
+                "🐤 Canary fill for {} deviated {:.1}bps from paper (limit {:.1}bps)",
+                order.symbol, deviation_bps, self.config.max_slippage_bps
+            );
+        } else {
+            info!("🐤 Canary fill for {} within {:.1}bps of paper", order.symbol, deviation_bps);
+        }
+
+        Ok(fill)
+    }
+
+    /// Current canary record
+    pub async fn stats(&self) -> CanaryStats {
+        self.stats.read().await.clone()
+    }
+
+    /// Whether the canary has enough clean fills to justify going fully live
+    pub async fn promotion_ready(&self) -> bool {
+        let stats = self.stats.read().await;
+        stats.is_clean() && stats.fills >= self.config.min_clean_fills
+    }
+}
+
+/// Absolute price deviation between a live and an expected fill, in basis points
+fn deviation_bps(actual: Decimal, expected: Decimal) -> f64 {
+    if expected.is_zero() {
+        return 0.0;
+    }
+    ((actual - expected).abs() / expected * Decimal::from(10_000))
+        .to_f64()
+        .unwrap_or(f64::MAX)
+}
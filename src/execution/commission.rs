@@ -0,0 +1,72 @@
+//! Broker commission model
+//!
+//! Brokers charge a rate on the traded amount, round the result to the cent
+//! and apply a minimum per trade; modelling the same keeps paper P&L in line
+//! with real statements.
+
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::core::config::CommissionConfig;
+
+/// Computes the commission charged on a trade
+#[derive(Debug, Clone)]
+pub struct CommissionModel {
+    rate: Decimal,
+    round_to_cents: bool,
+    minimum: Decimal,
+}
+
+impl Default for CommissionModel {
+    /// A flat 0.1% with no rounding or minimum
+    fn default() -> Self {
+        Self {
+            rate: Decimal::new(1, 3),
+            round_to_cents: false,
+            minimum: Decimal::ZERO,
+        }
+    }
+}
+
+impl CommissionModel {
+    /// Build the model from the configured rate and rounding rules
+    pub fn from_config(rate: Decimal, config: &CommissionConfig) -> Self {
+        Self {
+            rate,
+            round_to_cents: config.round_to_cents,
+            minimum: config.minimum_per_trade,
+        }
+    }
+
+    /// Commission on a trade worth `notional`
+    pub fn commission(&self, notional: Decimal) -> Decimal {
+        let mut commission = notional.abs() * self.rate;
+        if self.round_to_cents {
+            commission = commission.round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero);
+        }
+        commission.max(self.minimum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimum_and_cent_rounding() {
+        let model = CommissionModel::from_config(
+            Decimal::new(1, 3), // 0.1%
+            &CommissionConfig {
+                round_to_cents: true,
+                minimum_per_trade: Decimal::new(99, 2),
+            },
+        );
+
+        // 0.1% of $5 is half a cent, well under the minimum
+        assert_eq!(model.commission(Decimal::from(5)), Decimal::new(99, 2));
+        // 0.1% of $2,345.67 is $2.34567
+        assert_eq!(model.commission(Decimal::new(234_567, 2)), Decimal::new(235, 2));
+
+        // The default keeps unrounded precision
+        assert_eq!(CommissionModel::default().commission(Decimal::new(234_567, 2)), Decimal::new(234_567, 5));
+    }
+}
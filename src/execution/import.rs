@@ -0,0 +1,139 @@
+//! Import of positions already held at the broker
+//!
+//! Seeds the portfolio at startup so that pre-existing holdings are managed
+//! (risk-checked, exited, reported) rather than ignored.
+
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::core::errors::{TradingError, TradingResult};
+use crate::core::types::{Portfolio, Position, Symbol};
+
+/// A position as reported by the broker or an import file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportedPosition {
+    pub symbol: Symbol,
+    pub quantity: Decimal,
+    pub average_cost: Decimal,
+    #[serde(default)]
+    pub current_price: Option<Decimal>,
+}
+
+/// Somewhere existing positions can be fetched from
+#[async_trait]
+pub trait PositionSource: Send + Sync {
+    async fn fetch_positions(&self) -> TradingResult<Vec<ImportedPosition>>;
+}
+
+/// Positions listed in a JSON file, for paper trading
+pub struct FilePositionSource {
+    path: PathBuf,
+}
+
+impl FilePositionSource {
+    /// Read positions from the JSON array at `path`
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl PositionSource for FilePositionSource {
+    async fn fetch_positions(&self) -> TradingResult<Vec<ImportedPosition>> {
+        let contents = tokio::fs::read_to_string(&self.path).await?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+}
+
+impl Portfolio {
+    /// Seed the portfolio with externally held positions.
+    ///
+    /// Positions are valued at their current price (or average cost when none
+    /// is reported) and added to `total_value`, since the holdings already
+    /// exist outside the cash balance. Nothing is modified if any position is
+    /// invalid or a total would overflow.
+    pub fn import_positions(&mut self, imported: Vec<ImportedPosition>) -> TradingResult<usize> {
+        let mut positions = self.positions.clone();
+        let mut total_value = self.total_value;
+
+        for entry in imported {
+            if entry.quantity.is_zero() || entry.average_cost <= Decimal::ZERO {
+                return Err(TradingError::execution(format!(
+                    "Cannot import {}: quantity {} at average cost {}",
+                    entry.symbol, entry.quantity, entry.average_cost
+                )));
+            }
+            if positions.contains_key(&entry.symbol) {
+                return Err(TradingError::execution(format!("Duplicate imported position for {}", entry.symbol)));
+            }
+
+            let current_price = entry.current_price.unwrap_or(entry.average_cost);
+            let overflow = || TradingError::overflow(format!("imported position {}", entry.symbol));
+            let unrealized_pnl = current_price
+                .checked_sub(entry.average_cost)
+                .and_then(|diff| diff.checked_mul(entry.quantity))
+                .ok_or_else(overflow)?;
+
+            let position = Position {
+                id: uuid::Uuid::new_v4(),
+                symbol: entry.symbol.clone(),
+                quantity: entry.quantity,
+                entry_price: entry.average_cost,
+                current_price,
+                unrealized_pnl,
+                realized_pnl: Decimal::ZERO,
+                timestamp: chrono::Utc::now(),
+            };
+            total_value = total_value.checked_add(position.market_value()?).ok_or_else(overflow)?;
+            positions.insert(entry.symbol, position);
+        }
+
+        let count = positions.len() - self.positions.len();
+        self.positions = positions;
+        self.total_value = total_value;
+        self.last_updated = chrono::Utc::now();
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::SystemConfig;
+    use crate::core::types::SystemContext;
+    use crate::risk::reconciliation::PortfolioReconciler;
+
+    #[tokio::test]
+    async fn test_imported_positions_populate_portfolio() {
+        let path = std::env::temp_dir().join(format!("positions-{}.json", uuid::Uuid::new_v4()));
+        tokio::fs::write(
+            &path,
+            r#"[
+                {"symbol": "AAPL", "quantity": "3", "average_cost": "150.25"},
+                {"symbol": "MSFT", "quantity": "2", "average_cost": "300", "current_price": "310"}
+            ]"#,
+        )
+        .await
+        .unwrap();
+
+        let imported = FilePositionSource::new(&path).fetch_positions().await.unwrap();
+        tokio::fs::remove_file(&path).await.unwrap();
+
+        let mut portfolio = SystemContext::initial(&SystemConfig::default()).portfolio;
+        assert_eq!(portfolio.import_positions(imported).unwrap(), 2);
+
+        let aapl = &portfolio.positions["AAPL"];
+        assert_eq!(aapl.quantity, Decimal::from(3));
+        assert_eq!(aapl.entry_price, Decimal::new(15025, 2));
+
+        let msft = &portfolio.positions["MSFT"];
+        assert_eq!(msft.quantity, Decimal::from(2));
+        assert_eq!(msft.entry_price, Decimal::from(300));
+        assert_eq!(msft.unrealized_pnl, Decimal::from(20));
+
+        // Imported holdings keep the accounting invariant intact
+        assert!(PortfolioReconciler::new(Decimal::new(1, 2)).check(&portfolio).is_ok());
+    }
+}
@@ -0,0 +1,189 @@
+//! Trade journal of executed and rejected orders
+//!
+//! Every order outcome is appended as one JSON line. Rejections carry a
+//! structured [`ExecutionError`] so trades that never happened can be
+//! analysed by reason rather than by parsing log messages.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+use crate::agents::decision_trace::PipelineStage;
+use crate::core::calendar::SessionType;
+use crate::core::config::TradeJournalConfig;
+use crate::core::errors::{TradingError, TradingResult};
+use crate::core::types::{CloseReason, ExecutionResult, Order, OrderId, OrderSide, Symbol};
+use crate::execution::slippage::SlippageAttribution;
+
+/// Why the execution engine refused to send an order
+#[derive(Error, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum ExecutionError {
+    #[error("Market closed ({session:?})")]
+    MarketClosed { session: SessionType },
+
+    #[error("Spread too wide to trade in the {session:?} session")]
+    WideSpread { session: SessionType },
+
+    #[error("No quote in the last {max_age_secs}s")]
+    StaleQuote { max_age_secs: u64 },
+
+    #[error("Cannot execute hold signal")]
+    HoldSignal,
+
+    #[error("Compliance veto ({check}): {reason}")]
+    ComplianceVeto { check: String, reason: String },
+
+    #[error("Conflicts with open opposing orders {open_orders:?}")]
+    OrderConflict { open_orders: Vec<OrderId> },
+
+    #[error("Would dip below the minimum cash buffer of {reserve}")]
+    InsufficientCash { reserve: Decimal },
+
+    #[error("Order quantity {quantity} is not above the minimum of {minimum}")]
+    InvalidQuantity { quantity: Decimal, minimum: Decimal },
+
+    #[error("Order worth {notional} is below the minimum tradable {minimum}")]
+    BelowMinimumNotional { notional: Decimal, minimum: Decimal },
+
+    #[error("Only {remaining} left after netting, below the minimum quantity of {minimum}")]
+    NettedBelowMinimum { remaining: Decimal, minimum: Decimal },
+}
+
+impl ExecutionError {
+    /// Decision pipeline stage that refused the order
+    pub fn stage(&self) -> PipelineStage {
+        match self {
+            ExecutionError::ComplianceVeto { .. } => PipelineStage::Compliance,
+            ExecutionError::InsufficientCash { .. }
+            | ExecutionError::InvalidQuantity { .. }
+            | ExecutionError::BelowMinimumNotional { .. } => PipelineStage::Sizing,
+            _ => PipelineStage::Execution,
+        }
+    }
+}
+
+impl From<ExecutionError> for TradingError {
+    fn from(error: ExecutionError) -> Self {
+        match &error {
+            ExecutionError::StaleQuote { .. } => TradingError::market_data(error.to_string()),
+            ExecutionError::ComplianceVeto { .. } | ExecutionError::InsufficientCash { .. } => {
+                TradingError::risk_management(error.to_string())
+            }
+            _ => TradingError::execution(error.to_string()),
+        }
+    }
+}
+
+/// What happened to an order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "outcome")]
+pub enum JournalOutcome {
+    Executed { result: ExecutionResult },
+    Rejected { reason: ExecutionError },
+}
+
+/// One row of the trade journal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub timestamp: DateTime<Utc>,
+    pub symbol: Symbol,
+    pub side: Option<OrderSide>,      // Absent when rejected before an order was built
+    pub quantity: Option<Decimal>,
+    pub order_id: Option<OrderId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub close_reason: Option<CloseReason>, // Set when the order closes (part of) a position
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slippage: Option<SlippageAttribution>, // Absent without a quote from decision time
+    #[serde(flatten)]
+    pub outcome: JournalOutcome,
+}
+
+/// JSONL sink for order outcomes
+#[derive(Debug, Clone)]
+pub struct TradeJournal {
+    path: PathBuf,
+    record_rejections: bool,
+    write_lock: Arc<Mutex<()>>,
+}
+
+impl TradeJournal {
+    /// Append journal rows to the configured file, creating it if needed
+    pub fn from_config(config: &TradeJournalConfig) -> Self {
+        Self {
+            path: PathBuf::from(&config.path),
+            record_rejections: config.record_rejections,
+            write_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Record a filled order, with why it was sent if it closes a position and
+    /// where its slippage came from
+    pub async fn record_execution(
+        &self,
+        order: &Order,
+        result: &ExecutionResult,
+        close_reason: Option<CloseReason>,
+        slippage: Option<SlippageAttribution>,
+    ) -> TradingResult<()> {
+        self.append(&JournalEntry {
+            timestamp: Utc::now(),
+            symbol: order.symbol.clone(),
+            side: Some(order.side.clone()),
+            quantity: Some(order.quantity),
+            order_id: Some(order.id),
+            close_reason,
+            slippage,
+            outcome: JournalOutcome::Executed { result: result.clone() },
+        })
+        .await
+    }
+
+    /// Record an order refused before it reached the venue. `order` is absent
+    /// when the signal was rejected before an order was built from it.
+    pub async fn record_rejection(
+        &self,
+        symbol: &str,
+        order: Option<&Order>,
+        reason: &ExecutionError,
+    ) -> TradingResult<()> {
+        if !self.record_rejections {
+            return Ok(());
+        }
+        self.append(&JournalEntry {
+            timestamp: Utc::now(),
+            symbol: symbol.to_string(),
+            side: order.map(|order| order.side.clone()),
+            quantity: order.map(|order| order.quantity),
+            order_id: order.map(|order| order.id),
+            close_reason: None,
+            slippage: None,
+            outcome: JournalOutcome::Rejected { reason: reason.clone() },
+        })
+        .await
+    }
+
+    async fn append(&self, entry: &JournalEntry) -> TradingResult<()> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+
+        // Serialize writers so concurrent executions never interleave lines
+        let _guard = self.write_lock.lock().await;
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+        Ok(())
+    }
+}
@@ -1,9 +1,25 @@
 //! Execution module - Trade execution and order management
 
+pub mod algorithm;
 pub mod api;
+pub mod canary;
+pub mod commission;
+pub mod import;
+pub mod journal;
+pub mod multi_leg;
 pub mod orders;
+pub mod protection;
+pub mod retry;
 pub mod routing;
+pub mod sizing;
+pub mod slippage;
+pub mod take_profit;
+pub mod tick_size;
+pub mod twap;
+pub mod venues;
 
 pub use api::*;
 pub use orders::*;
 pub use routing::*;
+pub use tick_size::*;
+pub use venues::*;
@@ -0,0 +1,357 @@
+//! Multi-leg orders
+//!
+//! A multi-leg order, such as a vertical options spread, trades several
+//! instruments as one position. Under all-or-none the simulator fills every
+//! leg or none of them, so a spread is never left half open, and P&L is
+//! tracked net across the legs. Legs are priced from a `QuoteSource`, since
+//! option contracts aren't on the streamed watchlist.
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::core::config::MultiLegConfig;
+use crate::core::errors::{TradingError, TradingResult};
+use crate::core::types::{ExecutionResult, Fill, Liquidity, MarketData, OrderId, OrderSide, Position, Symbol};
+
+/// On-demand quotes for instruments outside the streamed watchlist
+#[async_trait]
+pub trait QuoteSource: Send + Sync {
+    /// Latest quote for `symbol`, or `None` if the source doesn't quote it
+    async fn quote(&self, symbol: &str) -> TradingResult<Option<MarketData>>;
+}
+
+/// Call or put
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum OptionRight {
+    Call,
+    Put,
+}
+
+/// A listed option on an underlying
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OptionContract {
+    pub underlying: Symbol,
+    pub expiry: NaiveDate,
+    pub strike: Decimal,
+    pub right: OptionRight,
+}
+
+impl OptionContract {
+    /// OCC-style symbol, e.g. `AAPL250117C00150000`. Fails for a strike the
+    /// eight-digit, thousandths-of-a-dollar field can't hold.
+    pub fn symbol(&self) -> TradingResult<Symbol> {
+        let right = match self.right {
+            OptionRight::Call => 'C',
+            OptionRight::Put => 'P',
+        };
+        let thousandths = self.strike * Decimal::from(1000);
+        let strike = thousandths
+            .to_u64()
+            .filter(|strike| self.strike > Decimal::ZERO && thousandths.fract().is_zero() && *strike < 100_000_000)
+            .ok_or_else(|| TradingError::execution(format!("Strike {} has no OCC symbol for {}", self.strike, self.underlying)))?;
+        Ok(format!("{}{}{}{:08}", self.underlying, self.expiry.format("%y%m%d"), right, strike))
+    }
+}
+
+/// One instrument within a multi-leg order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderLeg {
+    pub symbol: Symbol,
+    pub side: OrderSide,
+    pub quantity: Decimal,
+    pub limit_price: Option<Decimal>, // Per unit; None takes the quote
+    pub multiplier: Decimal,          // Units per contract; 1 for shares
+    pub contract: Option<OptionContract>,
+}
+
+impl OrderLeg {
+    /// Leg trading `quantity` contracts of an option
+    pub fn option(contract: OptionContract, side: OrderSide, quantity: Decimal, multiplier: Decimal) -> TradingResult<Self> {
+        Ok(Self {
+            symbol: contract.symbol()?,
+            side,
+            quantity,
+            limit_price: None,
+            multiplier,
+            contract: Some(contract),
+        })
+    }
+}
+
+/// Several legs traded together as one position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiLegOrder {
+    pub id: OrderId,
+    pub strategy: String,
+    pub legs: Vec<OrderLeg>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl MultiLegOrder {
+    pub fn new(strategy: &str, legs: Vec<OrderLeg>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4(),
+            strategy: strategy.to_string(),
+            legs,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Vertical spread: buy `quantity` contracts at `long_strike` and sell as
+    /// many at `short_strike`, on the same underlying, expiry and right
+    pub fn vertical_spread(
+        underlying: &str,
+        expiry: NaiveDate,
+        right: OptionRight,
+        long_strike: Decimal,
+        short_strike: Decimal,
+        quantity: Decimal,
+        multiplier: Decimal,
+    ) -> TradingResult<Self> {
+        if long_strike == short_strike {
+            return Err(TradingError::execution(format!("Vertical spread on {} needs two different strikes", underlying)));
+        }
+        let contract = |strike| OptionContract { underlying: underlying.to_string(), expiry, strike, right };
+        let order = Self::new(
+            "vertical_spread",
+            vec![
+                OrderLeg::option(contract(long_strike), OrderSide::Buy, quantity, multiplier)?,
+                OrderLeg::option(contract(short_strike), OrderSide::Sell, quantity, multiplier)?,
+            ],
+        );
+        order.validate()?;
+        Ok(order)
+    }
+
+    /// Refuse an order without legs, or with a leg of no size
+    pub fn validate(&self) -> TradingResult<()> {
+        if self.legs.is_empty() {
+            return Err(TradingError::execution(format!("Multi-leg order {} has no legs", self.id)));
+        }
+        match self.legs.iter().find(|leg| leg.quantity <= Decimal::ZERO || leg.multiplier <= Decimal::ZERO) {
+            Some(leg) => Err(TradingError::execution(format!(
+                "{} leg {} needs a positive quantity and multiplier, got {} x {}",
+                self.strategy, leg.symbol, leg.quantity, leg.multiplier
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Execution of one leg
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LegFill {
+    pub symbol: Symbol,
+    pub side: OrderSide,
+    pub quantity: Decimal,
+    pub price: Decimal,
+    pub multiplier: Decimal,
+}
+
+impl LegFill {
+    /// Units held: positive when bought, negative when sold
+    fn position(&self) -> Decimal {
+        match self.side {
+            OrderSide::Buy => self.quantity * self.multiplier,
+            OrderSide::Sell => -self.quantity * self.multiplier,
+        }
+    }
+}
+
+/// Executed legs of a multi-leg order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiLegFill {
+    pub order_id: OrderId,
+    pub strategy: String,
+    pub legs: Vec<LegFill>,
+    pub complete: bool, // Every leg of the order filled
+}
+
+impl MultiLegFill {
+    /// Net premium paid to open the position; negative for a net credit
+    pub fn net_debit(&self) -> Decimal {
+        self.legs.iter().map(|leg| leg.position() * leg.price).sum()
+    }
+
+    /// Premium traded across every leg, bought or sold; commission is charged on this
+    pub fn gross_premium(&self) -> Decimal {
+        self.legs.iter().map(|leg| (leg.position() * leg.price).abs()).sum()
+    }
+
+    /// Name the combined position is booked under, e.g.
+    /// `vertical_spread:AAPL250117C00150000/AAPL250117C00155000`
+    pub fn position_symbol(&self) -> Symbol {
+        let legs: Vec<&str> = self.legs.iter().map(|leg| leg.symbol.as_str()).collect();
+        format!("{}:{}", self.strategy, legs.join("/"))
+    }
+
+    /// The legs as one position of as many units as the largest leg, priced
+    /// at the net debit per unit
+    pub fn position(&self) -> Position {
+        let quantity = self.legs.iter().map(|leg| leg.quantity).max().unwrap_or(Decimal::ZERO);
+        let price = if quantity.is_zero() { Decimal::ZERO } else { self.net_debit() / quantity };
+        Position {
+            id: uuid::Uuid::new_v4(),
+            symbol: self.position_symbol(),
+            quantity,
+            entry_price: price,
+            current_price: price,
+            unrealized_pnl: Decimal::ZERO,
+            realized_pnl: Decimal::ZERO,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// The fill as one execution of the combined position
+    pub fn execution_result(&self, commission: Decimal, execution_time_ms: u64) -> TradingResult<ExecutionResult> {
+        let position = self.position();
+        let fill = Fill {
+            order_id: self.order_id,
+            quantity: position.quantity,
+            price: position.entry_price,
+            timestamp: Utc::now(),
+            liquidity: Liquidity::Taker,
+        };
+        ExecutionResult::from_fills(self.order_id, vec![fill], Decimal::ZERO, commission, execution_time_ms)
+    }
+
+    /// Combined P&L of the legs marked at `marks`. Legs without a mark are
+    /// valued at their fill price.
+    pub fn pnl(&self, marks: &HashMap<Symbol, Decimal>) -> Decimal {
+        self.legs
+            .iter()
+            .map(|leg| {
+                let mark = marks.get(&leg.symbol).copied().unwrap_or(leg.price);
+                leg.position() * (mark - leg.price)
+            })
+            .sum()
+    }
+}
+
+/// Fills multi-leg orders against quotes when no gateway is attached
+#[derive(Debug, Clone)]
+pub struct MultiLegSimulator {
+    all_or_none: bool,
+}
+
+impl MultiLegSimulator {
+    pub fn from_config(config: &MultiLegConfig) -> Self {
+        Self { all_or_none: config.all_or_none }
+    }
+
+    /// Fill each leg at its quote. Under all-or-none, a leg that can't fill
+    /// rejects the whole order and nothing is executed.
+    pub fn fill(&self, order: &MultiLegOrder, quotes: &HashMap<Symbol, MarketData>) -> TradingResult<MultiLegFill> {
+        order.validate()?;
+
+        let mut legs = Vec::with_capacity(order.legs.len());
+        for leg in &order.legs {
+            match fill_price(leg, quotes.get(&leg.symbol)) {
+                Some(price) => legs.push(LegFill {
+                    symbol: leg.symbol.clone(),
+                    side: leg.side.clone(),
+                    quantity: leg.quantity,
+                    price,
+                    multiplier: leg.multiplier,
+                }),
+                None if self.all_or_none => {
+                    return Err(TradingError::execution(format!(
+                        "{} leg {} cannot fill; all-or-none order {} rejected with no legs executed",
+                        order.strategy, leg.symbol, order.id
+                    )));
+                }
+                None => {}
+            }
+        }
+
+        Ok(MultiLegFill {
+            order_id: order.id,
+            strategy: order.strategy.clone(),
+            complete: legs.len() == order.legs.len(),
+            legs,
+        })
+    }
+}
+
+/// Price a leg fills at: the ask when buying and the bid when selling,
+/// falling back to the last price. None without a quote or if the limit
+/// isn't met.
+fn fill_price(leg: &OrderLeg, quote: Option<&MarketData>) -> Option<Decimal> {
+    let quote = quote?;
+    let price = match leg.side {
+        OrderSide::Buy => quote.ask.unwrap_or(quote.price),
+        OrderSide::Sell => quote.bid.unwrap_or(quote.price),
+    };
+    match (&leg.side, leg.limit_price) {
+        (OrderSide::Buy, Some(limit)) if price > limit => None,
+        (OrderSide::Sell, Some(limit)) if price < limit => None,
+        _ => Some(price),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(symbol: &str, bid: Decimal, ask: Decimal) -> (Symbol, MarketData) {
+        let data = MarketData {
+            symbol: symbol.to_string(),
+            timestamp: Utc::now(),
+            price: (bid + ask) / Decimal::from(2),
+            volume: 100,
+            bid: Some(bid),
+            ask: Some(ask),
+            bid_size: Some(10),
+            ask_size: Some(10),
+            source: Default::default(),
+        };
+        (symbol.to_string(), data)
+    }
+
+    #[test]
+    fn test_spread_fills_atomically_with_net_pnl() {
+        let expiry = NaiveDate::from_ymd_opt(2025, 1, 17).unwrap();
+        let spread = MultiLegOrder::vertical_spread("AAPL", expiry, OptionRight::Call, Decimal::from(150), Decimal::from(155), Decimal::from(1), Decimal::from(100)).unwrap();
+        let (long, short) = (spread.legs[0].symbol.clone(), spread.legs[1].symbol.clone());
+        assert_eq!(long, "AAPL250117C00150000");
+
+        let simulator = MultiLegSimulator::from_config(&MultiLegConfig::default());
+        let quotes: HashMap<_, _> = [quote(&long, Decimal::new(48, 1), Decimal::new(50, 1)), quote(&short, Decimal::new(20, 1), Decimal::new(22, 1))].into();
+        let fill = simulator.fill(&spread, &quotes).unwrap();
+        assert!(fill.complete);
+        // Buy the 150 call at the 5.00 ask, sell the 155 call at the 2.00 bid
+        assert_eq!(fill.net_debit(), Decimal::from(300));
+        let marks = HashMap::from([(long.clone(), Decimal::new(70, 1)), (short.clone(), Decimal::new(30, 1))]);
+        assert_eq!(fill.pnl(&marks), Decimal::from(100));
+        // Booked as one spread, bought for its net debit
+        let position = fill.position();
+        assert_eq!(position.symbol, format!("vertical_spread:{}/{}", long, short));
+        assert_eq!((position.quantity, position.market_value().unwrap()), (Decimal::from(1), Decimal::from(300)));
+        assert_eq!(fill.gross_premium(), Decimal::from(700));
+
+        // No bid for the short leg: neither leg executes
+        let one_sided: HashMap<_, _> = [quote(&long, Decimal::new(48, 1), Decimal::new(50, 1))].into();
+        let error = simulator.fill(&spread, &one_sided).unwrap_err();
+        assert!(error.to_string().contains("no legs executed"), "{}", error);
+
+        // Without all-or-none the fillable leg goes through alone
+        let partial = MultiLegSimulator::from_config(&MultiLegConfig { all_or_none: false });
+        let fill = partial.fill(&spread, &one_sided).unwrap();
+        assert!(!fill.complete);
+        assert_eq!(fill.legs.len(), 1);
+
+        // Malformed spreads are refused rather than sent
+        let spread = |long: i64, short: i64, quantity: i64, multiplier: i64| {
+            MultiLegOrder::vertical_spread("AAPL", expiry, OptionRight::Call, Decimal::from(long), Decimal::from(short), Decimal::from(quantity), Decimal::from(multiplier))
+        };
+        assert!(spread(150, 150, 1, 100).is_err());
+        assert!(spread(150, 155, 0, 100).is_err());
+        assert!(spread(150, 155, 1, 0).is_err());
+        assert!(spread(-5, 155, 1, 100).is_err());
+        assert!(spread(150, 100_000, 1, 100).is_err());
+    }
+}
@@ -1,10 +1,372 @@
 //! Order management
 
-/// Placeholder for order management
-pub struct OrderManager;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::agents::traits::OrderStatus as OrderStatusReport;
+use crate::core::errors::TradingResult;
+use crate::core::types::{Fill, Order, OrderId, OrderSide, OrderStatus};
+use crate::execution::retry::RetryBudget;
+
+/// What to do with a new order that opposes an open order on the same symbol
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum OrderConflictPolicy {
+    /// Refuse the new order
+    #[default]
+    Reject,
+    /// Offset the orders against each other, sending only the difference
+    Net,
+    /// Cancel the opposing open orders and send the new order in full
+    CancelAndReplace,
+}
+
+/// How an order conflict was handled
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConflictResolution {
+    /// No opposing open orders
+    NoConflict,
+    /// The new order must not be sent because of these open orders
+    Rejected(Vec<OrderId>),
+    /// Opposing orders were cancelled or reduced to the given quantity; the
+    /// new order keeps `remaining`
+    Netted { cancelled: Vec<OrderId>, reduced: Vec<(OrderId, Decimal)>, remaining: Decimal },
+    /// These opposing orders were cancelled in favour of the new order
+    Replaced(Vec<OrderId>),
+}
+
+/// Broker-side view of order state, queried to resync after a reconnect
+#[async_trait]
+pub trait OrderStatusSource: Send + Sync {
+    /// Whether the gateway connection is currently up
+    fn is_connected(&self) -> bool;
+
+    /// Current status of an order, or `None` if the broker doesn't know it
+    async fn order_status(&self, order_id: OrderId) -> TradingResult<Option<OrderStatusReport>>;
+}
+
+/// Tracks orders that are still open locally
+#[derive(Debug, Default)]
+pub struct OrderManager {
+    open: HashMap<OrderId, Order>,
+}
 
 impl OrderManager {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Start tracking an order awaiting its fill
+    pub fn track(&mut self, order: Order) {
+        self.open.insert(order.id, order);
+    }
+
+    /// Stop tracking an order, returning it if it was open
+    pub fn remove(&mut self, order_id: &OrderId) -> Option<Order> {
+        self.open.remove(order_id)
+    }
+
+    /// Stop tracking every open order, returning them
+    pub fn drain(&mut self) -> Vec<Order> {
+        self.open.drain().map(|(_, order)| order).collect()
+    }
+
+    /// Snapshot of the open orders
+    pub fn open_orders(&self) -> Vec<Order> {
+        self.open.values().cloned().collect()
+    }
+
+    /// Whether an order is still tracked as open
+    pub fn is_open(&self, order_id: &OrderId) -> bool {
+        self.open.contains_key(order_id)
+    }
+
+    /// Number of open orders
+    pub fn open_count(&self) -> usize {
+        self.open.len()
+    }
+
+    /// Open orders on `order`'s symbol on the opposite side, oldest first
+    pub fn opposing(&self, order: &Order) -> Vec<&Order> {
+        let mut opposing: Vec<&Order> = self
+            .open
+            .values()
+            .filter(|open| open.id != order.id && open.symbol == order.symbol && !same_side(&open.side, &order.side))
+            .collect();
+        opposing.sort_by_key(|open| (open.timestamp, open.id));
+        opposing
+    }
+
+    /// Check `order` against opposing open orders and apply `policy`. When
+    /// netting, `order.quantity` is reduced by the offset amount; a zero
+    /// remainder means it was absorbed entirely and should not be sent.
+    pub fn resolve_conflict(&mut self, order: &mut Order, policy: OrderConflictPolicy) -> ConflictResolution {
+        let opposing: Vec<OrderId> = self.opposing(order).iter().map(|open| open.id).collect();
+        if opposing.is_empty() {
+            return ConflictResolution::NoConflict;
+        }
+
+        match policy {
+            OrderConflictPolicy::Reject => ConflictResolution::Rejected(opposing),
+            OrderConflictPolicy::CancelAndReplace => {
+                for id in &opposing {
+                    self.open.remove(id);
+                }
+                ConflictResolution::Replaced(opposing)
+            }
+            OrderConflictPolicy::Net => {
+                let (mut cancelled, mut reduced) = (Vec::new(), Vec::new());
+                for id in opposing {
+                    if order.quantity.is_zero() {
+                        break;
+                    }
+                    let Some(open) = self.open.get_mut(&id) else {
+                        continue;
+                    };
+                    if open.quantity <= order.quantity {
+                        order.quantity -= open.quantity;
+                        self.open.remove(&id);
+                        cancelled.push(id);
+                    } else {
+                        open.quantity -= order.quantity;
+                        order.quantity = Decimal::ZERO;
+                        reduced.push((id, open.quantity));
+                    }
+                }
+                ConflictResolution::Netted { cancelled, reduced, remaining: order.quantity }
+            }
+        }
+    }
+
+    /// Apply a broker status report. Orders reaching a terminal state are no
+    /// longer tracked and are returned with their final status.
+    pub fn apply_status(&mut self, report: &OrderStatusReport) -> Option<Order> {
+        let order = self.open.get_mut(&report.order_id)?;
+        order.status = report.status.clone();
+
+        match report.status {
+            OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected => {
+                self.open.remove(&report.order_id)
+            }
+            OrderStatus::Pending | OrderStatus::PartiallyFilled => None,
+        }
+    }
+}
+
+/// Submitted versus filled quantity of one order
+#[derive(Debug, Clone)]
+pub struct OrderProgress {
+    pub order: Order, // As submitted; `status` tracks its lifecycle
+    pub filled_quantity: Decimal,
+    pub retry_budget: RetryBudget, // Shared by every layer that may retry the order
+    notional: Decimal,
+}
+
+impl OrderProgress {
+    /// Quantity still working; nothing remains once the order is cancelled or rejected
+    pub fn remaining_quantity(&self) -> Decimal {
+        match self.order.status {
+            OrderStatus::Cancelled | OrderStatus::Rejected => Decimal::ZERO,
+            _ => (self.order.quantity - self.filled_quantity).max(Decimal::ZERO),
+        }
+    }
+
+    /// Volume-weighted price of the fills so far
+    pub fn average_price(&self) -> Decimal {
+        if self.filled_quantity.is_zero() {
+            Decimal::ZERO
+        } else {
+            self.notional / self.filled_quantity
+        }
+    }
+
+    pub fn report(&self) -> OrderStatusReport {
+        OrderStatusReport {
+            order_id: self.order.id,
+            status: self.order.status.clone(),
+            filled_quantity: self.filled_quantity,
+            average_price: self.average_price(),
+            remaining_quantity: self.remaining_quantity(),
+            estimated_completion: None,
+        }
+    }
+}
+
+/// Every submitted order and how much of it has filled, kept until the order
+/// reaches a terminal state
+#[derive(Debug, Default)]
+pub struct OrderBook {
+    orders: HashMap<OrderId, OrderProgress>,
+}
+
+impl OrderBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start tracking a submitted order and the retries it may still use
+    pub fn submit(&mut self, order: &Order, retry_budget: RetryBudget) {
+        self.orders.insert(order.id, OrderProgress {
+            order: Order { status: OrderStatus::Pending, ..order.clone() },
+            filled_quantity: Decimal::ZERO,
+            retry_budget,
+            notional: Decimal::ZERO,
+        });
+    }
+
+    /// Apply fills to `order_id`. Returns the order once it is completely
+    /// filled, after which it is no longer tracked.
+    pub fn record_fills(&mut self, order_id: OrderId, fills: &[Fill]) -> Option<OrderProgress> {
+        let progress = self.orders.get_mut(&order_id)?;
+        for fill in fills {
+            progress.filled_quantity += fill.quantity;
+            progress.notional += fill.price * fill.quantity;
+        }
+
+        if progress.filled_quantity >= progress.order.quantity {
+            progress.order.status = OrderStatus::Filled;
+            return self.orders.remove(&order_id);
+        }
+        if !progress.filled_quantity.is_zero() {
+            progress.order.status = OrderStatus::PartiallyFilled;
+        }
+        None
+    }
+
+    /// End an order that will not fill any further, keeping whatever already
+    /// filled. Returns its final state.
+    pub fn finish(&mut self, order_id: OrderId, status: OrderStatus) -> Option<OrderProgress> {
+        let mut progress = self.orders.remove(&order_id)?;
+        progress.order.status = status;
+        Some(progress)
+    }
+
+    /// Change the quantity a tracked order was submitted for, after the
+    /// broker accepted the amendment
+    pub fn amend(&mut self, order_id: OrderId, quantity: Decimal) {
+        if let Some(progress) = self.orders.get_mut(&order_id) {
+            progress.order.quantity = quantity;
+        }
+    }
+
+    /// Retry budget of a tracked order
+    pub fn retry_budget(&self, order_id: OrderId) -> Option<RetryBudget> {
+        self.orders.get(&order_id).map(|progress| progress.retry_budget.clone())
+    }
+
+    /// Unfilled quantity of a tracked order
+    pub fn remaining(&self, order_id: OrderId) -> Option<Decimal> {
+        self.orders.get(&order_id).map(OrderProgress::remaining_quantity)
+    }
+
+    /// Ids of every order still working
+    pub fn open_ids(&self) -> Vec<OrderId> {
+        self.orders.keys().copied().collect()
+    }
+
+    /// Status of every tracked order
+    pub fn reports(&self) -> Vec<OrderStatusReport> {
+        self.orders.values().map(OrderProgress::report).collect()
+    }
+}
+
+fn same_side(a: &OrderSide, b: &OrderSide) -> bool {
+    matches!((a, b), (OrderSide::Buy, OrderSide::Buy) | (OrderSide::Sell, OrderSide::Sell))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::OrderType;
+
+    fn order(side: OrderSide, quantity: i64) -> Order {
+        Order {
+            id: uuid::Uuid::new_v4(),
+            symbol: "AAPL".to_string(),
+            order_type: OrderType::Limit,
+            side,
+            quantity: Decimal::from(quantity),
+            price: Some(Decimal::from(150)),
+            timestamp: chrono::Utc::now(),
+            status: OrderStatus::Pending,
+        }
+    }
+
+    fn manager_with_open_sell(quantity: i64) -> (OrderManager, OrderId) {
+        let mut manager = OrderManager::new();
+        let sell = order(OrderSide::Sell, quantity);
+        let id = sell.id;
+        manager.track(sell);
+        manager.track(Order { symbol: "MSFT".to_string(), ..order(OrderSide::Sell, 5) });
+        (manager, id)
+    }
+
+    #[test]
+    fn test_opposing_order_handled_per_policy() {
+        let (mut manager, sell) = manager_with_open_sell(4);
+        let mut buy = order(OrderSide::Buy, 10);
+        assert_eq!(manager.resolve_conflict(&mut buy, OrderConflictPolicy::Reject), ConflictResolution::Rejected(vec![sell]));
+        assert_eq!((manager.open_count(), buy.quantity), (2, Decimal::from(10)));
+
+        // Same-side orders never conflict
+        let mut another_sell = order(OrderSide::Sell, 1);
+        assert_eq!(manager.resolve_conflict(&mut another_sell, OrderConflictPolicy::Reject), ConflictResolution::NoConflict);
+
+        let (mut manager, sell) = manager_with_open_sell(4);
+        let mut buy = order(OrderSide::Buy, 10);
+        assert_eq!(
+            manager.resolve_conflict(&mut buy, OrderConflictPolicy::Net),
+            ConflictResolution::Netted { cancelled: vec![sell], reduced: Vec::new(), remaining: Decimal::from(6) }
+        );
+        assert_eq!((manager.open_count(), buy.quantity), (1, Decimal::from(6)));
+
+        // A smaller opposing order is absorbed into the open one
+        let (mut manager, sell) = manager_with_open_sell(4);
+        let mut buy = order(OrderSide::Buy, 3);
+        assert_eq!(
+            manager.resolve_conflict(&mut buy, OrderConflictPolicy::Net),
+            ConflictResolution::Netted { cancelled: Vec::new(), reduced: vec![(sell, Decimal::ONE)], remaining: Decimal::ZERO }
+        );
+        let open = manager.open_orders().into_iter().find(|open| open.id == sell).unwrap();
+        assert_eq!(open.quantity, Decimal::ONE);
+
+        let (mut manager, sell) = manager_with_open_sell(4);
+        let mut buy = order(OrderSide::Buy, 10);
+        assert_eq!(manager.resolve_conflict(&mut buy, OrderConflictPolicy::CancelAndReplace), ConflictResolution::Replaced(vec![sell]));
+        assert_eq!((manager.open_count(), buy.quantity), (1, Decimal::from(10)));
+    }
+
+    #[test]
+    fn test_partial_fill_then_cancel_keeps_filled_quantity() {
+        let fill = |order_id, quantity: i64, price: i64| Fill {
+            order_id,
+            quantity: Decimal::from(quantity),
+            price: Decimal::from(price),
+            timestamp: chrono::Utc::now(),
+            liquidity: crate::core::types::Liquidity::Maker,
+        };
+        let mut book = OrderBook::new();
+        let partial = order(OrderSide::Buy, 10);
+        let complete = order(OrderSide::Sell, 4);
+        book.submit(&partial, RetryBudget::new(0));
+        book.submit(&complete, RetryBudget::new(0));
+
+        assert!(book.record_fills(partial.id, &[fill(partial.id, 4, 100), fill(partial.id, 2, 103)]).is_none());
+        let report = book.reports().into_iter().find(|report| report.order_id == partial.id).unwrap();
+        assert!(matches!(report.status, OrderStatus::PartiallyFilled));
+        assert_eq!(report.filled_quantity, Decimal::from(6));
+        assert_eq!(report.remaining_quantity, Decimal::from(4));
+        assert_eq!(report.average_price, Decimal::from(101));
+
+        // Cancelling the remainder keeps the partial fill but leaves nothing working
+        let cancelled = book.finish(partial.id, OrderStatus::Cancelled).unwrap();
+        assert_eq!(cancelled.filled_quantity, Decimal::from(6));
+        assert_eq!(cancelled.remaining_quantity(), Decimal::ZERO);
+        assert!(book.remaining(partial.id).is_none());
+
+        let filled = book.record_fills(complete.id, &[fill(complete.id, 4, 99)]).unwrap();
+        assert!(matches!(filled.order.status, OrderStatus::Filled));
+        assert!(book.reports().is_empty());
     }
 }
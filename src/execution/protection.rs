@@ -0,0 +1,47 @@
+//! Persistence of per-position protective levels
+//!
+//! Stops and take-profit plans live in memory while trading; this store keeps
+//! a snapshot on disk so they can be re-armed after a restart instead of
+//! leaving open positions unprotected.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::core::errors::TradingResult;
+use crate::core::types::Symbol;
+use crate::execution::take_profit::ScaleOut;
+
+/// JSON snapshot of the protective levels for each managed position
+#[derive(Debug, Clone)]
+pub struct ProtectionStore {
+    path: PathBuf,
+}
+
+impl ProtectionStore {
+    /// Keep the snapshot in the file at `path`
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Replace the snapshot. Written to a temporary file first so a crash
+    /// mid-write can't corrupt the previous snapshot.
+    pub async fn save(&self, levels: &HashMap<Symbol, ScaleOut>) -> TradingResult<()> {
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let temp = self.path.with_extension("tmp");
+        tokio::fs::write(&temp, serde_json::to_vec_pretty(levels)?).await?;
+        tokio::fs::rename(&temp, &self.path).await?;
+        Ok(())
+    }
+
+    /// Load the last snapshot; no snapshot means nothing to re-arm
+    pub async fn load(&self) -> TradingResult<HashMap<Symbol, ScaleOut>> {
+        match tokio::fs::read(&self.path).await {
+            Ok(contents) => Ok(serde_json::from_slice(&contents)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! Per-order retry budget
+//!
+//! The gateway client, partial-fill re-submission and late-fill reconciliation
+//! can each retry an order. They draw from one budget carried with the order,
+//! so the total number of retries stays bounded however the layers combine.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+/// Retries left for one order, shared by every layer that may retry it
+#[derive(Debug, Clone)]
+pub struct RetryBudget {
+    remaining: Arc<AtomicU32>,
+}
+
+impl RetryBudget {
+    pub fn new(max_retries: u32) -> Self {
+        Self { remaining: Arc::new(AtomicU32::new(max_retries)) }
+    }
+
+    /// Take one retry from the budget, returning false once it is exhausted
+    pub fn try_consume(&self) -> bool {
+        self.remaining
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |remaining| remaining.checked_sub(1))
+            .is_ok()
+    }
+
+    /// Retries not yet used
+    pub fn remaining(&self) -> u32 {
+        self.remaining.load(Ordering::SeqCst)
+    }
+}
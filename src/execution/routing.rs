@@ -1,10 +1,97 @@
 //! Order routing optimization
 
-/// Placeholder for order routing
-pub struct OrderRouter;
+use crate::core::config::VenueRoutingConfig;
+use crate::execution::venues::{VenueStatistics, VenueStats};
+
+/// Chooses between venues by their recent execution quality
+#[derive(Debug, Clone)]
+pub struct OrderRouter {
+    config: VenueRoutingConfig,
+}
 
 impl OrderRouter {
-    pub fn new() -> Self {
-        Self
+    /// Create a router from configuration
+    pub fn from_config(config: &VenueRoutingConfig) -> Self {
+        Self { config: config.clone() }
+    }
+
+    /// Cost of routing to a venue, in basis-point equivalents (lower is better)
+    pub fn score(&self, stats: &VenueStats) -> f64 {
+        stats.avg_slippage_bps() * self.config.slippage_weight
+            + stats.avg_latency_ms() * self.config.latency_weight_per_ms
+            + (1.0 - stats.fill_rate()) * self.config.fill_rate_weight
+    }
+
+    /// Pick the best-scoring candidate with at least `min_orders` of history.
+    /// Falls back to the first candidate until any venue has enough history.
+    pub fn preferred_venue<'a>(&self, statistics: &VenueStatistics, candidates: &'a [String]) -> Option<&'a str> {
+        candidates
+            .iter()
+            .filter_map(|venue| {
+                let stats = statistics.get(venue)?;
+                (stats.submitted >= self.config.min_orders).then(|| (venue, self.score(stats)))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(venue, _)| venue.as_str())
+            .or_else(|| candidates.first().map(String::as_str))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::ExecutionResult;
+    use rust_decimal::Decimal;
+
+    fn fill(price: i64, slippage: Decimal, latency_ms: u64, success: bool) -> ExecutionResult {
+        ExecutionResult {
+            order_id: uuid::Uuid::new_v4(),
+            executed_quantity: Decimal::from(10),
+            executed_price: Decimal::from(price),
+            execution_time_ms: latency_ms,
+            slippage,
+            commission: Decimal::ZERO,
+            success,
+            error_message: None,
+            fills: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_router_prefers_venue_with_better_fills() {
+        let mut statistics = VenueStatistics::new();
+        for _ in 0..5 {
+            // 2bps and 10ms on every order
+            statistics.record("fast", &fill(100, Decimal::new(2, 2), 10, true));
+            // 5bps and 30ms, but a quarter of orders never fill
+            statistics.record("slow", &fill(100, Decimal::new(5, 2), 30, true));
+            statistics.record("slow", &fill(100, Decimal::new(5, 2), 30, true));
+            statistics.record("slow", &fill(100, Decimal::new(5, 2), 30, true));
+            statistics.record("slow", &fill(0, Decimal::ZERO, 0, false));
+        }
+
+        let fast = statistics.get("fast").unwrap();
+        assert_eq!((fast.submitted, fast.filled), (5, 5));
+        assert!((fast.avg_slippage_bps() - 2.0).abs() < 1e-9);
+        assert!((fast.avg_latency_ms() - 10.0).abs() < 1e-9);
+        assert_eq!(fast.fill_rate(), 1.0);
+
+        let slow = statistics.get("slow").unwrap();
+        assert_eq!((slow.submitted, slow.filled), (20, 15));
+        assert!((slow.avg_slippage_bps() - 5.0).abs() < 1e-9);
+        assert!((slow.avg_latency_ms() - 30.0).abs() < 1e-9);
+        assert_eq!(slow.fill_rate(), 0.75);
+
+        let candidates = vec!["slow".to_string(), "fast".to_string()];
+        let mut config = VenueRoutingConfig { min_orders: 10, ..VenueRoutingConfig::default() };
+
+        // Too little history on the better venue to trust it yet
+        let router = OrderRouter::from_config(&config);
+        assert_eq!(router.preferred_venue(&statistics, &candidates), Some("slow"));
+
+        config.min_orders = 5;
+        let router = OrderRouter::from_config(&config);
+        assert!(router.score(fast) < router.score(slow));
+        assert_eq!(router.preferred_venue(&statistics, &candidates), Some("fast"));
     }
 }
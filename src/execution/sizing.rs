@@ -0,0 +1,169 @@
+//! Confidence-weighted position sizing
+//!
+//! Orders start at the base size scaled by signal strength. Confidence above
+//! the reference grows the order and confidence below it shrinks it, by as
+//! much as the configured weight allows and never beyond the multiplier cap.
+//! Orders worth less than the minimum tradable notional are skipped or held
+//! back until later intent on the same symbol makes up the difference.
+
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::core::config::{OrderMinimumConfig, PositionSizingConfig};
+use crate::core::types::{OrderSide, Symbol};
+
+/// Sizes orders from a signal's strength and confidence
+#[derive(Debug, Clone)]
+pub struct PositionSizer {
+    config: PositionSizingConfig,
+}
+
+impl PositionSizer {
+    pub fn from_config(config: &PositionSizingConfig) -> Self {
+        Self { config: config.clone() }
+    }
+
+    /// Multiplier applied for a signal of `confidence`, between 0 and the cap
+    pub fn confidence_multiplier(&self, confidence: f64) -> f64 {
+        let weight = self.config.confidence_weight.clamp(0.0, 1.0);
+        let relative = confidence.max(0.0) / self.config.reference_confidence;
+        ((1.0 - weight) + weight * relative).clamp(0.0, self.config.max_confidence_multiplier.max(1.0))
+    }
+
+    /// Order quantity for a signal, before the aggressiveness multiplier
+    pub fn quantity(&self, strength: f64, confidence: f64) -> Decimal {
+        let multiplier = Decimal::from_f64(self.confidence_multiplier(confidence)).unwrap_or(Decimal::ONE);
+        let strength = Decimal::from_f64(strength).unwrap_or(Decimal::ZERO);
+        self.config.base_size * strength * multiplier
+    }
+}
+
+/// What to do with an order worth less than the minimum tradable notional
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum SubMinimumPolicy {
+    /// Drop the order
+    #[default]
+    Skip,
+    /// Hold it back, adding later orders on the same symbol and side until
+    /// together they reach the minimum
+    Accumulate,
+}
+
+/// Outcome of checking an order against the minimum tradable notional
+#[derive(Debug, Clone, PartialEq)]
+pub enum MinimumCheck {
+    /// Send an order worth this much, including any accumulated intent
+    Tradable(Decimal),
+    /// Too small to trade and dropped
+    Skipped,
+    /// Too small so far; this much is held back for the symbol
+    Deferred { accumulated: Decimal },
+}
+
+/// Enforces the minimum tradable notional, remembering deferred intent per symbol
+#[derive(Debug, Clone)]
+pub struct OrderMinimum {
+    min_notional: Decimal,
+    policy: SubMinimumPolicy,
+    pending: HashMap<Symbol, (OrderSide, Decimal)>,
+}
+
+impl OrderMinimum {
+    pub fn from_config(config: &OrderMinimumConfig) -> Self {
+        Self {
+            min_notional: config.min_notional,
+            policy: config.policy,
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn min_notional(&self) -> Decimal {
+        self.min_notional
+    }
+
+    /// Check an order worth `notional`. Under `Accumulate`, an order on the
+    /// other side of the symbol discards the intent held back so far.
+    pub fn check(&mut self, symbol: &Symbol, side: &OrderSide, notional: Decimal) -> MinimumCheck {
+        match self.policy {
+            SubMinimumPolicy::Skip if notional < self.min_notional => MinimumCheck::Skipped,
+            SubMinimumPolicy::Skip => MinimumCheck::Tradable(notional),
+            SubMinimumPolicy::Accumulate => {
+                let held = match self.pending.remove(symbol) {
+                    Some((held_side, held)) if std::mem::discriminant(&held_side) == std::mem::discriminant(side) => held,
+                    _ => Decimal::ZERO,
+                };
+                let total = held + notional;
+                if total >= self.min_notional {
+                    MinimumCheck::Tradable(total)
+                } else {
+                    self.pending.insert(symbol.clone(), (side.clone(), total));
+                    MinimumCheck::Deferred { accumulated: total }
+                }
+            }
+        }
+    }
+
+    /// Hold `amount` of intent for the symbol again, after an order that
+    /// carried it failed before reaching the market
+    pub fn restore(&mut self, symbol: &Symbol, side: &OrderSide, amount: Decimal) {
+        if self.policy != SubMinimumPolicy::Accumulate || amount <= Decimal::ZERO {
+            return;
+        }
+        let held = match self.pending.get(symbol) {
+            Some((held_side, held)) if std::mem::discriminant(held_side) == std::mem::discriminant(side) => *held,
+            _ => Decimal::ZERO,
+        };
+        self.pending.insert(symbol.clone(), (side.clone(), held + amount));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_higher_confidence_sizes_larger_within_the_cap() {
+        let sizer = PositionSizer::from_config(&PositionSizingConfig::default());
+
+        // Same strength: 0.9 confidence scales by 0.5 + 0.5 * 0.9 / 0.7, 0.5 by 0.5 + 0.5 * 0.5 / 0.7
+        let confident = sizer.quantity(0.8, 0.9);
+        let unsure = sizer.quantity(0.8, 0.5);
+        assert!(confident > unsure);
+        assert!((sizer.confidence_multiplier(0.9) - 8.0 / 7.0).abs() < 1e-9);
+        assert!((sizer.confidence_multiplier(0.5) - 6.0 / 7.0).abs() < 1e-9);
+        assert_eq!(sizer.quantity(0.8, 0.7), Decimal::from(8));
+
+        // Full weighting is capped at the multiplier limit
+        let eager = PositionSizer::from_config(&PositionSizingConfig {
+            confidence_weight: 1.0,
+            reference_confidence: 0.5,
+            ..PositionSizingConfig::default()
+        });
+        assert_eq!(eager.confidence_multiplier(1.0), 1.5);
+        assert_eq!(eager.quantity(0.8, 1.0), Decimal::from(12));
+
+        // No weight leaves confidence out of it, as before
+        let flat = PositionSizer::from_config(&PositionSizingConfig {
+            confidence_weight: 0.0,
+            ..PositionSizingConfig::default()
+        });
+        assert_eq!(flat.quantity(0.8, 0.9), flat.quantity(0.8, 0.1));
+    }
+
+    #[test]
+    fn test_failed_order_holds_carried_intent_again() {
+        let mut minimum = OrderMinimum::from_config(&OrderMinimumConfig {
+            min_notional: Decimal::from(5),
+            policy: SubMinimumPolicy::Accumulate,
+        });
+        let symbol = "AAPL".to_string();
+        assert_eq!(minimum.check(&symbol, &OrderSide::Buy, Decimal::from(3)), MinimumCheck::Deferred { accumulated: Decimal::from(3) });
+        assert_eq!(minimum.check(&symbol, &OrderSide::Buy, Decimal::from(3)), MinimumCheck::Tradable(Decimal::from(6)));
+
+        // The $6 order failed downstream: the $3 it carried is held again
+        minimum.restore(&symbol, &OrderSide::Buy, Decimal::from(3));
+        assert_eq!(minimum.check(&symbol, &OrderSide::Buy, Decimal::from(1)), MinimumCheck::Deferred { accumulated: Decimal::from(4) });
+    }
+}
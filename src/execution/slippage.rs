@@ -0,0 +1,219 @@
+//! Realized-versus-modeled slippage tracking
+//!
+//! Compares each fill's slippage with the cost model's assumption and raises
+//! an alert when the rolling average discrepancy suggests the model needs
+//! recalibrating. Fills can also be attributed to spread, market impact and
+//! timing relative to the quote the decision was made on.
+
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::core::config::{SlippageAttributionConfig, SlippageMonitorConfig};
+use crate::core::types::{ExecutionResult, MarketData, OrderSide};
+
+/// Raised when fills are persistently worse than the cost model predicts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlippageAlert {
+    pub average_discrepancy_bps: f64,
+    pub threshold_bps: f64,
+    pub sample_size: usize,
+}
+
+/// Rolling window of realized-minus-modeled slippage, in basis points
+#[derive(Debug, Clone)]
+pub struct SlippageMonitor {
+    modeled_bps: f64,
+    threshold_bps: f64,
+    window: usize,
+    discrepancies: VecDeque<f64>,
+    alerting: bool,
+}
+
+impl SlippageMonitor {
+    /// Create a monitor comparing fills against `modeled_slippage` (a fraction of price)
+    pub fn new(modeled_slippage: f64, config: &SlippageMonitorConfig) -> Self {
+        Self {
+            modeled_bps: modeled_slippage * 10_000.0,
+            threshold_bps: config.alert_threshold_bps,
+            window: config.window.max(1),
+            discrepancies: VecDeque::with_capacity(config.window),
+            alerting: false,
+        }
+    }
+
+    /// Average discrepancy over the window, if any fills have been recorded
+    pub fn average_discrepancy_bps(&self) -> Option<f64> {
+        if self.discrepancies.is_empty() {
+            return None;
+        }
+        Some(self.discrepancies.iter().sum::<f64>() / self.discrepancies.len() as f64)
+    }
+
+    /// Record a fill. Returns an alert when the rolling average first crosses
+    /// the threshold; the alert re-arms once the average recovers.
+    pub fn record(&mut self, fill: &ExecutionResult) -> Option<SlippageAlert> {
+        if !fill.success || fill.executed_price.is_zero() {
+            return None;
+        }
+
+        let realized_bps = (fill.slippage / fill.executed_price).to_f64()? * 10_000.0;
+        self.discrepancies.push_back(realized_bps - self.modeled_bps);
+        while self.discrepancies.len() > self.window {
+            self.discrepancies.pop_front();
+        }
+
+        // Only judge a full window so one bad fill can't trip the alert
+        if self.discrepancies.len() < self.window {
+            return None;
+        }
+
+        let average = self.average_discrepancy_bps()?;
+        if average <= self.threshold_bps {
+            self.alerting = false;
+            return None;
+        }
+        if self.alerting {
+            return None;
+        }
+
+        self.alerting = true;
+        Some(SlippageAlert {
+            average_discrepancy_bps: average,
+            threshold_bps: self.threshold_bps,
+            sample_size: self.discrepancies.len(),
+        })
+    }
+}
+
+/// Per-unit slippage of a fill split into its causes. Costs are positive,
+/// and the components always sum to `total`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlippageAttribution {
+    pub decision_price: Decimal, // Quote mid when the signal was acted on
+    pub spread: Decimal,         // Half the quoted spread, paid to cross it
+    pub impact: Decimal,         // Modeled market impact of the order's size
+    pub timing: Decimal,         // Remaining drift between decision and fill
+    pub total: Decimal,          // Fill price versus decision price
+}
+
+/// Decomposes fills against the quote at decision time
+#[derive(Debug, Clone)]
+pub struct SlippageAttributor {
+    impact_bps_per_unit: Decimal,
+}
+
+impl SlippageAttributor {
+    pub fn from_config(config: &SlippageAttributionConfig) -> Self {
+        Self {
+            impact_bps_per_unit: Decimal::from_f64(config.impact_bps_per_unit).unwrap_or(Decimal::ZERO),
+        }
+    }
+
+    /// Attribute `quantity` filled at `price` against the `decision` quote
+    pub fn attribute(&self, side: &OrderSide, decision: &MarketData, price: Decimal, quantity: Decimal) -> SlippageAttribution {
+        let (decision_price, spread) = match (decision.bid, decision.ask) {
+            (Some(bid), Some(ask)) if ask >= bid => ((bid + ask) / Decimal::TWO, (ask - bid) / Decimal::TWO),
+            _ => (decision.price, Decimal::ZERO),
+        };
+        let impact = decision_price * self.impact_bps_per_unit * quantity.abs() / Decimal::from(10_000);
+        let total = match side {
+            OrderSide::Buy => price - decision_price,
+            OrderSide::Sell => decision_price - price,
+        };
+
+        SlippageAttribution {
+            decision_price,
+            spread,
+            impact,
+            timing: total - spread - impact,
+            total,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Fill, Liquidity};
+
+    fn fill(slippage_cents: i64) -> ExecutionResult {
+        ExecutionResult {
+            order_id: uuid::Uuid::new_v4(),
+            executed_quantity: Decimal::from(1),
+            executed_price: Decimal::from(100),
+            execution_time_ms: 1,
+            slippage: Decimal::new(slippage_cents, 2),
+            commission: Decimal::ZERO,
+            success: true,
+            error_message: None,
+            fills: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_worse_than_modeled_fills_trigger_alert() {
+        // Model assumes 5bps; alert when fills average 10bps worse
+        let mut monitor = SlippageMonitor::new(0.0005, &SlippageMonitorConfig {
+            enabled: true,
+            window: 3,
+            alert_threshold_bps: 10.0,
+        });
+
+        // 5bps fills match the model
+        for _ in 0..3 {
+            assert!(monitor.record(&fill(5)).is_none());
+        }
+
+        // 20bps fills are 15bps worse than modeled
+        assert!(monitor.record(&fill(20)).is_none());
+        assert!(monitor.record(&fill(20)).is_none());
+        let alert = monitor.record(&fill(20)).expect("discrepancy alert");
+        assert!((alert.average_discrepancy_bps - 15.0).abs() < 1e-9);
+        assert_eq!(alert.sample_size, 3);
+
+        // No repeat alert while the discrepancy persists
+        assert!(monitor.record(&fill(20)).is_none());
+    }
+
+    #[test]
+    fn test_attributed_components_sum_to_realized_slippage() {
+        let attributor = SlippageAttributor::from_config(&SlippageAttributionConfig {
+            enabled: true,
+            impact_bps_per_unit: 0.5,
+        });
+        let decision = MarketData {
+            symbol: "AAPL".to_string(),
+            timestamp: chrono::Utc::now(),
+            price: Decimal::new(10002, 2),
+            volume: 1_000,
+            bid: Some(Decimal::new(9998, 2)),
+            ask: Some(Decimal::new(10002, 2)),
+            bid_size: None,
+            ask_size: None,
+            source: Default::default(),
+        };
+        let fill = Fill {
+            order_id: uuid::Uuid::new_v4(),
+            quantity: Decimal::from(20),
+            price: Decimal::new(10015, 2),
+            timestamp: chrono::Utc::now(),
+            liquidity: Liquidity::Taker,
+        };
+
+        let buy = attributor.attribute(&OrderSide::Buy, &decision, fill.price, fill.quantity);
+        assert_eq!(buy.decision_price, Decimal::from(100));
+        assert_eq!(buy.total, Decimal::new(15, 2));
+        assert_eq!(buy.spread, Decimal::new(2, 2));
+        assert_eq!(buy.impact, Decimal::new(10, 2)); // 0.5bps x 20 units = 10bps of $100
+        let sum = buy.spread + buy.impact + buy.timing;
+        assert!((sum - buy.total).abs() < Decimal::new(1, 8));
+        assert_eq!(buy.timing, Decimal::new(3, 2));
+
+        // Selling at the same price is favorable: negative total, timing absorbs the gain
+        let sell = attributor.attribute(&OrderSide::Sell, &decision, fill.price, fill.quantity);
+        assert_eq!(sell.total, Decimal::new(-15, 2));
+        assert!((sell.spread + sell.impact + sell.timing - sell.total).abs() < Decimal::new(1, 8));
+    }
+}
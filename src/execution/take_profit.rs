@@ -0,0 +1,264 @@
+//! Scaling out of positions in take-profit tranches
+//!
+//! A position is closed piecewise as price reaches successive profit levels,
+//! with an optional trailing stop managing whatever remains afterwards. A
+//! closing order only reduces the position once its fill is confirmed.
+
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::core::config::TakeProfitConfig;
+use crate::core::types::{CloseReason, Order, OrderId, OrderSide, OrderStatus, OrderType, Position, Symbol};
+
+/// Scale-outs of every managed position, shared so risk management can see
+/// their stops
+pub type ProtectiveLevels = Arc<RwLock<HashMap<Symbol, ScaleOut>>>;
+
+/// A closing order sent but not yet confirmed
+#[derive(Debug, Clone)]
+struct InFlight {
+    quantity: Decimal,
+    level: Option<usize>, // Take-profit level it was sent for, re-armed if nothing fills
+}
+
+/// Tracks the remaining quantity of a position being scaled out of
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScaleOut {
+    symbol: Symbol,
+    long: bool,
+    entry_price: Decimal,
+    initial_quantity: Decimal,
+    remaining: Decimal,
+    levels: Vec<(Decimal, Decimal)>, // (gain fraction, quantity fraction), ascending by gain
+    next_level: usize,
+    trail_pct: Option<Decimal>,
+    best_price: Decimal,
+    #[serde(default)]
+    stop_price: Option<Decimal>,
+    #[serde(skip)]
+    in_flight: HashMap<OrderId, InFlight>,
+}
+
+impl ScaleOut {
+    /// Start managing `position` with the given take-profit plan
+    pub fn new(position: &Position, config: &TakeProfitConfig) -> Self {
+        let mut levels: Vec<(Decimal, Decimal)> = config
+            .levels
+            .iter()
+            .filter_map(|level| Some((Decimal::from_f64(level.gain_pct)?, Decimal::from_f64(level.fraction)?)))
+            .collect();
+        levels.sort_by_key(|level| level.0);
+
+        let long = position.quantity > Decimal::ZERO;
+        let stop_price = config.stop_loss_pct.and_then(Decimal::from_f64).map(|pct| {
+            if long {
+                position.entry_price * (Decimal::ONE - pct)
+            } else {
+                position.entry_price * (Decimal::ONE + pct)
+            }
+        });
+
+        Self {
+            symbol: position.symbol.clone(),
+            long,
+            entry_price: position.entry_price,
+            initial_quantity: position.quantity.abs(),
+            remaining: position.quantity.abs(),
+            levels,
+            next_level: 0,
+            trail_pct: config.trail_pct.and_then(Decimal::from_f64),
+            best_price: position.entry_price,
+            stop_price,
+            in_flight: HashMap::new(),
+        }
+    }
+
+    /// Protective stop price for the remaining quantity, if one is set
+    pub fn stop_price(&self) -> Option<Decimal> {
+        self.stop_price
+    }
+
+    /// Quantity not yet scaled out
+    pub fn remaining(&self) -> Decimal {
+        self.remaining
+    }
+
+    /// Remaining quantity not already being closed by an unconfirmed order
+    fn available(&self) -> Decimal {
+        self.remaining - self.in_flight.values().map(|flight| flight.quantity).sum::<Decimal>()
+    }
+
+    /// Whether `order_id` is one of this plan's unconfirmed closing orders
+    pub fn awaits(&self, order_id: &OrderId) -> bool {
+        self.in_flight.contains_key(order_id)
+    }
+
+    /// Settle a closing order once its outcome is known, reducing the
+    /// position by what actually filled. A take-profit tranche that filled
+    /// nothing re-arms its level.
+    pub fn confirm(&mut self, order_id: &OrderId, filled: Decimal) {
+        let Some(flight) = self.in_flight.remove(order_id) else {
+            return;
+        };
+        self.remaining -= filled.min(flight.quantity).min(self.remaining);
+        if filled.is_zero() {
+            if let Some(level) = flight.level {
+                self.next_level = self.next_level.min(level);
+            }
+        }
+    }
+
+    /// Whether the position has been fully closed
+    pub fn is_closed(&self) -> bool {
+        self.remaining.is_zero()
+    }
+
+    /// Feed a new price, returning the closing orders it triggers and why
+    pub fn on_price(&mut self, price: Decimal) -> Vec<(Order, CloseReason)> {
+        let mut orders = Vec::new();
+        if self.is_closed() || self.entry_price.is_zero() {
+            return orders;
+        }
+
+        // The stop protects whatever is left, regardless of how many levels have filled
+        if let Some(stop) = self.stop_price {
+            let stopped = if self.long { price <= stop } else { price >= stop };
+            if stopped {
+                orders.extend(self.close(self.available(), None).map(|order| (order, CloseReason::StopLoss)));
+                return orders;
+            }
+        }
+
+        let gain = if self.long {
+            (price - self.entry_price) / self.entry_price
+        } else {
+            (self.entry_price - price) / self.entry_price
+        };
+
+        while let Some(&(level_gain, fraction)) = self.levels.get(self.next_level) {
+            if gain < level_gain {
+                break;
+            }
+            let level = self.next_level;
+            self.next_level += 1;
+
+            // Round to the position's own precision so thirds of whole shares stay whole
+            let tranche = (self.initial_quantity * fraction)
+                .round_dp(self.initial_quantity.scale())
+                .min(self.available());
+            if let Some(order) = self.close(tranche, Some(level)) {
+                orders.push((order, CloseReason::TakeProfit));
+            }
+        }
+
+        // Once every level has filled, trail the remainder from its best price
+        if self.next_level == self.levels.len() && !self.is_closed() {
+            if let Some(trail) = self.trail_pct {
+                let improved = if self.long { price > self.best_price } else { price < self.best_price };
+                if improved {
+                    self.best_price = price;
+                }
+
+                let retrace = if self.long {
+                    (self.best_price - price) / self.best_price
+                } else {
+                    (price - self.best_price) / self.best_price
+                };
+                if retrace >= trail {
+                    if let Some(order) = self.close(self.available(), None) {
+                        orders.push((order, CloseReason::TrailingStop));
+                    }
+                }
+            }
+        }
+
+        orders
+    }
+
+    fn close(&mut self, quantity: Decimal, level: Option<usize>) -> Option<Order> {
+        if quantity <= Decimal::ZERO {
+            return None;
+        }
+
+        let id = uuid::Uuid::new_v4();
+        self.in_flight.insert(id, InFlight { quantity, level });
+        Some(Order {
+            id,
+            symbol: self.symbol.clone(),
+            order_type: OrderType::Market,
+            side: if self.long { OrderSide::Sell } else { OrderSide::Buy },
+            quantity,
+            price: None,
+            timestamp: chrono::Utc::now(),
+            status: OrderStatus::Pending,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::TakeProfitLevel;
+
+    fn position() -> Position {
+        Position {
+            id: uuid::Uuid::new_v4(),
+            symbol: "AAPL".to_string(),
+            quantity: Decimal::from(3),
+            entry_price: Decimal::from(100),
+            current_price: Decimal::from(100),
+            unrealized_pnl: Decimal::ZERO,
+            realized_pnl: Decimal::ZERO,
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_levels_scale_out_then_trail_closes_remainder() {
+        let config = TakeProfitConfig {
+            levels: vec![
+                TakeProfitLevel { gain_pct: 0.02, fraction: 1.0 / 3.0 },
+                TakeProfitLevel { gain_pct: 0.04, fraction: 1.0 / 3.0 },
+            ],
+            trail_pct: Some(0.01),
+            stop_loss_pct: None,
+        };
+        let mut scale_out = ScaleOut::new(&position(), &config);
+
+        assert!(scale_out.on_price(Decimal::from(101)).is_empty());
+
+        let first = scale_out.on_price(Decimal::from(102));
+        assert_eq!(first.len(), 1);
+        assert!(matches!(first[0].0.side, OrderSide::Sell));
+        assert_eq!(first[0].0.quantity, Decimal::from(1));
+        assert_eq!(first[0].1, CloseReason::TakeProfit);
+        // Nothing is deducted until the fill is confirmed
+        assert_eq!(scale_out.remaining(), Decimal::from(3));
+        scale_out.confirm(&first[0].0.id, Decimal::ZERO);
+        assert_eq!(scale_out.remaining(), Decimal::from(3));
+
+        // The unfilled level re-arms and fires again
+        let first = scale_out.on_price(Decimal::from(102));
+        scale_out.confirm(&first[0].0.id, Decimal::from(1));
+        assert_eq!(scale_out.remaining(), Decimal::from(2));
+
+        let second = scale_out.on_price(Decimal::from(104));
+        assert_eq!(second[0].0.quantity, Decimal::from(1));
+        scale_out.confirm(&second[0].0.id, Decimal::from(1));
+        assert_eq!(scale_out.remaining(), Decimal::from(1));
+
+        // The remainder rides the move and is closed on a 1% pullback, once
+        assert!(scale_out.on_price(Decimal::from(110)).is_empty());
+        let last = scale_out.on_price(Decimal::new(1089, 1));
+        assert_eq!(last[0].0.quantity, Decimal::from(1));
+        assert_eq!(last[0].1, CloseReason::TrailingStop);
+        assert!(scale_out.on_price(Decimal::new(1085, 1)).is_empty());
+        scale_out.confirm(&last[0].0.id, Decimal::from(1));
+        assert!(scale_out.is_closed());
+        assert!(scale_out.on_price(Decimal::from(90)).is_empty());
+    }
+}
@@ -0,0 +1,101 @@
+//! Per-symbol tick size and price rounding
+
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+use crate::core::config::TickSizeConfig;
+use crate::core::errors::{TradingError, TradingResult};
+use crate::core::types::{OrderSide, OrderType, Symbol};
+
+/// Tick size lookup used to snap order prices onto the venue's price grid
+#[derive(Debug, Clone)]
+pub struct TickSizeTable {
+    default_tick: Decimal,
+    symbols: HashMap<Symbol, Decimal>,
+}
+
+impl TickSizeTable {
+    /// Build the table from configuration
+    pub fn from_config(config: &TickSizeConfig) -> Self {
+        Self {
+            default_tick: config.default_tick,
+            symbols: config.symbols.clone(),
+        }
+    }
+
+    /// Get the tick size for a symbol
+    pub fn tick_size(&self, symbol: &str) -> Decimal {
+        self.symbols.get(symbol).copied().unwrap_or(self.default_tick)
+    }
+
+    /// Round a price onto the tick grid.
+    ///
+    /// Limit prices round away from the market (buys down, sells up) so the
+    /// rounded order is never more aggressive than requested. Stop triggers
+    /// round the other way so they never fire earlier than requested.
+    pub fn round_price(
+        &self,
+        symbol: &str,
+        side: &OrderSide,
+        order_type: &OrderType,
+        price: Decimal,
+    ) -> TradingResult<Decimal> {
+        let tick = self.tick_size(symbol);
+        if tick <= Decimal::ZERO {
+            return Ok(price);
+        }
+
+        if price < tick {
+            return Err(TradingError::execution(format!(
+                "Price {} for {} is below the minimum tick {}",
+                price, symbol, tick
+            )));
+        }
+
+        let round_down = match (order_type, side) {
+            (OrderType::Stop, OrderSide::Buy) => false,
+            (OrderType::Stop, OrderSide::Sell) => true,
+            (_, OrderSide::Buy) => true,
+            (_, OrderSide::Sell) => false,
+        };
+
+        let ticks = price / tick;
+        let rounded = if round_down { ticks.floor() } else { ticks.ceil() };
+        Ok(rounded * tick)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> TickSizeTable {
+        let mut symbols = HashMap::new();
+        symbols.insert("ES".to_string(), Decimal::new(25, 2)); // 0.25
+        TickSizeTable::from_config(&TickSizeConfig {
+            default_tick: Decimal::new(1, 2), // 0.01
+            symbols,
+        })
+    }
+
+    #[test]
+    fn test_off_grid_price_rounds_by_side() {
+        let table = table();
+        let price = Decimal::new(150_127, 3); // 150.127
+
+        let buy = table.round_price("AAPL", &OrderSide::Buy, &OrderType::Limit, price).unwrap();
+        let sell = table.round_price("AAPL", &OrderSide::Sell, &OrderType::Limit, price).unwrap();
+        assert_eq!(buy, Decimal::new(15012, 2));
+        assert_eq!(sell, Decimal::new(15013, 2));
+
+        let es = table.round_price("ES", &OrderSide::Buy, &OrderType::Limit, Decimal::new(500_010, 2)).unwrap();
+        assert_eq!(es, Decimal::from(5000));
+    }
+
+    #[test]
+    fn test_sub_tick_price_rejected() {
+        let table = table();
+        let result = table.round_price("ES", &OrderSide::Buy, &OrderType::Limit, Decimal::new(10, 2));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,351 @@
+//! Time-weighted average price execution
+//!
+//! A TWAP order is split into equal child orders submitted at a fixed
+//! interval across the plan's time horizon. Fills from every child are
+//! accumulated into one result for the parent, priced at their
+//! volume-weighted average. Slices not yet sent are dropped when a
+//! [`SliceAbort`] is triggered for the order's symbol or for every order.
+
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use crate::agents::traits::ExecutionPlan;
+use crate::core::errors::TradingResult;
+use crate::core::types::{ExecutionResult, Fill, Order, Symbol};
+
+/// Plan algorithm name that routes an order through [`execute_twap`]
+pub const TWAP_ALGORITHM: &str = "TWAP";
+
+/// Abort counts, for every order and per symbol
+#[derive(Debug, Default)]
+struct Generations {
+    all: u64,
+    symbols: HashMap<Symbol, u64>,
+}
+
+impl Generations {
+    fn of(&self, symbol: &str) -> (u64, u64) {
+        (self.all, self.symbols.get(symbol).copied().unwrap_or(0))
+    }
+}
+
+/// Aborts the remaining slices of TWAP orders running when triggered, either
+/// all of them or those on one symbol. Orders started afterwards are unaffected.
+#[derive(Debug, Clone)]
+pub struct SliceAbort {
+    generations: Arc<watch::Sender<Generations>>,
+}
+
+impl SliceAbort {
+    pub fn new() -> Self {
+        let (generations, _) = watch::channel(Generations::default());
+        Self { generations: Arc::new(generations) }
+    }
+
+    /// Stop sending further slices of every in-flight TWAP order
+    pub fn abort_all(&self) {
+        self.generations.send_modify(|generations| generations.all += 1);
+    }
+
+    /// Stop sending further slices of in-flight TWAP orders on `symbol`
+    pub fn abort_symbol(&self, symbol: &str) {
+        self.generations.send_modify(|generations| *generations.symbols.entry(symbol.to_string()).or_default() += 1);
+    }
+
+    fn watch(&self, symbol: &str) -> AbortWatch {
+        let receiver = self.generations.subscribe();
+        let started = receiver.borrow().of(symbol);
+        AbortWatch { receiver, symbol: symbol.to_string(), started }
+    }
+}
+
+/// Aborts reaching one order since it started
+struct AbortWatch {
+    receiver: watch::Receiver<Generations>,
+    symbol: Symbol,
+    started: (u64, u64),
+}
+
+impl AbortWatch {
+    fn aborted(&self) -> bool {
+        self.receiver.borrow().of(&self.symbol) != self.started
+    }
+
+    /// Wait out `delay`, returning false as soon as the order is aborted.
+    /// Aborts for other symbols don't cut the wait short.
+    async fn sleep(&mut self, delay: Duration) -> bool {
+        let sleep = tokio::time::sleep(delay);
+        tokio::pin!(sleep);
+        loop {
+            tokio::select! {
+                _ = &mut sleep => return !self.aborted(),
+                changed = self.receiver.changed() => {
+                    if changed.is_err() {
+                        (&mut sleep).await;
+                        return true;
+                    }
+                    if self.aborted() {
+                        return false;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Default for SliceAbort {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Child order quantities: `slice_size` is the fraction of the parent per
+/// slice, with the last slice taking any rounding remainder
+fn slice_quantities(quantity: Decimal, slice_size: f64) -> Vec<Decimal> {
+    let fraction = Decimal::from_f64(slice_size)
+        .filter(|fraction| *fraction > Decimal::ZERO && *fraction < Decimal::ONE)
+        .unwrap_or(Decimal::ONE);
+    let slice = (quantity * fraction).round_dp(quantity.scale());
+    if slice <= Decimal::ZERO {
+        return vec![quantity];
+    }
+
+    let mut slices = Vec::new();
+    let mut remaining = quantity;
+    while remaining > Decimal::ZERO {
+        let next = slice.min(remaining);
+        slices.push(next);
+        remaining -= next;
+    }
+    slices
+}
+
+/// Execute `order` as evenly spaced child orders spanning `plan.time_horizon`,
+/// sending each through `submit`. An abort, or a slice failing after earlier
+/// ones filled, stops further slices and returns what has filled so far.
+pub async fn execute_twap<F, Fut>(
+    order: &Order,
+    plan: &ExecutionPlan,
+    abort: &SliceAbort,
+    submit: F,
+) -> TradingResult<ExecutionResult>
+where
+    F: Fn(Order) -> Fut,
+    Fut: Future<Output = TradingResult<ExecutionResult>>,
+{
+    let started = Instant::now();
+    let mut abort = abort.watch(&order.symbol);
+    let slices = slice_quantities(order.quantity, plan.slice_size);
+    let interval = plan.time_horizon / slices.len() as u32;
+
+    let mut fills = Vec::new();
+    let mut slippage_notional = Decimal::ZERO;
+    let mut commission = Decimal::ZERO;
+    let mut sent = 0;
+    let mut failure = None;
+    for (i, quantity) in slices.iter().enumerate() {
+        if (i > 0 && !abort.sleep(interval).await) || abort.aborted() {
+            break;
+        }
+
+        let child = Order {
+            id: uuid::Uuid::new_v4(),
+            quantity: *quantity,
+            timestamp: chrono::Utc::now(),
+            ..order.clone()
+        };
+        let result = match submit(child).await {
+            Ok(result) => result,
+            Err(e) if fills.is_empty() => return Err(e),
+            Err(e) => {
+                failure = Some(e);
+                break;
+            }
+        };
+        sent += 1;
+        slippage_notional += result.slippage * result.executed_quantity;
+        commission += result.commission;
+        fills.extend(result.fills.into_iter().map(|fill| Fill { order_id: order.id, ..fill }));
+    }
+
+    // Report slippage as the quantity-weighted average across slices
+    let executed: Decimal = fills.iter().map(|fill| fill.quantity).sum();
+    let slippage = if executed.is_zero() { Decimal::ZERO } else { slippage_notional / executed };
+    let mut result = ExecutionResult::from_fills(
+        order.id,
+        fills,
+        slippage,
+        commission,
+        started.elapsed().as_millis() as u64,
+    )?;
+    if let Some(e) = failure {
+        warn!("🛑 TWAP for {} stopped after {}/{} slices: {}", order.symbol, sent, slices.len(), e);
+        result.error_message = Some(format!("TWAP stopped after {}/{} slices: {}", sent, slices.len(), e));
+    } else if sent < slices.len() {
+        warn!("🛑 TWAP for {} aborted after {}/{} slices", order.symbol, sent, slices.len());
+        result.error_message = Some(format!("TWAP aborted after {}/{} slices", sent, slices.len()));
+    } else {
+        info!("⏱️ TWAP for {} completed in {} slices at {}", order.symbol, sent, result.executed_price);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::errors::TradingError;
+    use crate::core::types::{Liquidity, OrderSide, OrderStatus, OrderType};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    fn plan(time_horizon: Duration) -> ExecutionPlan {
+        ExecutionPlan {
+            algorithm: TWAP_ALGORITHM.to_string(),
+            time_horizon,
+            slice_size: 0.25,
+            price_improvement_target: 0.0,
+            contingency_plans: Vec::new(),
+            rationale: String::new(),
+        }
+    }
+
+    fn order() -> Order {
+        Order {
+            id: uuid::Uuid::new_v4(),
+            symbol: "AAPL".to_string(),
+            order_type: OrderType::Market,
+            side: OrderSide::Buy,
+            quantity: Decimal::from(400),
+            price: None,
+            timestamp: chrono::Utc::now(),
+            status: OrderStatus::Pending,
+        }
+    }
+
+    /// Fill each child in full at 100, 101, 102, ... recording when it was sent
+    fn venue(sent: Arc<Mutex<Vec<(Instant, Decimal)>>>) -> impl Fn(Order) -> std::future::Ready<TradingResult<ExecutionResult>> {
+        move |child: Order| {
+            let mut sent = sent.lock().unwrap();
+            let price = Decimal::from(100 + sent.len() as i64);
+            sent.push((Instant::now(), child.quantity));
+            let fill = Fill {
+                order_id: child.id,
+                quantity: child.quantity,
+                price,
+                timestamp: chrono::Utc::now(),
+                liquidity: Liquidity::Taker,
+            };
+            std::future::ready(ExecutionResult::from_fills(child.id, vec![fill], Decimal::ZERO, Decimal::ONE, 0))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_twap_slices_over_horizon_at_vwap() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let order = order();
+        let started = Instant::now();
+        let result = execute_twap(&order, &plan(Duration::from_millis(200)), &SliceAbort::new(), venue(sent.clone()))
+            .await
+            .unwrap();
+
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 4);
+        assert!(sent.iter().all(|(_, quantity)| *quantity == Decimal::from(100)));
+        // Slices are spread across the horizon rather than sent at once
+        assert!(sent[3].0 - sent[0].0 >= Duration::from_millis(150));
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        assert!(result.success);
+        assert_eq!(result.order_id, order.id);
+        assert_eq!(result.executed_quantity, Decimal::from(400));
+        assert_eq!(result.executed_price, Decimal::new(1015, 1));
+        assert_eq!(result.commission, Decimal::from(4));
+        assert!(result.fills.iter().all(|fill| fill.order_id == order.id));
+        assert!(result.error_message.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_abort_stops_remaining_slices() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let abort = SliceAbort::new();
+        let order = order();
+        let slow = plan(Duration::from_secs(4));
+        let twap = execute_twap(&order, &slow, &abort, venue(sent.clone()));
+
+        let trigger = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            abort.abort_all();
+        };
+        let (result, _) = tokio::join!(twap, trigger);
+        let result = result.unwrap();
+
+        assert_eq!(sent.lock().unwrap().len(), 1);
+        assert_eq!(result.executed_quantity, Decimal::from(100));
+        assert!(result.error_message.unwrap().contains("1/4"));
+
+        // A TWAP started after the abort runs normally
+        let quick = execute_twap(&order, &plan(Duration::from_millis(4)), &abort, venue(sent.clone()))
+            .await
+            .unwrap();
+        assert_eq!(quick.executed_quantity, Decimal::from(400));
+    }
+
+    #[tokio::test]
+    async fn test_symbol_abort_spares_other_symbols() {
+        let (aapl_sent, msft_sent) = (Arc::new(Mutex::new(Vec::new())), Arc::new(Mutex::new(Vec::new())));
+        let abort = SliceAbort::new();
+        let (aapl, msft) = (order(), Order { symbol: "MSFT".to_string(), ..order() });
+        let horizon = plan(Duration::from_millis(400));
+
+        let trigger = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            abort.abort_symbol("AAPL");
+        };
+        let (aapl_result, msft_result, _) = tokio::join!(
+            execute_twap(&aapl, &horizon, &abort, venue(aapl_sent.clone())),
+            execute_twap(&msft, &horizon, &abort, venue(msft_sent.clone())),
+            trigger,
+        );
+
+        assert_eq!(aapl_result.unwrap().executed_quantity, Decimal::from(100));
+        let msft_result = msft_result.unwrap();
+        assert_eq!(msft_result.executed_quantity, Decimal::from(400));
+        assert!(msft_result.error_message.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_slice_keeps_earlier_fills() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let fill = venue(sent.clone());
+        let calls = Arc::new(Mutex::new(0));
+        let flaky = |child: Order| {
+            let mut calls = calls.lock().unwrap();
+            *calls += 1;
+            match *calls {
+                2 => std::future::ready(Err(TradingError::execution("venue rejected slice"))),
+                _ => fill(child),
+            }
+        };
+        let order = Order { quantity: Decimal::from(300), ..order() };
+        let three_slices = ExecutionPlan { slice_size: 1.0 / 3.0, ..plan(Duration::from_millis(20)) };
+
+        let result = execute_twap(&order, &three_slices, &SliceAbort::new(), flaky).await.unwrap();
+
+        // The first slice traded and is reported; the third was never sent
+        assert_eq!(*calls.lock().unwrap(), 2);
+        assert!(result.success);
+        assert_eq!(result.executed_quantity, Decimal::from(100));
+        let message = result.error_message.unwrap();
+        assert!(message.contains("1/3") && message.contains("venue rejected slice"), "{}", message);
+
+        // Nothing is reported filled when the very first slice fails
+        let failing = |_: Order| std::future::ready(Err(TradingError::execution("venue down")));
+        assert!(execute_twap(&order, &three_slices, &SliceAbort::new(), failing).await.is_err());
+    }
+}
@@ -0,0 +1,83 @@
+//! Per-venue execution quality
+//!
+//! Running fill statistics for each venue orders are sent to, used by the
+//! order router to prefer the venue with the best recent execution.
+
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::core::types::ExecutionResult;
+
+/// Accumulated fill quality for one venue
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VenueStats {
+    pub submitted: u64,
+    pub filled: u64,
+    total_slippage_bps: f64,
+    total_latency_ms: u64,
+}
+
+impl VenueStats {
+    /// Average slippage of filled orders, in basis points of the fill price
+    pub fn avg_slippage_bps(&self) -> f64 {
+        if self.filled == 0 {
+            0.0
+        } else {
+            self.total_slippage_bps / self.filled as f64
+        }
+    }
+
+    /// Average time from submission to fill, in milliseconds
+    pub fn avg_latency_ms(&self) -> f64 {
+        if self.filled == 0 {
+            0.0
+        } else {
+            self.total_latency_ms as f64 / self.filled as f64
+        }
+    }
+
+    /// Fraction of submitted orders that filled
+    pub fn fill_rate(&self) -> f64 {
+        if self.submitted == 0 {
+            0.0
+        } else {
+            self.filled as f64 / self.submitted as f64
+        }
+    }
+}
+
+/// Execution statistics keyed by venue name
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VenueStatistics {
+    venues: HashMap<String, VenueStats>,
+}
+
+impl VenueStatistics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the outcome of an order sent to `venue`; unsuccessful results count as unfilled
+    pub fn record(&mut self, venue: &str, result: &ExecutionResult) {
+        let stats = self.venues.entry(venue.to_string()).or_default();
+        stats.submitted += 1;
+        if !result.success || result.executed_price.is_zero() {
+            return;
+        }
+
+        stats.filled += 1;
+        stats.total_slippage_bps += (result.slippage / result.executed_price).to_f64().unwrap_or(0.0) * 10_000.0;
+        stats.total_latency_ms += result.execution_time_ms;
+    }
+
+    /// Statistics for one venue, if it has seen any orders
+    pub fn get(&self, venue: &str) -> Option<&VenueStats> {
+        self.venues.get(venue)
+    }
+
+    /// All venues with their statistics
+    pub fn venues(&self) -> &HashMap<String, VenueStats> {
+        &self.venues
+    }
+}
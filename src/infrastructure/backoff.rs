@@ -0,0 +1,62 @@
+//! Retry delays
+//!
+//! Shared by the market data feed's reconnects and the agent supervisor's
+//! restarts. Delays grow with each attempt up to a cap, and start over once
+//! the caller reports success.
+
+use std::time::Duration;
+
+/// Retry delays grown per `RateLimitConfig.backoff_strategy`:
+/// "exponential" doubles, "linear" adds the initial delay, anything else
+/// keeps it fixed
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    strategy: String,
+    initial: Duration,
+    max: Duration,
+    attempt: u32,
+}
+
+impl Backoff {
+    pub fn new(strategy: &str, initial: Duration, max: Duration) -> Self {
+        Self {
+            strategy: strategy.to_ascii_lowercase(),
+            initial,
+            max,
+            attempt: 0,
+        }
+    }
+
+    /// Delay before the next attempt
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = match self.strategy.as_str() {
+            "exponential" => self.initial.saturating_mul(2u32.saturating_pow(self.attempt)),
+            "linear" => self.initial.saturating_mul(self.attempt.saturating_add(1)),
+            _ => self.initial,
+        };
+        self.attempt = self.attempt.saturating_add(1);
+        delay.min(self.max)
+    }
+
+    /// Start over from the initial delay after a success
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delays_grow_per_strategy_up_to_the_cap() {
+        let mut exponential = Backoff::new("exponential", Duration::from_millis(100), Duration::from_millis(500));
+        let delays: Vec<_> = (0..4).map(|_| exponential.next_delay().as_millis()).collect();
+        assert_eq!(delays, [100, 200, 400, 500]);
+        exponential.reset();
+        assert_eq!(exponential.next_delay(), Duration::from_millis(100));
+        let mut linear = Backoff::new("linear", Duration::from_millis(100), Duration::from_secs(1));
+        let delays: Vec<_> = (0..3).map(|_| linear.next_delay().as_millis()).collect();
+        assert_eq!(delays, [100, 200, 300]);
+    }
+}
@@ -0,0 +1,91 @@
+//! Programmatic fault injection for resilience testing
+//!
+//! A `FaultInjector` is inert until a test arms one of its faults, so it can be
+//! threaded through production components at no cost.
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// Shared switchboard of simulated infrastructure failures
+#[derive(Debug, Clone, Default)]
+pub struct FaultInjector {
+    inner: Arc<FaultState>,
+}
+
+#[derive(Debug, Default)]
+struct FaultState {
+    drop_messages: AtomicBool,
+    fill_delay_ms: AtomicU64,
+    feed_disconnected: AtomicBool,
+    crashing_agents: Mutex<HashSet<String>>,
+    crash_requested: Notify,
+}
+
+impl FaultInjector {
+    /// Create an injector with no faults armed
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop inter-agent messages while enabled
+    pub fn set_drop_messages(&self, enabled: bool) {
+        self.inner.drop_messages.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Whether inter-agent messages should currently be dropped
+    pub fn should_drop_message(&self) -> bool {
+        self.inner.drop_messages.load(Ordering::SeqCst)
+    }
+
+    /// Delay every simulated fill by `delay` (zero disables)
+    pub fn set_fill_delay(&self, delay: Duration) {
+        self.inner.fill_delay_ms.store(delay.as_millis() as u64, Ordering::SeqCst);
+    }
+
+    /// Currently injected fill delay, if any
+    pub fn fill_delay(&self) -> Option<Duration> {
+        match self.inner.fill_delay_ms.load(Ordering::SeqCst) {
+            0 => None,
+            ms => Some(Duration::from_millis(ms)),
+        }
+    }
+
+    /// Simulate the market data feed disconnecting or reconnecting
+    pub fn set_feed_disconnected(&self, disconnected: bool) {
+        self.inner.feed_disconnected.store(disconnected, Ordering::SeqCst);
+    }
+
+    /// Whether the market data feed is currently disconnected
+    pub fn is_feed_disconnected(&self) -> bool {
+        self.inner.feed_disconnected.load(Ordering::SeqCst)
+    }
+
+    /// Make the named agent's running task panic, as if it hit a bug
+    pub fn crash_agent(&self, agent: &str) {
+        self.inner.crashing_agents.lock().expect("fault lock poisoned").insert(agent.to_string());
+        self.inner.crash_requested.notify_waiters();
+    }
+
+    /// Resolves once a crash of the named agent is requested, consuming it
+    pub async fn agent_crashed(&self, agent: &str) {
+        loop {
+            // Registered before checking, so a request in between still wakes it
+            let requested = self.inner.crash_requested.notified();
+            if self.inner.crashing_agents.lock().expect("fault lock poisoned").remove(agent) {
+                return;
+            }
+            requested.await;
+        }
+    }
+
+    /// Disarm all faults
+    pub fn clear(&self) {
+        self.set_drop_messages(false);
+        self.set_fill_delay(Duration::ZERO);
+        self.set_feed_disconnected(false);
+        self.inner.crashing_agents.lock().expect("fault lock poisoned").clear();
+    }
+}
@@ -0,0 +1,99 @@
+//! Shared HTTP client for outbound integrations
+//!
+//! Webhooks, data providers and other HTTP integrations take their client
+//! from one `HttpClientFactory` rather than building their own, so they share
+//! a connection pool and the configured timeouts.
+
+use std::time::Duration;
+
+use crate::core::config::HttpClientConfig;
+use crate::core::errors::TradingResult;
+
+/// Builds the pooled `reqwest::Client` handed to HTTP integrations
+#[derive(Debug, Clone)]
+pub struct HttpClientFactory {
+    client: reqwest::Client,
+    timeout: Duration,
+}
+
+impl HttpClientFactory {
+    /// Build the shared client with the configured pool and timeouts
+    pub fn from_config(config: &HttpClientConfig) -> TradingResult<Self> {
+        let timeout = Duration::from_millis(config.timeout_ms);
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .connect_timeout(Duration::from_millis(config.connect_timeout_ms))
+            .pool_max_idle_per_host(config.pool_max_idle_per_host)
+            .pool_idle_timeout(Duration::from_secs(config.pool_idle_timeout_secs))
+            .build()?;
+
+        Ok(Self { client, timeout })
+    }
+
+    /// Handle to the shared client; clones share one connection pool
+    pub fn client(&self) -> reqwest::Client {
+        self.client.clone()
+    }
+
+    /// Whole-request timeout applied to every call
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Answer every request with `200 ok` over keep-alive connections, counting connections
+    async fn keep_alive_server(connections: Arc<AtomicUsize>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                connections.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    while let Ok(n) = stream.read(&mut buf).await {
+                        if n == 0 {
+                            break;
+                        }
+                        let response = "HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok";
+                        if stream.write_all(response.as_bytes()).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_factory_clients_share_pool_and_apply_timeout() {
+        let config = HttpClientConfig { timeout_ms: 200, ..HttpClientConfig::default() };
+        let factory = HttpClientFactory::from_config(&config).unwrap();
+        assert_eq!(factory.timeout(), Duration::from_millis(200));
+
+        // Separate handles reuse the same pooled connection
+        let connections = Arc::new(AtomicUsize::new(0));
+        let url = keep_alive_server(connections.clone()).await;
+        for _ in 0..3 {
+            let body = factory.client().get(&url).send().await.unwrap().text().await.unwrap();
+            assert_eq!(body, "ok");
+        }
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+
+        // A server that never answers hits the configured timeout
+        let silent = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", silent.local_addr().unwrap());
+        let started = std::time::Instant::now();
+        let error = factory.client().get(&url).send().await.unwrap_err();
+        assert!(error.is_timeout());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,168 @@
+//! Contention instrumentation for shared `RwLock`s
+//!
+//! Wraps lock acquisitions so that long waits and long hold times are logged,
+//! making it possible to see which component serializes the agents under load.
+
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use tracing::warn;
+
+use crate::core::config::LockContentionConfig;
+use crate::core::errors::{TradingError, TradingResult};
+use crate::core::metrics::MetricsCollector;
+
+/// Times lock acquisitions and reports waits or holds beyond a threshold
+#[derive(Debug, Clone)]
+pub struct LockMonitor {
+    warn_threshold: Duration,
+    max_wait: Option<Duration>,
+    contention_events: Arc<AtomicU64>,
+}
+
+impl Default for LockMonitor {
+    fn default() -> Self {
+        Self::new(&LockContentionConfig::default())
+    }
+}
+
+impl LockMonitor {
+    /// Create a monitor from configuration
+    pub fn new(config: &LockContentionConfig) -> Self {
+        Self {
+            warn_threshold: Duration::from_millis(config.warn_threshold_ms),
+            max_wait: config.max_wait_ms.map(Duration::from_millis),
+            contention_events: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Number of slow acquisitions or long holds observed so far
+    pub fn contention_events(&self) -> u64 {
+        self.contention_events.load(Ordering::Relaxed)
+    }
+
+    /// Acquire a read lock, failing if it cannot be obtained within the configured maximum wait
+    pub async fn read<'a, T>(
+        &self,
+        lock: &'a RwLock<T>,
+        label: &'static str,
+    ) -> TradingResult<InstrumentedGuard<RwLockReadGuard<'a, T>>> {
+        let started = Instant::now();
+        let guard = self.acquire(lock.read(), label).await?;
+        self.check("waited for read lock", label, started.elapsed());
+        Ok(self.wrap(guard, label))
+    }
+
+    /// Acquire a write lock, failing if it cannot be obtained within the configured maximum wait
+    pub async fn write<'a, T>(
+        &self,
+        lock: &'a RwLock<T>,
+        label: &'static str,
+    ) -> TradingResult<InstrumentedGuard<RwLockWriteGuard<'a, T>>> {
+        let started = Instant::now();
+        let guard = self.acquire(lock.write(), label).await?;
+        self.check("waited for write lock", label, started.elapsed());
+        Ok(self.wrap(guard, label))
+    }
+
+    async fn acquire<G>(&self, acquisition: impl std::future::Future<Output = G>, label: &'static str) -> TradingResult<G> {
+        match self.max_wait {
+            Some(max_wait) => tokio::time::timeout(max_wait, acquisition).await.map_err(|_| {
+                self.check("timed out waiting for lock", label, max_wait);
+                TradingError::timeout(format!("{} lock acquisition", label))
+            }),
+            None => Ok(acquisition.await),
+        }
+    }
+
+    fn wrap<G>(&self, guard: G, label: &'static str) -> InstrumentedGuard<G> {
+        InstrumentedGuard {
+            guard,
+            label,
+            acquired_at: Instant::now(),
+            monitor: self.clone(),
+        }
+    }
+
+    fn check(&self, action: &str, label: &str, elapsed: Duration) {
+        if elapsed >= self.warn_threshold {
+            warn!("🔒 Lock contention: {} {} for {}ms", action, label, elapsed.as_millis());
+            self.contention_events.fetch_add(1, Ordering::Relaxed);
+            MetricsCollector::record_lock_contention();
+        }
+    }
+}
+
+/// Lock guard that reports how long it was held when dropped
+pub struct InstrumentedGuard<G> {
+    guard: G,
+    label: &'static str,
+    acquired_at: Instant,
+    monitor: LockMonitor,
+}
+
+impl<G: Deref> Deref for InstrumentedGuard<G> {
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<G: DerefMut> DerefMut for InstrumentedGuard<G> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<G> Drop for InstrumentedGuard<G> {
+    fn drop(&mut self) {
+        self.monitor.check("held", self.label, self.acquired_at.elapsed());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_long_held_write_lock_reports_contention() {
+        let monitor = LockMonitor::new(&LockContentionConfig {
+            warn_threshold_ms: 20,
+            max_wait_ms: None,
+        });
+        let lock = Arc::new(RwLock::new(0u32));
+
+        let writer = {
+            let monitor = monitor.clone();
+            let lock = lock.clone();
+            tokio::spawn(async move {
+                let mut guard = monitor.write(&lock, "test_context").await.unwrap();
+                tokio::time::sleep(Duration::from_millis(60)).await;
+                *guard += 1;
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        // The reader waits behind the writer and the writer's hold is reported on release
+        let value = *monitor.read(&lock, "test_context").await.unwrap();
+        writer.await.unwrap();
+
+        assert_eq!(value, 1);
+        assert_eq!(monitor.contention_events(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_max_wait_times_out() {
+        let monitor = LockMonitor::new(&LockContentionConfig {
+            warn_threshold_ms: 1_000,
+            max_wait_ms: Some(10),
+        });
+        let lock = RwLock::new(0u32);
+
+        let _held = lock.write().await;
+        assert!(monitor.read(&lock, "test_context").await.is_err());
+    }
+}
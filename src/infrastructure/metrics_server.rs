@@ -0,0 +1,117 @@
+//! Prometheus metrics exporter
+//!
+//! Serves everything registered with the default `prometheus` registry at
+//! `/metrics` in the Prometheus text format, on the configured port.
+
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{header, Body, Method, Request, Response, Server, StatusCode};
+use prometheus::{Encoder, TextEncoder};
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::{SocketAddr, TcpListener};
+use tracing::info;
+
+use crate::core::errors::{TradingError, TradingResult};
+
+/// HTTP exporter for the default Prometheus registry
+pub struct MetricsServer {
+    addr: SocketAddr,
+}
+
+impl MetricsServer {
+    /// Exporter listening on `port` on the loopback interface only
+    pub fn new(port: u16) -> Self {
+        Self {
+            addr: SocketAddr::from(([127, 0, 0, 1], port)),
+        }
+    }
+
+    /// Exporter listening on an explicit address
+    pub fn with_address(addr: SocketAddr) -> Self {
+        Self { addr }
+    }
+
+    /// Bind the port now, so a port already in use is reported to the caller.
+    /// Returns the bound address and the future that serves requests.
+    pub fn bind(self) -> TradingResult<(SocketAddr, impl Future<Output = TradingResult<()>>)> {
+        let listener = TcpListener::bind(self.addr).map_err(|e| match e.kind() {
+            std::io::ErrorKind::AddrInUse => {
+                TradingError::interface(format!("Metrics port {} is already in use", self.addr.port()))
+            }
+            _ => TradingError::interface(format!("Failed to bind metrics exporter on {}: {}", self.addr, e)),
+        })?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|e| TradingError::interface(format!("Failed to configure metrics listener: {}", e)))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|e| TradingError::interface(format!("Failed to read metrics listener address: {}", e)))?;
+
+        let make_service = make_service_fn(|_conn| async {
+            Ok::<_, Infallible>(service_fn(handle_request))
+        });
+        let server = Server::from_tcp(listener)
+            .map_err(|e| TradingError::interface(format!("Failed to start metrics exporter: {}", e)))?
+            .serve(make_service);
+
+        info!("📈 Prometheus metrics at http://{}/metrics", addr);
+
+        Ok((addr, async move {
+            server
+                .await
+                .map_err(|e| TradingError::interface(format!("Metrics exporter error: {}", e)))
+        }))
+    }
+}
+
+/// Serve `/metrics`; anything else is not found
+async fn handle_request(req: Request<Body>) -> Result<Response<Body>, Infallible> {
+    if req.method() != Method::GET || req.uri().path() != "/metrics" {
+        return Ok(status_response(StatusCode::NOT_FOUND));
+    }
+
+    let encoder = TextEncoder::new();
+    let mut body = Vec::new();
+    if encoder.encode(&prometheus::gather(), &mut body).is_err() {
+        return Ok(status_response(StatusCode::INTERNAL_SERVER_ERROR));
+    }
+    Ok(Response::builder()
+        .header(header::CONTENT_TYPE, encoder.format_type())
+        .body(Body::from(body))
+        .unwrap_or_else(|_| status_response(StatusCode::INTERNAL_SERVER_ERROR)))
+}
+
+/// Build an empty response with the given status
+fn status_response(status: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = status;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::metrics::MetricsCollector;
+    use rust_decimal::Decimal;
+
+    #[tokio::test]
+    async fn test_scrape_serves_portfolio_metrics() {
+        MetricsCollector::update_portfolio_metrics(Decimal::from(100), Decimal::from(90), Decimal::ZERO, Decimal::ZERO, 1);
+
+        let (addr, server) = MetricsServer::with_address(SocketAddr::from(([127, 0, 0, 1], 0))).bind().unwrap();
+        tokio::spawn(server);
+
+        let response = hyper::Client::new()
+            .get(format!("http://{}/metrics", addr).parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("portfolio_value_usd"), "{}", body);
+
+        // A second exporter on the same port fails cleanly instead of panicking
+        let error = MetricsServer::with_address(addr).bind().err().unwrap();
+        assert!(error.to_string().contains("already in use"), "{}", error);
+    }
+}
@@ -1,5 +1,11 @@
 //! Infrastructure module - System infrastructure and monitoring
 
+pub mod backoff;
+pub mod faults;
+pub mod http;
+pub mod locks;
+pub mod metrics_server;
+
 /// Placeholder for infrastructure module
 pub struct InfrastructureManager;
 
@@ -0,0 +1,81 @@
+//! Volatility-driven analysis interval
+//!
+//! Polls faster when markets move and backs off when they are calm, so CPU
+//! isn't spent re-analysing quiet markets and fast ones aren't missed.
+
+use tokio::time::Duration;
+
+use crate::core::config::AdaptiveIntervalConfig;
+
+/// Scales the analysis interval inversely with observed volatility
+#[derive(Debug, Clone)]
+pub struct AdaptiveInterval {
+    enabled: bool,
+    reference_volatility: f64,
+    min: Duration,
+    max: Duration,
+}
+
+impl AdaptiveInterval {
+    /// Create an adaptive interval from configuration
+    pub fn from_config(config: &AdaptiveIntervalConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            reference_volatility: config.reference_volatility,
+            min: Duration::from_millis(config.min_interval_ms),
+            max: Duration::from_millis(config.max_interval_ms.max(config.min_interval_ms)),
+        }
+    }
+
+    /// Effective interval for `base` given the latest volatility reading.
+    ///
+    /// At the reference volatility the base interval is used unchanged; twice
+    /// the reference halves it, half the reference doubles it. The result is
+    /// always within the configured bounds.
+    pub fn period(&self, base: Duration, volatility: Option<f64>) -> Duration {
+        let Some(volatility) = volatility.filter(|v| self.enabled && v.is_finite() && *v > 0.0) else {
+            return base;
+        };
+
+        let scaled = base.mul_f64((self.reference_volatility / volatility).min(1e6));
+        scaled.clamp(self.min, self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volatility_shortens_and_calm_lengthens_interval() {
+        let adaptive = AdaptiveInterval::from_config(&AdaptiveIntervalConfig {
+            enabled: true,
+            reference_volatility: 0.3,
+            min_interval_ms: 20,
+            max_interval_ms: 250,
+        });
+        let base = Duration::from_millis(100);
+
+        assert_eq!(adaptive.period(base, Some(0.3)), base);
+        assert_eq!(adaptive.period(base, Some(0.6)), Duration::from_millis(50));
+        assert_eq!(adaptive.period(base, Some(0.15)), Duration::from_millis(200));
+
+        // Extreme readings stay within bounds
+        assert_eq!(adaptive.period(base, Some(30.0)), Duration::from_millis(20));
+        assert_eq!(adaptive.period(base, Some(0.01)), Duration::from_millis(250));
+
+        assert_eq!(adaptive.period(base, None), base);
+    }
+
+    #[test]
+    fn test_non_positive_reference_volatility_is_refused() {
+        for reference_volatility in [0.0, -0.3, f64::NAN] {
+            let mut config = crate::core::config::SystemConfig::default();
+            let adaptive = &mut config.agents.market_intelligence.adaptive_interval;
+            adaptive.enabled = true;
+            adaptive.reference_volatility = reference_volatility;
+            let error = config.validate().unwrap_err().to_string();
+            assert!(error.contains("reference volatility must be positive"), "{}", error);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Per-cycle compute budget for market analysis
+//!
+//! With a large universe, analysing every symbol each cycle would blow the
+//! latency target. The budget admits as many symbols as the observed
+//! per-symbol cost allows, highest priority first, and defers the rest to
+//! later cycles. Deferred symbols gain priority so none are starved.
+
+use std::collections::HashMap;
+use tokio::time::Duration;
+
+use crate::core::config::ComputeBudgetConfig;
+use crate::core::types::{MarketData, Symbol};
+
+/// Weight of the newest observation in the per-symbol cost estimate
+const COST_SMOOTHING: f64 = 0.2;
+
+/// Selects which symbols to analyse within a cycle's compute budget
+#[derive(Debug, Clone)]
+pub struct ComputeBudget {
+    enabled: bool,
+    budget: Duration,
+    cost_per_symbol: Duration,
+    deferred_cycles: HashMap<Symbol, u32>,
+}
+
+impl ComputeBudget {
+    /// Create a budget from configuration
+    pub fn from_config(config: &ComputeBudgetConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            budget: Duration::from_millis(config.max_cycle_ms),
+            cost_per_symbol: Duration::from_micros(config.initial_cost_per_symbol_us.max(1)),
+            deferred_cycles: HashMap::new(),
+        }
+    }
+
+    /// Number of symbols that fit in one cycle at the current cost estimate
+    pub fn capacity(&self) -> usize {
+        ((self.budget.as_secs_f64() / self.cost_per_symbol.as_secs_f64()) as usize).max(1)
+    }
+
+    /// Split quotes into those to analyse now and those deferred.
+    ///
+    /// Symbols deferred for the most cycles go first, then the most heavily
+    /// traded.
+    pub fn select(&mut self, mut quotes: Vec<MarketData>) -> (Vec<MarketData>, Vec<MarketData>) {
+        if !self.enabled || quotes.len() <= self.capacity() {
+            self.deferred_cycles.clear();
+            return (quotes, Vec::new());
+        }
+
+        quotes.sort_by(|a, b| {
+            let waited = |quote: &MarketData| self.deferred_cycles.get(&quote.symbol).copied().unwrap_or(0);
+            waited(b)
+                .cmp(&waited(a))
+                .then(b.volume.cmp(&a.volume))
+                .then(a.symbol.cmp(&b.symbol))
+        });
+        let deferred = quotes.split_off(self.capacity());
+
+        for quote in &quotes {
+            self.deferred_cycles.remove(&quote.symbol);
+        }
+        for quote in &deferred {
+            *self.deferred_cycles.entry(quote.symbol.clone()).or_insert(0) += 1;
+        }
+
+        (quotes, deferred)
+    }
+
+    /// Fold the time spent analysing `symbols` into the per-symbol cost estimate
+    pub fn observe(&mut self, symbols: usize, elapsed: Duration) {
+        if symbols == 0 {
+            return;
+        }
+        let observed = elapsed.as_secs_f64() / symbols as f64;
+        let estimate = self.cost_per_symbol.as_secs_f64() * (1.0 - COST_SMOOTHING) + observed * COST_SMOOTHING;
+        self.cost_per_symbol = Duration::from_secs_f64(estimate.max(1e-6));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(symbol: &str, volume: u64) -> MarketData {
+        MarketData {
+            symbol: symbol.to_string(),
+            timestamp: chrono::Utc::now(),
+            price: rust_decimal::Decimal::from(100),
+            volume,
+            bid: None,
+            ask: None,
+            bid_size: None,
+            ask_size: None,
+            source: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_tight_budget_analyzes_prioritized_subset() {
+        // 1ms budget at 250us per symbol fits four symbols
+        let mut budget = ComputeBudget::from_config(&ComputeBudgetConfig {
+            enabled: true,
+            max_cycle_ms: 1,
+            initial_cost_per_symbol_us: 250,
+        });
+        let universe: Vec<MarketData> = (0..10).map(|i| quote(&format!("SYM{}", i), i * 1_000)).collect();
+
+        let (analyzed, deferred) = budget.select(universe.clone());
+        let symbols: Vec<&str> = analyzed.iter().map(|q| q.symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["SYM9", "SYM8", "SYM7", "SYM6"]);
+        assert_eq!(deferred.len(), 6);
+
+        // Deferred symbols jump the queue next cycle
+        let (analyzed, _) = budget.select(universe);
+        let symbols: Vec<&str> = analyzed.iter().map(|q| q.symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["SYM5", "SYM4", "SYM3", "SYM2"]);
+    }
+}
@@ -0,0 +1,122 @@
+//! Inbound quote coalescing for high tick-rate feeds
+
+use std::collections::HashMap;
+
+use crate::core::metrics::MetricsCollector;
+use crate::core::types::{MarketData, Symbol};
+
+/// Keeps only the latest quote per symbol between analysis cycles.
+/// When disabled, every tick is passed through in arrival order.
+#[derive(Debug, Clone)]
+pub struct QuoteCoalescer {
+    enabled: bool,
+    latest: HashMap<Symbol, MarketData>,
+    passthrough: Vec<MarketData>,
+    coalesced: u64,
+}
+
+impl QuoteCoalescer {
+    /// Create an empty coalescer
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            latest: HashMap::new(),
+            passthrough: Vec::new(),
+            coalesced: 0,
+        }
+    }
+
+    /// Buffer a quote, replacing any older pending quote for the same symbol.
+    /// Out-of-order quotes older than the pending one are dropped.
+    pub fn push(&mut self, quote: MarketData) {
+        if !self.enabled {
+            self.passthrough.push(quote);
+            return;
+        }
+
+        match self.latest.get_mut(&quote.symbol) {
+            Some(pending) => {
+                if quote.timestamp >= pending.timestamp {
+                    *pending = quote;
+                }
+                self.coalesced += 1;
+                MetricsCollector::record_coalesced_tick();
+            }
+            None => {
+                self.latest.insert(quote.symbol.clone(), quote);
+            }
+        }
+    }
+
+    /// Take the pending quotes for this interval, ordered by symbol
+    pub fn drain(&mut self) -> Vec<MarketData> {
+        if !self.enabled {
+            return std::mem::take(&mut self.passthrough);
+        }
+
+        let mut quotes: Vec<MarketData> = self.latest.drain().map(|(_, quote)| quote).collect();
+        quotes.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        quotes
+    }
+
+    /// Total ticks dropped or replaced since creation
+    pub fn coalesced_count(&self) -> u64 {
+        self.coalesced
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use rust_decimal::Decimal;
+
+    fn quote(symbol: &str, price: i64, offset_ms: i64) -> MarketData {
+        MarketData {
+            symbol: symbol.to_string(),
+            timestamp: Utc::now() + Duration::milliseconds(offset_ms),
+            price: Decimal::from(price),
+            volume: 100,
+            bid: None,
+            ask: None,
+            bid_size: None,
+            ask_size: None,
+            source: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_burst_coalesced_to_latest() {
+        let mut coalescer = QuoteCoalescer::new(true);
+        for i in 0..100 {
+            coalescer.push(quote("AAPL", 150 + i, i));
+        }
+        coalescer.push(quote("MSFT", 300, 0));
+
+        let quotes = coalescer.drain();
+        assert_eq!(quotes.len(), 2);
+        assert_eq!(quotes[0].symbol, "AAPL");
+        assert_eq!(quotes[0].price, Decimal::from(249));
+        assert_eq!(coalescer.coalesced_count(), 99);
+        assert!(coalescer.drain().is_empty());
+    }
+
+    #[test]
+    fn test_stale_quote_does_not_replace_newer() {
+        let mut coalescer = QuoteCoalescer::new(true);
+        coalescer.push(quote("AAPL", 151, 10));
+        coalescer.push(quote("AAPL", 150, 0));
+
+        assert_eq!(coalescer.drain()[0].price, Decimal::from(151));
+    }
+
+    #[test]
+    fn test_disabled_passes_every_tick() {
+        let mut coalescer = QuoteCoalescer::new(false);
+        coalescer.push(quote("AAPL", 150, 0));
+        coalescer.push(quote("AAPL", 151, 1));
+
+        assert_eq!(coalescer.drain().len(), 2);
+        assert_eq!(coalescer.coalesced_count(), 0);
+    }
+}
@@ -0,0 +1,223 @@
+//! Live market data over a WebSocket subscription
+//!
+//! The feed connects to the configured data provider, subscribes to the
+//! watchlist and forwards each quote over a channel. A dropped connection is
+//! retried with backoff until the receiver goes away.
+//!
+//! OpenD itself speaks protobuf over TCP, so the provider is a relay speaking
+//! this JSON protocol over WebSocket text frames:
+//!
+//! - on connect the feed sends `{"action": "subscribe", "symbols": [...]}`
+//! - the relay then sends one `MarketData` object per frame, e.g.
+//!   `{"symbol": "AAPL", "timestamp": "2025-01-17T14:30:00Z", "price": "150.25",
+//!   "volume": 100, "bid": null, "ask": null, "bid_size": null, "ask_size": null}`,
+//!   optionally with a `source`; frames that don't parse are skipped
+
+use futures::{SinkExt, StreamExt};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{info, warn};
+
+use crate::core::config::{ApiConfig, LiveFeedConfig};
+use crate::core::errors::{TradingError, TradingResult};
+use crate::core::types::{DataSource, MarketData};
+use crate::infrastructure::backoff::Backoff;
+
+/// Quotes buffered between the feed and the agent before the feed waits
+pub const FEED_CHANNEL_CAPACITY: usize = 1024;
+
+/// WebSocket subscription pushing quotes for a watchlist
+#[derive(Debug, Clone)]
+pub struct MarketDataFeed {
+    url: String,
+    watchlist: Vec<String>,
+    source: DataSource,
+    backoff: Backoff,
+}
+
+impl MarketDataFeed {
+    /// Build the feed for the relay named by `config.provider`. Quotes that
+    /// don't name their source are tagged with `source`.
+    pub fn from_config(config: &LiveFeedConfig, api: &ApiConfig, watchlist: Vec<String>, source: DataSource) -> TradingResult<Self> {
+        let name = config
+            .provider
+            .as_ref()
+            .ok_or_else(|| TradingError::market_data("The live feed needs a provider from api.data_providers"))?;
+        let url = api
+            .data_providers
+            .iter()
+            .find(|provider| provider.enabled && provider.name == *name)
+            .map(|provider| provider.url.clone())
+            .ok_or_else(|| TradingError::market_data(format!("No enabled data provider named {}", name)))?;
+        let backoff = Backoff::new(
+            &api.rate_limits.backoff_strategy,
+            Duration::from_millis(config.initial_backoff_ms),
+            Duration::from_millis(config.max_backoff_ms),
+        );
+
+        Ok(Self {
+            url: websocket_url(&url),
+            watchlist,
+            source,
+            backoff,
+        })
+    }
+
+    /// Stream quotes into `sender`, reconnecting on disconnect, until the
+    /// receiving side is dropped
+    pub async fn run(mut self, sender: mpsc::Sender<MarketData>) {
+        while !sender.is_closed() {
+            match self.stream(&sender).await {
+                Ok(()) => warn!("📡 Market data feed {} closed", self.url),
+                Err(e) => warn!("📡 Market data feed {} failed: {}", self.url, e),
+            }
+            if sender.is_closed() {
+                break;
+            }
+
+            let delay = self.backoff.next_delay();
+            info!("📡 Reconnecting to {} in {:?}", self.url, delay);
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = sender.closed() => break,
+            }
+        }
+    }
+
+    /// One connection: subscribe, then forward quotes until it drops
+    async fn stream(&mut self, sender: &mpsc::Sender<MarketData>) -> TradingResult<()> {
+        let (mut socket, _) = tokio_tungstenite::connect_async(self.url.as_str()).await?;
+        let subscribe = serde_json::json!({ "action": "subscribe", "symbols": self.watchlist });
+        socket.send(Message::Text(subscribe.to_string())).await?;
+        info!("📡 Subscribed to {} symbols on {}", self.watchlist.len(), self.url);
+        self.backoff.reset();
+
+        loop {
+            let frame = tokio::select! {
+                frame = socket.next() => frame,
+                _ = sender.closed() => return Ok(()),
+            };
+            let text = match frame {
+                Some(Ok(Message::Text(text))) => text,
+                Some(Ok(Message::Close(_))) | None => return Ok(()),
+                Some(Ok(_)) => continue,
+                Some(Err(e)) => return Err(e.into()),
+            };
+
+            let mut quote: MarketData = match serde_json::from_str(&text) {
+                Ok(quote) => quote,
+                Err(e) => {
+                    warn!("📡 Ignoring unreadable market data frame: {}", e);
+                    continue;
+                }
+            };
+            if quote.source == DataSource::default() {
+                quote.source = self.source.clone();
+            }
+            if sender.send(quote).await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Provider URLs are configured as HTTP; the feed speaks WebSocket to the same host
+fn websocket_url(url: &str) -> String {
+    if let Some(rest) = url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        url.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::{DataProviderConfig, SystemConfig};
+    use rust_decimal::Decimal;
+    use tokio::net::TcpListener;
+
+    fn quote(price: i64) -> String {
+        serde_json::json!({
+            "symbol": "AAPL",
+            "timestamp": chrono::Utc::now(),
+            "price": Decimal::from(price),
+            "volume": 100,
+            "bid": null,
+            "ask": null,
+            "bid_size": null,
+            "ask_size": null,
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_feed_subscribes_forwards_quotes_and_reconnects() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (subscriptions_tx, mut subscriptions) = mpsc::unbounded_channel();
+
+        // Each connection gets one quote and is then dropped
+        tokio::spawn(async move {
+            for price in [100, 101] {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+                let subscribe = socket.next().await.unwrap().unwrap();
+                subscriptions_tx.send(subscribe.into_text().unwrap()).unwrap();
+                socket.send(Message::Text(quote(price))).await.unwrap();
+                socket.close(None).await.unwrap();
+            }
+        });
+
+        let mut api = SystemConfig::default().api;
+        api.rate_limits.backoff_strategy = "exponential".to_string();
+        api.data_providers.push(DataProviderConfig {
+            name: "quote_relay".to_string(),
+            url: format!("http://{}", addr),
+            api_key: None,
+            enabled: true,
+            priority: 1,
+        });
+        let mut config = LiveFeedConfig { enabled: true, initial_backoff_ms: 10, ..LiveFeedConfig::default() };
+        let source = DataSource::new("moomoo", "level1");
+
+        // There is no implicit OpenD fallback: its API port doesn't speak this protocol
+        let error = MarketDataFeed::from_config(&config, &api, vec!["AAPL".to_string()], source.clone()).unwrap_err();
+        assert!(error.to_string().contains("needs a provider"), "{}", error);
+        config.provider = Some("quote_relay".to_string());
+        let feed = MarketDataFeed::from_config(&config, &api, vec!["AAPL".to_string(), "TSLA".to_string()], source.clone()).unwrap();
+        assert_eq!(feed.url, format!("ws://{}", addr));
+
+        let (sender, mut quotes) = mpsc::channel(FEED_CHANNEL_CAPACITY);
+        let task = tokio::spawn(feed.run(sender));
+        let wait = Duration::from_secs(5);
+
+        let subscribe: serde_json::Value = serde_json::from_str(&subscriptions.recv().await.unwrap()).unwrap();
+        assert_eq!(subscribe["symbols"], serde_json::json!(["AAPL", "TSLA"]));
+        let first = tokio::time::timeout(wait, quotes.recv()).await.unwrap().unwrap();
+        assert_eq!((first.price, first.source), (Decimal::from(100), source));
+
+        // The feed reconnects and resubscribes after the server hangs up
+        assert!(subscriptions.recv().await.is_some());
+        let second = tokio::time::timeout(wait, quotes.recv()).await.unwrap().unwrap();
+        assert_eq!(second.price, Decimal::from(101));
+
+        // Dropping the receiver stops the feed
+        drop(quotes);
+        tokio::time::timeout(wait, task).await.unwrap().unwrap();
+
+        // Validation wants an enabled relay, and refuses OpenD's API port
+        let mut system = SystemConfig::default();
+        system.api.moomoo.api_key = "test".to_string();
+        system.agents.market_intelligence.live_feed = LiveFeedConfig { enabled: true, ..LiveFeedConfig::default() };
+        assert!(system.validate().is_err());
+        system.agents.market_intelligence.live_feed.provider = Some("quote_relay".to_string());
+        system.api.data_providers = api.data_providers.clone();
+        assert!(system.validate().is_ok());
+        system.api.data_providers[0].url = system.api.moomoo.base_url.clone();
+        assert!(system.validate().is_err());
+    }
+}
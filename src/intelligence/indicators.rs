@@ -0,0 +1,216 @@
+//! Technical indicators over recent prices
+//!
+//! Readings come from the `ta` crate and only the indicators named in
+//! `IntelligenceConfig.technical_indicators` are computed. Each indicator
+//! reports once the series covers its period.
+
+use std::collections::{HashMap, VecDeque};
+use ta::indicators::{
+    AverageTrueRange, ExponentialMovingAverage, MovingAverageConvergenceDivergence, RelativeStrengthIndex,
+    SimpleMovingAverage,
+};
+use ta::Next;
+
+use crate::core::types::Symbol;
+
+/// Prices kept per symbol, enough to warm up every indicator
+const PRICE_HISTORY_LEN: usize = 200;
+
+/// Bars per year used to annualize volatility, counting each recorded price as
+/// one trading day's bar
+const BARS_PER_YEAR: f64 = 252.0;
+
+/// MACD line, its signal line and their difference
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MacdReading {
+    pub macd: f64,
+    pub signal: f64,
+    pub histogram: f64,
+}
+
+/// Latest indicator values for one price series; `None` when an indicator is
+/// disabled or not yet warmed up
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IndicatorReadings {
+    pub price: f64,
+    pub sma: Option<f64>,
+    pub ema: Option<f64>,
+    pub rsi: Option<f64>,
+    pub macd: Option<MacdReading>,
+    pub atr: Option<f64>,
+}
+
+impl IndicatorReadings {
+    /// Trend from -1 (strong downtrend) to 1 (strong uptrend), averaged over
+    /// the available readings. `None` if there are none.
+    pub fn trend(&self) -> Option<f64> {
+        let mut votes = Vec::new();
+        // Price 5% away from a moving average counts as a full-strength trend
+        for average in [self.sma, self.ema].into_iter().flatten() {
+            if average > 0.0 {
+                votes.push(((self.price / average - 1.0) * 20.0).clamp(-1.0, 1.0));
+            }
+        }
+        if let Some(rsi) = self.rsi {
+            votes.push((rsi - 50.0) / 50.0);
+        }
+        // As does a MACD histogram of 1% of the price
+        if let Some(macd) = self.macd.filter(|_| self.price > 0.0) {
+            votes.push((macd.histogram / self.price * 100.0).clamp(-1.0, 1.0));
+        }
+
+        (!votes.is_empty()).then(|| votes.iter().sum::<f64>() / votes.len() as f64)
+    }
+
+    /// Annualized volatility from the ATR as a fraction of price. `None`
+    /// until the ATR is warmed up.
+    pub fn volatility(&self) -> Option<f64> {
+        let atr = self.atr?;
+        (self.price > 0.0).then(|| atr / self.price * BARS_PER_YEAR.sqrt())
+    }
+}
+
+/// The enabled indicators and their periods
+#[derive(Debug, Clone)]
+pub struct IndicatorSet {
+    sma: Option<usize>,
+    ema: Option<usize>,
+    rsi: Option<usize>,
+    macd: Option<(usize, usize, usize)>,
+    atr: Option<usize>,
+}
+
+impl IndicatorSet {
+    /// Enable the indicators named in config ("sma", "ema", "rsi", "macd", "atr")
+    /// with their conventional periods. Other names are ignored.
+    pub fn from_config(names: &[String]) -> Self {
+        let enabled = |name: &str| names.iter().any(|configured| configured.eq_ignore_ascii_case(name));
+        Self {
+            sma: enabled("sma").then_some(20),
+            ema: enabled("ema").then_some(20),
+            rsi: enabled("rsi").then_some(14),
+            macd: enabled("macd").then_some((12, 26, 9)),
+            atr: enabled("atr").then_some(14),
+        }
+    }
+
+    /// Readings at the end of `prices`, given oldest first. `None` for an
+    /// empty series.
+    pub fn compute(&self, prices: &[f64]) -> Option<IndicatorReadings> {
+        let price = *prices.last()?;
+        let warm = |period: usize| period > 0 && prices.len() >= period;
+
+        Some(IndicatorReadings {
+            price,
+            sma: self
+                .sma
+                .filter(|period| warm(*period))
+                .and_then(|period| SimpleMovingAverage::new(period).ok())
+                .and_then(|sma| last(sma, prices)),
+            ema: self
+                .ema
+                .filter(|period| warm(*period))
+                .and_then(|period| ExponentialMovingAverage::new(period).ok())
+                .and_then(|ema| last(ema, prices)),
+            rsi: self
+                .rsi
+                .filter(|period| warm(*period))
+                .and_then(|period| RelativeStrengthIndex::new(period).ok())
+                .and_then(|rsi| last(rsi, prices)),
+            macd: self
+                .macd
+                .filter(|(_, slow, _)| warm(*slow))
+                .and_then(|(fast, slow, signal)| MovingAverageConvergenceDivergence::new(fast, slow, signal).ok())
+                .and_then(|macd| last(macd, prices))
+                .map(|output| MacdReading {
+                    macd: output.macd,
+                    signal: output.signal,
+                    histogram: output.histogram,
+                }),
+            atr: self
+                .atr
+                .filter(|period| warm(*period))
+                .and_then(|period| AverageTrueRange::new(period).ok())
+                .and_then(|atr| last(atr, prices)),
+        })
+    }
+}
+
+/// Feed every price through `indicator`, keeping its final output
+fn last<I: Next<f64>>(mut indicator: I, prices: &[f64]) -> Option<I::Output> {
+    prices.iter().map(|price| indicator.next(*price)).last()
+}
+
+/// Recent prices per symbol, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct PriceHistory {
+    prices: HashMap<Symbol, VecDeque<f64>>,
+}
+
+impl PriceHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a price for `symbol`, dropping the oldest beyond the window
+    pub fn record(&mut self, symbol: &str, price: f64) {
+        if !price.is_finite() {
+            return;
+        }
+        let prices = self.prices.entry(symbol.to_string()).or_default();
+        prices.push_back(price);
+        while prices.len() > PRICE_HISTORY_LEN {
+            prices.pop_front();
+        }
+    }
+
+    /// Prices recorded for `symbol`, oldest first
+    pub fn series(&self, symbol: &str) -> Vec<f64> {
+        self.prices.get(symbol).map(|prices| prices.iter().copied().collect()).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rsi_and_macd_match_hand_computed_values() {
+        let set = IndicatorSet {
+            sma: Some(3),
+            ema: None,
+            rsi: Some(3),
+            macd: Some((2, 3, 2)),
+            atr: Some(3),
+        };
+        let prices = [10.0, 10.5, 10.0, 9.5];
+        let readings = set.compute(&prices).unwrap();
+
+        // EMAs weight each new value by 2/(n+1) and start at the first value.
+        // RSI(3): average gains 0.1, 0.3, 0.15, 0.075 and losses 0.1, 0.05,
+        // 0.275, 0.3875 (both seeded at 0.1), so 100 * 0.075 / 0.4625
+        assert!((readings.rsi.unwrap() - 600.0 / 37.0).abs() < 1e-9);
+
+        // MACD(2, 3, 2): fast EMA 262/27 less slow EMA 157/16 is -47/432;
+        // the signal line over MACD values 0, 1/12, -1/72, -47/432 is -5/72
+        let macd = readings.macd.unwrap();
+        assert!((macd.macd + 47.0 / 432.0).abs() < 1e-9);
+        assert!((macd.signal + 5.0 / 72.0).abs() < 1e-9);
+        assert!((macd.histogram + 17.0 / 432.0).abs() < 1e-9);
+
+        // ATR(3) over closes: true ranges 0, 0.5, 0.5, 0.5 averaged with an EMA
+        assert!((readings.atr.unwrap() - 7.0 / 16.0).abs() < 1e-9);
+        assert!((readings.volatility().unwrap() - 7.0 / 16.0 / 9.5 * 252f64.sqrt()).abs() < 1e-9);
+
+        assert!((readings.sma.unwrap() - 10.0).abs() < 1e-9);
+        assert_eq!(readings.ema, None);
+        // Falling prices read as a downtrend
+        assert!(readings.trend().unwrap() < 0.0);
+
+        // Indicators stay silent until their period is covered
+        let short = set.compute(&prices[..2]).unwrap();
+        assert_eq!((short.sma, short.rsi, short.macd, short.atr), (None, None, None, None));
+        assert_eq!(short.trend(), None);
+        assert_eq!(short.volatility(), None);
+    }
+}
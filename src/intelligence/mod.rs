@@ -1,5 +1,13 @@
 //! Intelligence module - Market analysis and signal generation
 
+pub mod adaptive;
+pub mod budget;
+pub mod coalescer;
+pub mod feed;
+pub mod indicators;
+pub mod quotes;
+pub mod replay;
+
 /// Placeholder for intelligence module
 pub struct IntelligenceEngine;
 
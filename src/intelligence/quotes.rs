@@ -0,0 +1,168 @@
+//! Quote freshness tracking shared between analysis and execution
+//!
+//! Quotes are kept per data source. When several providers quote the same
+//! symbol, the one from the highest-priority source that is still fresh is
+//! used, failing over to the next source once a provider goes quiet.
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::core::config::DataSourceConfig;
+use crate::core::types::{DataSource, MarketData, Symbol};
+
+/// Latest quote seen for each symbol from each data source
+#[derive(Debug, Clone, Default)]
+pub struct QuoteBook {
+    latest: Arc<RwLock<HashMap<Symbol, HashMap<DataSource, MarketData>>>>,
+    last_seen: Arc<RwLock<HashMap<DataSource, DateTime<Utc>>>>,
+    sources: Arc<Vec<DataSourceConfig>>,
+}
+
+impl QuoteBook {
+    /// Create an empty quote book
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty quote book preferring `sources` in the given order
+    pub fn with_sources(sources: Vec<DataSourceConfig>) -> Self {
+        Self {
+            sources: Arc::new(sources),
+            ..Self::default()
+        }
+    }
+
+    /// Record that a quote arrived
+    pub async fn record(&self, quote: &MarketData) {
+        let mut latest = self.latest.write().await;
+        let by_source = latest.entry(quote.symbol.clone()).or_default();
+        let entry = by_source.entry(quote.source.clone()).or_insert_with(|| quote.clone());
+        if quote.timestamp > entry.timestamp {
+            *entry = quote.clone();
+        }
+
+        let mut last_seen = self.last_seen.write().await;
+        let seen = last_seen.entry(quote.source.clone()).or_insert(quote.timestamp);
+        *seen = (*seen).max(quote.timestamp);
+    }
+
+    /// Most recent quote for `symbol`, from the preferred fresh source if any
+    pub async fn latest(&self, symbol: &str) -> Option<MarketData> {
+        self.latest_at(symbol, Utc::now()).await
+    }
+
+    /// Check whether `symbol` has a quote no older than `max_age_secs`
+    /// (0 disables the check)
+    pub async fn is_fresh(&self, symbol: &str, now: DateTime<Utc>, max_age_secs: u64) -> bool {
+        if max_age_secs == 0 {
+            return true;
+        }
+
+        self.latest_at(symbol, now)
+            .await
+            .is_some_and(|quote| now - quote.timestamp <= Duration::seconds(max_age_secs as i64))
+    }
+
+    /// Check whether `source` has quoted anything within its configured
+    /// `max_age_secs`. Sources that aren't configured never go stale.
+    pub async fn is_source_fresh(&self, source: &DataSource, now: DateTime<Utc>) -> bool {
+        let last_seen = self.last_seen.read().await;
+        self.source_fresh(&last_seen, source, now)
+    }
+
+    /// Highest-priority configured source that is still fresh
+    pub async fn active_source(&self, now: DateTime<Utc>) -> Option<DataSource> {
+        let last_seen = self.last_seen.read().await;
+        self.sources
+            .iter()
+            .map(DataSourceConfig::source)
+            .find(|source| last_seen.contains_key(source) && self.source_fresh(&last_seen, source, now))
+    }
+
+    async fn latest_at(&self, symbol: &str, now: DateTime<Utc>) -> Option<MarketData> {
+        let latest = self.latest.read().await;
+        let by_source = latest.get(symbol)?;
+        let last_seen = self.last_seen.read().await;
+
+        // Prefer configured sources in order, then fall back to the newest quote
+        self.sources
+            .iter()
+            .map(DataSourceConfig::source)
+            .filter(|source| self.source_fresh(&last_seen, source, now))
+            .find_map(|source| by_source.get(&source))
+            .or_else(|| by_source.values().max_by_key(|quote| quote.timestamp))
+            .cloned()
+    }
+
+    fn source_fresh(&self, last_seen: &HashMap<DataSource, DateTime<Utc>>, source: &DataSource, now: DateTime<Utc>) -> bool {
+        let max_age_secs = self
+            .sources
+            .iter()
+            .find(|config| config.provider == source.provider && config.feed == source.feed)
+            .map_or(0, |config| config.max_age_secs);
+        if max_age_secs == 0 {
+            return true;
+        }
+
+        last_seen
+            .get(source)
+            .is_some_and(|seen| now - *seen <= Duration::seconds(max_age_secs as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn source(provider: &str, max_age_secs: u64) -> DataSourceConfig {
+        DataSourceConfig {
+            provider: provider.to_string(),
+            feed: "level1".to_string(),
+            max_age_secs,
+        }
+    }
+
+    fn quote(source: &DataSourceConfig, price: i64, timestamp: DateTime<Utc>) -> MarketData {
+        MarketData {
+            symbol: "AAPL".to_string(),
+            timestamp,
+            price: Decimal::from(price),
+            volume: 100,
+            bid: None,
+            ask: None,
+            bid_size: None,
+            ask_size: None,
+            source: source.source(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_quotes_are_tagged_and_staleness_tracked_per_source() {
+        let primary = source("moomoo", 5);
+        let backup = source("polygon", 5);
+        let book = QuoteBook::with_sources(vec![primary.clone(), backup.clone()]);
+        let now = Utc::now();
+
+        book.record(&quote(&primary, 100, now - Duration::seconds(1))).await;
+        book.record(&quote(&backup, 101, now)).await;
+        assert_ne!(primary.source(), backup.source());
+
+        // The primary is preferred while fresh, even with a newer backup quote
+        let latest = book.latest_at("AAPL", now).await.unwrap();
+        assert_eq!(latest.source, primary.source());
+        assert_eq!(latest.price, Decimal::from(100));
+        assert_eq!(book.active_source(now).await, Some(primary.source()));
+
+        // Once the primary goes quiet the backup takes over
+        let later = now + Duration::seconds(5);
+        assert!(!book.is_source_fresh(&primary.source(), later).await);
+        assert!(book.is_source_fresh(&backup.source(), later).await);
+        assert_eq!(book.active_source(later).await, Some(backup.source()));
+        let latest = book.latest_at("AAPL", later).await.unwrap();
+        assert_eq!(latest.source, backup.source());
+        assert!(book.is_fresh("AAPL", later, 5).await);
+    }
+}
@@ -0,0 +1,176 @@
+//! Historical market data replay for backtests and simulations
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio::time::Duration;
+
+use crate::core::config::ReplayConfig;
+use crate::core::errors::{TradingError, TradingResult};
+use crate::core::types::MarketData;
+
+/// How fast recorded bars are re-emitted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ReplaySpeed {
+    /// Emit every bar immediately (fast backtests)
+    #[default]
+    Instant,
+    /// Preserve the original spacing between bars
+    RealTime,
+    /// Compress the original spacing by the given factor
+    Multiplier(f64),
+}
+
+impl ReplaySpeed {
+    /// Wall-clock delay before emitting a bar that was recorded `gap` after the previous one
+    pub fn pace(&self, gap: Duration) -> Duration {
+        match self {
+            ReplaySpeed::Instant => Duration::ZERO,
+            ReplaySpeed::RealTime => gap,
+            // Checked, since a tiny factor would overflow the stretched gap
+            ReplaySpeed::Multiplier(factor) if *factor > 0.0 => {
+                Duration::try_from_secs_f64(gap.as_secs_f64() / factor).unwrap_or(Duration::MAX)
+            }
+            ReplaySpeed::Multiplier(_) => Duration::ZERO,
+        }
+    }
+}
+
+/// Source of delays for paced replay, replaceable in tests
+#[async_trait]
+pub trait ReplayClock: Send + Sync {
+    async fn sleep(&self, duration: Duration);
+}
+
+/// Clock backed by the tokio timer
+pub struct TokioClock;
+
+#[async_trait]
+impl ReplayClock for TokioClock {
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await;
+    }
+}
+
+/// Replays recorded bars into the live pipeline at a configured speed
+pub struct MarketReplay {
+    bars: Vec<MarketData>,
+    speed: ReplaySpeed,
+}
+
+impl MarketReplay {
+    /// Create a replay of `bars`, which are emitted in timestamp order
+    pub fn new(mut bars: Vec<MarketData>, config: &ReplayConfig) -> Self {
+        bars.sort_by_key(|bar| bar.timestamp);
+        Self {
+            bars,
+            speed: config.speed,
+        }
+    }
+
+    /// Emit every bar to `sender`, pacing with `clock`. Returns the number of bars emitted.
+    pub async fn run<C: ReplayClock>(self, clock: &C, sender: mpsc::UnboundedSender<MarketData>) -> TradingResult<usize> {
+        let mut previous: Option<DateTime<Utc>> = None;
+        let mut emitted = 0;
+
+        for bar in self.bars {
+            if let Some(previous) = previous {
+                let gap = (bar.timestamp - previous).to_std().unwrap_or(Duration::ZERO);
+                let delay = self.speed.pace(gap);
+                if !delay.is_zero() {
+                    clock.sleep(delay).await;
+                }
+            }
+            previous = Some(bar.timestamp);
+
+            sender
+                .send(bar)
+                .map_err(|_| TradingError::market_data("Replay receiver closed"))?;
+            emitted += 1;
+        }
+
+        Ok(emitted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use std::sync::Mutex;
+
+    /// Clock that records requested sleeps instead of waiting
+    #[derive(Default)]
+    struct MockClock {
+        sleeps: Mutex<Vec<Duration>>,
+    }
+
+    #[async_trait]
+    impl ReplayClock for MockClock {
+        async fn sleep(&self, duration: Duration) {
+            self.sleeps.lock().unwrap().push(duration);
+        }
+    }
+
+    fn bars() -> Vec<MarketData> {
+        let start = Utc::now();
+        [0, 60, 180]
+            .iter()
+            .map(|offset| MarketData {
+                symbol: "AAPL".to_string(),
+                timestamp: start + chrono::Duration::seconds(*offset),
+                price: Decimal::from(150),
+                volume: 100,
+                bid: None,
+                ask: None,
+                bid_size: None,
+                ask_size: None,
+                source: Default::default(),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_instant_mode_emits_without_pacing() {
+        let clock = MockClock::default();
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let replay = MarketReplay::new(bars(), &ReplayConfig { speed: ReplaySpeed::Instant });
+
+        assert_eq!(replay.run(&clock, sender).await.unwrap(), 3);
+        assert!(clock.sleeps.lock().unwrap().is_empty());
+
+        let mut received = 0;
+        while receiver.try_recv().is_ok() {
+            received += 1;
+        }
+        assert_eq!(received, 3);
+    }
+
+    #[tokio::test]
+    async fn test_multiplier_mode_paces_proportionally() {
+        let clock = MockClock::default();
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let replay = MarketReplay::new(bars(), &ReplayConfig { speed: ReplaySpeed::Multiplier(10.0) });
+
+        replay.run(&clock, sender).await.unwrap();
+        assert_eq!(
+            *clock.sleeps.lock().unwrap(),
+            vec![Duration::from_secs(6), Duration::from_secs(12)]
+        );
+    }
+
+    #[test]
+    fn test_unusable_multipliers_are_refused_and_never_panic() {
+        for factor in [0.0, -2.0, f64::NAN, f64::INFINITY] {
+            let mut config = crate::core::config::SystemConfig::default();
+            config.replay.speed = ReplaySpeed::Multiplier(factor);
+            let error = config.validate().unwrap_err().to_string();
+            assert!(error.contains("Replay speed multiplier"), "{}", error);
+        }
+
+        let gap = Duration::from_secs(60);
+        assert_eq!(ReplaySpeed::Multiplier(f64::NAN).pace(gap), Duration::ZERO);
+        assert_eq!(ReplaySpeed::Multiplier(1e-300).pace(gap), Duration::MAX);
+    }
+}
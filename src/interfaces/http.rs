@@ -0,0 +1,174 @@
+//! HTTP API exposing read-only system state
+
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{header, Body, Method, Request, Response, Server, StatusCode};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::agents::change_log::ConfigChangeLog;
+use crate::core::config::HttpApiConfig;
+use crate::core::errors::{TradingError, TradingResult};
+use crate::core::heartbeat::{AgentLiveness, HeartbeatMonitor};
+use crate::core::types::{AgentId, SystemContext, SystemHealth};
+
+/// HTTP API server for dashboards and external tooling
+pub struct HttpApiServer {
+    config: HttpApiConfig,
+    system_context: Arc<RwLock<SystemContext>>,
+    change_log: Option<ConfigChangeLog>,
+    heartbeats: Option<HeartbeatMonitor>,
+    degraded_agents: Vec<String>,
+}
+
+/// Overall health and the liveness of each agent, served at `/health`
+#[derive(Debug, serde::Serialize)]
+struct HealthReport {
+    status: SystemHealth,
+    agents: Vec<AgentLiveness>,
+    degraded_agents: Vec<String>, // Skipped at startup; the status is at best Degraded while any are
+}
+
+impl HttpApiServer {
+    /// Create a new HTTP API server
+    pub fn new(config: HttpApiConfig, system_context: Arc<RwLock<SystemContext>>) -> Self {
+        Self {
+            config,
+            system_context,
+            change_log: None,
+            heartbeats: None,
+            degraded_agents: Vec::new(),
+        }
+    }
+
+    /// Serve the history of evolved configuration changes
+    pub fn with_change_log(mut self, change_log: ConfigChangeLog) -> Self {
+        self.change_log = Some(change_log);
+        self
+    }
+
+    /// Report each agent's liveness from the given heartbeats
+    pub fn with_heartbeats(mut self, heartbeats: HeartbeatMonitor) -> Self {
+        self.heartbeats = Some(heartbeats);
+        self
+    }
+
+    /// Report agents that were skipped because they failed to start
+    pub fn with_degraded_agents(mut self, degraded_agents: Vec<String>) -> Self {
+        self.degraded_agents = degraded_agents;
+        self
+    }
+
+    /// Bind the configured address and serve requests until the server stops
+    pub async fn serve(self) -> TradingResult<()> {
+        let addr: SocketAddr = format!("{}:{}", self.config.bind_address, self.config.port)
+            .parse()
+            .map_err(|e| TradingError::interface(format!("Invalid HTTP bind address: {}", e)))?;
+
+        let system_context = self.system_context.clone();
+        let change_log = self.change_log.clone();
+        let heartbeats = self.heartbeats.clone();
+        let degraded_agents = Arc::new(self.degraded_agents.clone());
+        let make_service = make_service_fn(move |_conn| {
+            let system_context = system_context.clone();
+            let change_log = change_log.clone();
+            let heartbeats = heartbeats.clone();
+            let degraded_agents = degraded_agents.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req| {
+                    handle_request(req, system_context.clone(), change_log.clone(), heartbeats.clone(), degraded_agents.clone())
+                }))
+            }
+        });
+
+        let server = Server::try_bind(&addr)
+            .map_err(|e| TradingError::interface(format!("Failed to bind {}: {}", addr, e)))?
+            .serve(make_service);
+
+        info!("🌐 HTTP API listening on http://{}", addr);
+
+        server
+            .await
+            .map_err(|e| TradingError::interface(format!("HTTP server error: {}", e)))
+    }
+}
+
+/// Route an incoming request to its handler
+async fn handle_request(
+    req: Request<Body>,
+    system_context: Arc<RwLock<SystemContext>>,
+    change_log: Option<ConfigChangeLog>,
+    heartbeats: Option<HeartbeatMonitor>,
+    degraded_agents: Arc<Vec<String>>,
+) -> Result<Response<Body>, Infallible> {
+    let response = match (req.method(), req.uri().path()) {
+        (&Method::GET, "/equity") | (&Method::GET, "/equity.csv") => {
+            let context = system_context.read().await;
+            if req.uri().path().ends_with(".csv") || wants_csv(req.uri().query()) {
+                text_response("text/csv", context.equity_curve.to_csv())
+            } else {
+                json_response(&context.equity_curve.points())
+            }
+        }
+        (&Method::GET, "/config/changes") => match (&change_log, agent_filter(req.uri().query())) {
+            (Some(log), Ok(agent)) => json_response(&log.entries(agent).await),
+            (Some(_), Err(())) => status_response(StatusCode::BAD_REQUEST),
+            (None, _) => status_response(StatusCode::NOT_FOUND),
+        },
+        (&Method::GET, "/health") => {
+            let mut status = system_context.read().await.system_health.clone();
+            if !degraded_agents.is_empty() {
+                status = status.max(SystemHealth::Degraded);
+            }
+            let agents = heartbeats.map(|heartbeats| heartbeats.statuses(chrono::Utc::now())).unwrap_or_default();
+            json_response(&HealthReport { status, agents, degraded_agents: degraded_agents.to_vec() })
+        }
+        _ => status_response(StatusCode::NOT_FOUND),
+    };
+
+    Ok(response)
+}
+
+/// Check whether the query string asks for CSV output
+fn wants_csv(query: Option<&str>) -> bool {
+    query
+        .map(|q| q.split('&').any(|pair| pair == "format=csv"))
+        .unwrap_or(false)
+}
+
+/// Parse an optional `agent=<id>` filter from the query string
+fn agent_filter(query: Option<&str>) -> Result<Option<AgentId>, ()> {
+    let value = query
+        .into_iter()
+        .flat_map(|q| q.split('&'))
+        .find_map(|pair| pair.strip_prefix("agent="));
+    match value {
+        Some(id) => id.parse().map(Some).map_err(|_| ()),
+        None => Ok(None),
+    }
+}
+
+/// Build a JSON response from any serializable value
+fn json_response<T: serde::Serialize>(value: &T) -> Response<Body> {
+    match serde_json::to_string(value) {
+        Ok(body) => text_response("application/json", body),
+        Err(_) => status_response(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// Build a 200 response with the given content type
+fn text_response(content_type: &str, body: String) -> Response<Body> {
+    Response::builder()
+        .header(header::CONTENT_TYPE, content_type)
+        .body(Body::from(body))
+        .unwrap_or_else(|_| status_response(StatusCode::INTERNAL_SERVER_ERROR))
+}
+
+/// Build an empty response with the given status
+fn status_response(status: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = status;
+    response
+}
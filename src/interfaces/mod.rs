@@ -1,5 +1,8 @@
 //! Interfaces module - User interfaces and external APIs
 
+pub mod http;
+pub mod ws;
+
 /// Placeholder for interfaces module
 pub struct InterfaceManager;
 
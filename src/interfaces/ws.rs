@@ -0,0 +1,207 @@
+//! WebSocket stream of AI thoughts for browser dashboards
+//!
+//! Each client is subscribed to the thought broadcaster through its own
+//! filtered channel. A client that falls too far behind is disconnected
+//! rather than slowing the broadcaster down.
+
+use futures::{SinkExt, StreamExt};
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast::error::RecvError;
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{info, warn};
+
+use crate::core::ai_thoughts::{AIThought, AIThoughtBroadcaster, ThoughtFilter};
+use crate::core::config::ThoughtStreamConfig;
+use crate::core::errors::{TradingError, TradingResult};
+
+/// WebSocket server forwarding AI thoughts to connected clients as JSON frames
+pub struct ThoughtStreamServer {
+    config: ThoughtStreamConfig,
+    broadcaster: AIThoughtBroadcaster,
+}
+
+impl ThoughtStreamServer {
+    /// Create a new thought stream server
+    pub fn new(config: ThoughtStreamConfig, broadcaster: AIThoughtBroadcaster) -> Self {
+        Self { config, broadcaster }
+    }
+
+    /// Bind the configured address and serve clients until the server stops
+    pub async fn serve(self) -> TradingResult<()> {
+        let addr: SocketAddr = format!("{}:{}", self.config.bind_address, self.config.port)
+            .parse()
+            .map_err(|e| TradingError::interface(format!("Invalid WebSocket bind address: {}", e)))?;
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|e| TradingError::interface(format!("Failed to bind {}: {}", addr, e)))?;
+
+        self.serve_listener(listener).await
+    }
+
+    /// Serve clients accepted from an already bound listener
+    pub async fn serve_listener(self, listener: TcpListener) -> TradingResult<()> {
+        info!("📡 AI thought stream listening on ws://{}", listener.local_addr()?);
+
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let broadcaster = self.broadcaster.clone();
+            let history = self.config.history_on_connect;
+            tokio::spawn(async move {
+                if let Err(e) = serve_client(stream, broadcaster, history).await {
+                    warn!("📡 Thought stream client {} failed: {}", peer, e);
+                }
+            });
+        }
+    }
+}
+
+/// Complete the handshake, replay recent history, then forward live thoughts
+async fn serve_client(stream: TcpStream, broadcaster: AIThoughtBroadcaster, history: usize) -> TradingResult<()> {
+    let mut filter = None;
+    let socket = tokio_tungstenite::accept_hdr_async(stream, |request: &Request, response: Response| {
+        match parse_filter(request.uri().query()) {
+            Ok(parsed) => {
+                filter = Some(parsed);
+                Ok(response)
+            }
+            Err(reason) => {
+                let mut error = ErrorResponse::new(Some(reason));
+                *error.status_mut() = StatusCode::BAD_REQUEST;
+                Err(error)
+            }
+        }
+    })
+    .await?;
+    let filter = filter.unwrap_or_default();
+    let (mut sink, mut incoming) = socket.split();
+
+    // Subscribe before reading history so nothing falls between the two
+    let mut thoughts = broadcaster.subscribe_filtered(filter.clone());
+    let recent: Vec<AIThought> = broadcaster
+        .get_recent_thoughts(usize::MAX)
+        .await
+        .into_iter()
+        .filter(|thought| filter.accepts(thought))
+        .collect();
+    let recent = &recent[recent.len().saturating_sub(history)..];
+    let replayed: HashSet<String> = recent.iter().map(|thought| thought.id.clone()).collect();
+    for thought in recent {
+        sink.send(Message::Text(serde_json::to_string(thought)?)).await?;
+    }
+
+    loop {
+        tokio::select! {
+            thought = thoughts.recv() => match thought {
+                Ok(thought) if replayed.contains(&thought.id) => continue,
+                Ok(thought) => sink.send(Message::Text(serde_json::to_string(&thought)?)).await?,
+                Err(RecvError::Lagged(missed)) => {
+                    warn!("📡 Dropping thought stream client {} thoughts behind", missed);
+                    let close = CloseFrame {
+                        code: CloseCode::Again,
+                        reason: format!("Client fell {} thoughts behind", missed).into(),
+                    };
+                    let _ = sink.send(Message::Close(Some(close))).await;
+                    return Ok(());
+                }
+                Err(RecvError::Closed) => return Ok(()),
+            },
+            message = incoming.next() => match message {
+                Some(Ok(Message::Close(_))) | None => return Ok(()),
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e.into()),
+            },
+        }
+    }
+}
+
+/// Build a filter from `agent`, `type`, `min_confidence` and `educational`
+/// query parameters. Agent and type may repeat or be comma separated.
+fn parse_filter(query: Option<&str>) -> Result<ThoughtFilter, String> {
+    let mut filter = ThoughtFilter::default();
+    for pair in query.into_iter().flat_map(|q| q.split('&')).filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        match key {
+            "agent" => {
+                for agent in value.split(',') {
+                    filter.agents.insert(parse_variant(agent)?);
+                }
+            }
+            "type" => {
+                for thought_type in value.split(',') {
+                    filter.thought_types.insert(parse_variant(thought_type)?);
+                }
+            }
+            "min_confidence" => {
+                filter.min_confidence = value
+                    .parse()
+                    .map_err(|_| format!("Invalid min_confidence '{}'", value))?;
+            }
+            "educational" => filter.educational_only = value == "true",
+            _ => return Err(format!("Unknown query parameter '{}'", key)),
+        }
+    }
+    Ok(filter)
+}
+
+/// Parse a unit enum variant by name, e.g. `RiskManager`
+fn parse_variant<T: serde::de::DeserializeOwned>(name: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(name.to_string()))
+        .map_err(|_| format!("Unknown value '{}'", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ai_thoughts::{AIAgent, ThoughtType};
+
+    fn thought(agent: AIAgent, confidence: f64, message: &str) -> AIThought {
+        AIThought::new(agent, ThoughtType::RiskCheck, message.to_string(), confidence)
+    }
+
+    async fn next_message(
+        socket: &mut tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<TcpStream>>,
+    ) -> String {
+        let frame = tokio::time::timeout(std::time::Duration::from_secs(5), socket.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let thought: AIThought = serde_json::from_str(frame.to_text().unwrap()).unwrap();
+        thought.message
+    }
+
+    #[tokio::test]
+    async fn test_client_receives_filtered_history_then_live_thoughts() {
+        let broadcaster = AIThoughtBroadcaster::new(100);
+        for i in 0..3 {
+            broadcaster.broadcast_thought(thought(AIAgent::RiskManager, 0.9, &format!("old {}", i))).await;
+        }
+        broadcaster.broadcast_thought(thought(AIAgent::ExecutionEngine, 0.9, "other agent")).await;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let config = ThoughtStreamConfig { history_on_connect: 2, ..ThoughtStreamConfig::default() };
+        tokio::spawn(ThoughtStreamServer::new(config, broadcaster.clone()).serve_listener(listener));
+
+        let url = format!("ws://{}/?agent=RiskManager&min_confidence=0.5", addr);
+        let (mut socket, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+
+        // The last two matching thoughts are replayed on connect
+        assert_eq!(next_message(&mut socket).await, "old 1");
+        assert_eq!(next_message(&mut socket).await, "old 2");
+
+        broadcaster.broadcast_thought(thought(AIAgent::RiskManager, 0.2, "unsure")).await;
+        broadcaster.broadcast_thought(thought(AIAgent::RiskManager, 0.8, "live")).await;
+        assert_eq!(next_message(&mut socket).await, "live");
+
+        // Unknown filter values are refused at the handshake
+        let bad = format!("ws://{}/?agent=Nobody", addr);
+        assert!(tokio_tungstenite::connect_async(bad).await.is_err());
+    }
+}
@@ -5,7 +5,7 @@
 
 use anyhow::Result;
 use tokio::signal;
-use tracing::{info, error};
+use tracing::{info, warn, error};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 mod agents;
@@ -21,9 +21,22 @@ mod vector_store;
 
 use crate::core::system::TradingSystem;
 use crate::core::config::SystemConfig;
+use crate::core::runtime::build_runtime;
+
+fn main() -> Result<()> {
+    // Configuration is read on a small runtime so `[runtime]` can size the real one
+    let bootstrap = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+
+    // `--check-config [path]` validates the config and exits without starting anything
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(position) = args.iter().position(|arg| arg == "--check-config") {
+        let path = args
+            .get(position + 1)
+            .filter(|arg| !arg.starts_with("--"))
+            .map_or("config.toml", String::as_str);
+        std::process::exit(bootstrap.block_on(check_config(path)));
+    }
 
-#[tokio::main]
-async fn main() -> Result<()> {
     // Initialize logging and tracing
     tracing_subscriber::registry()
         .with(
@@ -39,15 +52,35 @@ async fn main() -> Result<()> {
 
     // Load system configuration
     info!("📁 Loading configuration from config.toml...");
-    let config = SystemConfig::load().await?;
+    let config = bootstrap.block_on(SystemConfig::load())?;
+    drop(bootstrap);
     info!("⚙️  Configuration loaded successfully");
     info!("🔑 API Key: {} (length: {})", if config.api.moomoo.api_key.is_empty() { "EMPTY" } else { &config.api.moomoo.api_key }, config.api.moomoo.api_key.len());
     info!("📄 Paper Trading: {}", config.api.moomoo.paper_trading);
 
+    let runtime = build_runtime(&config.runtime)?;
+    info!("🧵 Runtime started with {} worker threads", runtime.metrics().num_workers());
+    runtime.block_on(run(config))
+}
+
+/// Start the trading system and run it until it completes or is interrupted
+async fn run(config: SystemConfig) -> Result<()> {
+    let hot_reload = config.hot_reload.enabled;
+
     // Initialize the trading system
     let mut trading_system = TradingSystem::new(config).await?;
     info!("🤖 Trading system initialized");
 
+    if hot_reload {
+        match SystemConfig::watch("config.toml").await {
+            Ok(updates) => {
+                trading_system.watch_config(updates);
+                info!("👀 Watching config.toml for changes");
+            }
+            Err(e) => warn!("⚠️  Config hot reload unavailable: {}", e),
+        }
+    }
+
     // Start all agents and services
     trading_system.start().await?;
     info!("🎯 All agents started - system is operational");
@@ -77,3 +110,26 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Load and validate a config file and print what it would run, returning the exit code
+async fn check_config(path: &str) -> i32 {
+    let config = match SystemConfig::load_from_file(path).await {
+        Ok(mut config) => {
+            config.apply_env_overrides();
+            config
+        }
+        Err(e) => {
+            eprintln!("❌ Cannot load {}: {}", path, e);
+            return 1;
+        }
+    };
+
+    if let Err(e) = config.validate() {
+        eprintln!("❌ Invalid configuration in {}: {}", path, e);
+        return 1;
+    }
+
+    println!("{}", config.summary());
+    println!("✅ {} is valid", path);
+    0
+}
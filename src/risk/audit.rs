@@ -0,0 +1,95 @@
+//! Append-only audit trail of risk decisions
+//!
+//! Each trade validation is written as one JSON line together with the signal
+//! that triggered it and the portfolio state it was judged against.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+use crate::agents::traits::RiskValidation;
+use crate::core::errors::TradingResult;
+use crate::core::types::{SystemContext, TradingSignal};
+
+/// Portfolio state a risk decision was made against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditContext {
+    pub total_value: Decimal,
+    pub cash_balance: Decimal,
+    pub daily_pnl: Decimal,
+    pub active_positions: u32,
+    pub portfolio_heat: f64,
+}
+
+impl From<&SystemContext> for AuditContext {
+    fn from(context: &SystemContext) -> Self {
+        Self {
+            total_value: context.portfolio.total_value,
+            cash_balance: context.portfolio.cash_balance,
+            daily_pnl: context.portfolio.daily_pnl,
+            active_positions: context.active_positions,
+            portfolio_heat: context.risk_metrics.portfolio_heat,
+        }
+    }
+}
+
+/// One audited risk decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskAuditRecord {
+    pub timestamp: DateTime<Utc>,
+    pub signal: TradingSignal,
+    pub validation: RiskValidation,
+    pub context: AuditContext,
+}
+
+/// JSONL sink for risk decisions
+#[derive(Debug, Clone)]
+pub struct RiskAuditLog {
+    path: PathBuf,
+    write_lock: Arc<Mutex<()>>,
+}
+
+impl RiskAuditLog {
+    /// Append audit records to the file at `path`, creating it if needed
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            write_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Persist a risk decision
+    pub async fn record(
+        &self,
+        signal: &TradingSignal,
+        validation: &RiskValidation,
+        context: &SystemContext,
+    ) -> TradingResult<()> {
+        let record = RiskAuditRecord {
+            timestamp: Utc::now(),
+            signal: signal.clone(),
+            validation: validation.clone(),
+            context: AuditContext::from(context),
+        };
+        let mut line = serde_json::to_string(&record)?;
+        line.push('\n');
+
+        // Serialize writers so concurrent validations never interleave lines
+        let _guard = self.write_lock.lock().await;
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+        Ok(())
+    }
+}
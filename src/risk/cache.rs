@@ -0,0 +1,47 @@
+//! Short-lived cache of computed portfolio risk
+//!
+//! A burst of signals would otherwise recompute the same portfolio risk for
+//! each validation. Metrics are reused until the TTL lapses or a fill changes
+//! the portfolio.
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tokio::time::{Duration, Instant};
+
+use crate::core::config::RiskCacheConfig;
+use crate::core::types::RiskMetrics;
+
+/// Most recent risk metrics and when they were computed, shared across clones
+#[derive(Debug, Clone)]
+pub struct RiskMetricsCache {
+    ttl: Option<Duration>, // None disables caching
+    entry: Arc<RwLock<Option<(Instant, RiskMetrics)>>>,
+}
+
+impl RiskMetricsCache {
+    pub fn from_config(config: &RiskCacheConfig) -> Self {
+        Self {
+            ttl: config.enabled.then(|| Duration::from_millis(config.ttl_ms)),
+            entry: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Metrics computed within the TTL, if any
+    pub async fn get(&self) -> Option<RiskMetrics> {
+        let ttl = self.ttl?;
+        let entry = self.entry.read().await;
+        let (computed_at, metrics) = entry.as_ref()?;
+        (computed_at.elapsed() < ttl).then(|| metrics.clone())
+    }
+
+    pub async fn store(&self, metrics: &RiskMetrics) {
+        if self.ttl.is_some() {
+            *self.entry.write().await = Some((Instant::now(), metrics.clone()));
+        }
+    }
+
+    /// Drop the cached metrics, e.g. after a fill changed the portfolio
+    pub async fn invalidate(&self) {
+        *self.entry.write().await = None;
+    }
+}
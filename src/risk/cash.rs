@@ -0,0 +1,96 @@
+//! Minimum free-cash buffer
+//!
+//! Keeps part of the account uninvested so fees and slippage can always be
+//! covered, which matters most on small accounts.
+
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+
+use crate::core::config::CashBufferConfig;
+use crate::core::types::Portfolio;
+
+/// Outcome of checking an order against the cash buffer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CashCheck {
+    /// The order leaves the buffer intact
+    Approved,
+    /// The order fits only if its size is multiplied by this factor (0 < factor < 1)
+    Scaled(Decimal),
+    /// No cash is available above the buffer
+    Rejected,
+}
+
+/// Reserves cash that orders may not consume
+#[derive(Debug, Clone)]
+pub struct CashBuffer {
+    min_cash: Decimal,
+    min_cash_pct: Decimal,
+}
+
+impl CashBuffer {
+    /// Create a buffer from configuration
+    pub fn from_config(config: &CashBufferConfig) -> Self {
+        Self {
+            min_cash: config.min_cash,
+            min_cash_pct: Decimal::from_f64(config.min_cash_pct).unwrap_or(Decimal::ZERO),
+        }
+    }
+
+    /// Cash that must stay free: the larger of the absolute floor and the share of equity
+    pub fn reserve(&self, portfolio: &Portfolio) -> Decimal {
+        let pct_reserve = portfolio
+            .total_value
+            .checked_mul(self.min_cash_pct)
+            .unwrap_or(Decimal::MAX);
+        self.min_cash.max(pct_reserve)
+    }
+
+    /// Cash available to new orders without dipping into the buffer
+    pub fn available(&self, portfolio: &Portfolio) -> Decimal {
+        portfolio
+            .cash_balance
+            .checked_sub(self.reserve(portfolio))
+            .unwrap_or(Decimal::ZERO)
+            .max(Decimal::ZERO)
+    }
+
+    /// Check whether an order costing `order_value` fits above the buffer
+    pub fn check(&self, portfolio: &Portfolio, order_value: Decimal) -> CashCheck {
+        let available = self.available(portfolio);
+        if order_value <= available {
+            CashCheck::Approved
+        } else if available.is_zero() {
+            CashCheck::Rejected
+        } else {
+            CashCheck::Scaled(available / order_value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::SystemConfig;
+    use crate::core::types::SystemContext;
+
+    #[test]
+    fn test_orders_may_not_consume_the_buffer() {
+        let buffer = CashBuffer::from_config(&CashBufferConfig {
+            min_cash: Decimal::from(2),
+            min_cash_pct: 0.05,
+        });
+        let mut portfolio = SystemContext::initial(&SystemConfig::default()).portfolio;
+
+        // $100 account: reserve max($2, 5% of $100) = $5, leaving $95
+        assert_eq!(buffer.reserve(&portfolio), Decimal::from(5));
+        assert_eq!(buffer.check(&portfolio, Decimal::from(90)), CashCheck::Approved);
+        assert_eq!(buffer.check(&portfolio, Decimal::from(95)), CashCheck::Approved);
+        assert_eq!(
+            buffer.check(&portfolio, Decimal::from(190)),
+            CashCheck::Scaled(Decimal::new(5, 1))
+        );
+
+        portfolio.cash_balance = Decimal::from(4);
+        assert_eq!(buffer.check(&portfolio, Decimal::from(1)), CashCheck::Rejected);
+    }
+}
@@ -0,0 +1,127 @@
+//! Intraday drawdown circuit breaker
+//!
+//! The breaker trips open when equity falls `circuit_breaker_threshold` below
+//! the day's high, halting new trades for a cooldown. It then moves to
+//! half-open, allowing reduced-size trades for another cooldown measured from
+//! a fresh equity high, and closes if the drawdown doesn't recur.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Where the breaker is in its trip and recovery cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BreakerState {
+    /// Trading normally
+    Closed,
+    /// Tripped: every new trade is rejected until `until`
+    Open { until: DateTime<Utc> },
+    /// On probation until `until`: trades allowed at reduced size
+    HalfOpen { until: DateTime<Utc> },
+}
+
+/// Tracks intraday drawdown against the circuit breaker threshold
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    cooldown: Duration,
+    state: BreakerState,
+    day: Option<NaiveDate>,
+    peak: Decimal,
+}
+
+impl CircuitBreaker {
+    /// Create a closed breaker that stays open for `cooldown_secs` once tripped
+    pub fn new(cooldown_secs: u64) -> Self {
+        Self {
+            cooldown: Duration::seconds(cooldown_secs as i64),
+            state: BreakerState::Closed,
+            day: None,
+            peak: Decimal::ZERO,
+        }
+    }
+
+    pub fn state(&self) -> BreakerState {
+        self.state
+    }
+
+    /// Observe equity on trading day `day`, returning the drawdown that
+    /// tripped the breaker if this observation opened it
+    pub fn observe(&mut self, day: NaiveDate, equity: Decimal, threshold: Decimal, now: DateTime<Utc>) -> Option<Decimal> {
+        // The intraday high starts over each trading day
+        if self.day != Some(day) {
+            self.day = Some(day);
+            self.peak = equity;
+        }
+        self.peak = self.peak.max(equity);
+
+        match self.state {
+            BreakerState::Open { until } if now >= until => {
+                self.state = BreakerState::HalfOpen { until: now + self.cooldown };
+                self.peak = equity;
+                None
+            }
+            BreakerState::Open { .. } => None,
+            BreakerState::HalfOpen { until } if now >= until && !self.breached(equity, threshold) => {
+                self.state = BreakerState::Closed;
+                None
+            }
+            BreakerState::Closed | BreakerState::HalfOpen { .. } => {
+                if !self.breached(equity, threshold) {
+                    return None;
+                }
+                self.state = BreakerState::Open { until: now + self.cooldown };
+                Some(self.drawdown(equity))
+            }
+        }
+    }
+
+    fn breached(&self, equity: Decimal, threshold: Decimal) -> bool {
+        threshold > Decimal::ZERO && self.drawdown(equity) >= threshold
+    }
+
+    /// Fall from the intraday high as a fraction of it
+    fn drawdown(&self, equity: Decimal) -> Decimal {
+        if self.peak <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+        (self.peak - equity) / self.peak
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trips_open_then_half_opens_then_closes() {
+        let mut breaker = CircuitBreaker::new(60);
+        let threshold = Decimal::new(5, 2);
+        let day = NaiveDate::from_ymd_opt(2024, 3, 12).unwrap();
+        let start = Utc::now();
+        let at = |secs: i64| start + Duration::seconds(secs);
+
+        // A 4% fall from the 100 high stays closed; 6% trips it
+        assert_eq!(breaker.observe(day, Decimal::from(100), threshold, at(0)), None);
+        assert_eq!(breaker.observe(day, Decimal::from(96), threshold, at(1)), None);
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        assert_eq!(breaker.observe(day, Decimal::from(94), threshold, at(2)), Some(Decimal::new(6, 2)));
+        assert_eq!(breaker.state(), BreakerState::Open { until: at(62) });
+
+        // Stays open through the cooldown, then half-opens from a fresh high
+        assert_eq!(breaker.observe(day, Decimal::from(93), threshold, at(30)), None);
+        assert!(matches!(breaker.state(), BreakerState::Open { .. }));
+        assert_eq!(breaker.observe(day, Decimal::from(93), threshold, at(62)), None);
+        assert_eq!(breaker.state(), BreakerState::HalfOpen { until: at(122) });
+
+        // A quiet probation closes it
+        assert_eq!(breaker.observe(day, Decimal::from(92), threshold, at(90)), None);
+        assert_eq!(breaker.observe(day, Decimal::from(92), threshold, at(122)), None);
+        assert_eq!(breaker.state(), BreakerState::Closed);
+
+        // A fresh drawdown during probation trips it again
+        let mut probation = breaker.clone();
+        probation.state = BreakerState::HalfOpen { until: at(200) };
+        assert!(probation.observe(day, Decimal::from(80), threshold, at(150)).is_some());
+        assert!(matches!(probation.state(), BreakerState::Open { .. }));
+    }
+}
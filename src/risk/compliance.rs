@@ -0,0 +1,144 @@
+//! Pre-trade compliance checks
+//!
+//! Each check may veto an order with a reason before it reaches the venue,
+//! e.g. because the symbol is on a restricted list.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::core::errors::TradingResult;
+use crate::core::types::{Order, SystemContext, Symbol};
+
+/// Outcome of a compliance check
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComplianceDecision {
+    Allowed,
+    Vetoed(String),
+}
+
+/// A rule every new order must pass before it is sent
+pub trait ComplianceCheck: Send + Sync {
+    /// Short name used when reporting a veto
+    fn name(&self) -> &str;
+
+    /// Decide whether `order` may be placed given the current context
+    fn check(&self, order: &Order, context: &SystemContext) -> ComplianceDecision;
+}
+
+/// Symbols that may not be traded
+#[derive(Debug, Clone, Default)]
+pub struct RestrictedList {
+    symbols: HashSet<Symbol>,
+}
+
+impl RestrictedList {
+    /// Restrict the given symbols
+    pub fn new<I: IntoIterator<Item = Symbol>>(symbols: I) -> Self {
+        Self {
+            symbols: symbols.into_iter().map(|symbol| symbol.trim().to_uppercase()).collect(),
+        }
+    }
+
+    /// Read one symbol per line; blank lines and `#` comments are ignored
+    pub async fn load<P: AsRef<Path>>(path: P) -> TradingResult<Self> {
+        let contents = tokio::fs::read_to_string(path).await?;
+        Ok(Self::new(
+            contents
+                .lines()
+                .map(|line| line.split('#').next().unwrap_or("").trim())
+                .filter(|symbol| !symbol.is_empty())
+                .map(str::to_string),
+        ))
+    }
+
+    /// Number of restricted symbols
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Whether no symbols are restricted
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
+
+impl ComplianceCheck for RestrictedList {
+    fn name(&self) -> &str {
+        "restricted_list"
+    }
+
+    fn check(&self, order: &Order, _context: &SystemContext) -> ComplianceDecision {
+        if self.symbols.contains(&order.symbol.to_uppercase()) {
+            ComplianceDecision::Vetoed(format!("{} is on the restricted list", order.symbol))
+        } else {
+            ComplianceDecision::Allowed
+        }
+    }
+}
+
+/// The checks run, in order, before each new order
+#[derive(Clone, Default)]
+pub struct CompliancePipeline {
+    checks: Vec<Arc<dyn ComplianceCheck>>,
+}
+
+impl CompliancePipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a check to the pipeline
+    pub fn with_check<C: ComplianceCheck + 'static>(mut self, check: C) -> Self {
+        self.checks.push(Arc::new(check));
+        self
+    }
+
+    /// Run every check, returning the first veto as `(check name, reason)`
+    pub fn review(&self, order: &Order, context: &SystemContext) -> Option<(String, String)> {
+        self.checks.iter().find_map(|check| match check.check(order, context) {
+            ComplianceDecision::Allowed => None,
+            ComplianceDecision::Vetoed(reason) => Some((check.name().to_string(), reason)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::SystemConfig;
+    use crate::core::types::{OrderSide, OrderStatus, OrderType};
+    use rust_decimal::Decimal;
+
+    fn order(symbol: &str) -> Order {
+        Order {
+            id: uuid::Uuid::new_v4(),
+            symbol: symbol.to_string(),
+            order_type: OrderType::Market,
+            side: OrderSide::Buy,
+            quantity: Decimal::from(10),
+            price: None,
+            timestamp: chrono::Utc::now(),
+            status: OrderStatus::Pending,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restricted_symbol_is_vetoed_with_reason() {
+        let path = std::env::temp_dir().join(format!("restricted-{}.txt", uuid::Uuid::new_v4()));
+        tokio::fs::write(&path, "# Insider blackout\ntsla\n\nGME  # meme stock\n").await.unwrap();
+        let restricted = RestrictedList::load(&path).await.unwrap();
+        tokio::fs::remove_file(&path).await.unwrap();
+        assert_eq!(restricted.len(), 2);
+
+        let pipeline = CompliancePipeline::new().with_check(restricted);
+        let context = SystemContext::initial(&SystemConfig::default());
+
+        assert_eq!(
+            pipeline.review(&order("TSLA"), &context),
+            Some(("restricted_list".to_string(), "TSLA is on the restricted list".to_string()))
+        );
+        assert!(pipeline.review(&order("GME"), &context).is_some());
+        assert_eq!(pipeline.review(&order("AAPL"), &context), None);
+    }
+}
@@ -0,0 +1,122 @@
+//! Forced exits for positions whose unrealized loss exceeds the configured cap
+//!
+//! A safety net independent of stop orders: strategies that never set a stop
+//! still cannot let a single position bleed without bound.
+
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+use crate::core::config::UnrealizedLossConfig;
+use crate::core::types::{Order, OrderId, OrderSide, OrderStatus, OrderType, Portfolio, Position, Symbol};
+
+/// Checks open positions against absolute and percentage unrealized-loss caps
+#[derive(Debug, Clone)]
+pub struct UnrealizedLossMonitor {
+    max_loss: Option<Decimal>,
+    max_loss_pct: Option<Decimal>,
+}
+
+impl UnrealizedLossMonitor {
+    /// Create a monitor from configuration
+    pub fn from_config(config: &UnrealizedLossConfig) -> Self {
+        Self {
+            max_loss: config.max_loss,
+            max_loss_pct: config.max_loss_pct.and_then(Decimal::from_f64),
+        }
+    }
+
+    /// Whether a position's unrealized loss is beyond either cap
+    pub fn is_breached(&self, position: &Position) -> bool {
+        if position.unrealized_pnl >= Decimal::ZERO {
+            return false;
+        }
+        let loss = position.unrealized_pnl.abs();
+
+        if self.max_loss.is_some_and(|cap| loss > cap) {
+            return true;
+        }
+
+        // Percentage of cost basis; an unrepresentable basis is too large to breach
+        let cost_basis = position
+            .entry_price
+            .checked_mul(position.quantity.abs())
+            .unwrap_or(Decimal::MAX);
+        match self.max_loss_pct {
+            Some(cap) if !cost_basis.is_zero() => loss / cost_basis > cap,
+            _ => false,
+        }
+    }
+
+    /// Market orders closing every position whose loss is beyond a cap
+    pub fn exit_orders(&self, portfolio: &Portfolio) -> Vec<Order> {
+        let mut breached: Vec<&Position> = portfolio
+            .positions
+            .values()
+            .filter(|position| !position.quantity.is_zero() && self.is_breached(position))
+            .collect();
+        breached.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+
+        breached.into_iter().map(closing_order).collect()
+    }
+}
+
+/// Forced exits sent to execution and not yet filled or rejected, at most one
+/// per symbol so a breach isn't answered with a new market order every tick
+#[derive(Debug, Clone, Default)]
+pub struct PendingExits {
+    orders: HashMap<Symbol, OrderId>,
+}
+
+impl PendingExits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an exit about to be sent; false if one is already pending for its symbol
+    pub fn begin(&mut self, order: &Order) -> bool {
+        if self.orders.contains_key(&order.symbol) {
+            return false;
+        }
+        self.orders.insert(order.symbol.clone(), order.id);
+        true
+    }
+
+    /// Clear the exit with this order id once it has filled or been rejected
+    pub fn resolve(&mut self, order_id: OrderId) -> Option<Symbol> {
+        let symbol = self.orders.iter().find(|(_, id)| **id == order_id).map(|(symbol, _)| symbol.clone())?;
+        self.orders.remove(&symbol);
+        Some(symbol)
+    }
+
+    /// Drop exits for positions that are no longer held, e.g. filled after reconciliation
+    pub fn retain_held(&mut self, portfolio: &Portfolio) {
+        self.orders.retain(|symbol, _| {
+            portfolio.positions.get(symbol).is_some_and(|position| !position.quantity.is_zero())
+        });
+    }
+
+    pub fn is_pending(&self, symbol: &str) -> bool {
+        self.orders.contains_key(symbol)
+    }
+}
+
+/// Market order that flattens `position`
+fn closing_order(position: &Position) -> Order {
+    let side = if position.quantity > Decimal::ZERO {
+        OrderSide::Sell
+    } else {
+        OrderSide::Buy
+    };
+
+    Order {
+        id: uuid::Uuid::new_v4(),
+        symbol: position.symbol.clone(),
+        order_type: OrderType::Market,
+        side,
+        quantity: position.quantity.abs(),
+        price: None,
+        timestamp: chrono::Utc::now(),
+        status: OrderStatus::Pending,
+    }
+}
@@ -0,0 +1,158 @@
+//! Portfolio heat: capital at risk as a share of equity
+//!
+//! By default each open position contributes what it would lose if its stop
+//! were hit. Positions without a protective stop are assumed stopped
+//! `stop_distance_pct` from entry. Realized
+//! profit leaves heat unchanged, while each new risky position adds to it.
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::core::errors::{TradingError, TradingResult};
+use crate::core::types::{Portfolio, Position, Symbol};
+
+/// How portfolio heat is measured
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HeatMethod {
+    /// Loss if every open position were stopped out
+    #[default]
+    StopRisk,
+    /// Gross market value of open positions
+    GrossExposure,
+    /// Today's loss; a profitable day has no heat
+    DailyLoss,
+}
+
+/// Portfolio heat measurement settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioHeatConfig {
+    pub method: HeatMethod,
+    pub stop_distance_pct: Decimal, // Stop assumed below a long's (above a short's) entry when it has none, 0.05 for 5%
+}
+
+impl Default for PortfolioHeatConfig {
+    fn default() -> Self {
+        Self {
+            method: HeatMethod::StopRisk,
+            stop_distance_pct: Decimal::new(5, 2),
+        }
+    }
+}
+
+/// Capital at risk in `portfolio` as a fraction of its equity, from 0 to 1.
+/// `stops` holds the protective stop price of each position that has one.
+pub fn portfolio_heat(portfolio: &Portfolio, config: &PortfolioHeatConfig, stops: &HashMap<Symbol, Decimal>) -> TradingResult<f64> {
+    if portfolio.total_value <= Decimal::ZERO {
+        return Ok(0.0);
+    }
+
+    let at_risk = match config.method {
+        HeatMethod::StopRisk => portfolio
+            .positions
+            .values()
+            .map(|position| risk_to_stop(position, stops.get(&position.symbol).copied(), config.stop_distance_pct))
+            .sum::<TradingResult<Decimal>>()?,
+        HeatMethod::GrossExposure => portfolio
+            .positions
+            .values()
+            .map(|position| position.market_value().map(|value| value.abs()))
+            .sum::<TradingResult<Decimal>>()?,
+        HeatMethod::DailyLoss => (-portfolio.daily_pnl).max(Decimal::ZERO),
+    };
+
+    Ok((at_risk / portfolio.total_value).to_f64().unwrap_or(1.0).clamp(0.0, 1.0))
+}
+
+/// What `position` would lose from its current price at `stop`, or without
+/// one at a stop `stop_distance_pct` from entry; nothing once price is
+/// already through the stop
+fn risk_to_stop(position: &Position, stop: Option<Decimal>, stop_distance_pct: Decimal) -> TradingResult<Decimal> {
+    let stop_price = if let Some(stop) = stop {
+        stop
+    } else if position.quantity >= Decimal::ZERO {
+        position.entry_price * (Decimal::ONE - stop_distance_pct)
+    } else {
+        position.entry_price * (Decimal::ONE + stop_distance_pct)
+    };
+    let loss = (position.current_price - stop_price)
+        .checked_mul(position.quantity)
+        .ok_or_else(|| TradingError::overflow(format!("stop risk of {}", position.symbol)))?;
+    Ok(loss.max(Decimal::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn portfolio(total_value: i64) -> Portfolio {
+        Portfolio {
+            total_value: Decimal::from(total_value),
+            cash_balance: Decimal::from(total_value),
+            positions: Default::default(),
+            daily_pnl: Decimal::ZERO,
+            total_pnl: Decimal::ZERO,
+            max_drawdown: Decimal::ZERO,
+            sharpe_ratio: None,
+            last_updated: chrono::Utc::now(),
+            trading_day: None,
+        }
+    }
+
+    fn position(symbol: &str, quantity: i64, entry: i64, current: i64) -> Position {
+        Position {
+            id: uuid::Uuid::new_v4(),
+            symbol: symbol.to_string(),
+            quantity: Decimal::from(quantity),
+            entry_price: Decimal::from(entry),
+            current_price: Decimal::from(current),
+            unrealized_pnl: Decimal::ZERO,
+            realized_pnl: Decimal::ZERO,
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_open_risk_adds_heat_and_realized_profit_does_not() {
+        let config = PortfolioHeatConfig::default();
+        let mut book = portfolio(10_000);
+        assert_eq!(portfolio_heat(&book, &config, &HashMap::new()).unwrap(), 0.0);
+
+        // A realized profit alone carries no risk
+        book.daily_pnl = Decimal::from(500);
+        book.total_pnl = Decimal::from(500);
+        assert_eq!(portfolio_heat(&book, &config, &HashMap::new()).unwrap(), 0.0);
+
+        // 100 shares at 100 with a 5% stop put $500 of $10,000 at risk
+        book.positions.insert("AAPL".to_string(), position("AAPL", 100, 100, 100));
+        assert!((portfolio_heat(&book, &config, &HashMap::new()).unwrap() - 0.05).abs() < 1e-9);
+
+        // A second risky position adds its own: short 50 at 200, stop at 210
+        book.positions.insert("TSLA".to_string(), position("TSLA", -50, 200, 200));
+        assert!((portfolio_heat(&book, &config, &HashMap::new()).unwrap() - 0.10).abs() < 1e-9);
+
+        // Gross exposure counts full position value; daily loss ignores a profitable day
+        let gross = PortfolioHeatConfig { method: HeatMethod::GrossExposure, ..config.clone() };
+        assert!((portfolio_heat(&book, &gross, &HashMap::new()).unwrap() - 1.0).abs() < 1e-9);
+        let daily = PortfolioHeatConfig { method: HeatMethod::DailyLoss, ..config };
+        assert_eq!(portfolio_heat(&book, &daily, &HashMap::new()).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_protective_stop_replaces_the_assumed_distance() {
+        let config = PortfolioHeatConfig::default();
+        let mut book = portfolio(10_000);
+        book.positions.insert("AAPL".to_string(), position("AAPL", 100, 100, 110));
+        book.positions.insert("TSLA".to_string(), position("TSLA", 100, 100, 100));
+
+        // AAPL's stop at 108 risks $200; TSLA has none and risks 5% of entry
+        let stops = HashMap::from([("AAPL".to_string(), Decimal::from(108))]);
+        assert!((portfolio_heat(&book, &config, &stops).unwrap() - 0.07).abs() < 1e-9);
+
+        // Price already through TSLA's stop risks nothing more; without its
+        // stop AAPL falls back to 95 and risks $1,500 of its gain
+        let stops = HashMap::from([("TSLA".to_string(), Decimal::from(101))]);
+        assert!((portfolio_heat(&book, &config, &stops).unwrap() - 0.15).abs() < 1e-9);
+    }
+}
@@ -1,5 +1,16 @@
 //! Risk module - Risk management and portfolio protection
 
+pub mod audit;
+pub mod cache;
+pub mod cash;
+pub mod circuit_breaker;
+pub mod compliance;
+pub mod exits;
+pub mod heat;
+pub mod monte_carlo;
+pub mod reconciliation;
+pub mod var;
+
 /// Placeholder for risk module
 pub struct RiskEngine;
 
@@ -0,0 +1,113 @@
+//! Portfolio accounting invariant checks
+
+use rust_decimal::Decimal;
+use std::fmt;
+
+use crate::core::types::Portfolio;
+
+/// Reported portfolio value disagrees with cash plus position market value
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvariantViolation {
+    pub reported_total: Decimal,
+    pub expected_total: Decimal,
+    pub discrepancy: Decimal,
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "total_value {} != cash + positions {} (discrepancy {})",
+            self.reported_total, self.expected_total, self.discrepancy
+        )
+    }
+}
+
+/// Verifies `total_value ≈ cash_balance + Σ position market value`
+#[derive(Debug, Clone)]
+pub struct PortfolioReconciler {
+    tolerance: Decimal,
+}
+
+impl PortfolioReconciler {
+    /// Create a new reconciler with the given absolute tolerance
+    pub fn new(tolerance: Decimal) -> Self {
+        Self { tolerance }
+    }
+
+    /// Check the portfolio accounting invariant
+    pub fn check(&self, portfolio: &Portfolio) -> Result<(), InvariantViolation> {
+        // A sum too large to represent can never match the reported total
+        let expected_total = portfolio
+            .positions_value()
+            .ok()
+            .and_then(|positions_value| portfolio.cash_balance.checked_add(positions_value))
+            .unwrap_or(Decimal::MAX);
+        let discrepancy = portfolio
+            .total_value
+            .checked_sub(expected_total)
+            .map(|d| d.abs())
+            .unwrap_or(Decimal::MAX);
+
+        if discrepancy > self.tolerance {
+            return Err(InvariantViolation {
+                reported_total: portfolio.total_value,
+                expected_total,
+                discrepancy,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Position;
+    use chrono::Utc;
+    use std::collections::HashMap;
+
+    fn portfolio(total_value: Decimal) -> Portfolio {
+        let mut positions = HashMap::new();
+        positions.insert(
+            "AAPL".to_string(),
+            Position {
+                id: uuid::Uuid::new_v4(),
+                symbol: "AAPL".to_string(),
+                quantity: Decimal::from(2),
+                entry_price: Decimal::from(10),
+                current_price: Decimal::from(12),
+                unrealized_pnl: Decimal::from(4),
+                realized_pnl: Decimal::ZERO,
+                timestamp: Utc::now(),
+            },
+        );
+
+        Portfolio {
+            total_value,
+            cash_balance: Decimal::from(80),
+            positions,
+            daily_pnl: Decimal::ZERO,
+            total_pnl: Decimal::ZERO,
+            max_drawdown: Decimal::ZERO,
+            sharpe_ratio: None,
+            last_updated: Utc::now(),
+            trading_day: None,
+        }
+    }
+
+    #[test]
+    fn test_consistent_portfolio_passes() {
+        let reconciler = PortfolioReconciler::new(Decimal::new(1, 2));
+        assert!(reconciler.check(&portfolio(Decimal::from(104))).is_ok());
+    }
+
+    #[test]
+    fn test_corrupted_portfolio_violates_invariant() {
+        let reconciler = PortfolioReconciler::new(Decimal::new(1, 2));
+        let violation = reconciler.check(&portfolio(Decimal::from(110))).unwrap_err();
+        assert_eq!(violation.expected_total, Decimal::from(104));
+        assert_eq!(violation.discrepancy, Decimal::from(6));
+    }
+}
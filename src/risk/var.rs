@@ -0,0 +1,133 @@
+//! Historical-simulation Value at Risk
+//!
+//! VaR is read straight off the empirical distribution of recent daily
+//! returns rather than assuming a shape for it, so fat tails in the history
+//! show up in the estimate.
+
+use chrono::NaiveDate;
+use std::collections::VecDeque;
+
+/// Rolling window of daily portfolio returns, one sample per day
+#[derive(Debug, Clone)]
+pub struct ReturnsWindow {
+    returns: VecDeque<f64>,
+    capacity: usize,
+    current_day: Option<NaiveDate>,
+}
+
+impl ReturnsWindow {
+    /// Keep at most `capacity` days of returns
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            returns: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+            current_day: None,
+        }
+    }
+
+    /// Record `day`'s return so far. Later samples on the same day replace the
+    /// earlier one, so the window holds each day's latest return.
+    pub fn record(&mut self, day: NaiveDate, daily_return: f64) {
+        if !daily_return.is_finite() {
+            return;
+        }
+        if self.current_day == Some(day) {
+            if let Some(last) = self.returns.back_mut() {
+                *last = daily_return;
+                return;
+            }
+        }
+
+        self.current_day = Some(day);
+        self.returns.push_back(daily_return);
+        while self.returns.len() > self.capacity {
+            self.returns.pop_front();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.returns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.returns.is_empty()
+    }
+
+    /// Sample standard deviation of the recorded daily returns, once at
+    /// least two days exist
+    pub fn volatility(&self) -> Option<f64> {
+        let n = self.returns.len();
+        if n < 2 {
+            return None;
+        }
+        let mean = self.returns.iter().sum::<f64>() / n as f64;
+        let variance = self.returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+        Some(variance.sqrt())
+    }
+
+    /// Loss, as a positive fraction of portfolio value, not exceeded on
+    /// `confidence` of the recorded days. `None` without any history.
+    pub fn value_at_risk(&self, confidence: f64) -> Option<f64> {
+        let sorted = self.sorted();
+        let index = tail_index(sorted.len(), confidence)?;
+        Some((-sorted[index]).max(0.0))
+    }
+
+    /// Average loss on the days at or beyond the VaR quantile
+    pub fn expected_shortfall(&self, confidence: f64) -> Option<f64> {
+        let sorted = self.sorted();
+        let index = tail_index(sorted.len(), confidence)?;
+        let tail = &sorted[..=index];
+        Some((-tail.iter().sum::<f64>() / tail.len() as f64).max(0.0))
+    }
+
+    fn sorted(&self) -> Vec<f64> {
+        let mut sorted: Vec<f64> = self.returns.iter().copied().collect();
+        sorted.sort_by(f64::total_cmp);
+        sorted
+    }
+}
+
+/// Nearest-rank index of the `1 - confidence` quantile in `n` ascending samples
+fn tail_index(n: usize, confidence: f64) -> Option<usize> {
+    if n == 0 {
+        return None;
+    }
+    // Shave float noise so 5% of 100 samples is rank 5, not 6
+    let rank = ((1.0 - confidence) * n as f64 - 1e-9).ceil() as usize;
+    Some(rank.clamp(1, n) - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_var_is_empirical_quantile_and_grows_with_confidence() {
+        let mut window = ReturnsWindow::new(100);
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        // Returns of -10%, -9.8%, ..., +9.8%: one per day, shuffled
+        let mut returns: Vec<f64> = (0..100).map(|i| (i as f64 - 50.0) / 500.0).collect();
+        returns.reverse();
+        returns.rotate_left(37);
+        for (day, daily_return) in returns.into_iter().enumerate() {
+            window.record(start + chrono::Duration::days(day as i64), daily_return);
+        }
+        assert_eq!(window.len(), 100);
+
+        // 5th worst of 100 days at 95%, worst day at 99%
+        let var_95 = window.value_at_risk(0.95).unwrap();
+        let var_99 = window.value_at_risk(0.99).unwrap();
+        assert!((var_95 - 0.092).abs() < 1e-12);
+        assert!((var_99 - 0.1).abs() < 1e-12);
+        assert!(var_99 >= var_95);
+        assert!((window.expected_shortfall(0.95).unwrap() - 0.096).abs() < 1e-12);
+
+        // A later sample on the same day replaces that day's return
+        let last = start + chrono::Duration::days(99);
+        window.record(last, -0.5);
+        assert_eq!(window.len(), 100);
+        assert!((window.value_at_risk(0.99).unwrap() - 0.5).abs() < 1e-12);
+        assert!(ReturnsWindow::new(10).value_at_risk(0.95).is_none());
+    }
+}
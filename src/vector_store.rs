@@ -13,20 +13,25 @@ use qdrant_client::{
     Qdrant,
     qdrant::{
         CreateCollectionBuilder, Distance, PointStruct, SearchPointsBuilder, VectorParamsBuilder, 
-        UpsertPointsBuilder, Datatype, Value as QdrantValue,
+        UpsertPointsBuilder, Datatype, Value as QdrantValue, Condition, Filter, Range,
+        DeletePointsBuilder, PointsIdsList, SetPayloadPointsBuilder,
     },
 };
 
+/// Points sent per upsert request by the batch store methods
+pub const DEFAULT_UPSERT_BATCH_SIZE: usize = 256;
+
 /// Vector database client for AI learning
 pub struct VectorStore {
     #[cfg(feature = "ai-learning")]
     client: Qdrant,
     collection_name: String,
     embedding_dim: usize,
+    batch_size: usize,
 }
 
 /// Market pattern stored as vector embedding
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MarketPattern {
     pub id: String,
     pub timestamp: i64,
@@ -39,8 +44,43 @@ pub struct MarketPattern {
     pub metadata: PatternMetadata,
 }
 
+/// Text-log view of a `MarketPattern` for the trade journal and audit log.
+///
+/// The embedding is omitted unless requested; `vector_id` refers back to the
+/// full vector held in the vector store.
+#[derive(Debug, Clone, Serialize)]
+pub struct PatternRecord<'a> {
+    pub vector_id: &'a str,
+    pub timestamp: i64,
+    pub symbol: &'a str,
+    pub pattern_type: &'a PatternType,
+    pub market_conditions: &'a MarketConditions,
+    pub outcome: &'a TradingOutcome,
+    pub success_rate: f64,
+    pub metadata: &'a PatternMetadata,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<&'a [f32]>,
+}
+
+impl MarketPattern {
+    /// Journal/audit view of this pattern, optionally keeping the embedding
+    pub fn record(&self, include_embedding: bool) -> PatternRecord<'_> {
+        PatternRecord {
+            vector_id: &self.id,
+            timestamp: self.timestamp,
+            symbol: &self.symbol,
+            pattern_type: &self.pattern_type,
+            market_conditions: &self.market_conditions,
+            outcome: &self.outcome,
+            success_rate: self.success_rate,
+            metadata: &self.metadata,
+            embedding: include_embedding.then_some(self.embedding.as_slice()),
+        }
+    }
+}
+
 /// Trading strategy stored as vector embedding  
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TradingStrategyVector {
     pub strategy_id: String,
     pub name: String,
@@ -57,7 +97,7 @@ pub struct TradingStrategyVector {
 }
 
 /// Market conditions context
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MarketConditions {
     pub volatility: f64,
     pub trend_strength: f64,
@@ -71,7 +111,7 @@ pub struct MarketConditions {
 }
 
 /// Pattern types for classification
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PatternType {
     TechnicalBreakout,
     MeanReversion,
@@ -84,7 +124,7 @@ pub enum PatternType {
 }
 
 /// Trading outcome for learning
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TradingOutcome {
     pub success: bool,
     pub return_pct: f64,
@@ -96,7 +136,7 @@ pub struct TradingOutcome {
 }
 
 /// Pattern metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PatternMetadata {
     pub confidence: f64,
     pub similar_patterns: Vec<String>,
@@ -120,6 +160,56 @@ pub struct SimilarStrategy {
     pub distance: f64,
 }
 
+/// Convert a JSON value into its Qdrant payload equivalent
+#[cfg(feature = "ai-learning")]
+fn json_to_qdrant_value(value: serde_json::Value) -> QdrantValue {
+    use qdrant_client::qdrant::value::Kind;
+
+    let kind = match value {
+        serde_json::Value::Null => Kind::NullValue(0),
+        serde_json::Value::Bool(b) => Kind::BoolValue(b),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Kind::IntegerValue(i),
+            None => Kind::DoubleValue(n.as_f64().unwrap_or(0.0)),
+        },
+        serde_json::Value::String(s) => Kind::StringValue(s),
+        serde_json::Value::Array(values) => Kind::ListValue(qdrant_client::qdrant::ListValue {
+            values: values.into_iter().map(json_to_qdrant_value).collect(),
+        }),
+        serde_json::Value::Object(map) => Kind::StructValue(qdrant_client::qdrant::Struct {
+            fields: map.into_iter().map(|(k, v)| (k, json_to_qdrant_value(v))).collect(),
+        }),
+    };
+    QdrantValue { kind: Some(kind) }
+}
+
+/// Serialize a struct's fields into a Qdrant point payload
+#[cfg(feature = "ai-learning")]
+fn to_payload<T: Serialize>(value: &T) -> Result<HashMap<String, QdrantValue>> {
+    match serde_json::to_value(value)? {
+        serde_json::Value::Object(map) => Ok(map.into_iter().map(|(k, v)| (k, json_to_qdrant_value(v))).collect()),
+        other => anyhow::bail!("Payload must serialize to an object, got {}", other),
+    }
+}
+
+#[cfg(feature = "ai-learning")]
+fn pattern_point(pattern: &MarketPattern) -> Result<PointStruct> {
+    Ok(PointStruct {
+        id: Some(pattern.id.clone().into()),
+        vectors: Some(pattern.embedding.clone().into()),
+        payload: to_payload(pattern)?,
+    })
+}
+
+#[cfg(feature = "ai-learning")]
+fn strategy_point(strategy: &TradingStrategyVector) -> Result<PointStruct> {
+    Ok(PointStruct {
+        id: Some(strategy.strategy_id.clone().into()),
+        vectors: Some(strategy.embedding.clone().into()),
+        payload: to_payload(strategy)?,
+    })
+}
+
 #[cfg(feature = "ai-learning")]
 fn qdrant_value_to_json(value: QdrantValue) -> serde_json::Value {
     match value.kind {
@@ -157,6 +247,7 @@ impl VectorStore {
                 client,
                 collection_name: collection_name.to_string(),
                 embedding_dim,
+                batch_size: DEFAULT_UPSERT_BATCH_SIZE,
             };
             
             // Initialize collections
@@ -172,10 +263,28 @@ impl VectorStore {
             Ok(Self {
                 collection_name: collection_name.to_string(),
                 embedding_dim,
+                batch_size: DEFAULT_UPSERT_BATCH_SIZE,
             })
         }
     }
     
+    /// Refuse embeddings the collections were not created for
+    fn check_dimension(&self, kind: &str, id: &str, embedding: &[f32]) -> Result<()> {
+        if embedding.len() != self.embedding_dim {
+            anyhow::bail!(
+                "{} {} has a {}-dimensional embedding but the store expects {}",
+                kind, id, embedding.len(), self.embedding_dim
+            );
+        }
+        Ok(())
+    }
+    
+    /// Send at most `batch_size` points per upsert request from the batch store methods
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+    
     /// Initialize vector database collections
     #[cfg(feature = "ai-learning")]
     async fn initialize_collections(&self) -> Result<()> {
@@ -217,17 +326,13 @@ impl VectorStore {
     
     /// Store a market pattern
     pub async fn store_pattern(&self, pattern: &MarketPattern) -> Result<()> {
+        self.check_dimension("Pattern", &pattern.id, &pattern.embedding)?;
+        
         #[cfg(feature = "ai-learning")]
         {
             let collection_name = format!("{}_patterns", self.collection_name);
             
-            let point = PointStruct {
-                id: Some(pattern.id.clone().into()),
-                vectors: Some(pattern.embedding.clone().into()),
-                payload: Default::default(), // Simplified for now
-            };
-            
-            let upsert_request = UpsertPointsBuilder::new(collection_name, vec![point]).build();
+            let upsert_request = UpsertPointsBuilder::new(collection_name, vec![pattern_point(pattern)?]).build();
             self.client.upsert_points(upsert_request).await?;
                 
             info!("💾 Stored pattern: {} ({:?})", pattern.id, pattern.pattern_type);
@@ -243,17 +348,13 @@ impl VectorStore {
     
     /// Store a trading strategy
     pub async fn store_strategy(&self, strategy: &TradingStrategyVector) -> Result<()> {
+        self.check_dimension("Strategy", &strategy.strategy_id, &strategy.embedding)?;
+        
         #[cfg(feature = "ai-learning")]
         {
             let collection_name = format!("{}_strategies", self.collection_name);
             
-            let point = PointStruct {
-                id: Some(strategy.strategy_id.clone().into()),
-                vectors: Some(strategy.embedding.clone().into()),
-                payload: Default::default(), // Simplified for now
-            };
-            
-            let upsert_request = UpsertPointsBuilder::new(collection_name, vec![point]).build();
+            let upsert_request = UpsertPointsBuilder::new(collection_name, vec![strategy_point(strategy)?]).build();
             self.client.upsert_points(upsert_request).await?;
                 
             info!("💾 Stored strategy: {} (success rate: {:.1}%)", 
@@ -268,21 +369,165 @@ impl VectorStore {
         Ok(())
     }
     
-    /// Search for similar market patterns
+    /// Store many market patterns with one upsert request per `batch_size`
+    /// patterns instead of one per pattern. Larger batches are split so no
+    /// request exceeds Qdrant's payload limits. Returns the number of requests
+    /// sent; `bench_batched_upserts_against_single_upserts` measures the gain.
+    pub async fn store_patterns_batch(&self, patterns: &[MarketPattern]) -> Result<usize> {
+        for pattern in patterns {
+            self.check_dimension("Pattern", &pattern.id, &pattern.embedding)?;
+        }
+        let requests = patterns.chunks(self.batch_size).count();
+        
+        #[cfg(feature = "ai-learning")]
+        {
+            let collection_name = format!("{}_patterns", self.collection_name);
+            for chunk in patterns.chunks(self.batch_size) {
+                let points = chunk.iter().map(pattern_point).collect::<Result<Vec<_>>>()?;
+                let upsert_request = UpsertPointsBuilder::new(collection_name.clone(), points).build();
+                self.client.upsert_points(upsert_request).await?;
+            }
+            
+            info!("💾 Stored {} patterns in {} requests", patterns.len(), requests);
+        }
+        
+        #[cfg(not(feature = "ai-learning"))]
+        {
+            info!("💾 Mock: Would store {} patterns in {} requests", patterns.len(), requests);
+        }
+        
+        Ok(requests)
+    }
+    
+    /// Store many trading strategies, batched like [`Self::store_patterns_batch`]
+    pub async fn store_strategies_batch(&self, strategies: &[TradingStrategyVector]) -> Result<usize> {
+        for strategy in strategies {
+            self.check_dimension("Strategy", &strategy.strategy_id, &strategy.embedding)?;
+        }
+        let requests = strategies.chunks(self.batch_size).count();
+        
+        #[cfg(feature = "ai-learning")]
+        {
+            let collection_name = format!("{}_strategies", self.collection_name);
+            for chunk in strategies.chunks(self.batch_size) {
+                let points = chunk.iter().map(strategy_point).collect::<Result<Vec<_>>>()?;
+                let upsert_request = UpsertPointsBuilder::new(collection_name.clone(), points).build();
+                self.client.upsert_points(upsert_request).await?;
+            }
+            
+            info!("💾 Stored {} strategies in {} requests", strategies.len(), requests);
+        }
+        
+        #[cfg(not(feature = "ai-learning"))]
+        {
+            info!("💾 Mock: Would store {} strategies in {} requests", strategies.len(), requests);
+        }
+        
+        Ok(requests)
+    }
+    
+    /// Delete a stored market pattern
+    pub async fn delete_pattern(&self, id: &str) -> Result<()> {
+        #[cfg(feature = "ai-learning")]
+        {
+            let collection_name = format!("{}_patterns", self.collection_name);
+            self.delete_point(collection_name, id).await?;
+            info!("🗑️ Deleted pattern: {}", id);
+        }
+        
+        #[cfg(not(feature = "ai-learning"))]
+        {
+            info!("🗑️ Mock: Would delete pattern {}", id);
+        }
+        
+        Ok(())
+    }
+    
+    /// Delete a stored trading strategy
+    pub async fn delete_strategy(&self, id: &str) -> Result<()> {
+        #[cfg(feature = "ai-learning")]
+        {
+            let collection_name = format!("{}_strategies", self.collection_name);
+            self.delete_point(collection_name, id).await?;
+            info!("🗑️ Deleted strategy: {}", id);
+        }
+        
+        #[cfg(not(feature = "ai-learning"))]
+        {
+            info!("🗑️ Mock: Would delete strategy {}", id);
+        }
+        
+        Ok(())
+    }
+    
+    #[cfg(feature = "ai-learning")]
+    async fn delete_point(&self, collection_name: String, id: &str) -> Result<()> {
+        let delete_request = DeletePointsBuilder::new(collection_name)
+            .points(PointsIdsList { ids: vec![id.to_string().into()] })
+            .wait(true)
+            .build();
+        self.client.delete_points(delete_request).await?;
+        Ok(())
+    }
+    
+    /// Update a strategy's usage statistics in place, leaving its embedding
+    /// and other payload fields untouched
+    pub async fn update_strategy_stats(&self, id: &str, usage_count: u64, last_used: i64) -> Result<()> {
+        #[cfg(feature = "ai-learning")]
+        {
+            let collection_name = format!("{}_strategies", self.collection_name);
+            let payload = to_payload(&serde_json::json!({
+                "usage_count": usage_count,
+                "last_used": last_used,
+            }))?;
+            
+            let set_payload_request = SetPayloadPointsBuilder::new(collection_name, payload)
+                .points_selector(PointsIdsList { ids: vec![id.to_string().into()] })
+                .wait(true)
+                .build();
+            self.client.set_payload(set_payload_request).await?;
+            
+            info!("📈 Updated strategy {} stats (used {} times)", id, usage_count);
+        }
+        
+        #[cfg(not(feature = "ai-learning"))]
+        {
+            info!("📈 Mock: Would update strategy {} stats to {} uses, last at {}", id, usage_count, last_used);
+        }
+        
+        Ok(())
+    }
+    
+    /// Search for similar market patterns, optionally ignoring patterns older
+    /// than `max_pattern_age`. The age is chosen per query, since how far back
+    /// a pattern stays relevant depends on what it's being matched against.
     pub async fn find_similar_patterns(
         &self,
         query_embedding: &[f32],
         limit: usize,
         min_similarity: f64,
+        max_pattern_age: Option<chrono::Duration>,
     ) -> Result<Vec<SimilarPattern>> {
         #[cfg(feature = "ai-learning")]
         {
             let collection_name = format!("{}_patterns", self.collection_name);
+            let cutoff = max_pattern_age.map(|age| (chrono::Utc::now() - age).timestamp());
             
-            let search_request = SearchPointsBuilder::new(collection_name, query_embedding.to_vec(), limit as u64)
+            let mut search_builder = SearchPointsBuilder::new(collection_name, query_embedding.to_vec(), limit as u64)
                 .score_threshold(min_similarity as f32)
-                .with_payload(true)
-                .build();
+                .with_payload(true);
+            
+            if let Some(cutoff) = cutoff {
+                search_builder = search_builder.filter(Filter::must([Condition::range(
+                    "timestamp",
+                    Range {
+                        gte: Some(cutoff as f64),
+                        ..Default::default()
+                    },
+                )]));
+            }
+            
+            let search_request = search_builder.build();
             
             let search_result = self.client.search_points(search_request).await?;
             
@@ -309,13 +554,15 @@ impl VectorStore {
                 }
             }
             
+            let patterns = retain_recent_patterns(patterns, cutoff);
+            
             info!("🔍 Found {} similar patterns", patterns.len());
             Ok(patterns)
         }
         
         #[cfg(not(feature = "ai-learning"))]
         {
-            info!("🔍 Mock: Would search for {} similar patterns", limit);
+            info!("🔍 Mock: Would search for {} similar patterns (max age: {:?})", limit, max_pattern_age);
             Ok(Vec::new())
         }
     }
@@ -402,6 +649,17 @@ impl VectorStore {
     }
 }
 
+/// Drop patterns whose timestamp (Unix seconds) is older than `cutoff`
+fn retain_recent_patterns(patterns: Vec<SimilarPattern>, cutoff: Option<i64>) -> Vec<SimilarPattern> {
+    match cutoff {
+        Some(cutoff) => patterns
+            .into_iter()
+            .filter(|p| p.pattern.timestamp >= cutoff)
+            .collect(),
+        None => patterns,
+    }
+}
+
 /// Vector store statistics
 #[derive(Debug, Clone)]
 pub struct VectorStoreStats {
@@ -411,24 +669,94 @@ pub struct VectorStoreStats {
     pub collection_name: String,
 }
 
+/// Default embedding length, matching common sentence-transformers
+pub const DEFAULT_EMBEDDING_DIM: usize = 384;
+
 /// Embedding generator for market data
+///
+/// With a model loaded, conditions and strategies are described in text and
+/// embedded by a sentence-transformer (ONNX via fastembed). Without one, raw
+/// features are packed into a zero-padded, normalized vector.
 pub struct EmbeddingGenerator {
     model_name: String,
+    #[cfg(feature = "ai-learning")]
+    model: Option<std::sync::Arc<std::sync::Mutex<fastembed::TextEmbedding>>>,
+    dimension: usize,
 }
 
 impl EmbeddingGenerator {
-    /// Create a new embedding generator
-    pub fn new(model_name: &str) -> Self {
+    /// Create a generator packing features into `dimension`-long vectors
+    pub fn new(model_name: &str, dimension: usize) -> Self {
         Self {
             model_name: model_name.to_string(),
+            #[cfg(feature = "ai-learning")]
+            model: None,
+            dimension,
+        }
+    }
+    
+    /// Load the sentence-transformer named by `model_name`, e.g.
+    /// `Qdrant/all-MiniLM-L6-v2-onnx`, failing if it does not produce
+    /// `dimension`-long vectors. An empty name keeps the naive packing.
+    pub fn load(model_name: &str, dimension: usize) -> Result<Self> {
+        if model_name.is_empty() {
+            return Ok(Self::new(model_name, dimension));
+        }
+        
+        #[cfg(feature = "ai-learning")]
+        {
+            use fastembed::{EmbeddingModel, InitOptions, TextEmbedding};
+            
+            let model: EmbeddingModel = model_name.parse().map_err(anyhow::Error::msg)?;
+            let model_dimension = TextEmbedding::get_model_info(&model)?.dim;
+            if model_dimension != dimension {
+                anyhow::bail!(
+                    "Embedding model {} produces {}-dimensional vectors but {} were configured",
+                    model_name, model_dimension, dimension
+                );
+            }
+            let embedder = TextEmbedding::try_new(InitOptions::new(model))?;
+            info!("🧠 Loaded embedding model {} ({} dimensions)", model_name, dimension);
+            
+            Ok(Self {
+                model_name: model_name.to_string(),
+                model: Some(std::sync::Arc::new(std::sync::Mutex::new(embedder))),
+                dimension,
+            })
+        }
+        
+        #[cfg(not(feature = "ai-learning"))]
+        {
+            warn!("🧠 Embedding model {} needs the ai-learning feature, using feature packing", model_name);
+            Ok(Self::new(model_name, dimension))
+        }
+    }
+    
+    /// Length of the vectors this generator produces; pass to `VectorStore::new`
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+    
+    /// Whether embeddings come from a loaded model rather than feature packing
+    pub fn has_model(&self) -> bool {
+        #[cfg(feature = "ai-learning")]
+        {
+            self.model.is_some()
+        }
+        
+        #[cfg(not(feature = "ai-learning"))]
+        {
+            false
         }
     }
     
     /// Generate embedding for market conditions
     pub async fn embed_market_conditions(&self, conditions: &MarketConditions) -> Result<Vec<f32>> {
-        // For now, create a simple feature vector from market conditions
-        // In production, this would use a proper embedding model
-        let features = vec![
+        if self.has_model() {
+            return self.embed_text(describe_market_conditions(conditions)).await;
+        }
+        
+        Ok(pack_features(self.dimension, &[
             conditions.volatility,
             conditions.trend_strength,
             conditions.volume_profile,
@@ -437,58 +765,286 @@ impl EmbeddingGenerator {
             conditions.rsi / 100.0, // Normalize RSI
             conditions.macd_signal,
             conditions.bollinger_position,
-        ];
-        
-        // Pad or truncate to fixed dimension (384 is common for sentence transformers)
-        let mut embedding = vec![0.0f32; 384];
-        for (i, &value) in features.iter().enumerate() {
-            if i < embedding.len() {
-                embedding[i] = value as f32;
-            }
-        }
-        
-        // Simple normalization
-        let norm: f32 = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
-        if norm > 0.0 {
-            for x in &mut embedding {
-                *x /= norm;
-            }
-        }
-        
-        Ok(embedding)
+        ]))
     }
     
     /// Generate embedding for trading strategy
     pub async fn embed_strategy(&self, strategy: &HashMap<String, f64>) -> Result<Vec<f32>> {
-        // Create feature vector from strategy parameters
-        let mut features = Vec::new();
+        if self.has_model() {
+            return self.embed_text(describe_strategy(strategy)).await;
+        }
         
         // Extract common strategy parameters
-        features.push(strategy.get("position_size").unwrap_or(&0.1).clone());
-        features.push(strategy.get("stop_loss").unwrap_or(&0.02).clone());
-        features.push(strategy.get("take_profit").unwrap_or(&0.04).clone());
-        features.push(strategy.get("holding_period").unwrap_or(&60.0).clone() / 1440.0); // Normalize to days
-        features.push(strategy.get("volatility_threshold").unwrap_or(&0.2).clone());
-        features.push(strategy.get("trend_strength").unwrap_or(&0.5).clone());
-        features.push(strategy.get("volume_multiplier").unwrap_or(&1.5).clone());
-        features.push(strategy.get("risk_reward_ratio").unwrap_or(&2.0).clone() / 10.0); // Normalize
-        
-        // Pad to fixed dimension
-        let mut embedding = vec![0.0f32; 384];
-        for (i, &value) in features.iter().enumerate() {
-            if i < embedding.len() {
-                embedding[i] = value as f32;
-            }
+        Ok(pack_features(self.dimension, &[
+            *strategy.get("position_size").unwrap_or(&0.1),
+            *strategy.get("stop_loss").unwrap_or(&0.02),
+            *strategy.get("take_profit").unwrap_or(&0.04),
+            strategy.get("holding_period").unwrap_or(&60.0) / 1440.0, // Normalize to days
+            *strategy.get("volatility_threshold").unwrap_or(&0.2),
+            *strategy.get("trend_strength").unwrap_or(&0.5),
+            *strategy.get("volume_multiplier").unwrap_or(&1.5),
+            strategy.get("risk_reward_ratio").unwrap_or(&2.0) / 10.0, // Normalize
+        ]))
+    }
+    
+    /// Embed a description with the loaded model off the async runtime
+    #[cfg(feature = "ai-learning")]
+    async fn embed_text(&self, text: String) -> Result<Vec<f32>> {
+        let model = self
+            .model
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("No embedding model loaded for {}", self.model_name))?;
+        let mut embeddings = tokio::task::spawn_blocking(move || {
+            let mut model = model.lock().map_err(|_| anyhow::anyhow!("Embedding model lock poisoned"))?;
+            model.embed(vec![text], None)
+        })
+        .await??;
+        embeddings.pop().ok_or_else(|| anyhow::anyhow!("Embedding model returned no vector"))
+    }
+    
+    #[cfg(not(feature = "ai-learning"))]
+    async fn embed_text(&self, _text: String) -> Result<Vec<f32>> {
+        anyhow::bail!("No embedding model loaded for {}", self.model_name)
+    }
+}
+
+/// Plain-language description of market conditions for a text embedding model
+pub fn describe_market_conditions(conditions: &MarketConditions) -> String {
+    format!(
+        "{} market with volatility {:.3}, trend strength {:.3}, relative volume {:.2}, \
+         sector rotation {:.3}, sentiment {:.3}, RSI {:.1}, MACD signal {:.4}, \
+         Bollinger band position {:.2}",
+        conditions.market_regime,
+        conditions.volatility,
+        conditions.trend_strength,
+        conditions.volume_profile,
+        conditions.sector_rotation,
+        conditions.sentiment_score,
+        conditions.rsi,
+        conditions.macd_signal,
+        conditions.bollinger_position,
+    )
+}
+
+/// Plain-language description of strategy parameters, in a stable order
+pub fn describe_strategy(strategy: &HashMap<String, f64>) -> String {
+    let mut parameters: Vec<_> = strategy.iter().collect();
+    parameters.sort_by(|a, b| a.0.cmp(b.0));
+    let parameters: Vec<String> = parameters
+        .into_iter()
+        .map(|(name, value)| format!("{} {:.4}", name.replace('_', " "), value))
+        .collect();
+    format!("Trading strategy with {}", parameters.join(", "))
+}
+
+/// Zero-pad or truncate features to `dimension` and normalize to unit length
+fn pack_features(dimension: usize, features: &[f64]) -> Vec<f32> {
+    let mut embedding = vec![0.0f32; dimension];
+    for (slot, &value) in embedding.iter_mut().zip(features) {
+        *slot = value as f32;
+    }
+    
+    let norm: f32 = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for x in &mut embedding {
+            *x /= norm;
         }
-        
-        // Normalization
+    }
+    
+    embedding
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_pattern(id: &str, timestamp: i64) -> MarketPattern {
+        MarketPattern {
+            id: id.to_string(),
+            timestamp,
+            symbol: "AAPL".to_string(),
+            pattern_type: PatternType::MeanReversion,
+            market_conditions: MarketConditions {
+                volatility: 0.2,
+                trend_strength: 0.5,
+                volume_profile: 1.0,
+                market_regime: "Sideways".to_string(),
+                sector_rotation: 0.0,
+                sentiment_score: 0.1,
+                rsi: 45.0,
+                macd_signal: 0.0,
+                bollinger_position: 0.5,
+            },
+            outcome: TradingOutcome {
+                success: true,
+                return_pct: 0.02,
+                hold_time_minutes: 30,
+                max_adverse_excursion: 0.01,
+                max_favorable_excursion: 0.03,
+                slippage: 0.0005,
+                commission: 0.001,
+            },
+            success_rate: 0.6,
+            embedding: vec![0.0; 8],
+            metadata: PatternMetadata {
+                confidence: 0.7,
+                similar_patterns: Vec::new(),
+                risk_score: 0.3,
+                tags: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_patterns_older_than_max_age_are_excluded() {
+        let now = chrono::Utc::now().timestamp();
+        let results = vec![
+            SimilarPattern { pattern: sample_pattern("fresh", now - 60), similarity_score: 0.9, distance: 0.1 },
+            SimilarPattern { pattern: sample_pattern("stale", now - 10 * 86_400), similarity_score: 0.95, distance: 0.05 },
+        ];
+
+        let cutoff = now - chrono::Duration::days(7).num_seconds();
+        let filtered = retain_recent_patterns(results.clone(), Some(cutoff));
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].pattern.id, "fresh");
+
+        assert_eq!(retain_recent_patterns(results, None).len(), 2);
+    }
+
+    #[test]
+    fn test_journal_record_excludes_embedding() {
+        let pattern = sample_pattern("pattern-1", 0);
+
+        let record = serde_json::to_value(pattern.record(false)).unwrap();
+        assert!(record.get("embedding").is_none());
+        assert_eq!(record["vector_id"], "pattern-1");
+        assert_eq!(record["outcome"]["success"], true);
+        assert_eq!(record["outcome"]["return_pct"], 0.02);
+
+        let full = serde_json::to_value(pattern.record(true)).unwrap();
+        assert_eq!(full["embedding"].as_array().unwrap().len(), 8);
+    }
+
+    #[cfg(feature = "ai-learning")]
+    #[test]
+    fn test_pattern_payload_round_trips() {
+        let mut pattern = sample_pattern("pattern-1", 1_700_000_000);
+        pattern.embedding = vec![0.25, -1.5, 3.0];
+        pattern.metadata.tags = vec!["breakout".to_string()];
+
+        // What a search returns is the stored payload read back through `qdrant_value_to_json`
+        let payload = to_payload(&pattern).unwrap();
+        let returned: MarketPattern = serde_json::from_value(serde_json::Value::Object(
+            payload.into_iter().map(|(k, v)| (k, qdrant_value_to_json(v))).collect(),
+        ))
+        .unwrap();
+        assert_eq!(returned, pattern);
+    }
+
+    #[cfg(not(feature = "ai-learning"))]
+    #[tokio::test]
+    async fn test_mock_store_delete_and_update_succeed() {
+        let store = VectorStore::new("http://localhost:6334", "test", 8).await.unwrap();
+        let pattern = sample_pattern("pattern-1", chrono::Utc::now().timestamp());
+
+        store.store_pattern(&pattern).await.unwrap();
+        store.delete_pattern(&pattern.id).await.unwrap();
+        store.delete_strategy("strategy-1").await.unwrap();
+        store.update_strategy_stats("strategy-1", 3, 0).await.unwrap();
+        assert!(store.find_similar_patterns(&pattern.embedding, 5, 0.0, None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[cfg_attr(feature = "ai-learning", ignore = "needs a Qdrant server at localhost:6334")]
+    async fn test_batches_split_at_batch_size() {
+        let store = VectorStore::new("http://localhost:6334", "test", 8).await.unwrap().with_batch_size(100);
+        let patterns: Vec<MarketPattern> = (0..250)
+            .map(|i| sample_pattern(&uuid::Uuid::new_v4().to_string(), i))
+            .collect();
+
+        assert_eq!(store.store_patterns_batch(&patterns).await.unwrap(), 3);
+        assert_eq!(store.store_patterns_batch(&patterns[..100]).await.unwrap(), 1);
+        assert_eq!(store.store_strategies_batch(&[]).await.unwrap(), 0);
+    }
+
+    /// Needs a Qdrant server; run with `cargo test -- --ignored`
+    #[cfg(feature = "ai-learning")]
+    #[tokio::test]
+    #[ignore]
+    async fn test_deleted_pattern_is_no_longer_found() {
+        let collection = format!("test_{}", uuid::Uuid::new_v4().simple());
+        let store = VectorStore::new("http://localhost:6334", &collection, 8).await.unwrap();
+        let mut pattern = sample_pattern(&uuid::Uuid::new_v4().to_string(), chrono::Utc::now().timestamp());
+        pattern.embedding = vec![1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+
+        store.store_pattern(&pattern).await.unwrap();
+        let found = store.find_similar_patterns(&pattern.embedding, 5, 0.5, None).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].pattern, pattern);
+
+        store.delete_pattern(&pattern.id).await.unwrap();
+        assert!(store.find_similar_patterns(&pattern.embedding, 5, 0.5, None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_embedding_generator_falls_back_to_feature_packing() {
+        let generator = EmbeddingGenerator::load("", DEFAULT_EMBEDDING_DIM).unwrap();
+        assert!(!generator.has_model());
+        assert_eq!(generator.dimension(), DEFAULT_EMBEDDING_DIM);
+
+        let conditions = sample_pattern("p", 0).market_conditions;
+        let embedding = generator.embed_market_conditions(&conditions).await.unwrap();
+        assert_eq!(embedding.len(), generator.dimension());
         let norm: f32 = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
-        if norm > 0.0 {
-            for x in &mut embedding {
-                *x /= norm;
-            }
+        assert!((norm - 1.0).abs() < 1e-5);
+
+        assert!(describe_market_conditions(&conditions).starts_with("Sideways market with volatility 0.200"));
+        let strategy = HashMap::from([("stop_loss".to_string(), 0.02), ("position_size".to_string(), 0.1)]);
+        assert_eq!(describe_strategy(&strategy), "Trading strategy with position size 0.1000, stop loss 0.0200");
+    }
+
+    #[tokio::test]
+    #[cfg_attr(feature = "ai-learning", ignore = "needs a Qdrant server at localhost:6334")]
+    async fn test_store_rejects_embeddings_of_the_wrong_dimension() {
+        let store = VectorStore::new("http://localhost:6334", "test", 8).await.unwrap();
+        let generator = EmbeddingGenerator::new("", 8);
+        let mut pattern = sample_pattern("p", 0);
+        pattern.embedding = generator.embed_market_conditions(&pattern.market_conditions).await.unwrap();
+        assert_eq!(pattern.embedding.len(), 8);
+        store.store_pattern(&pattern).await.unwrap();
+
+        pattern.embedding = EmbeddingGenerator::new("", 384).embed_market_conditions(&pattern.market_conditions).await.unwrap();
+        let error = store.store_pattern(&pattern).await.unwrap_err().to_string();
+        assert!(error.contains("384") && error.contains("expects 8"), "{}", error);
+        assert!(store.store_patterns_batch(std::slice::from_ref(&pattern)).await.is_err());
+    }
+
+    /// Throughput of batched against one-at-a-time upserts. Needs a Qdrant
+    /// server; run with `cargo test --release bench_ -- --ignored --nocapture`
+    #[cfg(feature = "ai-learning")]
+    #[tokio::test]
+    #[ignore = "benchmark against a Qdrant server at localhost:6334"]
+    async fn bench_batched_upserts_against_single_upserts() {
+        let collection = format!("bench_{}", uuid::Uuid::new_v4().simple());
+        let store = VectorStore::new("http://localhost:6334", &collection, 8).await.unwrap();
+        let patterns: Vec<MarketPattern> = (0..1_000)
+            .map(|i| sample_pattern(&uuid::Uuid::new_v4().to_string(), i))
+            .collect();
+
+        let started = std::time::Instant::now();
+        for pattern in &patterns {
+            store.store_pattern(pattern).await.unwrap();
         }
-        
-        Ok(embedding)
+        let single = patterns.len() as f64 / started.elapsed().as_secs_f64();
+
+        let started = std::time::Instant::now();
+        let requests = store.store_patterns_batch(&patterns).await.unwrap();
+        let batched = patterns.len() as f64 / started.elapsed().as_secs_f64();
+
+        println!(
+            "{} patterns: {:.0}/s one at a time, {:.0}/s batched in {} requests ({:.1}x)",
+            patterns.len(), single, batched, requests, batched / single
+        );
+        assert!(batched > single);
     }
-}
\ No newline at end of file
+}